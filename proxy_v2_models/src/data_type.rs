@@ -30,6 +30,10 @@ pub enum DataType {
     Document,
     /// 압축 파일 (ZIP, GZIP 등)
     Archive,
+    /// gRPC / gRPC-Web 메시지 (길이 접두 protobuf 메시지)
+    Grpc,
+    /// protobuf 메시지
+    Protobuf,
     /// 바이너리 데이터 (알 수 없는 형식)
     Binary,
     /// 빈 데이터
@@ -54,6 +58,8 @@ impl DataType {
             DataType::Audio => "audio/*",
             DataType::Document => "application/pdf",
             DataType::Archive => "application/zip",
+            DataType::Grpc => "application/grpc",
+            DataType::Protobuf => "application/x-protobuf",
             DataType::Binary => "application/octet-stream",
             DataType::Empty => "empty",
             DataType::Unknown => "application/octet-stream",
@@ -69,11 +75,14 @@ impl DataType {
             DataType::Css => "css",
             DataType::Javascript => "javascript",
             DataType::Text => "plaintext",
+            // 디스크립터로 변환한 JSON을 표시
+            DataType::Grpc => "json",
             DataType::Image
             | DataType::Video
             | DataType::Audio
             | DataType::Document
             | DataType::Archive
+            | DataType::Protobuf
             | DataType::Binary
             | DataType::Empty
             | DataType::Unknown => "plaintext",
@@ -127,6 +136,8 @@ impl DataType {
                 | DataType::Audio
                 | DataType::Document
                 | DataType::Archive
+                | DataType::Grpc
+                | DataType::Protobuf
                 | DataType::Binary
         )
     }
@@ -178,8 +189,25 @@ fn detect_brotli_content_type(data: &[u8]) -> DataType {
     }
 }
 
+/// Content-Type으로 gRPC / protobuf 본문 감지
+fn detect_protobuf_content_type(headers: &HeaderMap) -> Option<DataType> {
+    let content_type = headers.get("content-type")?.to_str().ok()?.to_lowercase();
+    if content_type.starts_with("application/grpc") {
+        Some(DataType::Grpc)
+    } else if content_type.contains("protobuf") {
+        Some(DataType::Protobuf)
+    } else {
+        None
+    }
+}
+
 /// 데이터 타입 감지 유틸리티 함수 (MITM 프록시에 최적화)
 pub fn detect_data_type(headers: &HeaderMap, body: &Bytes) -> DataType {
+    // gRPC/protobuf 본문은 내용만으로 구분할 수 없으므로 Content-Type을 먼저 확인
+    if let Some(data_type) = detect_protobuf_content_type(headers) {
+        return data_type;
+    }
+
     // 0. Content-Encoding 헤더 확인 (가장 우선순위 높음)
     if let Some(content_encoding) = headers.get("content-encoding") {
         if let Ok(encoding) = content_encoding.to_str() {
@@ -523,6 +551,23 @@ mod tests {
         assert_eq!(detect_data_type(&headers, &pdf_data), DataType::Document);
     }
 
+    #[test]
+    fn test_grpc_detection() {
+        let mut headers = HeaderMap::new();
+        // 첫 바이트가 JSON처럼 보여도 Content-Type을 우선
+        let grpc_data = Bytes::from(vec![0x00, 0x00, 0x00, 0x00, 0x02, 0x7b, 0x7d]);
+
+        headers.insert("content-type", "application/grpc+proto".parse().unwrap());
+        assert_eq!(detect_data_type(&headers, &grpc_data), DataType::Grpc);
+
+        headers.insert("content-type", "application/grpc-web-text".parse().unwrap());
+        assert_eq!(detect_data_type(&headers, &grpc_data), DataType::Grpc);
+
+        headers.insert("content-type", "application/x-protobuf".parse().unwrap());
+        assert_eq!(detect_data_type(&headers, &grpc_data), DataType::Protobuf);
+        assert!(DataType::Protobuf.is_binary());
+    }
+
     #[test]
     fn test_archive_detection() {
         let headers = HeaderMap::new();
@@ -0,0 +1,959 @@
+//! HAR 1.2 (HTTP Archive) 내보내기/가져오기
+//!
+//! 캡처된 요청/응답 쌍을 HAR 로그로 변환하고, HAR 파일을 다시 `RequestInfo` 목록으로 복원합니다.
+//! 스펙: <http://www.softwareishard.com/blog/har-12-spec/>
+
+use crate::{
+    BodyTruncation, ClientRequest, ClientResponse, ProxiedRequest, ProxiedResponse, RequestInfo,
+    Timings, UpstreamSession,
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use bytes::Bytes;
+use chrono::{DateTime, SecondsFormat};
+use http::{header, HeaderMap, HeaderName, HeaderValue, Method, StatusCode, Uri, Version};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// HAR 스펙 버전
+pub const HAR_VERSION: &str = "1.2";
+
+/// 바이너리 본문을 base64로 표시할 때 사용하는 인코딩 이름
+const BASE64_ENCODING: &str = "base64";
+
+/// HAR 변환 중 발생하는 오류
+#[derive(Debug)]
+pub enum HarError {
+    /// JSON 파싱/직렬화 실패
+    Json(serde_json::Error),
+    /// 잘못된 HTTP 메서드
+    InvalidMethod(String),
+    /// 잘못된 URL
+    InvalidUrl(String),
+    /// 잘못된 상태 코드
+    InvalidStatus(u16),
+    /// 잘못된 날짜 형식
+    InvalidDate(String),
+    /// base64 디코딩 실패
+    InvalidBase64(base64::DecodeError),
+}
+
+impl fmt::Display for HarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HarError::Json(e) => write!(f, "invalid HAR json: {}", e),
+            HarError::InvalidMethod(method) => write!(f, "invalid HTTP method: {}", method),
+            HarError::InvalidUrl(url) => write!(f, "invalid url: {}", url),
+            HarError::InvalidStatus(status) => write!(f, "invalid status code: {}", status),
+            HarError::InvalidDate(date) => write!(f, "invalid startedDateTime: {}", date),
+            HarError::InvalidBase64(e) => write!(f, "invalid base64 content: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for HarError {}
+
+impl From<serde_json::Error> for HarError {
+    fn from(e: serde_json::Error) -> Self {
+        HarError::Json(e)
+    }
+}
+
+impl From<base64::DecodeError> for HarError {
+    fn from(e: base64::DecodeError) -> Self {
+        HarError::InvalidBase64(e)
+    }
+}
+
+/// HAR 최상위 객체
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Har {
+    pub log: HarLog,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HarLog {
+    pub version: String,
+    pub creator: HarCreator,
+    pub entries: Vec<HarEntry>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HarCreator {
+    pub name: String,
+    pub version: String,
+}
+
+impl Default for HarCreator {
+    fn default() -> Self {
+        Self {
+            name: "cheolsu-proxy".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HarEntry {
+    pub started_date_time: String,
+    /// 전체 소요 시간 (ms)
+    pub time: f64,
+    pub request: HarRequest,
+    pub response: HarResponse,
+    #[serde(default)]
+    pub cache: HarCache,
+    pub timings: HarTimings,
+    #[serde(
+        rename = "serverIPAddress",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub server_ip_address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connection: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HarRequest {
+    pub method: String,
+    pub url: String,
+    pub http_version: String,
+    #[serde(default)]
+    pub cookies: Vec<HarCookie>,
+    #[serde(default)]
+    pub headers: Vec<HarHeader>,
+    #[serde(default)]
+    pub query_string: Vec<HarQueryParam>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_data: Option<HarPostData>,
+    #[serde(default = "unknown_size")]
+    pub headers_size: i64,
+    #[serde(default = "unknown_size")]
+    pub body_size: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HarResponse {
+    pub status: u16,
+    #[serde(default)]
+    pub status_text: String,
+    pub http_version: String,
+    #[serde(default)]
+    pub cookies: Vec<HarCookie>,
+    #[serde(default)]
+    pub headers: Vec<HarHeader>,
+    pub content: HarContent,
+    #[serde(rename = "redirectURL", default)]
+    pub redirect_url: String,
+    #[serde(default = "unknown_size")]
+    pub headers_size: i64,
+    #[serde(default = "unknown_size")]
+    pub body_size: i64,
+    /// 업스트림 TLS 세션 정보 (HAR 1.2에 없는 사용자 정의 필드)
+    #[serde(
+        rename = "_upstreamSession",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub upstream_session: Option<UpstreamSession>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HarHeader {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HarQueryParam {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HarCookie {
+    pub name: String,
+    pub value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_only: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secure: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HarPostData {
+    pub mime_type: String,
+    #[serde(default)]
+    pub text: String,
+    /// HAR 1.2에는 요청 본문 인코딩 필드가 없으므로 사용자 정의 필드(`_encoding`)를 사용
+    #[serde(rename = "_encoding", default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HarContent {
+    pub size: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression: Option<i64>,
+    #[serde(default)]
+    pub mime_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct HarCache {}
+
+/// 각 단계별 소요 시간 (ms, 알 수 없는 경우 -1)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HarTimings {
+    #[serde(default = "unknown_timing")]
+    pub blocked: f64,
+    #[serde(default = "unknown_timing")]
+    pub dns: f64,
+    #[serde(default = "unknown_timing")]
+    pub connect: f64,
+    pub send: f64,
+    pub wait: f64,
+    pub receive: f64,
+    #[serde(default = "unknown_timing")]
+    pub ssl: f64,
+}
+
+fn unknown_size() -> i64 {
+    -1
+}
+
+fn unknown_timing() -> f64 {
+    -1.0
+}
+
+impl Har {
+    /// 엔트리 목록으로 HAR 로그 생성
+    pub fn new(entries: Vec<HarEntry>) -> Self {
+        Self {
+            log: HarLog {
+                version: HAR_VERSION.to_string(),
+                creator: HarCreator::default(),
+                entries,
+                comment: None,
+            },
+        }
+    }
+
+    /// 캡처된 `RequestInfo` 목록으로 HAR 로그 생성 (요청이 없는 항목은 건너뜀)
+    pub fn from_request_infos<'a>(infos: impl IntoIterator<Item = &'a RequestInfo>) -> Self {
+        Self::new(
+            infos
+                .into_iter()
+                .filter_map(|RequestInfo(req, res)| {
+                    req.as_ref()
+                        .map(|req| HarEntry::from_client(req, res.as_ref()))
+                })
+                .collect(),
+        )
+    }
+
+    /// `ProxiedRequest`/`ProxiedResponse` 쌍으로 HAR 로그 생성
+    pub fn from_exchanges<'a>(
+        exchanges: impl IntoIterator<Item = (&'a ProxiedRequest, Option<&'a ProxiedResponse>)>,
+    ) -> Self {
+        Self::new(
+            exchanges
+                .into_iter()
+                .map(|(req, res)| HarEntry::from_proxied(req, res))
+                .collect(),
+        )
+    }
+
+    /// HAR JSON 문자열 파싱
+    pub fn from_json(json: &str) -> Result<Self, HarError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// HAR JSON 문자열로 직렬화
+    pub fn to_json(&self) -> Result<String, HarError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// HAR 엔트리를 UI에서 사용하는 `RequestInfo` 목록으로 변환
+    pub fn to_request_infos(&self) -> Result<Vec<RequestInfo>, HarError> {
+        self.log
+            .entries
+            .iter()
+            .map(HarEntry::to_request_info)
+            .collect()
+    }
+}
+
+impl HarEntry {
+    /// 클라이언트(타우리 UI)용 요청/응답으로 HAR 엔트리 생성
+    ///
+    /// 응답 본문은 이미 압축 해제된 상태이므로 `content.text`에 그대로 기록합니다.
+    pub fn from_client(req: &ClientRequest, res: Option<&ClientResponse>) -> Self {
+        let total_ms = res
+            .map(|res| nanos_to_millis(res.time() - req.time()).max(0.0))
+            .unwrap_or(0.0);
+
+        Self {
+            started_date_time: format_started_date_time(req.time()),
+            time: total_ms,
+            request: har_request(req),
+            response: res.map(har_response).unwrap_or_else(failed_response),
+            cache: HarCache::default(),
+            timings: har_timings(res.and_then(ClientResponse::timings), total_ms),
+            server_ip_address: None,
+            connection: None,
+            comment: truncation_comment(req, res),
+        }
+    }
+
+    /// 프록시에서 캡처한 요청/응답 쌍으로 HAR 엔트리 생성
+    pub fn from_proxied(req: &ProxiedRequest, res: Option<&ProxiedResponse>) -> Self {
+        let req = req.clone().for_client();
+        let res = res.map(|res| res.clone().for_client());
+        Self::from_client(&req, res.as_ref())
+    }
+
+    /// HAR 엔트리를 `ProxiedRequest`/`ProxiedResponse` 쌍으로 복원
+    ///
+    /// 상태 코드가 0인 응답(연결 실패 등)은 `None`으로 복원됩니다.
+    pub fn to_proxied(&self) -> Result<(ProxiedRequest, Option<ProxiedResponse>), HarError> {
+        let started = parse_started_date_time(&self.started_date_time)?;
+
+        let method = Method::from_bytes(self.request.method.as_bytes())
+            .map_err(|_| HarError::InvalidMethod(self.request.method.clone()))?;
+        let uri: Uri = self
+            .request
+            .url
+            .parse()
+            .map_err(|_| HarError::InvalidUrl(self.request.url.clone()))?;
+
+        let request_body = match &self.request.post_data {
+            Some(post_data) => decode_text(&post_data.text, post_data.encoding.as_deref())?,
+            None => Bytes::new(),
+        };
+
+        // 기록된 본문보다 원래 크기가 크면 일부만 캡처된 본문
+        let request_size = self.request.body_size;
+        let request_truncated = truncation(request_size, &request_body);
+        let mut request = ProxiedRequest::new(
+            method,
+            uri,
+            parse_http_version(&self.request.http_version),
+            header_map(&self.request.headers),
+            request_body,
+            started,
+        );
+        if let Some(truncated) = request_truncated {
+            request = request.with_truncated(truncated);
+        }
+
+        if self.response.status == 0 {
+            return Ok((request, None));
+        }
+
+        let status = StatusCode::from_u16(self.response.status)
+            .map_err(|_| HarError::InvalidStatus(self.response.status))?;
+
+        let content = &self.response.content;
+        let response_body = match &content.text {
+            Some(text) => decode_text(text, content.encoding.as_deref())?,
+            None => Bytes::new(),
+        };
+
+        // content.text는 압축 해제된 본문이므로 인코딩 관련 헤더를 본문에 맞게 정리
+        let mut headers = header_map(&self.response.headers);
+        headers.remove(header::CONTENT_ENCODING);
+        if headers.contains_key(header::CONTENT_LENGTH) {
+            headers.insert(
+                header::CONTENT_LENGTH,
+                HeaderValue::from(response_body.len()),
+            );
+        }
+
+        let response_truncated = truncation(content.size, &response_body);
+        let mut response = ProxiedResponse::new(
+            status,
+            parse_http_version(&self.response.http_version),
+            headers,
+            response_body,
+            started + millis_to_nanos(self.time),
+        )
+        .with_timings(self.timings.to_timings());
+        if let Some(truncated) = response_truncated {
+            response = response.with_truncated(truncated);
+        }
+        if let Some(upstream_session) = &self.response.upstream_session {
+            response = response.with_upstream_session(upstream_session.clone());
+        }
+
+        Ok((
+            request.with_timings(self.timings.to_timings()),
+            Some(response),
+        ))
+    }
+
+    /// HAR 엔트리를 UI에서 사용하는 `RequestInfo`로 변환
+    pub fn to_request_info(&self) -> Result<RequestInfo, HarError> {
+        let (req, res) = self.to_proxied()?;
+        Ok(RequestInfo(
+            Some(req.for_client()),
+            res.map(ProxiedResponse::for_client),
+        ))
+    }
+}
+
+impl HarTimings {
+    /// 구간별 소요 시간으로 변환 (-1은 측정하지 못한 구간)
+    fn to_timings(&self) -> Timings {
+        let known = |value: f64| (value >= 0.0).then_some(value);
+        let ssl = known(self.ssl);
+
+        Timings {
+            dns: known(self.dns),
+            // HAR의 connect는 ssl을 포함
+            connect: known(self.connect).map(|connect| (connect - ssl.unwrap_or(0.0)).max(0.0)),
+            ssl,
+            wait: known(self.wait),
+            receive: known(self.receive),
+        }
+    }
+}
+
+/// 구간별 소요 시간으로 HAR timings 생성 (없으면 전체 시간을 wait로 기록)
+fn har_timings(timings: Option<&Timings>, total_ms: f64) -> HarTimings {
+    let Some(timings) = timings else {
+        return HarTimings {
+            blocked: -1.0,
+            dns: -1.0,
+            connect: -1.0,
+            send: 0.0,
+            wait: total_ms,
+            receive: 0.0,
+            ssl: -1.0,
+        };
+    };
+
+    let unknown = |value: Option<f64>| value.unwrap_or(-1.0);
+    HarTimings {
+        blocked: -1.0,
+        dns: unknown(timings.dns),
+        connect: unknown(
+            timings
+                .connect
+                .map(|connect| connect + timings.ssl.unwrap_or(0.0)),
+        ),
+        send: 0.0,
+        wait: timings.wait.unwrap_or(total_ms),
+        receive: timings.receive.unwrap_or(0.0),
+        ssl: unknown(timings.ssl),
+    }
+}
+
+fn har_request(req: &ClientRequest) -> HarRequest {
+    let headers = har_headers(req.headers());
+
+    let post_data = if req.body().is_empty() {
+        None
+    } else {
+        let (text, encoding) = encode_text(req.body(), req.data_type().is_binary());
+        Some(HarPostData {
+            mime_type: content_type(req.headers())
+                .unwrap_or(req.mime_type())
+                .to_string(),
+            text,
+            encoding,
+        })
+    };
+
+    HarRequest {
+        method: req.method().to_string(),
+        url: req.uri().to_string(),
+        http_version: http_version_str(req.version()).to_string(),
+        cookies: request_cookies(req.headers()),
+        headers,
+        query_string: query_params(req.uri()),
+        post_data,
+        headers_size: -1,
+        body_size: body_size(req.body(), req.truncated()),
+    }
+}
+
+fn har_response(res: &ClientResponse) -> HarResponse {
+    let (text, encoding) = encode_text(res.body(), res.data_type().is_binary());
+
+    HarResponse {
+        status: res.status().as_u16(),
+        status_text: res.status().canonical_reason().unwrap_or("").to_string(),
+        http_version: http_version_str(res.version()).to_string(),
+        cookies: response_cookies(res.headers()),
+        headers: har_headers(res.headers()),
+        content: HarContent {
+            size: body_size(res.body(), res.truncated()),
+            compression: None,
+            mime_type: content_type(res.headers())
+                .unwrap_or(res.mime_type())
+                .to_string(),
+            text: Some(text),
+            encoding,
+        },
+        redirect_url: res
+            .headers()
+            .get(header::LOCATION)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("")
+            .to_string(),
+        headers_size: -1,
+        body_size: -1,
+        upstream_session: res.upstream_session().cloned(),
+    }
+}
+
+/// 응답을 받지 못한 요청용 HAR 응답 (브라우저와 동일하게 상태 코드 0 사용)
+fn failed_response() -> HarResponse {
+    HarResponse {
+        status: 0,
+        status_text: String::new(),
+        http_version: String::new(),
+        cookies: Vec::new(),
+        headers: Vec::new(),
+        content: HarContent {
+            size: 0,
+            compression: None,
+            mime_type: String::new(),
+            text: None,
+            encoding: None,
+        },
+        redirect_url: String::new(),
+        headers_size: -1,
+        body_size: -1,
+        upstream_session: None,
+    }
+}
+
+/// 본문의 원래 크기 (일부만 캡처되었으면 전달된 전체 크기)
+fn body_size(body: &Bytes, truncated: Option<&BodyTruncation>) -> i64 {
+    truncated.map_or(body.len() as u64, |truncated| truncated.size) as i64
+}
+
+/// 기록된 HAR 크기가 본문보다 크면 일부만 캡처된 본문으로 복원
+fn truncation(size: i64, body: &Bytes) -> Option<BodyTruncation> {
+    let size = u64::try_from(size).ok()?;
+    (size > body.len() as u64).then_some(BodyTruncation {
+        size,
+        spill_path: None,
+    })
+}
+
+/// 일부만 캡처된 본문이 있으면 HAR 엔트리 주석으로 표시
+fn truncation_comment(req: &ClientRequest, res: Option<&ClientResponse>) -> Option<String> {
+    let note = |name: &str, body: &Bytes, truncated: &BodyTruncation| {
+        format!(
+            "{} body truncated ({} of {} bytes captured)",
+            name,
+            body.len(),
+            truncated.size
+        )
+    };
+
+    let mut notes = Vec::new();
+    if let Some(truncated) = req.truncated() {
+        notes.push(note("request", req.body(), truncated));
+    }
+    if let Some((res, truncated)) = res.and_then(|res| Some((res, res.truncated()?))) {
+        notes.push(note("response", res.body(), truncated));
+    }
+
+    (!notes.is_empty()).then(|| notes.join("; "))
+}
+
+/// 본문을 HAR 텍스트로 변환 (바이너리이거나 UTF-8이 아니면 base64)
+fn encode_text(body: &Bytes, is_binary: bool) -> (String, Option<String>) {
+    match std::str::from_utf8(body) {
+        Ok(text) if !is_binary => (text.to_string(), None),
+        _ => (BASE64.encode(body), Some(BASE64_ENCODING.to_string())),
+    }
+}
+
+fn decode_text(text: &str, encoding: Option<&str>) -> Result<Bytes, HarError> {
+    match encoding {
+        Some(encoding) if encoding.eq_ignore_ascii_case(BASE64_ENCODING) => {
+            Ok(Bytes::from(BASE64.decode(text.trim())?))
+        }
+        _ => Ok(Bytes::from(text.to_string())),
+    }
+}
+
+fn content_type(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+}
+
+fn har_headers(headers: &HeaderMap) -> Vec<HarHeader> {
+    headers
+        .iter()
+        .map(|(name, value)| HarHeader {
+            name: name.as_str().to_string(),
+            value: String::from_utf8_lossy(value.as_bytes()).into_owned(),
+        })
+        .collect()
+}
+
+/// HAR 헤더 목록을 HeaderMap으로 변환 (잘못된 헤더는 무시)
+fn header_map(headers: &[HarHeader]) -> HeaderMap {
+    let mut map = HeaderMap::new();
+    for har_header in headers {
+        // HTTP/2 의사 헤더(:authority 등)는 HeaderMap에 넣을 수 없음
+        if har_header.name.starts_with(':') {
+            continue;
+        }
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(har_header.name.as_bytes()),
+            HeaderValue::from_str(&har_header.value),
+        ) {
+            map.append(name, value);
+        }
+    }
+    map
+}
+
+fn query_params(uri: &Uri) -> Vec<HarQueryParam> {
+    uri.query()
+        .map(|query| {
+            query
+                .split('&')
+                .filter(|pair| !pair.is_empty())
+                .map(|pair| {
+                    let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+                    HarQueryParam {
+                        name: name.to_string(),
+                        value: value.to_string(),
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn request_cookies(headers: &HeaderMap) -> Vec<HarCookie> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(';'))
+        .filter_map(|pair| {
+            let (name, value) = pair.trim().split_once('=')?;
+            Some(cookie(name, value))
+        })
+        .collect()
+}
+
+fn response_cookies(headers: &HeaderMap) -> Vec<HarCookie> {
+    headers
+        .get_all(header::SET_COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .filter_map(|set_cookie| {
+            let mut parts = set_cookie.split(';');
+            let (name, value) = parts.next()?.trim().split_once('=')?;
+            let mut cookie = cookie(name, value);
+
+            for attribute in parts {
+                let (key, value) = attribute
+                    .trim()
+                    .split_once('=')
+                    .unwrap_or((attribute.trim(), ""));
+                match key.to_lowercase().as_str() {
+                    "path" => cookie.path = Some(value.to_string()),
+                    "domain" => cookie.domain = Some(value.to_string()),
+                    "expires" => cookie.expires = Some(value.to_string()),
+                    "httponly" => cookie.http_only = Some(true),
+                    "secure" => cookie.secure = Some(true),
+                    _ => {}
+                }
+            }
+
+            Some(cookie)
+        })
+        .collect()
+}
+
+fn cookie(name: &str, value: &str) -> HarCookie {
+    HarCookie {
+        name: name.trim().to_string(),
+        value: value.trim().to_string(),
+        path: None,
+        domain: None,
+        expires: None,
+        http_only: None,
+        secure: None,
+    }
+}
+
+fn http_version_str(version: &Version) -> &'static str {
+    match *version {
+        Version::HTTP_09 => "HTTP/0.9",
+        Version::HTTP_10 => "HTTP/1.0",
+        Version::HTTP_2 => "HTTP/2.0",
+        Version::HTTP_3 => "HTTP/3.0",
+        _ => "HTTP/1.1",
+    }
+}
+
+/// HAR의 httpVersion 문자열 파싱 (브라우저별 표기 차이를 허용, 알 수 없으면 HTTP/1.1)
+fn parse_http_version(version: &str) -> Version {
+    match version.to_ascii_lowercase().as_str() {
+        "http/0.9" => Version::HTTP_09,
+        "http/1.0" => Version::HTTP_10,
+        "h2" | "http/2" | "http/2.0" => Version::HTTP_2,
+        "h3" | "http/3" | "http/3.0" => Version::HTTP_3,
+        _ => Version::HTTP_11,
+    }
+}
+
+fn format_started_date_time(time_nanos: i64) -> String {
+    DateTime::from_timestamp_nanos(time_nanos).to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+fn parse_started_date_time(started: &str) -> Result<i64, HarError> {
+    DateTime::parse_from_rfc3339(started)
+        .ok()
+        .and_then(|date_time| date_time.timestamp_nanos_opt())
+        .ok_or_else(|| HarError::InvalidDate(started.to_string()))
+}
+
+fn nanos_to_millis(nanos: i64) -> f64 {
+    nanos as f64 / 1_000_000.0
+}
+
+fn millis_to_nanos(millis: f64) -> i64 {
+    (millis.max(0.0) * 1_000_000.0) as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DataType;
+
+    const STARTED: i64 = 1_700_000_000_123_000_000;
+
+    fn exchange(
+        response_headers: HeaderMap,
+        response_body: Bytes,
+    ) -> (ProxiedRequest, ProxiedResponse) {
+        let mut request_headers = HeaderMap::new();
+        request_headers.insert(header::COOKIE, HeaderValue::from_static("a=1; b=2"));
+        request_headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+
+        let request = ProxiedRequest::new(
+            Method::POST,
+            "https://example.com/api?x=1&y".parse().unwrap(),
+            Version::HTTP_11,
+            request_headers,
+            Bytes::from(r#"{"hello":"world"}"#),
+            STARTED,
+        );
+        let response = ProxiedResponse::new(
+            StatusCode::OK,
+            Version::HTTP_11,
+            response_headers,
+            response_body,
+            STARTED + 250_000_000,
+        );
+
+        (request, response)
+    }
+
+    #[test]
+    fn test_text_exchange_round_trip() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        let (request, response) = exchange(headers, Bytes::from(r#"{"ok":true}"#));
+
+        let har = Har::from_exchanges([(&request, Some(&response))]);
+        let entry = &har.log.entries[0];
+
+        assert_eq!(har.log.version, HAR_VERSION);
+        assert_eq!(entry.started_date_time, "2023-11-14T22:13:20.123Z");
+        assert_eq!(entry.time, 250.0);
+        assert_eq!(entry.request.cookies.len(), 2);
+        assert_eq!(entry.request.query_string.len(), 2);
+        assert_eq!(
+            entry.response.content.text.as_deref(),
+            Some(r#"{"ok":true}"#)
+        );
+        assert_eq!(entry.response.content.encoding, None);
+
+        let parsed = Har::from_json(&har.to_json().unwrap()).unwrap();
+        let (restored_request, restored_response) = parsed.log.entries[0].to_proxied().unwrap();
+        let restored_response = restored_response.unwrap();
+
+        assert_eq!(restored_request.method(), request.method());
+        assert_eq!(restored_request.uri(), request.uri());
+        assert_eq!(restored_request.body(), request.body());
+        assert_eq!(restored_request.time(), STARTED);
+        assert_eq!(restored_response.status(), response.status());
+        assert_eq!(restored_response.body(), response.body());
+        assert_eq!(restored_response.time(), response.time());
+        assert_eq!(restored_response.data_type(), &DataType::Json);
+    }
+
+    #[test]
+    fn test_binary_body_uses_base64() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("image/png"));
+        let png = Bytes::from_static(b"\x89PNG\r\n\x1a\n\x00\xff");
+        let (request, response) = exchange(headers, png.clone());
+
+        let entry = HarEntry::from_proxied(&request, Some(&response));
+        assert_eq!(entry.response.content.encoding.as_deref(), Some("base64"));
+        assert_eq!(entry.response.content.mime_type, "image/png");
+
+        let (_, restored_response) = entry.to_proxied().unwrap();
+        assert_eq!(restored_response.unwrap().body(), &png);
+    }
+
+    #[test]
+    fn test_compressed_response_is_stored_decoded() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(br#"{"zipped":1}"#).unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(header::CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+        let (request, response) = exchange(headers, Bytes::from(encoder.finish().unwrap()));
+
+        let entry = HarEntry::from_proxied(&request, Some(&response));
+        assert_eq!(
+            entry.response.content.text.as_deref(),
+            Some(r#"{"zipped":1}"#)
+        );
+
+        let RequestInfo(_, restored_response) = entry.to_request_info().unwrap();
+        let restored_response = restored_response.unwrap();
+        assert!(!restored_response
+            .headers()
+            .contains_key(header::CONTENT_ENCODING));
+        assert_eq!(restored_response.body(), &Bytes::from(r#"{"zipped":1}"#));
+    }
+
+    #[test]
+    fn test_missing_response_round_trip() {
+        let (request, _) = exchange(HeaderMap::new(), Bytes::new());
+
+        let entry = HarEntry::from_proxied(&request, None);
+        assert_eq!(entry.response.status, 0);
+
+        let RequestInfo(restored_request, restored_response) = entry.to_request_info().unwrap();
+        assert!(restored_request.is_some());
+        assert!(restored_response.is_none());
+    }
+
+    #[test]
+    fn test_truncated_body_round_trip() {
+        let (request, response) = exchange(HeaderMap::new(), Bytes::from_static(b"part"));
+        let response = response.with_truncated(BodyTruncation {
+            size: 1024,
+            spill_path: Some("/tmp/body".to_string()),
+        });
+
+        let entry = HarEntry::from_proxied(&request, Some(&response));
+        assert_eq!(entry.response.content.size, 1024);
+        assert_eq!(
+            entry.comment.as_deref(),
+            Some("response body truncated (4 of 1024 bytes captured)")
+        );
+
+        let (restored_request, restored_response) = entry.to_proxied().unwrap();
+        assert!(restored_request.truncated().is_none());
+        let truncated = restored_response.unwrap().truncated().cloned().unwrap();
+        assert_eq!(truncated.size, 1024);
+        assert_eq!(truncated.spill_path, None);
+    }
+
+    #[test]
+    fn test_timings_round_trip() {
+        let (request, response) = exchange(HeaderMap::new(), Bytes::new());
+        let timings = Timings {
+            dns: Some(3.0),
+            connect: Some(10.0),
+            ssl: Some(20.0),
+            wait: Some(150.0),
+            receive: Some(40.0),
+        };
+        let response = response.with_timings(timings);
+
+        let entry = HarEntry::from_proxied(&request, Some(&response));
+        assert_eq!(entry.timings.connect, 30.0);
+        assert_eq!(entry.timings.ssl, 20.0);
+        assert_eq!(entry.timings.wait, 150.0);
+
+        let (_, restored_response) = entry.to_proxied().unwrap();
+        assert_eq!(restored_response.unwrap().timings(), Some(&timings));
+
+        // 측정값이 없으면 전체 시간을 wait로 기록
+        let entry = HarEntry::from_proxied(
+            &request,
+            Some(&response.clone().with_timings(Timings::default())),
+        );
+        assert_eq!(entry.timings.dns, -1.0);
+        assert_eq!(entry.timings.wait, 250.0);
+    }
+
+    #[test]
+    fn test_upstream_session_round_trip() {
+        let (request, response) = exchange(HeaderMap::new(), Bytes::new());
+        let session = UpstreamSession {
+            version: Some("TLS 1.3".to_string()),
+            cipher_suite: Some("TLS13_AES_128_GCM_SHA256".to_string()),
+            alpn: Some("h2".to_string()),
+            certificates: Vec::new(),
+        };
+        let response = response.with_upstream_session(session.clone());
+
+        let har = Har::from_exchanges([(&request, Some(&response))]);
+        let json = har.to_json().unwrap();
+        assert!(json.contains("\"_upstreamSession\""));
+
+        let parsed = Har::from_json(&json).unwrap();
+        let (_, restored_response) = parsed.log.entries[0].to_proxied().unwrap();
+        assert_eq!(
+            restored_response.unwrap().upstream_session(),
+            Some(&session)
+        );
+    }
+
+    #[test]
+    fn test_parse_http_version() {
+        assert_eq!(parse_http_version("h2"), Version::HTTP_2);
+        assert_eq!(parse_http_version("HTTP/2.0"), Version::HTTP_2);
+        assert_eq!(parse_http_version("http/1.0"), Version::HTTP_10);
+        assert_eq!(parse_http_version("unknown"), Version::HTTP_11);
+    }
+}
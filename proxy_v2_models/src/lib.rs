@@ -9,6 +9,26 @@ pub use http::{HeaderMap, Method, StatusCode, Uri, Version};
 pub mod data_type;
 pub use data_type::{decompress_brotli, decompress_gzip, detect_data_type, DataType};
 
+// HAR 내보내기/가져오기 모듈
+pub mod har;
+pub use har::{Har, HarEntry, HarError};
+
+// WebSocket 프레임 캡처 모듈
+pub mod websocket;
+pub use websocket::{WebSocketDirection, WebSocketFrame, WebSocketOpcode};
+
+// Server-Sent Events 캡처 모듈
+pub mod sse;
+pub use sse::ServerSentEvent;
+
+// 코드 스니펫 생성/cURL 파싱 모듈
+pub mod snippet;
+pub use snippet::{parse_curl, CurlError, SnippetLanguage};
+
+// 업스트림 TLS 세션/인증서 정보 모듈
+pub mod tls;
+pub use tls::{CertificateInfo, CertificateParseError, UpstreamSession};
+
 /// 압축된 body를 해제하는 헬퍼 함수
 fn decompress_body_if_needed(headers: &HeaderMap, body: &Bytes) -> Vec<u8> {
     // Content-Encoding 헤더 확인
@@ -41,6 +61,77 @@ fn decompress_body_if_needed(headers: &HeaderMap, body: &Bytes) -> Vec<u8> {
     body.to_vec()
 }
 
+/// 본문의 데이터 타입을 감지하고, JSON이면 파싱 시도
+fn parse_body(headers: &HeaderMap, body: &Bytes) -> (DataType, Option<serde_json::Value>) {
+    let data_type = detect_data_type(headers, body);
+    if data_type != DataType::Json {
+        return (data_type, None);
+    }
+
+    // 압축 해제 (필요한 경우)
+    let body_to_parse = decompress_body_if_needed(headers, body);
+    let body_json = std::str::from_utf8(&body_to_parse)
+        .ok()
+        .and_then(|body_str| serde_json::from_str(body_str).ok());
+    (data_type, body_json)
+}
+
+/// 교환의 구간별 소요 시간 (ms, 측정하지 못한 구간은 None)
+///
+/// 연결 단계(`dns`, `connect`, `ssl`)는 이 요청을 위해 새로 연결한 경우에만 있습니다.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub struct Timings {
+    /// DNS 조회
+    pub dns: Option<f64>,
+    /// TCP 연결
+    pub connect: Option<f64>,
+    /// TLS 핸드셰이크
+    pub ssl: Option<f64>,
+    /// 요청 전송부터 응답 헤더 수신까지 (time to first byte)
+    pub wait: Option<f64>,
+    /// 응답 본문 수신
+    pub receive: Option<f64>,
+}
+
+/// 캡처 정책에 따라 일부만 기록된 본문의 정보
+///
+/// 본문(`body`)에는 앞부분만 담기며(캡처 대상이 아니면 비어 있음), 임시 파일로 저장한 경우
+/// `spill_path`에 전체 본문이 있습니다.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BodyTruncation {
+    /// 실제로 전달된 본문의 전체 크기 (bytes)
+    pub size: u64,
+    /// 전체 본문을 저장한 임시 파일 경로
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spill_path: Option<String>,
+}
+
+/// 업스트림 서버 인증서 검증 결과
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CertificateStatus {
+    Valid,
+    Expired,
+    NotYetValid,
+    HostnameMismatch,
+    SelfSigned,
+    UnknownIssuer,
+    Revoked,
+    Invalid,
+}
+
+/// 업스트림 TLS 연결의 인증서 검증 정보
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UpstreamTls {
+    /// 인증서 검증 결과
+    pub status: CertificateStatus,
+    /// 검증 예외 호스트라서 검증 결과와 관계없이 연결했는지 여부
+    pub insecure: bool,
+    /// 검증 실패 사유 (`invalid`인 경우)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ProxiedRequest {
     #[serde(with = "http_serde::method")]
@@ -54,6 +145,26 @@ pub struct ProxiedRequest {
     body: Bytes,
     time: i64,
     id: String, // 고유 ID 추가
+    #[serde(
+        with = "http_serde::option::uri",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    original_uri: Option<Uri>, // Map Remote 적용 전 원래 URI
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timings: Option<Timings>, // 응답까지의 구간별 소요 시간
+    #[serde(
+        with = "http_serde::option::version",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    upstream_version: Option<Version>, // 업스트림 서버와 실제로 사용한 HTTP 버전
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    truncated: Option<BodyTruncation>, // 본문이 일부만 캡처된 경우
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    upstream_tls: Option<UpstreamTls>, // 업스트림 서버 인증서 검증 결과 (TLS 연결인 경우)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    client_identity: Option<String>, // 업스트림 연결에 사용한 클라이언트 인증서 이름 (mTLS)
     // 내부 처리용 필드들 (직렬화되지 않음)
     #[serde(skip)]
     data_type: DataType,
@@ -77,21 +188,7 @@ impl ProxiedRequest {
             uuid::Uuid::new_v4().to_string().replace('-', "")
         );
 
-        let data_type = detect_data_type(&headers, &body);
-
-        // JSON 타입인 경우 파싱 시도
-        let body_json = if data_type == data_type::DataType::Json {
-            // 압축 해제 (필요한 경우)
-            let body_to_parse = decompress_body_if_needed(&headers, &body);
-
-            if let Ok(body_str) = std::str::from_utf8(&body_to_parse) {
-                serde_json::from_str(body_str).ok()
-            } else {
-                None
-            }
-        } else {
-            None
-        };
+        let (data_type, body_json) = parse_body(&headers, &body);
 
         Self {
             method,
@@ -101,6 +198,12 @@ impl ProxiedRequest {
             body,
             time,
             id,
+            original_uri: None,
+            timings: None,
+            upstream_version: None,
+            truncated: None,
+            upstream_tls: None,
+            client_identity: None,
             data_type,
             body_json,
         }
@@ -134,6 +237,81 @@ impl ProxiedRequest {
         &self.id
     }
 
+    /// 본문 교체 (본문을 모으지 않고 전달한 요청은 본문이 끝난 뒤 기록)
+    pub fn with_body(mut self, body: Bytes) -> Self {
+        (self.data_type, self.body_json) = parse_body(&self.headers, &body);
+        self.body = body;
+        self
+    }
+
+    /// Map Remote 적용 전 원래 URI (적용되지 않았으면 None)
+    pub fn original_uri(&self) -> Option<&Uri> {
+        self.original_uri.as_ref()
+    }
+
+    /// Map Remote 적용 전 원래 URI 설정
+    pub fn with_original_uri(mut self, original_uri: Uri) -> Self {
+        self.original_uri = Some(original_uri);
+        self
+    }
+
+    /// 구간별 소요 시간 (응답을 받기 전이면 None)
+    pub fn timings(&self) -> Option<&Timings> {
+        self.timings.as_ref()
+    }
+
+    /// 구간별 소요 시간 설정
+    pub fn with_timings(mut self, timings: Timings) -> Self {
+        self.timings = Some(timings);
+        self
+    }
+
+    /// 업스트림 서버와 사용한 HTTP 버전 (응답을 받기 전이면 None)
+    ///
+    /// 클라이언트와 사용한 버전은 [`version`](Self::version)입니다.
+    pub fn upstream_version(&self) -> Option<&Version> {
+        self.upstream_version.as_ref()
+    }
+
+    /// 업스트림 서버와 사용한 HTTP 버전 설정
+    pub fn with_upstream_version(mut self, version: Version) -> Self {
+        self.upstream_version = Some(version);
+        self
+    }
+
+    /// 본문이 일부만 캡처되었으면 그 정보 (전체가 캡처되었으면 None)
+    pub fn truncated(&self) -> Option<&BodyTruncation> {
+        self.truncated.as_ref()
+    }
+
+    /// 본문이 일부만 캡처되었음을 표시
+    pub fn with_truncated(mut self, truncated: BodyTruncation) -> Self {
+        self.truncated = Some(truncated);
+        self
+    }
+
+    /// 업스트림 서버 인증서 검증 결과 (TLS로 연결하지 않았거나 기록되지 않았으면 None)
+    pub fn upstream_tls(&self) -> Option<&UpstreamTls> {
+        self.upstream_tls.as_ref()
+    }
+
+    /// 업스트림 서버 인증서 검증 결과 설정
+    pub fn with_upstream_tls(mut self, upstream_tls: UpstreamTls) -> Self {
+        self.upstream_tls = Some(upstream_tls);
+        self
+    }
+
+    /// 업스트림 연결에 사용한 클라이언트 인증서 이름 (사용하지 않았으면 None)
+    pub fn client_identity(&self) -> Option<&str> {
+        self.client_identity.as_deref()
+    }
+
+    /// 업스트림 연결에 사용한 클라이언트 인증서 이름 설정
+    pub fn with_client_identity(mut self, client_identity: impl Into<String>) -> Self {
+        self.client_identity = Some(client_identity.into());
+        self
+    }
+
     pub fn data_type(&self) -> &DataType {
         &self.data_type
     }
@@ -153,6 +331,12 @@ impl ProxiedRequest {
         &self.body_json
     }
 
+    /// JSON으로 변환한 본문 설정 (gRPC 메시지처럼 본문 자체는 JSON이 아닌 경우)
+    pub fn with_body_json(mut self, body_json: serde_json::Value) -> Self {
+        self.body_json = Some(body_json);
+        self
+    }
+
     /// 클라이언트(타우리 UI)용으로 변환
     pub fn for_client(self) -> ClientRequest {
         ClientRequest {
@@ -163,6 +347,12 @@ impl ProxiedRequest {
             body: self.body,
             time: self.time,
             id: self.id,
+            original_uri: self.original_uri,
+            timings: self.timings,
+            upstream_version: self.upstream_version,
+            truncated: self.truncated,
+            upstream_tls: self.upstream_tls,
+            client_identity: self.client_identity,
             data_type: self.data_type,
             body_json: self.body_json,
         }
@@ -183,6 +373,26 @@ pub struct ClientRequest {
     body: Bytes,
     time: i64,
     id: String,
+    #[serde(
+        with = "http_serde::option::uri",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    original_uri: Option<Uri>, // Map Remote 적용 전 원래 URI
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timings: Option<Timings>, // 응답까지의 구간별 소요 시간
+    #[serde(
+        with = "http_serde::option::version",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    upstream_version: Option<Version>, // 업스트림 서버와 실제로 사용한 HTTP 버전
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    truncated: Option<BodyTruncation>, // 본문이 일부만 캡처된 경우
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    upstream_tls: Option<UpstreamTls>, // 업스트림 서버 인증서 검증 결과 (TLS 연결인 경우)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    client_identity: Option<String>, // 업스트림 연결에 사용한 클라이언트 인증서 이름 (mTLS)
     data_type: DataType,
     body_json: Option<serde_json::Value>,
 }
@@ -216,6 +426,36 @@ impl ClientRequest {
         &self.id
     }
 
+    /// Map Remote 적용 전 원래 URI (적용되지 않았으면 None)
+    pub fn original_uri(&self) -> Option<&Uri> {
+        self.original_uri.as_ref()
+    }
+
+    /// 구간별 소요 시간 (측정되지 않았으면 None)
+    pub fn timings(&self) -> Option<&Timings> {
+        self.timings.as_ref()
+    }
+
+    /// 업스트림 서버와 사용한 HTTP 버전 (기록되지 않았으면 None)
+    pub fn upstream_version(&self) -> Option<&Version> {
+        self.upstream_version.as_ref()
+    }
+
+    /// 본문이 일부만 캡처되었으면 그 정보 (전체가 캡처되었으면 None)
+    pub fn truncated(&self) -> Option<&BodyTruncation> {
+        self.truncated.as_ref()
+    }
+
+    /// 업스트림 서버 인증서 검증 결과 (TLS로 연결하지 않았거나 기록되지 않았으면 None)
+    pub fn upstream_tls(&self) -> Option<&UpstreamTls> {
+        self.upstream_tls.as_ref()
+    }
+
+    /// 업스트림 연결에 사용한 클라이언트 인증서 이름 (사용하지 않았으면 None)
+    pub fn client_identity(&self) -> Option<&str> {
+        self.client_identity.as_deref()
+    }
+
     pub fn data_type(&self) -> &DataType {
         &self.data_type
     }
@@ -246,6 +486,18 @@ pub struct ProxiedResponse {
     headers: HeaderMap,
     body: Bytes,
     time: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timings: Option<Timings>, // 응답까지의 구간별 소요 시간
+    #[serde(
+        with = "http_serde::option::header_map",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    trailers: Option<HeaderMap>, // 본문 뒤에 전달된 트레일러 (gRPC의 grpc-status 등)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    truncated: Option<BodyTruncation>, // 본문이 일부만 캡처된 경우
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    upstream_session: Option<UpstreamSession>, // 업스트림 TLS 세션 정보 (TLS 연결인 경우)
     // 내부 처리용 필드들 (직렬화되지 않음)
     #[serde(skip)]
     data_type: DataType,
@@ -306,6 +558,10 @@ impl ProxiedResponse {
             headers,
             body,
             time,
+            timings: None,
+            trailers: None,
+            truncated: None,
+            upstream_session: None,
             data_type,
             body_json,
             decompressed_body,
@@ -351,11 +607,61 @@ impl ProxiedResponse {
         &self.body_json
     }
 
+    /// JSON으로 변환한 본문 설정 (gRPC 메시지처럼 본문 자체는 JSON이 아닌 경우)
+    pub fn with_body_json(mut self, body_json: serde_json::Value) -> Self {
+        self.body_json = Some(body_json);
+        self
+    }
+
     /// 압축 해제된 데이터 반환 (타우리 UI용)
     pub fn decompressed_body(&self) -> &Option<Bytes> {
         &self.decompressed_body
     }
 
+    /// 구간별 소요 시간 (측정되지 않았으면 None)
+    pub fn timings(&self) -> Option<&Timings> {
+        self.timings.as_ref()
+    }
+
+    /// 구간별 소요 시간 설정
+    pub fn with_timings(mut self, timings: Timings) -> Self {
+        self.timings = Some(timings);
+        self
+    }
+
+    /// 본문 뒤에 전달된 트레일러 (없으면 None)
+    pub fn trailers(&self) -> Option<&HeaderMap> {
+        self.trailers.as_ref()
+    }
+
+    /// 트레일러 설정
+    pub fn with_trailers(mut self, trailers: HeaderMap) -> Self {
+        self.trailers = Some(trailers);
+        self
+    }
+
+    /// 본문이 일부만 캡처되었으면 그 정보 (전체가 캡처되었으면 None)
+    pub fn truncated(&self) -> Option<&BodyTruncation> {
+        self.truncated.as_ref()
+    }
+
+    /// 본문이 일부만 캡처되었음을 표시
+    pub fn with_truncated(mut self, truncated: BodyTruncation) -> Self {
+        self.truncated = Some(truncated);
+        self
+    }
+
+    /// 업스트림 TLS 세션 정보 (TLS로 연결하지 않았거나 기록되지 않았으면 None)
+    pub fn upstream_session(&self) -> Option<&UpstreamSession> {
+        self.upstream_session.as_ref()
+    }
+
+    /// 업스트림 TLS 세션 정보 설정
+    pub fn with_upstream_session(mut self, upstream_session: UpstreamSession) -> Self {
+        self.upstream_session = Some(upstream_session);
+        self
+    }
+
     /// 클라이언트(타우리 UI)용으로 변환
     pub fn for_client(self) -> ClientResponse {
         ClientResponse {
@@ -364,6 +670,10 @@ impl ProxiedResponse {
             headers: self.headers,
             body: self.decompressed_body.unwrap_or(self.body),
             time: self.time,
+            timings: self.timings,
+            trailers: self.trailers,
+            truncated: self.truncated,
+            upstream_session: self.upstream_session,
             data_type: self.data_type,
             body_json: self.body_json,
         }
@@ -381,6 +691,18 @@ pub struct ClientResponse {
     headers: HeaderMap,
     body: Bytes,
     time: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timings: Option<Timings>, // 응답까지의 구간별 소요 시간
+    #[serde(
+        with = "http_serde::option::header_map",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    trailers: Option<HeaderMap>, // 본문 뒤에 전달된 트레일러 (gRPC의 grpc-status 등)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    truncated: Option<BodyTruncation>, // 본문이 일부만 캡처된 경우
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    upstream_session: Option<UpstreamSession>, // 업스트림 TLS 세션 정보 (TLS 연결인 경우)
     data_type: DataType,
     body_json: Option<serde_json::Value>,
 }
@@ -406,6 +728,26 @@ impl ClientResponse {
         self.time
     }
 
+    /// 구간별 소요 시간 (측정되지 않았으면 None)
+    pub fn timings(&self) -> Option<&Timings> {
+        self.timings.as_ref()
+    }
+
+    /// 본문 뒤에 전달된 트레일러 (없으면 None)
+    pub fn trailers(&self) -> Option<&HeaderMap> {
+        self.trailers.as_ref()
+    }
+
+    /// 본문이 일부만 캡처되었으면 그 정보 (전체가 캡처되었으면 None)
+    pub fn truncated(&self) -> Option<&BodyTruncation> {
+        self.truncated.as_ref()
+    }
+
+    /// 업스트림 TLS 세션 정보 (TLS로 연결하지 않았거나 기록되지 않았으면 None)
+    pub fn upstream_session(&self) -> Option<&UpstreamSession> {
+        self.upstream_session.as_ref()
+    }
+
     pub fn data_type(&self) -> &DataType {
         &self.data_type
     }
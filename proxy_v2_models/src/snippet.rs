@@ -0,0 +1,1062 @@
+//! 캡처한 요청을 재현하는 코드 스니펫 생성과 cURL 명령 파싱
+//!
+//! [`generate`]는 `ClientRequest`를 cURL, HTTPie, JavaScript `fetch`, Python `requests`,
+//! Rust `reqwest` 코드로 변환하고, [`parse_curl`]은 cURL 명령을 다시 `ProxiedRequest`로 변환합니다.
+
+use crate::{ClientRequest, ProxiedRequest};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use bytes::Bytes;
+use http::{header, HeaderMap, HeaderName, HeaderValue, Method, Uri, Version};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// 스니펫 언어
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SnippetLanguage {
+    /// cURL (POSIX 셸)
+    Curl,
+    /// HTTPie (POSIX 셸)
+    Httpie,
+    /// JavaScript `fetch`
+    Fetch,
+    /// Python `requests`
+    Python,
+    /// Rust `reqwest` (async)
+    Reqwest,
+}
+
+/// 요청을 지정한 언어의 코드 스니펫으로 변환
+///
+/// `content-length`처럼 도구가 다시 계산하는 헤더와 URI와 같은 `host` 헤더는 생략합니다.
+/// UTF-8이 아니거나 NUL이 들어 있는 본문은 바이너리로 취급해 각 언어의 바이트 표기로 출력합니다.
+pub fn generate(request: &ClientRequest, language: SnippetLanguage) -> String {
+    let snippet = SnippetRequest::new(request);
+    match language {
+        SnippetLanguage::Curl => snippet.curl(),
+        SnippetLanguage::Httpie => snippet.httpie(),
+        SnippetLanguage::Fetch => snippet.fetch(),
+        SnippetLanguage::Python => snippet.python(),
+        SnippetLanguage::Reqwest => snippet.reqwest(),
+    }
+}
+
+/// 스니펫 생성에 필요한 요청 정보
+struct SnippetRequest<'a> {
+    method: &'a Method,
+    url: String,
+    headers: Vec<(&'a str, String)>,
+    body: Option<Body<'a>>,
+}
+
+/// 스니펫에 넣을 본문
+enum Body<'a> {
+    Text(&'a str),
+    Binary(&'a [u8]),
+}
+
+impl<'a> SnippetRequest<'a> {
+    fn new(request: &'a ClientRequest) -> Self {
+        let authority = request
+            .uri()
+            .authority()
+            .map(|authority| authority.as_str());
+        let headers = request
+            .headers()
+            .iter()
+            .filter(|(name, value)| match *name {
+                &header::CONTENT_LENGTH | &header::TRANSFER_ENCODING => false,
+                &header::HOST => value.to_str().ok() != authority,
+                _ => true,
+            })
+            .map(|(name, value)| {
+                let value = String::from_utf8_lossy(value.as_bytes()).into_owned();
+                (name.as_str(), value)
+            })
+            .collect();
+
+        let body = request.body();
+        let body = if body.is_empty() {
+            None
+        } else {
+            match std::str::from_utf8(body) {
+                Ok(text) if !text.contains('\0') => Some(Body::Text(text)),
+                _ => Some(Body::Binary(body)),
+            }
+        };
+
+        Self {
+            method: request.method(),
+            url: request.uri().to_string(),
+            headers,
+            body,
+        }
+    }
+
+    /// 같은 이름의 헤더를 하나로 합친 목록 (객체/딕셔너리로 표현하는 언어용)
+    fn merged_headers(&self) -> Vec<(&'a str, String)> {
+        let mut merged: Vec<(&'a str, String)> = Vec::new();
+        for (name, value) in &self.headers {
+            match merged
+                .iter_mut()
+                .find(|(merged_name, _)| merged_name == name)
+            {
+                Some((_, merged_value)) => {
+                    let separator = if *name == header::COOKIE { "; " } else { ", " };
+                    merged_value.push_str(separator);
+                    merged_value.push_str(value);
+                }
+                None => merged.push((name, value.clone())),
+            }
+        }
+        merged
+    }
+
+    fn curl(&self) -> String {
+        let mut command = String::from("curl");
+        // 본문이 있으면 curl이 POST로 보내므로 다른 메서드는 항상 `-X`로 지정
+        // (HEAD도 `-I`는 본문 옵션과 함께 쓸 수 없으므로 `-X HEAD`)
+        let default_method = if self.body.is_some() {
+            Method::POST
+        } else {
+            Method::GET
+        };
+        if self.method == Method::HEAD && self.body.is_none() {
+            command.push_str(" -I");
+        } else if self.method != default_method {
+            command.push_str(" -X ");
+            command.push_str(&shell_quote(self.method.as_str()));
+        }
+        command.push(' ');
+        command.push_str(&shell_quote(&self.url));
+
+        let mut lines = vec![command];
+        for (name, value) in &self.headers {
+            lines.push(format!(
+                "-H {}",
+                shell_quote(&format!("{}: {}", name, value))
+            ));
+        }
+        let mut stdin = None;
+        match self.body {
+            Some(Body::Text(text)) => lines.push(format!("--data-raw {}", shell_quote(text))),
+            Some(Body::Binary(bytes)) => {
+                stdin = Some(printf(bytes));
+                lines.push("--data-binary @-".to_string());
+            }
+            None => {}
+        }
+
+        pipe(stdin, shell_lines(&lines))
+    }
+
+    fn httpie(&self) -> String {
+        let mut command = String::from("http");
+        let mut stdin = None;
+        match self.body {
+            Some(Body::Text(text)) => {
+                command.push_str(" --raw ");
+                command.push_str(&shell_quote(text));
+            }
+            Some(Body::Binary(bytes)) => stdin = Some(printf(bytes)),
+            None => {}
+        }
+        command.push(' ');
+        command.push_str(&shell_quote(self.method.as_str()));
+        command.push(' ');
+        command.push_str(&shell_quote(&self.url));
+
+        let mut lines = vec![command];
+        for (name, value) in &self.headers {
+            // 값이 빈 헤더는 `Name;`으로 표기
+            let item = if value.is_empty() {
+                format!("{};", name)
+            } else {
+                format!("{}:{}", name, value)
+            };
+            lines.push(shell_quote(&item));
+        }
+
+        pipe(stdin, shell_lines(&lines))
+    }
+
+    fn fetch(&self) -> String {
+        let mut options = vec![format!("  method: {},", js_string(self.method.as_str()))];
+        let headers = self.merged_headers();
+        if !headers.is_empty() {
+            options.push("  headers: {".to_string());
+            for (name, value) in &headers {
+                options.push(format!("    {}: {},", js_string(name), js_string(value)));
+            }
+            options.push("  },".to_string());
+        }
+        match self.body {
+            Some(Body::Text(text)) => options.push(format!("  body: {},", js_string(text))),
+            Some(Body::Binary(bytes)) => {
+                let bytes: Vec<String> = bytes.iter().map(|byte| byte.to_string()).collect();
+                options.push(format!("  body: new Uint8Array([{}]),", bytes.join(", ")));
+            }
+            None => {}
+        }
+
+        format!(
+            "const response = await fetch({}, {{\n{}\n}});\nconsole.log(response.status);\nconsole.log(await response.text());",
+            js_string(&self.url),
+            options.join("\n")
+        )
+    }
+
+    fn python(&self) -> String {
+        let mut arguments = vec![
+            format!("    {},", js_string(self.method.as_str())),
+            format!("    {},", js_string(&self.url)),
+        ];
+        let headers = self.merged_headers();
+        if !headers.is_empty() {
+            arguments.push("    headers={".to_string());
+            for (name, value) in &headers {
+                arguments.push(format!(
+                    "        {}: {},",
+                    js_string(name),
+                    js_string(value)
+                ));
+            }
+            arguments.push("    },".to_string());
+        }
+        match self.body {
+            // requests는 str 본문을 latin-1로 인코딩하므로 ASCII가 아니면 UTF-8로 직접 인코딩
+            Some(Body::Text(text)) if text.is_ascii() => {
+                arguments.push(format!("    data={},", js_string(text)));
+            }
+            Some(Body::Text(text)) => {
+                arguments.push(format!("    data={}.encode(\"utf-8\"),", js_string(text)));
+            }
+            Some(Body::Binary(bytes)) => {
+                arguments.push(format!("    data={},", byte_string(bytes)))
+            }
+            None => {}
+        }
+
+        format!(
+            "import requests\n\nresponse = requests.request(\n{}\n)\nprint(response.status_code)\nprint(response.text)",
+            arguments.join("\n")
+        )
+    }
+
+    fn reqwest(&self) -> String {
+        let method = match *self.method {
+            Method::GET
+            | Method::POST
+            | Method::PUT
+            | Method::DELETE
+            | Method::HEAD
+            | Method::OPTIONS
+            | Method::CONNECT
+            | Method::PATCH
+            | Method::TRACE => format!("reqwest::Method::{}", self.method),
+            _ => format!(
+                "reqwest::Method::from_bytes({})?",
+                byte_string(self.method.as_str().as_bytes())
+            ),
+        };
+
+        let mut lines = vec![
+            "let client = reqwest::Client::new();".to_string(),
+            "let response = client".to_string(),
+            format!("    .request({}, {:?})", method, self.url),
+        ];
+        for (name, value) in &self.headers {
+            lines.push(format!("    .header({:?}, {:?})", name, value));
+        }
+        match self.body {
+            Some(Body::Text(text)) => lines.push(format!("    .body({:?})", text)),
+            Some(Body::Binary(bytes)) => {
+                lines.push(format!("    .body({}.to_vec())", byte_string(bytes)));
+            }
+            None => {}
+        }
+        lines.push("    .send()".to_string());
+        lines.push("    .await?;".to_string());
+        lines.push("println!(\"{}\", response.status());".to_string());
+        lines.push("println!(\"{}\", response.text().await?);".to_string());
+
+        lines.join("\n")
+    }
+}
+
+/// POSIX 셸 인자로 인용 (안전한 문자만 있으면 그대로)
+fn shell_quote(value: &str) -> String {
+    let safe = |byte: u8| byte.is_ascii_alphanumeric() || b"-_./:=@,+".contains(&byte);
+    if !value.is_empty() && value.bytes().all(safe) {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
+/// 여러 줄로 나눈 셸 명령 (줄 끝에 `\`)
+fn shell_lines(lines: &[String]) -> String {
+    lines.join(" \\\n  ")
+}
+
+/// 바이너리 본문을 표준 입력으로 넘기는 명령 연결
+fn pipe(stdin: Option<String>, command: String) -> String {
+    match stdin {
+        Some(stdin) => format!("{} | {}", stdin, command),
+        None => command,
+    }
+}
+
+/// 바이트를 그대로 출력하는 `printf` 명령 (출력할 수 없는 바이트는 8진수 이스케이프)
+///
+/// 본문이 `-`로 시작해도 옵션으로 해석되지 않도록 `--` 뒤에 형식 문자열을 둡니다.
+fn printf(bytes: &[u8]) -> String {
+    let mut format = String::new();
+    for &byte in bytes {
+        match byte {
+            b'%' => format.push_str("%%"),
+            b'\\' => format.push_str("\\\\"),
+            b'\'' => format.push_str("\\047"),
+            0x20..=0x7e => format.push(byte as char),
+            _ => format.push_str(&format!("\\{:03o}", byte)),
+        }
+    }
+    format!("printf -- '{}'", format)
+}
+
+/// JavaScript/Python 문자열 리터럴 (JSON 문자열 표기는 두 언어 모두에서 유효)
+fn js_string(value: &str) -> String {
+    serde_json::Value::from(value).to_string()
+}
+
+/// Python/Rust 바이트 문자열 리터럴 (`b"..."`)
+fn byte_string(bytes: &[u8]) -> String {
+    let mut literal = String::from("b\"");
+    for &byte in bytes {
+        match byte {
+            b'\n' => literal.push_str("\\n"),
+            b'\r' => literal.push_str("\\r"),
+            b'\t' => literal.push_str("\\t"),
+            b'\\' => literal.push_str("\\\\"),
+            b'"' => literal.push_str("\\\""),
+            0x20..=0x7e => literal.push(byte as char),
+            _ => literal.push_str(&format!("\\x{:02x}", byte)),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// cURL 명령 파싱 중 발생하는 오류
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CurlError {
+    /// `curl`로 시작하지 않는 명령
+    NotCurl,
+    /// 닫히지 않은 따옴표
+    UnterminatedQuote,
+    /// 값이 필요한 옵션에 값이 없음
+    MissingArgument(String),
+    /// 지원하지 않는 옵션
+    UnsupportedOption(String),
+    /// 파일이나 표준 입력에서 읽는 본문
+    FileInput(String),
+    /// URL이 없음
+    MissingUrl,
+    /// URL이 둘 이상
+    MultipleUrls,
+    /// 잘못된 HTTP 메서드
+    InvalidMethod(String),
+    /// 잘못된 URL
+    InvalidUrl(String),
+    /// 잘못된 헤더
+    InvalidHeader(String),
+}
+
+impl fmt::Display for CurlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CurlError::NotCurl => write!(f, "not a curl command"),
+            CurlError::UnterminatedQuote => write!(f, "unterminated quote"),
+            CurlError::MissingArgument(option) => write!(f, "missing argument for {}", option),
+            CurlError::UnsupportedOption(option) => write!(f, "unsupported option: {}", option),
+            CurlError::FileInput(data) => {
+                write!(f, "reading data from files is not supported: {}", data)
+            }
+            CurlError::MissingUrl => write!(f, "missing url"),
+            CurlError::MultipleUrls => write!(f, "multiple urls are not supported"),
+            CurlError::InvalidMethod(method) => write!(f, "invalid HTTP method: {}", method),
+            CurlError::InvalidUrl(url) => write!(f, "invalid url: {}", url),
+            CurlError::InvalidHeader(header) => write!(f, "invalid header: {}", header),
+        }
+    }
+}
+
+impl std::error::Error for CurlError {}
+
+/// cURL 옵션 (짧은 옵션은 긴 이름으로 변환)
+fn long_option(short: char) -> Option<&'static str> {
+    Some(match short {
+        'X' => "request",
+        'H' => "header",
+        'd' => "data",
+        'b' => "cookie",
+        'A' => "user-agent",
+        'e' => "referer",
+        'u' => "user",
+        'I' => "head",
+        'G' => "get",
+        'F' => "form",
+        'T' => "upload-file",
+        'o' => "output",
+        'm' => "max-time",
+        'w' => "write-out",
+        'x' => "proxy",
+        'c' => "cookie-jar",
+        'E' => "cert",
+        'k' => "insecure",
+        'L' => "location",
+        's' => "silent",
+        'S' => "show-error",
+        'v' => "verbose",
+        'i' => "include",
+        'f' => "fail",
+        'N' => "no-buffer",
+        'g' => "globoff",
+        '0' => "http1.0",
+        _ => return None,
+    })
+}
+
+/// 옵션이 값을 받는지 여부 (모르는 옵션이면 None)
+fn takes_value(option: &str) -> Option<bool> {
+    match option {
+        "request" | "header" | "data" | "data-raw" | "data-binary" | "data-ascii"
+        | "data-urlencode" | "cookie" | "user-agent" | "referer" | "user" | "url" | "form"
+        | "upload-file" | "output" | "max-time" | "connect-timeout" | "write-out" | "proxy"
+        | "cookie-jar" | "cacert" | "cert" | "key" | "retry" | "resolve" | "connect-to" => {
+            Some(true)
+        }
+        "head"
+        | "get"
+        | "compressed"
+        | "insecure"
+        | "location"
+        | "silent"
+        | "show-error"
+        | "verbose"
+        | "include"
+        | "fail"
+        | "no-buffer"
+        | "globoff"
+        | "path-as-is"
+        | "http1.0"
+        | "http1.1"
+        | "http2"
+        | "http2-prior-knowledge"
+        | "http3" => Some(false),
+        _ => None,
+    }
+}
+
+/// cURL 명령을 요청으로 변환 (브라우저의 "Copy as cURL (bash)" 형식 지원)
+///
+/// 요청에 영향을 주지 않는 옵션(`--compressed`, `-k`, `-L` 등)은 무시하며, 파일에서 읽는 본문(`-d @file`)과
+/// 멀티파트 폼(`-F`)은 지원하지 않습니다.
+pub fn parse_curl(command: &str) -> Result<ProxiedRequest, CurlError> {
+    let mut args = tokenize(command)?.into_iter();
+    match args.next() {
+        Some(program) if program == b"curl" || program.ends_with(b"/curl") => {}
+        _ => return Err(CurlError::NotCurl),
+    }
+
+    let mut curl = CurlCommand::default();
+    while let Some(arg) = args.next() {
+        if let Some(option) = arg.strip_prefix(b"--").filter(|option| !option.is_empty()) {
+            let option = String::from_utf8_lossy(option).into_owned();
+            let value = match takes_value(&option) {
+                Some(true) => Some(
+                    args.next()
+                        .ok_or_else(|| CurlError::MissingArgument(format!("--{}", option)))?,
+                ),
+                Some(false) => None,
+                None => return Err(CurlError::UnsupportedOption(format!("--{}", option))),
+            };
+            curl.apply(&option, value)?;
+        } else if arg.len() > 1 && arg[0] == b'-' {
+            // `-sSL`처럼 묶인 짧은 옵션과 `-XPOST`처럼 값이 붙은 옵션
+            let cluster = String::from_utf8_lossy(&arg[1..]).into_owned();
+            for (index, short) in cluster.char_indices() {
+                let option = long_option(short)
+                    .ok_or_else(|| CurlError::UnsupportedOption(format!("-{}", short)))?;
+                if takes_value(option) == Some(true) {
+                    let rest = &cluster[index + short.len_utf8()..];
+                    let value = if rest.is_empty() {
+                        args.next()
+                            .ok_or_else(|| CurlError::MissingArgument(format!("-{}", short)))?
+                    } else {
+                        rest.as_bytes().to_vec()
+                    };
+                    curl.apply(option, Some(value))?;
+                    break;
+                }
+                curl.apply(option, None)?;
+            }
+        } else {
+            curl.set_url(arg)?;
+        }
+    }
+
+    curl.into_request()
+}
+
+/// 파싱 중인 cURL 옵션
+#[derive(Default)]
+struct CurlCommand {
+    method: Option<String>,
+    url: Option<String>,
+    headers: Vec<Vec<u8>>,
+    data: Vec<Vec<u8>>,
+    cookies: Vec<String>,
+    user: Option<String>,
+    head: bool,
+    get: bool,
+    compressed: bool,
+    version: Option<Version>,
+}
+
+impl CurlCommand {
+    fn apply(&mut self, option: &str, value: Option<Vec<u8>>) -> Result<(), CurlError> {
+        let value = value.unwrap_or_default();
+        let text = || String::from_utf8_lossy(&value).into_owned();
+        match option {
+            "request" => self.method = Some(text()),
+            "header" => self.headers.push(value),
+            "user-agent" => self
+                .headers
+                .push([b"User-Agent: ".as_slice(), &value].concat()),
+            "referer" => self
+                .headers
+                .push([b"Referer: ".as_slice(), &value].concat()),
+            "data" | "data-ascii" | "data-binary" => {
+                if value.starts_with(b"@") {
+                    return Err(CurlError::FileInput(text()));
+                }
+                self.data.push(value);
+            }
+            "data-raw" => self.data.push(value),
+            "data-urlencode" => self.data.push(url_encode_data(&value)?),
+            "cookie" => {
+                // `=`이 없으면 쿠키 파일 경로
+                if !value.contains(&b'=') {
+                    return Err(CurlError::FileInput(text()));
+                }
+                self.cookies.push(text());
+            }
+            "user" => self.user = Some(text()),
+            "url" => self.set_url(value)?,
+            "head" => self.head = true,
+            "get" => self.get = true,
+            "compressed" => self.compressed = true,
+            "http1.0" => self.version = Some(Version::HTTP_10),
+            "http1.1" => self.version = Some(Version::HTTP_11),
+            "http2" | "http2-prior-knowledge" => self.version = Some(Version::HTTP_2),
+            "http3" => self.version = Some(Version::HTTP_3),
+            "form" | "upload-file" => {
+                return Err(CurlError::UnsupportedOption(format!("--{}", option)))
+            }
+            // 요청 내용에 영향을 주지 않는 옵션
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn set_url(&mut self, url: Vec<u8>) -> Result<(), CurlError> {
+        if self.url.is_some() {
+            return Err(CurlError::MultipleUrls);
+        }
+        self.url = Some(String::from_utf8_lossy(&url).into_owned());
+        Ok(())
+    }
+
+    fn into_request(self) -> Result<ProxiedRequest, CurlError> {
+        let mut url = self.url.ok_or(CurlError::MissingUrl)?;
+        // cURL과 같이 스킴이 없으면 http로 간주
+        if !url.contains("://") {
+            url = format!("http://{}", url);
+        }
+
+        let data = (!self.data.is_empty()).then(|| self.data.join(&b'&'));
+        let mut body = Bytes::new();
+        if let Some(data) = data.clone() {
+            if self.get {
+                let separator = if url.contains('?') { '&' } else { '?' };
+                url = format!("{}{}{}", url, separator, String::from_utf8_lossy(&data));
+            } else {
+                body = Bytes::from(data);
+            }
+        }
+
+        let method = match self.method {
+            Some(method) => method,
+            None if self.head => "HEAD".to_string(),
+            None if data.is_some() && !self.get => "POST".to_string(),
+            None => "GET".to_string(),
+        };
+        let method =
+            Method::from_bytes(method.as_bytes()).map_err(|_| CurlError::InvalidMethod(method))?;
+        let uri = url
+            .parse::<Uri>()
+            .map_err(|_| CurlError::InvalidUrl(url.clone()))?;
+
+        let mut headers = HeaderMap::new();
+        for line in &self.headers {
+            let invalid = || CurlError::InvalidHeader(String::from_utf8_lossy(line).into_owned());
+            // `Name;`은 빈 값 헤더, `Name:`은 기본 헤더 제거(무시)
+            let (name, value) = match line.iter().position(|&byte| byte == b':') {
+                Some(index) => (&line[..index], line[index + 1..].trim_ascii_start()),
+                None => match line.strip_suffix(b";") {
+                    Some(name) => (name, b"".as_slice()),
+                    None => return Err(invalid()),
+                },
+            };
+            if value.is_empty() && line.contains(&b':') {
+                continue;
+            }
+            let name = HeaderName::from_bytes(name.trim_ascii()).map_err(|_| invalid())?;
+            let value = HeaderValue::from_bytes(value.trim_ascii_end()).map_err(|_| invalid())?;
+            headers.append(name, value);
+        }
+
+        let mut default_header = |name: HeaderName, value: String| -> Result<(), CurlError> {
+            if !headers.contains_key(&name) {
+                let value = HeaderValue::from_str(&value)
+                    .map_err(|_| CurlError::InvalidHeader(format!("{}: {}", name, value)))?;
+                headers.insert(name, value);
+            }
+            Ok(())
+        };
+        if !self.cookies.is_empty() {
+            default_header(header::COOKIE, self.cookies.join("; "))?;
+        }
+        if let Some(user) = self.user {
+            let credentials = if user.contains(':') {
+                user
+            } else {
+                format!("{}:", user)
+            };
+            default_header(
+                header::AUTHORIZATION,
+                format!("Basic {}", BASE64.encode(credentials)),
+            )?;
+        }
+        if self.compressed {
+            default_header(header::ACCEPT_ENCODING, "deflate, gzip, br".to_string())?;
+        }
+        if !body.is_empty() {
+            default_header(
+                header::CONTENT_TYPE,
+                "application/x-www-form-urlencoded".to_string(),
+            )?;
+        }
+
+        let time = chrono::Local::now()
+            .timestamp_nanos_opt()
+            .unwrap_or_default();
+        Ok(ProxiedRequest::new(
+            method,
+            uri,
+            self.version.unwrap_or(Version::HTTP_11),
+            headers,
+            body,
+            time,
+        ))
+    }
+}
+
+/// `--data-urlencode` 값 인코딩 (`content`, `=content`, `name=content` 형식)
+fn url_encode_data(value: &[u8]) -> Result<Vec<u8>, CurlError> {
+    let (name, content) = match value.iter().position(|&byte| byte == b'=' || byte == b'@') {
+        Some(index) if value[index] == b'@' => {
+            return Err(CurlError::FileInput(
+                String::from_utf8_lossy(value).into_owned(),
+            ));
+        }
+        Some(0) => (None, &value[1..]),
+        Some(index) => (Some(&value[..index]), &value[index + 1..]),
+        None => (None, value),
+    };
+
+    let mut encoded = match name {
+        Some(name) => [name, b"="].concat(),
+        None => Vec::new(),
+    };
+    for &byte in content {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            encoded.push(byte);
+        } else {
+            encoded.extend_from_slice(format!("%{:02X}", byte).as_bytes());
+        }
+    }
+    Ok(encoded)
+}
+
+/// POSIX 셸 규칙으로 명령을 인자 목록으로 분리
+///
+/// 작은따옴표, 큰따옴표, `$'...'`(ANSI-C 인용), 백슬래시 이스케이프와 줄 이음을 처리합니다.
+fn tokenize(command: &str) -> Result<Vec<Vec<u8>>, CurlError> {
+    let mut tokens = Vec::new();
+    let mut token = Vec::new();
+    let mut in_token = false;
+    let mut chars = command.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if in_token {
+                    tokens.push(std::mem::take(&mut token));
+                    in_token = false;
+                }
+            }
+            '\\' => match chars.next() {
+                // 줄 이음
+                Some('\n') => {}
+                Some('\r') if chars.peek() == Some(&'\n') => {
+                    chars.next();
+                }
+                Some(escaped) => {
+                    push_char(&mut token, escaped);
+                    in_token = true;
+                }
+                None => {}
+            },
+            '\'' => {
+                in_token = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => push_char(&mut token, c),
+                        None => return Err(CurlError::UnterminatedQuote),
+                    }
+                }
+            }
+            '"' => {
+                in_token = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('\n') => {}
+                            Some(escaped @ ('"' | '\\' | '$' | '`')) => {
+                                push_char(&mut token, escaped)
+                            }
+                            Some(c) => {
+                                token.push(b'\\');
+                                push_char(&mut token, c);
+                            }
+                            None => return Err(CurlError::UnterminatedQuote),
+                        },
+                        Some(c) => push_char(&mut token, c),
+                        None => return Err(CurlError::UnterminatedQuote),
+                    }
+                }
+            }
+            '$' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                in_token = true;
+                ansi_c_quoted(&mut chars, &mut token)?;
+            }
+            c => {
+                push_char(&mut token, c);
+                in_token = true;
+            }
+        }
+    }
+    if in_token {
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+/// 최대 `max`자리의 `radix` 진수 읽기 (숫자가 없으면 None)
+fn read_number(chars: &mut Peekable<Chars<'_>>, radix: u32, max: usize) -> Option<u32> {
+    let mut value = 0u32;
+    let mut digits = 0;
+    while digits < max {
+        match chars.peek().and_then(|c| c.to_digit(radix)) {
+            Some(digit) => {
+                value = value * radix + digit;
+                digits += 1;
+                chars.next();
+            }
+            None => break,
+        }
+    }
+    (digits > 0).then_some(value)
+}
+
+/// 문자를 UTF-8 바이트로 추가
+fn push_char(token: &mut Vec<u8>, c: char) {
+    let mut buffer = [0; 4];
+    token.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+}
+
+/// `$'...'` 안의 내용을 닫는 따옴표까지 해석
+fn ansi_c_quoted(chars: &mut Peekable<Chars<'_>>, token: &mut Vec<u8>) -> Result<(), CurlError> {
+    loop {
+        let c = chars.next().ok_or(CurlError::UnterminatedQuote)?;
+        let escaped = match c {
+            '\'' => return Ok(()),
+            '\\' => chars.next().ok_or(CurlError::UnterminatedQuote)?,
+            c => {
+                push_char(token, c);
+                continue;
+            }
+        };
+
+        match escaped {
+            'a' => token.push(0x07),
+            'b' => token.push(0x08),
+            'e' | 'E' => token.push(0x1b),
+            'f' => token.push(0x0c),
+            'n' => token.push(b'\n'),
+            'r' => token.push(b'\r'),
+            't' => token.push(b'\t'),
+            'v' => token.push(0x0b),
+            '0'..='7' => {
+                // 첫 자리를 포함해 최대 3자리 8진수
+                let mut value = escaped.to_digit(8).unwrap_or_default();
+                for _ in 0..2 {
+                    match chars.peek().and_then(|c| c.to_digit(8)) {
+                        Some(digit) => {
+                            value = value * 8 + digit;
+                            chars.next();
+                        }
+                        None => break,
+                    }
+                }
+                token.push(value as u8);
+            }
+            'x' => match read_number(chars, 16, 2) {
+                Some(value) => token.push(value as u8),
+                None => token.extend_from_slice(b"\\x"),
+            },
+            'u' | 'U' => {
+                let max = if escaped == 'u' { 4 } else { 8 };
+                match read_number(chars, 16, max).and_then(char::from_u32) {
+                    Some(c) => push_char(token, c),
+                    None => {
+                        token.push(b'\\');
+                        token.push(escaped as u8);
+                    }
+                }
+            }
+            '\\' | '\'' | '"' | '?' => token.push(escaped as u8),
+            other => {
+                token.push(b'\\');
+                push_char(token, other);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: Method, body: &'static [u8]) -> ClientRequest {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::HOST, HeaderValue::from_static("example.com"));
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        headers.insert(header::CONTENT_LENGTH, HeaderValue::from(body.len()));
+        headers.append(header::COOKIE, HeaderValue::from_static("a=1"));
+        headers.append(header::COOKIE, HeaderValue::from_static("b='2'"));
+
+        ProxiedRequest::new(
+            method,
+            "https://example.com/api?x=1&y".parse().unwrap(),
+            Version::HTTP_11,
+            headers,
+            Bytes::from_static(body),
+            0,
+        )
+        .for_client()
+    }
+
+    #[test]
+    fn test_curl_snippet_quotes_arguments() {
+        let snippet = generate(
+            &request(Method::PUT, br#"{"name":"it's"}"#),
+            SnippetLanguage::Curl,
+        );
+
+        assert_eq!(
+            snippet,
+            "curl -X PUT 'https://example.com/api?x=1&y' \\\n  \
+             -H 'content-type: application/json' \\\n  \
+             -H 'cookie: a=1' \\\n  \
+             -H 'cookie: b='\\''2'\\''' \\\n  \
+             --data-raw '{\"name\":\"it'\\''s\"}'"
+        );
+    }
+
+    #[test]
+    fn test_curl_snippet_methods_with_body() {
+        let get = generate(&request(Method::GET, b"{}"), SnippetLanguage::Curl);
+        assert!(get.starts_with("curl -X GET 'https://example.com/api?x=1&y'"));
+        assert!(get.ends_with("--data-raw '{}'"));
+
+        let head = generate(&request(Method::HEAD, b"{}"), SnippetLanguage::Curl);
+        assert!(head.starts_with("curl -X HEAD 'https://example.com/api?x=1&y'"));
+        assert!(!head.contains(" -I "));
+
+        let post = generate(&request(Method::POST, b"{}"), SnippetLanguage::Curl);
+        assert!(post.starts_with("curl 'https://example.com/api?x=1&y'"));
+
+        let head = generate(&request(Method::HEAD, b""), SnippetLanguage::Curl);
+        assert!(head.starts_with("curl -I 'https://example.com/api?x=1&y'"));
+        let get = generate(&request(Method::GET, b""), SnippetLanguage::Curl);
+        assert!(get.starts_with("curl 'https://example.com/api?x=1&y'"));
+
+        let parsed = parse_curl(&generate(
+            &request(Method::GET, b"{}"),
+            SnippetLanguage::Curl,
+        ));
+        assert_eq!(*parsed.unwrap().method(), Method::GET);
+    }
+
+    #[test]
+    fn test_binary_body_starting_with_dash() {
+        // `-`로 시작하는 본문도 printf 옵션으로 해석되지 않음
+        let request = request(Method::POST, b"-n\x00");
+        assert_eq!(
+            generate(&request, SnippetLanguage::Curl)
+                .split(" | ")
+                .next(),
+            Some(r#"printf -- '-n\000'"#)
+        );
+    }
+
+    #[test]
+    fn test_binary_body_snippets() {
+        let request = request(Method::POST, b"\x00\xff'%\"\n");
+
+        assert_eq!(
+            generate(&request, SnippetLanguage::Curl)
+                .split(" | ")
+                .next(),
+            Some(r#"printf -- '\000\377\047%%"\012'"#)
+        );
+        assert!(generate(&request, SnippetLanguage::Fetch)
+            .contains("body: new Uint8Array([0, 255, 39, 37, 34, 10]),"));
+        assert!(generate(&request, SnippetLanguage::Python).contains(r#"data=b"\x00\xff'%\"\n","#));
+        assert!(generate(&request, SnippetLanguage::Reqwest)
+            .contains(r#".body(b"\x00\xff'%\"\n".to_vec())"#));
+    }
+
+    #[test]
+    fn test_object_snippets_merge_headers() {
+        let request = request(Method::POST, "{\"name\":\"철수\"}".as_bytes());
+
+        let fetch = generate(&request, SnippetLanguage::Fetch);
+        assert!(
+            fetch.starts_with("const response = await fetch(\"https://example.com/api?x=1&y\", {")
+        );
+        assert!(fetch.contains("    \"cookie\": \"a=1; b='2'\",\n"));
+        assert!(!fetch.contains("content-length"));
+
+        let python = generate(&request, SnippetLanguage::Python);
+        assert!(python.contains("    data=\"{\\\"name\\\":\\\"철수\\\"}\".encode(\"utf-8\"),"));
+
+        let httpie = generate(&request, SnippetLanguage::Httpie);
+        assert!(httpie
+            .starts_with("http --raw '{\"name\":\"철수\"}' POST 'https://example.com/api?x=1&y'"));
+        assert!(httpie.contains("'cookie:b='\\''2'\\'''"));
+    }
+
+    #[test]
+    fn test_parse_browser_curl() {
+        let command = r#"curl 'https://example.com/api?x=1' \
+  -H 'accept: application/json' \
+  -H $'x-note: it\'s \u00e9' \
+  -b 'session=abc' \
+  --data-raw $'{"a":"line\\nbreak"}' \
+  --compressed"#;
+
+        let request = parse_curl(command).unwrap();
+        assert_eq!(*request.method(), Method::POST);
+        assert_eq!(*request.uri(), "https://example.com/api?x=1");
+        assert_eq!(request.headers()["accept"], "application/json");
+        assert_eq!(request.headers()["x-note"].as_bytes(), "it's é".as_bytes());
+        assert_eq!(request.headers()[header::COOKIE], "session=abc");
+        assert_eq!(
+            request.headers()[header::ACCEPT_ENCODING],
+            "deflate, gzip, br"
+        );
+        assert_eq!(
+            request.headers()[header::CONTENT_TYPE],
+            "application/x-www-form-urlencoded"
+        );
+        assert_eq!(request.body(), r#"{"a":"line\nbreak"}"#);
+    }
+
+    #[test]
+    fn test_parse_curl_options() {
+        let request = parse_curl(
+            "curl -sSL -XDELETE -u user:pass \"example.com/items?\\$top=1\" -H 'X-Empty;' --http2",
+        )
+        .unwrap();
+        assert_eq!(*request.method(), Method::DELETE);
+        assert_eq!(*request.uri(), "http://example.com/items?$top=1");
+        assert_eq!(*request.version(), Version::HTTP_2);
+        assert_eq!(
+            request.headers()[header::AUTHORIZATION],
+            "Basic dXNlcjpwYXNz"
+        );
+        assert_eq!(request.headers()["x-empty"], "");
+
+        let request =
+            parse_curl("curl -G https://example.com/search -d q=1 --data-urlencode 'name=a b&c'")
+                .unwrap();
+        assert_eq!(*request.method(), Method::GET);
+        assert_eq!(
+            *request.uri(),
+            "https://example.com/search?q=1&name=a%20b%26c"
+        );
+        assert!(request.body().is_empty());
+
+        assert_eq!(
+            parse_curl("curl -d @body.json https://example.com").unwrap_err(),
+            CurlError::FileInput("@body.json".to_string())
+        );
+        assert_eq!(
+            parse_curl("curl --unknown https://example.com").unwrap_err(),
+            CurlError::UnsupportedOption("--unknown".to_string())
+        );
+        assert_eq!(
+            parse_curl("wget https://example.com").unwrap_err(),
+            CurlError::NotCurl
+        );
+        assert_eq!(
+            parse_curl("curl 'https://example.com").unwrap_err(),
+            CurlError::UnterminatedQuote
+        );
+    }
+
+    #[test]
+    fn test_curl_snippet_round_trip() {
+        let original = request(Method::PATCH, b"{\"line\":\"a\nb\"}");
+        let parsed = parse_curl(&generate(&original, SnippetLanguage::Curl)).unwrap();
+
+        assert_eq!(parsed.method(), original.method());
+        assert_eq!(parsed.uri(), original.uri());
+        assert_eq!(parsed.body(), original.body());
+        assert_eq!(parsed.headers().get_all(header::COOKIE).iter().count(), 2);
+        assert_eq!(parsed.headers()[header::CONTENT_TYPE], "application/json");
+    }
+}
@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+/// 스트리밍 응답에서 파싱한 Server-Sent Event
+///
+/// `request_id`는 이벤트를 보낸 응답의 요청 ID([`ClientRequest::id`](crate::ClientRequest::id))이며,
+/// `index`는 같은 응답 안에서 이벤트가 도착한 순서입니다.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ServerSentEvent {
+    request_id: String,
+    index: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<String>, // 마지막으로 받은 이벤트 ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    event: Option<String>, // 이벤트 타입 (없으면 message)
+    data: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    retry: Option<u64>, // 재연결 대기 시간 (밀리초)
+    time: i64,
+}
+
+impl ServerSentEvent {
+    pub fn new(
+        request_id: impl Into<String>,
+        index: usize,
+        data: impl Into<String>,
+        time: i64,
+    ) -> Self {
+        Self {
+            request_id: request_id.into(),
+            index,
+            id: None,
+            event: None,
+            data: data.into(),
+            retry: None,
+            time,
+        }
+    }
+
+    /// 이벤트 ID 설정
+    pub fn with_id(mut self, id: Option<String>) -> Self {
+        self.id = id;
+        self
+    }
+
+    /// 이벤트 타입 설정
+    pub fn with_event(mut self, event: Option<String>) -> Self {
+        self.event = event;
+        self
+    }
+
+    /// 재연결 대기 시간 설정
+    pub fn with_retry(mut self, retry: Option<u64>) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn request_id(&self) -> &str {
+        &self.request_id
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// 이벤트 타입 (`event` 필드가 없으면 `message`)
+    pub fn event(&self) -> &str {
+        self.event.as_deref().unwrap_or("message")
+    }
+
+    pub fn data(&self) -> &str {
+        &self.data
+    }
+
+    pub fn retry(&self) -> Option<u64> {
+        self.retry
+    }
+
+    pub fn time(&self) -> i64 {
+        self.time
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_event() {
+        let event = ServerSentEvent::new("1-abc", 0, "hello", 1).with_id(Some("42".to_string()));
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["request_id"], "1-abc");
+        assert_eq!(json["id"], "42");
+        assert!(json.get("event").is_none());
+        assert!(json.get("retry").is_none());
+
+        let parsed: ServerSentEvent = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, event);
+        assert_eq!(parsed.event(), "message");
+    }
+}
@@ -0,0 +1,232 @@
+//! 업스트림 TLS 세션 정보
+//!
+//! 프록시가 업스트림 서버와 맺은 TLS 연결에서 협상한 버전, 암호 스위트, ALPN과 서버가 보낸 인증서 체인을
+//! 기록합니다. 인증서는 DER을 `x509-parser`로 해석해 화면 표시와 HAR 내보내기에 필요한 필드만 담습니다.
+
+use chrono::{DateTime, SecondsFormat};
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::net::IpAddr;
+use x509_parser::prelude::{FromDer, GeneralName, X509Certificate};
+
+/// 인증서 해석 중 발생하는 오류
+#[derive(Debug)]
+pub enum CertificateParseError {
+    /// 올바른 X.509 DER이 아님
+    InvalidDer(String),
+    /// 인증서 뒤에 남은 데이터가 있음
+    TrailingData(usize),
+}
+
+impl fmt::Display for CertificateParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CertificateParseError::InvalidDer(e) => write!(f, "invalid certificate: {}", e),
+            CertificateParseError::TrailingData(len) => {
+                write!(f, "{} bytes of trailing data after certificate", len)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CertificateParseError {}
+
+/// 업스트림 서버가 보낸 인증서 하나의 정보
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CertificateInfo {
+    /// 주체 DN (예: `C=US, O=Example, CN=example.com`)
+    pub subject: String,
+    /// 발급자 DN
+    pub issuer: String,
+    /// 일련번호 (`:`로 구분한 16진수)
+    pub serial_number: String,
+    /// 주체 대체 이름 (DNS 이름, IP 주소, 이메일, URI)
+    #[serde(default)]
+    pub subject_alt_names: Vec<String>,
+    /// 유효 기간 시작 (RFC 3339)
+    pub not_before: String,
+    /// 유효 기간 끝 (RFC 3339)
+    pub not_after: String,
+    /// DER의 SHA-256 지문 (`:`로 구분한 16진수)
+    pub sha256_fingerprint: String,
+    /// DER의 SHA-1 지문 (`:`로 구분한 16진수)
+    pub sha1_fingerprint: String,
+    /// CA 인증서 여부 (basicConstraints)
+    #[serde(default)]
+    pub is_ca: bool,
+}
+
+impl CertificateInfo {
+    /// DER 인증서 해석
+    pub fn from_der(der: &[u8]) -> Result<Self, CertificateParseError> {
+        let (rest, certificate) = X509Certificate::from_der(der)
+            .map_err(|e| CertificateParseError::InvalidDer(e.to_string()))?;
+        if !rest.is_empty() {
+            return Err(CertificateParseError::TrailingData(rest.len()));
+        }
+
+        let subject_alt_names = certificate
+            .subject_alternative_name()
+            .ok()
+            .flatten()
+            .map(|extension| {
+                extension
+                    .value
+                    .general_names
+                    .iter()
+                    .filter_map(general_name)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let validity = certificate.validity();
+
+        Ok(Self {
+            subject: certificate.subject().to_string(),
+            issuer: certificate.issuer().to_string(),
+            serial_number: certificate.raw_serial_as_string(),
+            subject_alt_names,
+            not_before: format_time(validity.not_before.timestamp()),
+            not_after: format_time(validity.not_after.timestamp()),
+            sha256_fingerprint: fingerprint(&Sha256::digest(der)),
+            sha1_fingerprint: fingerprint(&Sha1::digest(der)),
+            is_ca: certificate.is_ca(),
+        })
+    }
+}
+
+/// 업스트림 TLS 연결에서 협상한 세션 정보
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UpstreamSession {
+    /// 협상된 TLS 버전 (예: `TLS 1.3`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// 협상된 암호 스위트 (예: `TLS13_AES_128_GCM_SHA256`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cipher_suite: Option<String>,
+    /// 협상된 ALPN 프로토콜 (예: `h2`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alpn: Option<String>,
+    /// 서버가 보낸 인증서 체인 (서버 인증서부터)
+    #[serde(default)]
+    pub certificates: Vec<CertificateInfo>,
+}
+
+impl UpstreamSession {
+    /// 서버 인증서 (체인의 첫 번째 인증서)
+    pub fn leaf(&self) -> Option<&CertificateInfo> {
+        self.certificates.first()
+    }
+}
+
+fn general_name(name: &GeneralName<'_>) -> Option<String> {
+    match name {
+        GeneralName::DNSName(name) | GeneralName::RFC822Name(name) | GeneralName::URI(name) => {
+            Some(name.to_string())
+        }
+        GeneralName::IPAddress(octets) => match octets.len() {
+            4 => <[u8; 4]>::try_from(*octets).ok().map(IpAddr::from),
+            16 => <[u8; 16]>::try_from(*octets).ok().map(IpAddr::from),
+            _ => None,
+        }
+        .map(|address| address.to_string()),
+        _ => None,
+    }
+}
+
+fn format_time(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|time| time.to_rfc3339_opts(SecondsFormat::Secs, true))
+        .unwrap_or_default()
+}
+
+fn fingerprint(digest: &[u8]) -> String {
+    digest
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+
+    /// `openssl req -x509`로 만든 자체 서명 인증서 (P-256, 2024-01-01 ~ 2034-01-01)
+    const CERTIFICATE: &str = "\
+        MIIB4zCCAYmgAwIBAgICEjQwCgYIKoZIzj0EAwIwOzELMAkGA1UEBhMCS1IxFjAU\
+        BgNVBAoMDUNoZW9sc3UgUHJveHkxFDASBgNVBAMMC2V4YW1wbGUuY29tMB4XDTI0\
+        MDEwMTAwMDAwMFoXDTM0MDEwMTAwMDAwMFowOzELMAkGA1UEBhMCS1IxFjAUBgNV\
+        BAoMDUNoZW9sc3UgUHJveHkxFDASBgNVBAMMC2V4YW1wbGUuY29tMFkwEwYHKoZI\
+        zj0CAQYIKoZIzj0DAQcDQgAE8POHRYAkwrECSy8RPga4uIwSCrlPQc7nvqxs2cyp\
+        n7NR698trXFd0ZrGCRYiTOLInvnwW0jtSvTfGrcty+2+Y6N9MHswHQYDVR0OBBYE\
+        FM3SYiEWNl1iEBUrAnyK/oe+BXyeMB8GA1UdIwQYMBaAFM3SYiEWNl1iEBUrAnyK\
+        /oe+BXyeMCsGA1UdEQQkMCKCC2V4YW1wbGUuY29tgg0qLmV4YW1wbGUuY29thwTA\
+        AAIBMAwGA1UdEwEB/wQCMAAwCgYIKoZIzj0EAwIDSAAwRQIhAKsK+zE9qcZkr+tL\
+        Rt0UdtatwwSSn3vzjWdJUwIQiZPDAiBHQev4SSgBmr4W9pEN+OgX2NxHTQPhBwq6\
+        Q9yDO9yKSQ==";
+
+    fn der() -> Vec<u8> {
+        BASE64.decode(CERTIFICATE).unwrap()
+    }
+
+    #[test]
+    fn test_parse_certificate() {
+        let info = CertificateInfo::from_der(&der()).unwrap();
+
+        assert_eq!(info.subject, "C=KR, O=Cheolsu Proxy, CN=example.com");
+        assert_eq!(info.issuer, info.subject);
+        assert_eq!(info.serial_number, "12:34");
+        assert_eq!(
+            info.subject_alt_names,
+            vec!["example.com", "*.example.com", "192.0.2.1"]
+        );
+        assert_eq!(info.not_before, "2024-01-01T00:00:00Z");
+        assert_eq!(info.not_after, "2034-01-01T00:00:00Z");
+        assert_eq!(
+            info.sha256_fingerprint,
+            "AE:A3:B7:23:28:DB:C6:B2:DA:A7:64:8C:00:0A:C5:A9:98:48:31:65:CD:B8:17:31:25:D5:29:68:D3:3B:89:0E"
+        );
+        assert_eq!(
+            info.sha1_fingerprint,
+            "71:B7:6C:84:B3:DE:AD:07:89:1D:41:79:10:BB:79:DF:B9:22:EF:18"
+        );
+        assert!(!info.is_ca);
+    }
+
+    #[test]
+    fn test_invalid_certificate() {
+        assert!(matches!(
+            CertificateInfo::from_der(b"not a certificate"),
+            Err(CertificateParseError::InvalidDer(_))
+        ));
+
+        let mut der = der();
+        der.extend_from_slice(b"trailing");
+        assert!(matches!(
+            CertificateInfo::from_der(&der),
+            Err(CertificateParseError::TrailingData(8))
+        ));
+    }
+
+    #[test]
+    fn test_session_serialization() {
+        let session = UpstreamSession {
+            version: Some("TLS 1.3".to_string()),
+            cipher_suite: Some("TLS13_AES_128_GCM_SHA256".to_string()),
+            alpn: None,
+            certificates: vec![CertificateInfo::from_der(&der()).unwrap()],
+        };
+
+        let json = serde_json::to_value(&session).unwrap();
+        assert!(json.get("alpn").is_none());
+        assert_eq!(json["certificates"][0]["serial_number"], "12:34");
+        assert_eq!(
+            serde_json::from_value::<UpstreamSession>(json).unwrap(),
+            session
+        );
+        assert_eq!(session.leaf().unwrap().serial_number, "12:34");
+    }
+}
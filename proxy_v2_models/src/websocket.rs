@@ -0,0 +1,167 @@
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+/// WebSocket 프레임 방향
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WebSocketDirection {
+    /// 클라이언트 → 서버
+    ClientToServer,
+    /// 서버 → 클라이언트
+    ServerToClient,
+}
+
+/// WebSocket 프레임 종류
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WebSocketOpcode {
+    Text,
+    Binary,
+    Ping,
+    Pong,
+    Close,
+}
+
+/// 캡처한 WebSocket 프레임
+///
+/// 같은 연결의 프레임은 `connection_id`가 같으며, 업그레이드 요청을 기록한 경우
+/// `request_id`가 그 요청의 ID([`ClientRequest::id`](crate::ClientRequest::id))입니다.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WebSocketFrame {
+    connection_id: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>, // 업그레이드 요청 ID
+    direction: WebSocketDirection,
+    opcode: WebSocketOpcode,
+    payload: Bytes,
+    size: usize,
+    time: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    close_code: Option<u16>, // Close 프레임의 종료 코드
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    close_reason: Option<String>, // Close 프레임의 종료 사유
+}
+
+impl WebSocketFrame {
+    pub fn new(
+        connection_id: u64,
+        direction: WebSocketDirection,
+        opcode: WebSocketOpcode,
+        payload: Bytes,
+        time: i64,
+    ) -> Self {
+        Self {
+            connection_id,
+            request_id: None,
+            direction,
+            opcode,
+            size: payload.len(),
+            payload,
+            time,
+            close_code: None,
+            close_reason: None,
+        }
+    }
+
+    /// 업그레이드 요청 ID 설정
+    pub fn with_request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
+    /// Close 프레임의 종료 코드와 사유 설정
+    pub fn with_close(mut self, code: u16, reason: impl Into<String>) -> Self {
+        self.close_code = Some(code);
+        self.close_reason = Some(reason.into());
+        self
+    }
+
+    pub fn connection_id(&self) -> u64 {
+        self.connection_id
+    }
+
+    /// 업그레이드 요청 ID (기록되지 않았으면 None)
+    pub fn request_id(&self) -> Option<&str> {
+        self.request_id.as_deref()
+    }
+
+    pub fn direction(&self) -> WebSocketDirection {
+        self.direction
+    }
+
+    pub fn opcode(&self) -> WebSocketOpcode {
+        self.opcode
+    }
+
+    pub fn payload(&self) -> &Bytes {
+        &self.payload
+    }
+
+    /// 텍스트 프레임의 내용 (텍스트 프레임이 아니거나 UTF-8이 아니면 None)
+    pub fn text(&self) -> Option<&str> {
+        match self.opcode {
+            WebSocketOpcode::Text => std::str::from_utf8(&self.payload).ok(),
+            _ => None,
+        }
+    }
+
+    /// 페이로드 크기 (bytes)
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn time(&self) -> i64 {
+        self.time
+    }
+
+    pub fn close_code(&self) -> Option<u16> {
+        self.close_code
+    }
+
+    pub fn close_reason(&self) -> Option<&str> {
+        self.close_reason.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_frame() {
+        let frame = WebSocketFrame::new(
+            7,
+            WebSocketDirection::ClientToServer,
+            WebSocketOpcode::Text,
+            Bytes::from_static(b"hello"),
+            1,
+        )
+        .with_request_id("1-abc");
+
+        assert_eq!(frame.text(), Some("hello"));
+        assert_eq!(frame.size(), 5);
+        assert_eq!(frame.request_id(), Some("1-abc"));
+    }
+
+    #[test]
+    fn test_serialize_close_frame() {
+        let frame = WebSocketFrame::new(
+            7,
+            WebSocketDirection::ServerToClient,
+            WebSocketOpcode::Close,
+            Bytes::new(),
+            1,
+        )
+        .with_close(1000, "bye");
+
+        let json = serde_json::to_value(&frame).unwrap();
+        assert_eq!(json["direction"], "server_to_client");
+        assert_eq!(json["opcode"], "close");
+        assert_eq!(json["close_code"], 1000);
+        assert!(json.get("request_id").is_none());
+
+        let parsed: WebSocketFrame = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, frame);
+        assert_eq!(parsed.text(), None);
+    }
+}
@@ -35,22 +35,14 @@ pub struct Ssl {
     cache: Cache<Authority, Arc<ServerConfig>>,
 }
 
-impl Default for Ssl {
-    fn default() -> Self {
-        let private_key_bytes: &[u8] = include_bytes!("cheolsu-proxy.key");
-        let ca_cert_bytes: &[u8] = include_bytes!("cheolsu-proxy.cer");
-
-        let pkey =
-            PKey::private_key_from_pem(private_key_bytes).expect("Failed to parse private key");
-
-        let private_key = rustls::PrivateKey(
-            pkey.private_key_to_der()
-                .expect("Failed to encode private key"),
-        );
-
-        let ca_cert = X509::from_pem(ca_cert_bytes).expect("Failed to parse CA certificate");
+impl Ssl {
+    /// PEM 개인 키와 루트 CA 인증서로 생성 (설치마다 생성한 CA를 사용)
+    pub fn from_pem(private_key_pem: &[u8], ca_cert_pem: &[u8]) -> Result<Self, ErrorStack> {
+        let pkey = PKey::private_key_from_pem(private_key_pem)?;
+        let private_key = rustls::PrivateKey(pkey.private_key_to_pkcs8()?);
+        let ca_cert = X509::from_pem(ca_cert_pem)?;
 
-        Self {
+        Ok(Self {
             pkey,
             private_key,
             ca_cert,
@@ -59,7 +51,7 @@ impl Default for Ssl {
                 .max_capacity(1_000)
                 .time_to_live(Duration::from_secs(CACHE_TTL))
                 .build(),
-        }
+        })
     }
 }
 
@@ -28,6 +28,7 @@ pub struct Proxy {
     addr: SocketAddr,
     tx: Option<SyncSender<proxy_handler::ProxyHandler>>,
     sessions: Arc<Mutex<Value>>,
+    ssl: Arc<Ssl>,
 }
 
 impl Proxy {
@@ -35,11 +36,13 @@ impl Proxy {
         addr: SocketAddr,
         tx: Option<SyncSender<proxy_handler::ProxyHandler>>,
         sessions: Value,
+        ssl: Ssl,
     ) -> Self {
         Self {
             addr,
             tx,
             sessions: Arc::new(Mutex::new(sessions)),
+            ssl: Arc::new(ssl),
         }
     }
 
@@ -78,7 +81,7 @@ impl Proxy {
             .http1_preserve_header_case(true)
             .http1_title_case_headers(true);
 
-        let ssl = Arc::new(Ssl::default());
+        let ssl = Arc::clone(&self.ssl);
 
         let make_service = make_service_fn(move |conn: &AddrStream| {
             let client = client.clone();
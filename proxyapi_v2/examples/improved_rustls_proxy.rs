@@ -11,7 +11,7 @@ use proxyapi_v2::{
     certificate_authority::{RcgenAuthority, build_ca},
 };
 use rcgen::{CertificateParams, KeyPair};
-use std::net::SocketAddr;
+use std::{net::SocketAddr, path::Path};
 use tokio_rustls::rustls::crypto::aws_lc_rs;
 use tracing::{Level, info};
 use tracing_subscriber;
@@ -27,7 +27,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     info!("🚀 개선된 rustls MITM 프록시 시작");
 
     // CA 인증서 생성 또는 로드
-    let ca = match build_ca() {
+    let ca = match build_ca(Path::new("ca")) {
         Ok(ca) => {
             info!("✅ 기존 CA 인증서 로드 성공");
             ca
@@ -5,7 +5,7 @@ use proxyapi_v2::{
     certificate_authority::build_ca, hybrid_tls_handler::HybridTlsHandler,
     tls_version_detector::TlsVersionDetector,
 };
-use std::sync::Arc;
+use std::{path::Path, sync::Arc};
 
 #[tokio::main]
 async fn main() {
@@ -18,7 +18,7 @@ async fn main() {
     println!("================================\n");
 
     // CA 생성
-    let ca = match build_ca() {
+    let ca = match build_ca(Path::new("ca")) {
         Ok(ca) => {
             println!("✅ CA 인증서 로드 성공");
             Arc::new(ca)
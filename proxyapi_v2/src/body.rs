@@ -1,11 +1,19 @@
 use crate::Error;
-use futures::{Stream, TryStream, TryStreamExt};
+use futures::{Stream, StreamExt, TryStream, TryStreamExt, channel::mpsc};
+use http::HeaderMap;
 use http_body_util::{Collected, Empty, Full, StreamBody, combinators::BoxBody};
 use hyper::{
     Request, Response,
     body::{Body as HttpBody, Bytes, Frame, Incoming, SizeHint},
 };
-use std::{pin::Pin, task::Poll};
+use std::{
+    pin::Pin,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    task::{Poll, ready},
+};
 
 #[derive(Debug)]
 enum Internal {
@@ -43,6 +51,29 @@ impl Body {
             ))),
         }
     }
+
+    /// 프레임을 도착하는 대로 그대로 전달하면서 복사본을 [`BodyTap`]으로 내보내는 본문 생성
+    ///
+    /// 본문 전체를 모으지 않으므로 SSE, 롱 폴링, 대용량 다운로드도 지연 없이 전달됩니다.
+    /// 복사본은 최대 `limit` bytes까지만 캡처 버퍼에 보관되고, 넘어선 부분은 잘린 것으로 표시됩니다.
+    pub fn tee(self, limit: usize) -> (Self, BodyTap) {
+        let (tx, rx) = mpsc::unbounded();
+        let state = Arc::new(TapState {
+            limit,
+            captured: Mutex::new(Vec::new()),
+            size: AtomicU64::new(0),
+            truncated: AtomicBool::new(false),
+            complete: AtomicBool::new(false),
+        });
+
+        let body = Tee {
+            inner: self,
+            tx,
+            state: Arc::clone(&state),
+        };
+
+        (Self::from(BoxBody::new(body)), BodyTap { rx, state })
+    }
 }
 
 impl HttpBody for Body {
@@ -178,3 +209,201 @@ where
         value.into_body().into()
     }
 }
+
+/// [`Body::tee`]로 복사된 프레임
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TapFrame {
+    /// 본문 데이터 (캡처 한도와 관계없이 모든 조각)
+    Data(Bytes),
+    /// 트레일러
+    Trailers(HeaderMap),
+}
+
+#[derive(Debug)]
+struct TapState {
+    limit: usize,
+    captured: Mutex<Vec<u8>>,
+    size: AtomicU64,
+    truncated: AtomicBool,
+    complete: AtomicBool,
+}
+
+impl TapState {
+    fn capture(&self, data: &Bytes) {
+        self.size.fetch_add(data.len() as u64, Ordering::Relaxed);
+
+        let mut captured = self.captured.lock().expect("body tap lock poisoned");
+        let room = self.limit.saturating_sub(captured.len());
+        if data.len() > room {
+            self.truncated.store(true, Ordering::Relaxed);
+        }
+        captured.extend_from_slice(&data[..data.len().min(room)]);
+    }
+}
+
+#[derive(Debug)]
+struct Tee {
+    inner: Body,
+    tx: mpsc::UnboundedSender<TapFrame>,
+    state: Arc<TapState>,
+}
+
+impl HttpBody for Tee {
+    type Data = Bytes;
+    type Error = Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let frame = ready!(Pin::new(&mut self.inner).poll_frame(cx));
+
+        match &frame {
+            Some(Ok(frame)) => {
+                if let Some(data) = frame.data_ref() {
+                    self.state.capture(data);
+                    // 탭을 버린 경우 전송 실패는 무시
+                    let _ = self.tx.unbounded_send(TapFrame::Data(data.clone()));
+                } else if let Some(trailers) = frame.trailers_ref() {
+                    let _ = self.tx.unbounded_send(TapFrame::Trailers(trailers.clone()));
+                }
+            }
+            Some(Err(_)) => self.tx.close_channel(),
+            None => {
+                self.state.complete.store(true, Ordering::Relaxed);
+                self.tx.close_channel();
+            }
+        }
+
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// [`Body::tee`]로 갈라낸 본문의 복사본
+///
+/// 본문이 전달되는 대로 [`TapFrame`]을 내보내며, 본문이 끝나거나 전달이 중단되면 스트림도 끝납니다.
+/// 스트림을 읽지 않을 때는 복사본이 쌓이지 않도록 탭을 버려야 합니다.
+#[derive(Debug)]
+pub struct BodyTap {
+    rx: mpsc::UnboundedReceiver<TapFrame>,
+    state: Arc<TapState>,
+}
+
+impl BodyTap {
+    /// 지금까지 캡처한 본문 (최대 `limit` bytes)
+    pub fn captured(&self) -> Bytes {
+        let captured = self.state.captured.lock().expect("body tap lock poisoned");
+        Bytes::copy_from_slice(&captured)
+    }
+
+    /// 지금까지 전달된 본문의 전체 크기
+    pub fn size(&self) -> u64 {
+        self.state.size.load(Ordering::Relaxed)
+    }
+
+    /// 본문이 캡처 한도를 넘어 일부만 캡처되었는지 확인
+    pub fn is_truncated(&self) -> bool {
+        self.state.truncated.load(Ordering::Relaxed)
+    }
+
+    /// 본문이 끝까지 전달되었는지 확인 (클라이언트 연결이 끊기거나 오류가 나면 false)
+    pub fn is_complete(&self) -> bool {
+        self.state.complete.load(Ordering::Relaxed)
+    }
+}
+
+impl Stream for BodyTap {
+    type Item = TapFrame;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        self.rx.poll_next_unpin(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http_body_util::BodyExt;
+
+    fn chunked(chunks: &'static [&'static [u8]]) -> Body {
+        Body::from_stream(futures::stream::iter(
+            chunks
+                .iter()
+                .map(|chunk| Ok::<_, Error>(Bytes::from_static(chunk))),
+        ))
+    }
+
+    #[tokio::test]
+    async fn tee_forwards_every_frame() {
+        let (body, tap) = chunked(&[b"hello ", b"world"]).tee(1024);
+
+        let forwarded = body.collect().await.unwrap().to_bytes();
+        let copied: Vec<_> = tap.collect().await;
+
+        assert_eq!(forwarded, "hello world");
+        assert_eq!(
+            copied,
+            vec![
+                TapFrame::Data(Bytes::from_static(b"hello ")),
+                TapFrame::Data(Bytes::from_static(b"world")),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn tee_bounds_captured_body() {
+        let (body, tap) = chunked(&[b"hello ", b"world"]).tee(8);
+
+        let forwarded = body.collect().await.unwrap().to_bytes();
+
+        assert_eq!(forwarded, "hello world");
+        assert_eq!(tap.captured(), "hello wo");
+        assert_eq!(tap.size(), 11);
+        assert!(tap.is_truncated());
+        assert!(tap.is_complete());
+    }
+
+    #[tokio::test]
+    async fn tee_copies_trailers() {
+        let mut trailers = HeaderMap::new();
+        trailers.insert("grpc-status", "0".parse().unwrap());
+        let frames = vec![
+            Ok::<_, Error>(Frame::data(Bytes::from_static(b"data"))),
+            Ok(Frame::trailers(trailers.clone())),
+        ];
+        let (body, tap) = Body::from(StreamBody::new(futures::stream::iter(frames))).tee(1024);
+
+        let collected = body.collect().await.unwrap();
+        let copied: Vec<_> = tap.collect().await;
+
+        assert_eq!(collected.trailers(), Some(&trailers));
+        assert_eq!(copied.last(), Some(&TapFrame::Trailers(trailers)));
+    }
+
+    #[tokio::test]
+    async fn tee_incomplete_when_dropped() {
+        let (mut body, mut tap) = chunked(&[b"hello ", b"world"]).tee(1024);
+
+        body.frame().await.unwrap().unwrap();
+        drop(body);
+
+        assert_eq!(
+            tap.next().await,
+            Some(TapFrame::Data(Bytes::from_static(b"hello ")))
+        );
+        assert_eq!(tap.next().await, None);
+        assert!(!tap.is_complete());
+    }
+}
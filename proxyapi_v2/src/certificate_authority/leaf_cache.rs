@@ -0,0 +1,310 @@
+use crate::certificate_authority::{CACHE_TTL, CertificateCacheStats, NOT_BEFORE_OFFSET, TTL_SECS};
+use http::uri::Authority;
+use sha2::{Digest, Sha256};
+use std::{
+    fmt::Write as _,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tokio_rustls::rustls::pki_types::CertificateDer;
+use tracing::{debug, warn};
+
+const EXTENSION: &str = "leaf";
+
+/// 재시작 후에도 유지되는 잎 인증서 디스크 저장소
+///
+/// 생성한 잎 인증서를 CA 공개 키 지문별 디렉터리에 호스트(authority)별 파일로 저장하므로, CA를 다시
+/// 생성하면 이전 CA로 서명한 인증서는 사용되지 않습니다. 잎 인증서는 CA 개인 키를 그대로 사용하므로
+/// 인증서만 저장하고 개인 키는 저장하지 않습니다.
+///
+/// [`RcgenAuthority::with_leaf_store`](crate::certificate_authority::RcgenAuthority) 또는
+/// `OpensslAuthority::with_leaf_store`로 연결하면 메모리 캐시에 없는 인증서를 서명하기 전에 먼저
+/// 확인합니다.
+#[derive(Clone, Debug)]
+pub struct LeafCertStore {
+    dir: PathBuf,
+}
+
+impl LeafCertStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// 저장된 모든 잎 인증서 삭제
+    pub fn clear(&self) -> io::Result<()> {
+        match fs::remove_dir_all(&self.dir) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// CA 공개 키(DER) 지문별 저장소
+    ///
+    /// CA 인증서는 읽을 때마다 다시 서명될 수 있으므로(rcgen) 인증서 대신 공개 키로 구분합니다.
+    pub(crate) fn for_ca(&self, ca_public_key_der: &[u8]) -> CaLeafStore {
+        CaLeafStore {
+            dir: self.dir.join(hex(&Sha256::digest(ca_public_key_der))),
+        }
+    }
+}
+
+/// CA 하나로 서명한 잎 인증서 저장소
+#[derive(Debug)]
+pub(crate) struct CaLeafStore {
+    dir: PathBuf,
+}
+
+impl CaLeafStore {
+    /// 저장된 인증서 읽기 (만료가 가까운 인증서는 삭제하고 무시)
+    ///
+    /// 메모리 캐시는 인증서를 최대 `CACHE_TTL` 동안 사용하므로 그보다 오래 유효한 인증서만 반환합니다.
+    fn load(&self, authority: &Authority) -> Option<CertificateDer<'static>> {
+        let path = self.path(authority);
+        let contents = match fs::read(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return None,
+            Err(e) => {
+                warn!(
+                    "Failed to read cached certificate {}: {}",
+                    path.display(),
+                    e
+                );
+                return None;
+            }
+        };
+
+        let Some((not_after, der)) = decode(&contents) else {
+            warn!("Ignoring corrupt cached certificate {}", path.display());
+            let _ = fs::remove_file(&path);
+            return None;
+        };
+        if not_after < SystemTime::now() + Duration::from_secs(CACHE_TTL) {
+            debug!("Cached certificate for {} is about to expire", authority);
+            let _ = fs::remove_file(&path);
+            return None;
+        }
+
+        Some(CertificateDer::from(der.to_vec()))
+    }
+
+    /// 인증서 저장 (실패하면 로그만 남김)
+    fn save(&self, authority: &Authority, cert: &CertificateDer<'_>, not_after: SystemTime) {
+        let path = self.path(authority);
+        if let Err(e) = self.write(&path, &encode(not_after, cert)) {
+            warn!("Failed to store certificate {}: {}", path.display(), e);
+        }
+    }
+
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        static NEXT_TEMP: AtomicU64 = AtomicU64::new(0);
+
+        fs::create_dir_all(&self.dir)?;
+        // 같은 호스트를 동시에 저장해도 서로의 임시 파일을 덮어쓰지 않도록 고유한 이름 사용
+        let temp = path.with_extension(format!(
+            "{}.{}.tmp",
+            std::process::id(),
+            NEXT_TEMP.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&temp, contents)?;
+        fs::rename(&temp, path).inspect_err(|_| {
+            let _ = fs::remove_file(&temp);
+        })
+    }
+
+    fn path(&self, authority: &Authority) -> PathBuf {
+        self.dir
+            .join(format!("{}.{}", file_name(authority), EXTENSION))
+    }
+}
+
+/// 인증 기관의 잎 인증서 캐시 (디스크 저장소와 통계)
+#[derive(Debug, Default)]
+pub(crate) struct LeafCache {
+    store: Option<CaLeafStore>,
+    memory_hits: AtomicU64,
+    disk_hits: AtomicU64,
+    misses: AtomicU64,
+    signing_nanos: AtomicU64,
+}
+
+impl LeafCache {
+    pub(crate) fn set_store(&mut self, store: CaLeafStore) {
+        self.store = Some(store);
+    }
+
+    pub(crate) fn memory_hit(&self) {
+        self.memory_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 디스크 저장소에서 인증서를 찾고, 없으면 서명해서 저장
+    pub(crate) fn load_or_sign<E>(
+        &self,
+        authority: &Authority,
+        sign: impl FnOnce() -> Result<CertificateDer<'static>, E>,
+    ) -> Result<CertificateDer<'static>, E> {
+        if let Some(cert) = self.store.as_ref().and_then(|store| store.load(authority)) {
+            debug!("Using stored certificate for {}", authority);
+            self.disk_hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(cert);
+        }
+
+        // 서명하기 전 시각 기준이므로 실제 만료 시각보다 늦지 않음
+        let not_after = SystemTime::now() - Duration::from_secs(NOT_BEFORE_OFFSET as u64)
+            + Duration::from_secs(TTL_SECS as u64);
+        let started = Instant::now();
+        let cert = sign()?;
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        self.signing_nanos
+            .fetch_add(started.elapsed().as_nanos() as u64, Ordering::Relaxed);
+
+        if let Some(store) = &self.store {
+            store.save(authority, &cert, not_after);
+        }
+        Ok(cert)
+    }
+
+    pub(crate) fn stats(&self) -> CertificateCacheStats {
+        CertificateCacheStats {
+            memory_hits: self.memory_hits.load(Ordering::Relaxed),
+            disk_hits: self.disk_hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            signing_time: Duration::from_nanos(self.signing_nanos.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+/// 만료 시각(UNIX 초, 빅 엔디언 8바이트) 뒤에 DER 인증서
+fn encode(not_after: SystemTime, cert: &CertificateDer<'_>) -> Vec<u8> {
+    let secs = not_after
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let mut contents = Vec::with_capacity(8 + cert.len());
+    contents.extend_from_slice(&secs.to_be_bytes());
+    contents.extend_from_slice(cert);
+    contents
+}
+
+fn decode(contents: &[u8]) -> Option<(SystemTime, &[u8])> {
+    let (secs, der) = contents.split_first_chunk::<8>()?;
+    // DER 인증서는 SEQUENCE로 시작
+    if der.first() != Some(&0x30) {
+        return None;
+    }
+    Some((
+        UNIX_EPOCH + Duration::from_secs(u64::from_be_bytes(*secs)),
+        der,
+    ))
+}
+
+/// 호스트와 포트를 파일 이름으로 사용할 수 있게 변환 (영숫자, `.`, `-` 외에는 `_XX`로 인코딩)
+fn file_name(authority: &Authority) -> String {
+    let mut name = String::new();
+    for byte in authority.as_str().to_ascii_lowercase().bytes() {
+        if byte.is_ascii_alphanumeric() || byte == b'.' || byte == b'-' {
+            name.push(byte as char);
+        } else {
+            let _ = write!(name, "_{:02x}", byte);
+        }
+    }
+    name
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, byte| {
+        let _ = write!(hex, "{:02x}", byte);
+        hex
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::Infallible;
+
+    fn cert(byte: u8) -> CertificateDer<'static> {
+        CertificateDer::from(vec![0x30, 0x03, 0x02, 0x01, byte])
+    }
+
+    #[test]
+    fn stores_certificates_per_ca() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LeafCertStore::new(dir.path());
+        let authority = Authority::from_static("example.com:443");
+
+        let mut cache = LeafCache::default();
+        cache.set_store(store.for_ca(b"first ca"));
+        let signed = cache
+            .load_or_sign(&authority, || Ok::<_, Infallible>(cert(1)))
+            .unwrap();
+        assert_eq!(signed, cert(1));
+
+        // 재시작한 것처럼 새 캐시에서 읽음
+        let mut restarted = LeafCache::default();
+        restarted.set_store(store.for_ca(b"first ca"));
+        let loaded = restarted
+            .load_or_sign(&authority, || -> Result<_, Infallible> {
+                panic!("stored certificate should be used")
+            })
+            .unwrap();
+        assert_eq!(loaded, cert(1));
+        assert_eq!(restarted.stats().disk_hits, 1);
+        assert_eq!(restarted.stats().misses, 0);
+
+        // 다른 CA는 저장된 인증서를 사용하지 않음
+        let mut other = LeafCache::default();
+        other.set_store(store.for_ca(b"second ca"));
+        let signed = other
+            .load_or_sign(&authority, || Ok::<_, Infallible>(cert(2)))
+            .unwrap();
+        assert_eq!(signed, cert(2));
+        assert_eq!(other.stats().misses, 1);
+
+        store.clear().unwrap();
+        assert!(!dir.path().exists());
+        store.clear().unwrap();
+    }
+
+    #[test]
+    fn ignores_expiring_certificates() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LeafCertStore::new(dir.path()).for_ca(b"ca");
+        let authority = Authority::from_static("example.com");
+
+        store.save(
+            &authority,
+            &cert(1),
+            SystemTime::now() + Duration::from_secs(60),
+        );
+        assert_eq!(store.load(&authority), None);
+        assert!(!store.path(&authority).exists());
+
+        store.save(
+            &authority,
+            &cert(1),
+            SystemTime::now() + Duration::from_secs(TTL_SECS as u64),
+        );
+        assert_eq!(store.load(&authority), Some(cert(1)));
+
+        fs::write(store.path(&authority), b"corrupt").unwrap();
+        assert_eq!(store.load(&authority), None);
+    }
+
+    #[test]
+    fn escapes_file_names() {
+        assert_eq!(
+            file_name(&Authority::from_static("Example.com:8443")),
+            "example.com_3a8443"
+        );
+        assert_eq!(
+            file_name(&Authority::from_static("[::1]:443")),
+            "_5b_3a_3a1_5d_3a443"
+        );
+    }
+}
@@ -1,56 +1,44 @@
+#[cfg(any(feature = "openssl-ca", feature = "rcgen-ca"))]
+mod leaf_cache;
 #[cfg(feature = "openssl-ca")]
 mod openssl_authority;
 #[cfg(feature = "rcgen-ca")]
 mod rcgen_authority;
+#[cfg(feature = "rcgen-ca")]
+mod root_ca;
 
 use http::uri::Authority;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use tokio_rustls::rustls::ServerConfig;
 
+#[cfg(any(feature = "openssl-ca", feature = "rcgen-ca"))]
+pub use leaf_cache::LeafCertStore;
 #[cfg(feature = "openssl-ca")]
 pub use openssl_authority::*;
 #[cfg(feature = "rcgen-ca")]
 pub use rcgen_authority::*;
+#[cfg(feature = "rcgen-ca")]
+pub use root_ca::*;
 
 const TTL_SECS: i64 = 365 * 24 * 60 * 60;
 const CACHE_TTL: u64 = TTL_SECS as u64 / 2;
 const NOT_BEFORE_OFFSET: i64 = 60;
 
-/// 기존 인증서 파일을 사용하여 RcgenAuthority 생성
-#[cfg(feature = "rcgen-ca")]
-pub fn build_ca() -> Result<RcgenAuthority, String> {
-    // 기존 인증서 파일에서 키 페어와 CA 인증서 로드
-    let private_key_bytes: &[u8] = include_bytes!("cheolsu-proxy.key");
-    let ca_cert_bytes: &[u8] = include_bytes!("cheolsu-proxy.cer");
-
-    // PEM 형식의 키 페어 파싱
-    let key_pair = rcgen::KeyPair::from_pem(
-        std::str::from_utf8(private_key_bytes)
-            .map_err(|e| format!("키 파일 인코딩 오류: {}", e))?,
-    )
-    .map_err(|e| format!("키 페어 파싱 실패: {}", e))?;
-
-    // PEM 형식의 CA 인증서 파싱
-    let ca_cert_params = rcgen::CertificateParams::from_ca_cert_pem(
-        std::str::from_utf8(ca_cert_bytes)
-            .map_err(|e| format!("인증서 파일 인코딩 오류: {}", e))?,
-    )
-    .map_err(|e| format!("CA 인증서 파싱 실패: {}", e))?;
+/// 새로 생성하는 루트 CA의 이름
+pub const DEFAULT_CA_NAME: &str = "Cheolsu Proxy CA";
 
-    // CertificateParams를 Certificate로 변환
-    let ca_cert = ca_cert_params
-        .self_signed(&key_pair)
-        .map_err(|e| format!("CA 인증서 서명 실패: {}", e))?;
+/// 디렉터리에 저장된 설치별 루트 CA로 RcgenAuthority 생성 (없으면 새로 생성해서 저장)
+#[cfg(feature = "rcgen-ca")]
+pub fn build_ca(dir: &std::path::Path) -> Result<RcgenAuthority, String> {
+    let root_ca = RootCa::load_or_generate(dir, DEFAULT_CA_NAME)
+        .map_err(|e| format!("CA 로드 실패: {}", e))?;
 
-    // RcgenAuthority 생성
-    let ca = RcgenAuthority::new(
-        key_pair,
-        ca_cert,
+    RcgenAuthority::from_root_ca(
+        &root_ca,
         1_000,
         tokio_rustls::rustls::crypto::aws_lc_rs::default_provider(),
-    );
-
-    Ok(ca)
+    )
+    .map_err(|e| format!("CA 인증서 파싱 실패: {}", e))
 }
 
 /// Issues certificates for use when communicating with clients.
@@ -75,4 +63,39 @@ pub trait CertificateAuthority: Send + Sync + 'static {
         &self,
         authority: &Authority,
     ) -> impl Future<Output = Option<Vec<u8>>> + Send;
+
+    /// Get statistics about the certificate cache.
+    /// Returns empty statistics if the authority does not track them.
+    fn cache_stats(&self) -> CertificateCacheStats {
+        CertificateCacheStats::default()
+    }
+}
+
+/// 인증서 캐시 통계
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CertificateCacheStats {
+    /// 메모리 캐시에서 찾은 횟수
+    pub memory_hits: u64,
+    /// 디스크 저장소에서 찾은 횟수
+    pub disk_hits: u64,
+    /// 새로 서명한 횟수
+    pub misses: u64,
+    /// 서명에 걸린 전체 시간
+    pub signing_time: Duration,
+}
+
+impl CertificateCacheStats {
+    /// 메모리 캐시 또는 디스크 저장소에서 찾은 횟수
+    pub fn hits(&self) -> u64 {
+        self.memory_hits + self.disk_hits
+    }
+
+    /// 인증서 하나를 서명하는 데 걸린 평균 시간
+    pub fn average_signing_time(&self) -> Option<Duration> {
+        u32::try_from(self.misses)
+            .ok()
+            .filter(|&misses| misses > 0)
+            .map(|misses| self.signing_time / misses)
+    }
 }
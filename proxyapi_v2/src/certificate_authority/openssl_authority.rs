@@ -1,4 +1,7 @@
-use crate::certificate_authority::{CACHE_TTL, CertificateAuthority, NOT_BEFORE_OFFSET, TTL_SECS};
+use crate::certificate_authority::{
+    CACHE_TTL, CertificateAuthority, CertificateCacheStats, LeafCertStore, NOT_BEFORE_OFFSET,
+    TTL_SECS, leaf_cache::LeafCache,
+};
 use http::uri::Authority;
 use moka::future::Cache;
 use openssl::{
@@ -55,6 +58,7 @@ pub struct OpensslAuthority {
     ca_cert: X509,
     hash: MessageDigest,
     cache: Cache<Authority, Arc<ServerConfig>>,
+    leaf_cache: LeafCache,
     provider: Arc<CryptoProvider>,
 }
 
@@ -81,10 +85,34 @@ impl OpensslAuthority {
                 .max_capacity(cache_size)
                 .time_to_live(Duration::from_secs(CACHE_TTL))
                 .build(),
+            leaf_cache: LeafCache::default(),
             provider: Arc::new(provider),
         }
     }
 
+    /// 메모리 캐시에 없는 인증서를 서명하기 전에 디스크 저장소에서 찾고, 새로 서명한 인증서는 저장
+    pub fn with_leaf_store(mut self, store: &LeafCertStore) -> Self {
+        let public_key_der = self
+            .pkey
+            .public_key_to_der()
+            .expect("Failed to encode public key");
+        self.leaf_cache.set_store(store.for_ca(&public_key_der));
+        self
+    }
+
+    /// 설치별 루트 CA로 생성
+    #[cfg(feature = "rcgen-ca")]
+    pub fn from_root_ca(
+        root_ca: &crate::certificate_authority::RootCa,
+        hash: MessageDigest,
+        cache_size: u64,
+        provider: CryptoProvider,
+    ) -> Result<Self, crate::certificate_authority::RootCaError> {
+        let pkey = PKey::private_key_from_pem(root_ca.key_pem().as_bytes())?;
+        let ca_cert = X509::from_pem(root_ca.cert_pem().as_bytes())?;
+        Ok(Self::new(pkey, ca_cert, hash, cache_size, provider))
+    }
+
     fn gen_cert(&self, authority: &Authority) -> Result<CertificateDer<'static>, ErrorStack> {
         let mut name_builder = X509NameBuilder::new()?;
         name_builder.append_entry_by_text("CN", authority.host())?;
@@ -127,12 +155,14 @@ impl CertificateAuthority for OpensslAuthority {
     async fn gen_server_config(&self, authority: &Authority) -> Arc<ServerConfig> {
         if let Some(server_cfg) = self.cache.get(authority).await {
             debug!("Using cached server config");
+            self.leaf_cache.memory_hit();
             return server_cfg;
         }
         debug!("Generating server config");
 
         let certs = vec![
-            self.gen_cert(authority)
+            self.leaf_cache
+                .load_or_sign(authority, || self.gen_cert(authority))
                 .unwrap_or_else(|_| panic!("Failed to generate certificate for {}", authority)),
         ];
 
@@ -163,6 +193,10 @@ impl CertificateAuthority for OpensslAuthority {
         self.ca_cert.to_der().ok()
     }
 
+    fn cache_stats(&self) -> CertificateCacheStats {
+        self.leaf_cache.stats()
+    }
+
     #[cfg(feature = "native-tls-client")]
     async fn gen_pkcs12_identity(&self, authority: &Authority) -> Option<Vec<u8>> {
         use openssl::pkcs12::Pkcs12;
@@ -217,25 +251,21 @@ impl CertificateAuthority for OpensslAuthority {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "rcgen-ca"))]
 mod tests {
     use super::*;
+    use crate::certificate_authority::RootCa;
     use tokio_rustls::rustls::crypto::aws_lc_rs;
 
     fn build_ca(cache_size: u64) -> OpensslAuthority {
-        let private_key_bytes: &[u8] = include_bytes!("cheolsu-proxy.key");
-        let ca_cert_bytes: &[u8] = include_bytes!("cheolsu-proxy.cer");
-        let private_key =
-            PKey::private_key_from_pem(private_key_bytes).expect("Failed to parse private key");
-        let ca_cert = X509::from_pem(ca_cert_bytes).expect("Failed to parse CA certificate");
-
-        OpensslAuthority::new(
-            private_key,
-            ca_cert,
+        let root_ca = RootCa::generate("Openssl Authority Test CA").expect("Failed to generate CA");
+        OpensslAuthority::from_root_ca(
+            &root_ca,
             MessageDigest::sha256(),
             cache_size,
             aws_lc_rs::default_provider(),
         )
+        .expect("Failed to build authority")
     }
 
     #[test]
@@ -1,11 +1,14 @@
-use crate::certificate_authority::{CACHE_TTL, CertificateAuthority, NOT_BEFORE_OFFSET, TTL_SECS};
+use crate::certificate_authority::{
+    CACHE_TTL, CertificateAuthority, CertificateCacheStats, LeafCertStore, NOT_BEFORE_OFFSET,
+    RootCa, RootCaError, TTL_SECS, leaf_cache::LeafCache,
+};
 use http::uri::Authority;
 use moka::future::Cache;
 use rand::{Rng, rng};
 use rcgen::{
     Certificate, CertificateParams, DistinguishedName, DnType, Ia5String, KeyPair, SanType,
 };
-use std::sync::Arc;
+use std::{convert::Infallible, sync::Arc};
 use time::{Duration, OffsetDateTime};
 use tokio_rustls::rustls::{
     ServerConfig,
@@ -41,6 +44,7 @@ pub struct RcgenAuthority {
     ca_cert: Certificate,
     private_key: PrivateKeyDer<'static>,
     cache: Cache<Authority, Arc<ServerConfig>>,
+    leaf_cache: LeafCache,
     provider: Arc<CryptoProvider>,
 }
 
@@ -62,10 +66,30 @@ impl RcgenAuthority {
                 .max_capacity(cache_size)
                 .time_to_live(std::time::Duration::from_secs(CACHE_TTL))
                 .build(),
+            leaf_cache: LeafCache::default(),
             provider: Arc::new(provider),
         }
     }
 
+    /// 메모리 캐시에 없는 인증서를 서명하기 전에 디스크 저장소에서 찾고, 새로 서명한 인증서는 저장
+    pub fn with_leaf_store(mut self, store: &LeafCertStore) -> Self {
+        self.leaf_cache
+            .set_store(store.for_ca(&self.key_pair.public_key_der()));
+        self
+    }
+
+    /// 설치별 루트 CA로 생성
+    pub fn from_root_ca(
+        root_ca: &RootCa,
+        cache_size: u64,
+        provider: CryptoProvider,
+    ) -> Result<Self, RootCaError> {
+        let key_pair = KeyPair::from_pem(root_ca.key_pem())?;
+        let ca_cert =
+            CertificateParams::from_ca_cert_pem(root_ca.cert_pem())?.self_signed(&key_pair)?;
+        Ok(Self::new(key_pair, ca_cert, cache_size, provider))
+    }
+
     fn gen_cert(&self, authority: &Authority) -> CertificateDer<'static> {
         info!("Generating certificate for authority: {}", authority);
 
@@ -162,11 +186,16 @@ impl CertificateAuthority for RcgenAuthority {
     async fn gen_server_config(&self, authority: &Authority) -> Arc<ServerConfig> {
         if let Some(server_cfg) = self.cache.get(authority).await {
             debug!("Using cached server config");
+            self.leaf_cache.memory_hit();
             return server_cfg;
         }
         debug!("Generating server config");
 
-        let certs = vec![self.gen_cert(authority)];
+        let certs = vec![
+            self.leaf_cache
+                .load_or_sign(authority, || Ok::<_, Infallible>(self.gen_cert(authority)))
+                .unwrap_or_else(|e| match e {}),
+        ];
 
         let mut server_cfg = ServerConfig::builder_with_provider(Arc::clone(&self.provider))
             .with_safe_default_protocol_versions()
@@ -206,6 +235,10 @@ impl CertificateAuthority for RcgenAuthority {
         Some(der_bytes)
     }
 
+    fn cache_stats(&self) -> CertificateCacheStats {
+        self.leaf_cache.stats()
+    }
+
     #[cfg(feature = "native-tls-client")]
     async fn gen_pkcs12_identity(&self, authority: &Authority) -> Option<Vec<u8>> {
         #[cfg(feature = "openssl-ca")]
@@ -275,15 +308,9 @@ mod tests {
     use tokio_rustls::rustls::crypto::aws_lc_rs;
 
     fn build_ca(cache_size: u64) -> RcgenAuthority {
-        let key_pair = include_str!("cheolsu-proxy.key");
-        let ca_cert = include_str!("cheolsu-proxy.cer");
-        let key_pair = KeyPair::from_pem(key_pair).expect("Failed to parse private key");
-        let ca_cert = CertificateParams::from_ca_cert_pem(ca_cert)
-            .expect("Failed to parse CA certificate")
-            .self_signed(&key_pair)
-            .expect("Failed to sign CA certificate");
-
-        RcgenAuthority::new(key_pair, ca_cert, cache_size, aws_lc_rs::default_provider())
+        let root_ca = RootCa::generate("Rcgen Authority Test CA").expect("Failed to generate CA");
+        RcgenAuthority::from_root_ca(&root_ca, cache_size, aws_lc_rs::default_provider())
+            .expect("Failed to build authority")
     }
 
     #[test]
@@ -315,4 +342,36 @@ mod tests {
         assert_ne!(cert1.raw_serial(), cert3.raw_serial());
         assert_ne!(cert2.raw_serial(), cert4.raw_serial());
     }
+
+    #[tokio::test]
+    async fn reuses_stored_certificates() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LeafCertStore::new(dir.path());
+        let root_ca = RootCa::generate("Rcgen Authority Test CA").unwrap();
+        let authority = Authority::from_static("example.com:443");
+        let build = || {
+            RcgenAuthority::from_root_ca(&root_ca, 1_000, aws_lc_rs::default_provider())
+                .unwrap()
+                .with_leaf_store(&store)
+        };
+
+        let ca = build();
+        ca.gen_server_config(&authority).await;
+        ca.gen_server_config(&authority).await;
+        let stats = ca.cache_stats();
+        assert_eq!(
+            (stats.memory_hits, stats.disk_hits, stats.misses),
+            (1, 0, 1)
+        );
+        assert!(stats.average_signing_time().is_some());
+
+        // 재시작한 것처럼 새 인증 기관에서는 저장된 인증서 사용
+        let restarted = build();
+        restarted.gen_server_config(&authority).await;
+        let stats = restarted.cache_stats();
+        assert_eq!(
+            (stats.memory_hits, stats.disk_hits, stats.misses),
+            (0, 1, 0)
+        );
+    }
 }
@@ -0,0 +1,301 @@
+use crate::certificate_authority::NOT_BEFORE_OFFSET;
+use base64::{Engine, prelude::BASE64_STANDARD};
+use rand::{Rng, rng};
+use rcgen::{
+    BasicConstraints, CertificateParams, DistinguishedName, DnType, IsCa, KeyPair, KeyUsagePurpose,
+};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+use time::{Duration, OffsetDateTime};
+use tracing::info;
+
+/// 루트 CA 인증서 유효 기간 (10년)
+const ROOT_TTL_DAYS: i64 = 10 * 365;
+
+const KEY_FILE: &str = "ca.key";
+const CERT_FILE: &str = "ca.crt";
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum RootCaError {
+    #[error("Failed to access CA files: {0}")]
+    Io(#[from] io::Error),
+    #[error("Invalid CA certificate or key: {0}")]
+    Certificate(#[from] rcgen::Error),
+    #[error("Invalid CA certificate: {0}")]
+    Pem(#[from] pem::PemError),
+    #[cfg(feature = "openssl-ca")]
+    #[error("Invalid CA certificate or key: {0}")]
+    Openssl(#[from] openssl::error::ErrorStack),
+}
+
+/// 설치마다 생성하는 루트 CA 인증서와 개인 키
+///
+/// [`load_or_generate`](Self::load_or_generate)는 처음 실행할 때 새 키 쌍으로 CA를 만들어 디렉터리에
+/// 저장하고(개인 키는 소유자만 읽을 수 있는 권한으로), 이후에는 저장된 CA를 읽습니다.
+/// [`RcgenAuthority::from_root_ca`](crate::certificate_authority::RcgenAuthority::from_root_ca)
+/// 또는 `OpensslAuthority::from_root_ca`로 인증 기관을 만듭니다.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RootCa {
+    key_pem: String,
+    cert_pem: String,
+}
+
+impl RootCa {
+    /// 새 키 쌍으로 자체 서명 루트 CA 생성
+    pub fn generate(common_name: &str) -> Result<Self, RootCaError> {
+        let key_pair = KeyPair::generate()?;
+
+        let mut params = CertificateParams::default();
+        params.serial_number = Some(rng().random::<u64>().into());
+        let mut distinguished_name = DistinguishedName::new();
+        distinguished_name.push(DnType::CommonName, common_name);
+        distinguished_name.push(DnType::OrganizationName, common_name);
+        params.distinguished_name = distinguished_name;
+        // 잎 인증서만 직접 발급하므로 중간 CA는 허용하지 않음
+        params.is_ca = IsCa::Ca(BasicConstraints::Constrained(0));
+        params.key_usages = vec![
+            KeyUsagePurpose::KeyCertSign,
+            KeyUsagePurpose::CrlSign,
+            KeyUsagePurpose::DigitalSignature,
+        ];
+        let not_before = OffsetDateTime::now_utc() - Duration::seconds(NOT_BEFORE_OFFSET);
+        params.not_before = not_before;
+        params.not_after = not_before + Duration::days(ROOT_TTL_DAYS);
+
+        let cert = params.self_signed(&key_pair)?;
+        Ok(Self {
+            key_pem: key_pair.serialize_pem(),
+            cert_pem: cert.pem(),
+        })
+    }
+
+    /// PEM 개인 키와 PEM CA 인증서로 생성
+    pub fn from_pem(key_pem: &str, cert_pem: &str) -> Result<Self, RootCaError> {
+        KeyPair::from_pem(key_pem)?;
+        CertificateParams::from_ca_cert_pem(cert_pem)?;
+        Ok(Self {
+            key_pem: key_pem.to_string(),
+            cert_pem: cert_pem.to_string(),
+        })
+    }
+
+    /// 디렉터리에 저장된 CA를 읽고, 없으면 새로 생성해서 저장
+    pub fn load_or_generate(dir: &Path, common_name: &str) -> Result<Self, RootCaError> {
+        let (key_path, cert_path) = paths(dir);
+        if key_path.exists() && cert_path.exists() {
+            let root_ca = Self::from_pem(
+                &fs::read_to_string(&key_path)?,
+                &fs::read_to_string(&cert_path)?,
+            )?;
+            restrict_permissions(&key_path, 0o600)?;
+            return Ok(root_ca);
+        }
+
+        info!("Generating new root CA in {}", dir.display());
+        let root_ca = Self::generate(common_name)?;
+        root_ca.save(dir)?;
+        Ok(root_ca)
+    }
+
+    /// 디렉터리에 개인 키(`ca.key`)와 인증서(`ca.crt`)를 저장 (기존 파일은 교체)
+    ///
+    /// 디렉터리와 개인 키는 소유자만 접근할 수 있는 권한으로 만듭니다(Unix).
+    pub fn save(&self, dir: &Path) -> Result<(), RootCaError> {
+        fs::create_dir_all(dir)?;
+        restrict_permissions(dir, 0o700)?;
+
+        let (key_path, cert_path) = paths(dir);
+        write_atomic(&key_path, self.key_pem.as_bytes(), 0o600)?;
+        write_atomic(&cert_path, self.cert_pem.as_bytes(), 0o644)?;
+        Ok(())
+    }
+
+    pub fn key_pem(&self) -> &str {
+        &self.key_pem
+    }
+
+    pub fn cert_pem(&self) -> &str {
+        &self.cert_pem
+    }
+
+    /// CA 인증서 (DER)
+    pub fn cert_der(&self) -> Result<Vec<u8>, RootCaError> {
+        Ok(pem::parse(&self.cert_pem)?.into_contents())
+    }
+
+    /// CA 인증서를 신뢰 저장소에 추가하는 Apple 구성 프로파일(`.mobileconfig`) 생성
+    pub fn mobileconfig(&self, display_name: &str) -> Result<String, RootCaError> {
+        let certificate = BASE64_STANDARD.encode(self.cert_der()?);
+        let display_name = escape_xml(display_name);
+        let payload_uuid = uuid();
+        let profile_uuid = uuid();
+
+        Ok(format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>PayloadContent</key>
+	<array>
+		<dict>
+			<key>PayloadCertificateFileName</key>
+			<string>{CERT_FILE}</string>
+			<key>PayloadContent</key>
+			<data>{certificate}</data>
+			<key>PayloadDisplayName</key>
+			<string>{display_name}</string>
+			<key>PayloadIdentifier</key>
+			<string>com.apple.security.root.{payload_uuid}</string>
+			<key>PayloadType</key>
+			<string>com.apple.security.root</string>
+			<key>PayloadUUID</key>
+			<string>{payload_uuid}</string>
+			<key>PayloadVersion</key>
+			<integer>1</integer>
+		</dict>
+	</array>
+	<key>PayloadDisplayName</key>
+	<string>{display_name}</string>
+	<key>PayloadIdentifier</key>
+	<string>com.cheolsu-proxy.ca.{profile_uuid}</string>
+	<key>PayloadRemovalDisallowed</key>
+	<false/>
+	<key>PayloadType</key>
+	<string>Configuration</string>
+	<key>PayloadUUID</key>
+	<string>{profile_uuid}</string>
+	<key>PayloadVersion</key>
+	<integer>1</integer>
+</dict>
+</plist>
+"#
+        ))
+    }
+}
+
+fn paths(dir: &Path) -> (PathBuf, PathBuf) {
+    (dir.join(KEY_FILE), dir.join(CERT_FILE))
+}
+
+/// 임시 파일에 쓴 뒤 이름을 바꿔서, 쓰는 도중에 중단되어도 기존 파일이 깨지지 않게 저장
+fn write_atomic(path: &Path, contents: &[u8], mode: u32) -> io::Result<()> {
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    let temp = PathBuf::from(temp);
+    {
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, mode);
+        io::Write::write_all(&mut options.open(&temp)?, contents)?;
+    }
+    restrict_permissions(&temp, mode)?;
+    fs::rename(&temp, path)
+}
+
+/// 파일 권한 설정 (Unix 외에는 무시)
+fn restrict_permissions(path: &Path, mode: u32) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(mode))?;
+    }
+    #[cfg(not(unix))]
+    let _ = (path, mode);
+    Ok(())
+}
+
+/// 무작위 UUID (버전 4) 문자열
+fn uuid() -> String {
+    let mut bytes: [u8; 16] = rng().random();
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = bytes
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<String>();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_unique_ca() {
+        let first = RootCa::generate("Root CA Test").unwrap();
+        let second = RootCa::generate("Root CA Test").unwrap();
+        assert_ne!(first.key_pem(), second.key_pem());
+
+        let der = first.cert_der().unwrap();
+        let (_, certificate) = x509_parser::parse_x509_certificate(&der).unwrap();
+        assert!(certificate.is_ca());
+        assert_eq!(certificate.subject(), certificate.issuer());
+        assert!(
+            certificate
+                .subject()
+                .iter_common_name()
+                .any(|name| name.as_str() == Ok("Root CA Test"))
+        );
+    }
+
+    #[test]
+    fn saves_and_loads_ca() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path().join("ca");
+
+        let generated = RootCa::load_or_generate(&dir, "Root CA Test").unwrap();
+        let loaded = RootCa::load_or_generate(&dir, "Root CA Test").unwrap();
+        assert_eq!(generated, loaded);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(dir.join(KEY_FILE))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+            let mode = fs::metadata(&dir).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o700);
+        }
+
+        // 다시 생성하면 파일을 교체
+        let regenerated = RootCa::generate("Root CA Test").unwrap();
+        regenerated.save(&dir).unwrap();
+        assert_eq!(
+            RootCa::load_or_generate(&dir, "Root CA Test").unwrap(),
+            regenerated
+        );
+        assert!(!dir.join("ca.key.tmp").exists());
+    }
+
+    #[test]
+    fn builds_mobileconfig() {
+        let root_ca = RootCa::generate("Root CA Test").unwrap();
+        let profile = root_ca.mobileconfig("Proxy <CA>").unwrap();
+
+        assert!(profile.contains("<string>com.apple.security.root</string>"));
+        assert!(profile.contains("<string>Proxy &lt;CA&gt;</string>"));
+        assert!(profile.contains(&BASE64_STANDARD.encode(root_ca.cert_der().unwrap())));
+    }
+}
@@ -0,0 +1,459 @@
+//! 업스트림 서버에 보내는 클라이언트 인증서 (mTLS)
+//!
+//! [`ClientIdentities`]에 호스트 패턴별로 [`ClientIdentity`](PEM 또는 PKCS#12)를 등록하면, 업스트림
+//! 연결의 SNI(요청 호스트)에 맞는 인증서를 골라 서버가 요청할 때 보냅니다. rustls의
+//! [`ResolvesClientCert`]는 SNI를 전달받지 않으므로 인증서마다 별도의 TLS 설정을 만들고,
+//! [`IdentityConnector`]가 연결할 호스트에 따라 TLS 설정을 선택합니다.
+//!
+//! `legacy-tls-client` 기능의 [`legacy_client`](crate::replay::legacy_client)도 같은 방식으로 호스트별
+//! OpenSSL 설정을 선택합니다.
+//!
+//! 인증서를 선택한 연결의 응답에는 [`UsedClientIdentity`]가 기록되어
+//! [`HttpContext::client_identity`](crate::HttpContext::client_identity)로 핸들러에 전달됩니다.
+
+use crate::host_pattern::host_matches;
+use http::Uri;
+use hyper::rt::{Read, ReadBufCursor, Write};
+use hyper_util::client::legacy::connect::{Connected, Connection};
+use std::{
+    convert::Infallible,
+    future::Future,
+    io,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll, ready},
+};
+use thiserror::Error;
+use tokio_rustls::rustls::{
+    self, ClientConfig, SignatureScheme,
+    client::ResolvesClientCert,
+    crypto::CryptoProvider,
+    pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer, pem::PemObject},
+    sign::CertifiedKey,
+};
+use tower_service::Service;
+use tracing::{debug, warn};
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ClientIdentityError {
+    #[error("Failed to parse PEM: {0}")]
+    Pem(#[from] rustls::pki_types::pem::Error),
+    #[error("Failed to parse PKCS#12: {0}")]
+    Pkcs12(#[from] p12_keystore::error::Error),
+    #[error("No certificate found")]
+    NoCertificate,
+    #[error("No private key found")]
+    NoPrivateKey,
+    #[error("Unsupported private key: {0}")]
+    Key(#[from] rustls::Error),
+}
+
+/// 이름이 붙은 클라이언트 인증서 체인과 개인 키
+#[derive(Clone, Debug)]
+pub struct ClientIdentity {
+    name: String,
+    key: Arc<CertifiedKey>,
+    /// OpenSSL 연결에 사용할 개인 키
+    #[cfg(feature = "legacy-tls-client")]
+    private_key: Arc<PrivateKeyDer<'static>>,
+}
+
+impl ClientIdentity {
+    /// PEM 인증서 체인(클라이언트 인증서부터)과 PEM 개인 키로 생성
+    pub fn from_pem(
+        name: impl Into<String>,
+        certificate_pem: &[u8],
+        key_pem: &[u8],
+        provider: &CryptoProvider,
+    ) -> Result<Self, ClientIdentityError> {
+        let chain =
+            CertificateDer::pem_slice_iter(certificate_pem).collect::<Result<Vec<_>, _>>()?;
+        let key = PrivateKeyDer::from_pem_slice(key_pem).map_err(|e| match e {
+            rustls::pki_types::pem::Error::NoItemsFound => ClientIdentityError::NoPrivateKey,
+            e => ClientIdentityError::Pem(e),
+        })?;
+        Self::new(name, chain, key, provider)
+    }
+
+    /// 암호로 보호된 PKCS#12(.p12, .pfx) 파일 내용으로 생성
+    pub fn from_pkcs12(
+        name: impl Into<String>,
+        der: &[u8],
+        password: &str,
+        provider: &CryptoProvider,
+    ) -> Result<Self, ClientIdentityError> {
+        let store = p12_keystore::KeyStore::from_pkcs12(der, password)?;
+        let (_, key_chain) = store
+            .private_key_chain()
+            .ok_or(ClientIdentityError::NoPrivateKey)?;
+        let chain = key_chain
+            .chain()
+            .iter()
+            .map(|certificate| CertificateDer::from(certificate.as_der().to_vec()))
+            .collect();
+        let key = PrivatePkcs8KeyDer::from(key_chain.key().to_vec()).into();
+        Self::new(name, chain, key, provider)
+    }
+
+    fn new(
+        name: impl Into<String>,
+        chain: Vec<CertificateDer<'static>>,
+        key: PrivateKeyDer<'static>,
+        provider: &CryptoProvider,
+    ) -> Result<Self, ClientIdentityError> {
+        if chain.is_empty() {
+            return Err(ClientIdentityError::NoCertificate);
+        }
+        #[cfg(feature = "legacy-tls-client")]
+        let private_key = Arc::new(key.clone_key());
+        let key = CertifiedKey::from_der(chain, key, provider)?;
+        Ok(Self {
+            name: name.into(),
+            key: Arc::new(key),
+            #[cfg(feature = "legacy-tls-client")]
+            private_key,
+        })
+    }
+
+    /// 인증서 이름 (교환마다 어떤 인증서를 사용했는지 기록할 때 사용)
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 클라이언트 인증서 체인 (클라이언트 인증서부터)
+    pub fn certificates(&self) -> &[CertificateDer<'static>] {
+        &self.key.cert
+    }
+
+    /// 개인 키 (DER)
+    #[cfg(feature = "legacy-tls-client")]
+    pub(crate) fn private_key(&self) -> &PrivateKeyDer<'static> {
+        &self.private_key
+    }
+}
+
+/// 호스트 패턴별 클라이언트 인증서 목록
+///
+/// 호스트 패턴은 [`TlsVerificationPolicy`](crate::upstream_tls::TlsVerificationPolicy)의 예외 호스트와
+/// 같은 형식이며, 여러 패턴이 일치하면 먼저 추가한 인증서를 사용합니다.
+#[derive(Clone, Debug, Default)]
+pub struct ClientIdentities {
+    identities: Vec<(String, ClientIdentity)>,
+}
+
+impl ClientIdentities {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 호스트 패턴에 사용할 클라이언트 인증서 추가
+    pub fn with_identity(mut self, pattern: impl Into<String>, identity: ClientIdentity) -> Self {
+        self.identities
+            .push((pattern.into().to_ascii_lowercase(), identity));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.identities.is_empty()
+    }
+
+    /// 호스트에 사용할 클라이언트 인증서 찾기
+    pub fn find(&self, host: &str) -> Option<&ClientIdentity> {
+        self.identities
+            .iter()
+            .find(|(pattern, _)| host_matches(pattern, host))
+            .map(|(_, identity)| identity)
+    }
+
+    /// 호스트에 맞는 클라이언트 인증서로 연결하는 커넥터 생성
+    ///
+    /// `make_connector`는 TLS 설정으로 안쪽 커넥터를 만들며, 인증서가 없는 호스트용 기본 설정(`config`
+    /// 그대로)과 인증서마다 [`IdentityResolver`]를 설정한 복사본으로 한 번씩 호출됩니다.
+    pub fn connector<C>(
+        &self,
+        config: ClientConfig,
+        make_connector: impl Fn(ClientConfig) -> C,
+    ) -> IdentityConnector<C> {
+        let connector = self.try_connector(|identity| {
+            let mut config = config.clone();
+            if let Some(identity) = identity {
+                config.client_auth_cert_resolver =
+                    Arc::new(IdentityResolver::new(identity.clone()));
+            }
+            Ok::<_, Infallible>(make_connector(config))
+        });
+        match connector {
+            Ok(connector) => connector,
+            Err(never) => match never {},
+        }
+    }
+
+    /// 인증서마다 안쪽 커넥터를 만들어 호스트에 맞게 선택하는 커넥터 생성
+    ///
+    /// `make_connector`는 인증서가 없는 호스트용으로 `None`, 인증서마다 `Some`으로 한 번씩 호출됩니다.
+    pub(crate) fn try_connector<C, E>(
+        &self,
+        mut make_connector: impl FnMut(Option<&ClientIdentity>) -> Result<C, E>,
+    ) -> Result<IdentityConnector<C>, E> {
+        let identities = self
+            .identities
+            .iter()
+            .map(|(pattern, identity)| {
+                Ok((
+                    pattern.clone(),
+                    identity.name.clone(),
+                    make_connector(Some(identity))?,
+                ))
+            })
+            .collect::<Result<_, E>>()?;
+
+        Ok(IdentityConnector {
+            default: make_connector(None)?,
+            identities,
+        })
+    }
+}
+
+/// 서버가 요청하면 정해진 클라이언트 인증서를 보내는 rustls 인증서 선택기
+#[derive(Debug)]
+pub struct IdentityResolver {
+    identity: ClientIdentity,
+}
+
+impl IdentityResolver {
+    pub fn new(identity: ClientIdentity) -> Self {
+        Self { identity }
+    }
+}
+
+impl ResolvesClientCert for IdentityResolver {
+    fn resolve(
+        &self,
+        _root_hint_subjects: &[&[u8]],
+        sigschemes: &[SignatureScheme],
+    ) -> Option<Arc<CertifiedKey>> {
+        if self.identity.key.key.choose_scheme(sigschemes).is_none() {
+            warn!(
+                "Client certificate {} does not support any signature scheme offered by the server",
+                self.identity.name
+            );
+            return None;
+        }
+        debug!("Sending client certificate {}", self.identity.name);
+        Some(self.identity.key.clone())
+    }
+
+    fn has_certs(&self) -> bool {
+        true
+    }
+}
+
+/// 연결에 선택된 클라이언트 인증서 이름 (응답 extension)
+///
+/// 인증서는 서버가 요청한 경우에만 실제로 전송됩니다.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct UsedClientIdentity(pub String);
+
+/// 연결할 호스트에 따라 클라이언트 인증서별 커넥터를 선택하는 커넥터
+///
+/// [`ClientIdentities::connector`]로 생성합니다.
+#[derive(Clone, Debug)]
+pub struct IdentityConnector<C> {
+    default: C,
+    identities: Vec<(String, String, C)>,
+}
+
+impl<C> Service<Uri> for IdentityConnector<C>
+where
+    C: Service<Uri>,
+    C::Future: Send + 'static,
+{
+    type Response = IdentityStream<C::Response>;
+    type Error = C::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        for (_, _, connector) in &mut self.identities {
+            ready!(connector.poll_ready(cx))?;
+        }
+        self.default.poll_ready(cx)
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        // 평문 연결에는 클라이언트 인증서를 사용하지 않음
+        let host = uri
+            .host()
+            .filter(|_| uri.scheme() == Some(&http::uri::Scheme::HTTPS))
+            .map(|host| host.trim_start_matches('[').trim_end_matches(']'))
+            .map(str::to_ascii_lowercase);
+        let selected = host.and_then(|host| {
+            self.identities
+                .iter_mut()
+                .find(|(pattern, _, _)| host_matches(pattern, &host))
+        });
+        let (identity, connecting) = match selected {
+            Some((_, name, connector)) => (Some(name.clone()), connector.call(uri)),
+            None => (None, self.default.call(uri)),
+        };
+
+        Box::pin(async move {
+            Ok(IdentityStream {
+                inner: connecting.await?,
+                identity,
+            })
+        })
+    }
+}
+
+/// 선택한 클라이언트 인증서 이름을 연결 정보에 기록하는 스트림
+#[derive(Debug)]
+pub struct IdentityStream<S> {
+    inner: S,
+    identity: Option<String>,
+}
+
+impl<S: Connection> Connection for IdentityStream<S> {
+    fn connected(&self) -> Connected {
+        let connected = self.inner.connected();
+        match &self.identity {
+            Some(identity) => connected.extra(UsedClientIdentity(identity.clone())),
+            None => connected,
+        }
+    }
+}
+
+impl<S: Read + Unpin> Read for IdentityStream<S> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: ReadBufCursor<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl<S: Write + Unpin> Write for IdentityStream<S> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write_vectored(cx, bufs)
+    }
+}
+
+#[cfg(all(test, feature = "rcgen-ca"))]
+mod tests {
+    use super::*;
+    use rcgen::{CertificateParams, DnType, KeyPair};
+    use tokio_rustls::rustls::crypto::aws_lc_rs;
+
+    fn certificate() -> (rcgen::Certificate, KeyPair) {
+        let key = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        params
+            .distinguished_name
+            .push(DnType::CommonName, "Client Identity Test");
+        (params.self_signed(&key).unwrap(), key)
+    }
+
+    #[test]
+    fn loads_pem_identity() {
+        let provider = aws_lc_rs::default_provider();
+        let (certificate, key) = certificate();
+
+        let identity = ClientIdentity::from_pem(
+            "internal",
+            certificate.pem().as_bytes(),
+            key.serialize_pem().as_bytes(),
+            &provider,
+        )
+        .unwrap();
+        assert_eq!(identity.name(), "internal");
+        assert_eq!(identity.certificates(), [certificate.der().clone()]);
+
+        assert!(matches!(
+            ClientIdentity::from_pem("internal", b"", key.serialize_pem().as_bytes(), &provider),
+            Err(ClientIdentityError::NoCertificate)
+        ));
+        assert!(matches!(
+            ClientIdentity::from_pem("internal", certificate.pem().as_bytes(), b"", &provider),
+            Err(ClientIdentityError::NoPrivateKey)
+        ));
+    }
+
+    #[test]
+    fn loads_pkcs12_identity() {
+        use p12_keystore::{Certificate, KeyStore, KeyStoreEntry, PrivateKeyChain};
+
+        let provider = aws_lc_rs::default_provider();
+        let (certificate, key) = certificate();
+        let mut store = KeyStore::new();
+        store.add_entry(
+            "client",
+            KeyStoreEntry::PrivateKeyChain(PrivateKeyChain::new(
+                key.serialize_der(),
+                [1],
+                [Certificate::from_der(certificate.der()).unwrap()],
+            )),
+        );
+        let der = store.writer("secret").write().unwrap();
+
+        let identity = ClientIdentity::from_pkcs12("internal", &der, "secret", &provider).unwrap();
+        assert_eq!(identity.certificates(), [certificate.der().clone()]);
+        assert!(matches!(
+            ClientIdentity::from_pkcs12("internal", &der, "wrong", &provider),
+            Err(ClientIdentityError::Pkcs12(_))
+        ));
+    }
+
+    #[test]
+    fn finds_identity_by_host() {
+        let provider = aws_lc_rs::default_provider();
+        let identity = |name| {
+            let (certificate, key) = certificate();
+            ClientIdentity::from_pem(
+                name,
+                certificate.pem().as_bytes(),
+                key.serialize_pem().as_bytes(),
+                &provider,
+            )
+            .unwrap()
+        };
+        let identities = ClientIdentities::new()
+            .with_identity("api.internal.test", identity("exact"))
+            .with_identity("*.Internal.test", identity("wildcard"));
+
+        assert_eq!(
+            identities.find("API.internal.test").unwrap().name(),
+            "exact"
+        );
+        assert_eq!(
+            identities.find("db.internal.test").unwrap().name(),
+            "wildcard"
+        );
+        assert!(identities.find("internal.test").is_none());
+        assert!(identities.find("example.com").is_none());
+    }
+}
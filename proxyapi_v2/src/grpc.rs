@@ -0,0 +1,597 @@
+//! gRPC / gRPC-Web 메시지 디코딩
+//!
+//! [`GrpcBody::decode`]로 길이 접두(length-prefixed) 형식의 gRPC 본문을 메시지 단위로 나누고
+//! (압축된 메시지는 `grpc-encoding`에 따라 압축 해제), [`GrpcStatus`]로 `grpc-status`/`grpc-message`
+//! 트레일러를 읽습니다. [`ProtoDescriptors`]에 `FileDescriptorSet`이나 `.proto` 파일을 등록하면
+//! 요청 경로(`/package.Service/Method`)의 메시지 타입으로 메시지를 JSON으로 변환할 수 있습니다.
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use flate2::read::{GzDecoder, ZlibDecoder};
+use http::{HeaderMap, HeaderName, HeaderValue, header::CONTENT_TYPE};
+use hyper::body::Bytes;
+use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor, MethodDescriptor};
+use std::{
+    io::{self, Read},
+    path::Path,
+    process::Command,
+    sync::atomic::{AtomicU64, Ordering},
+};
+use thiserror::Error;
+
+/// 메시지가 압축되어 있음을 나타내는 플래그
+const COMPRESSED_FLAG: u8 = 0x01;
+/// gRPC-Web 본문의 트레일러 프레임 플래그
+const TRAILER_FLAG: u8 = 0x80;
+/// 플래그(1 byte) + 길이(4 bytes)
+const PREFIX_LEN: usize = 5;
+/// [`GrpcBody::decode`]에서 압축을 풀 수 있는 본문 전체의 최대 크기
+pub const DEFAULT_MAX_DECOMPRESSED_LEN: usize = 16 * 1024 * 1024;
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum GrpcError {
+    #[error("gRPC message is truncated")]
+    Truncated,
+    #[error("Unsupported grpc-encoding: {0}")]
+    UnsupportedEncoding(String),
+    #[error("Failed to decompress gRPC message: {0}")]
+    Decompress(io::Error),
+    #[error("Decompressed gRPC messages exceed {0} bytes")]
+    TooLarge(usize),
+    #[error("Invalid grpc-web-text body: {0}")]
+    Base64(#[from] base64::DecodeError),
+    #[error("Invalid protobuf descriptor: {0}")]
+    Descriptor(#[from] prost_reflect::DescriptorError),
+    #[error("Failed to run protoc: {0}")]
+    Protoc(String),
+    #[error("Unknown gRPC method: {0}")]
+    UnknownMethod(String),
+    #[error("Failed to decode protobuf message: {0}")]
+    Decode(#[from] prost::DecodeError),
+    #[error("Failed to convert protobuf message to JSON: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// `content-type`이 gRPC 또는 gRPC-Web인지 확인
+pub fn is_grpc(headers: &HeaderMap) -> bool {
+    content_type(headers).is_some_and(|content_type| content_type.starts_with("application/grpc"))
+}
+
+/// `content-type`이 gRPC-Web인지 확인
+pub fn is_grpc_web(headers: &HeaderMap) -> bool {
+    content_type(headers)
+        .is_some_and(|content_type| content_type.starts_with("application/grpc-web"))
+}
+
+fn content_type(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(CONTENT_TYPE)?
+        .to_str()
+        .ok()
+        .map(|content_type| content_type.trim().to_ascii_lowercase())
+}
+
+/// gRPC 본문의 한 메시지
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GrpcMessage {
+    /// 전송될 때 압축되어 있었는지 여부
+    pub compressed: bool,
+    /// 압축을 푼 protobuf 메시지
+    pub data: Bytes,
+}
+
+/// gRPC 호출 결과 상태
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GrpcStatus {
+    /// `grpc-status` 코드 (0이면 성공)
+    pub code: u32,
+    /// 퍼센트 인코딩을 푼 `grpc-message`
+    pub message: Option<String>,
+}
+
+impl GrpcStatus {
+    /// 트레일러(또는 trailers-only 응답의 헤더)에서 상태 읽기
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let code = headers
+            .get("grpc-status")?
+            .to_str()
+            .ok()?
+            .trim()
+            .parse()
+            .ok()?;
+        let message = headers
+            .get("grpc-message")
+            .map(|message| percent_decode(message.as_bytes()));
+
+        Some(Self { code, message })
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.code == 0
+    }
+}
+
+/// 메시지 단위로 나눈 gRPC 본문
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GrpcBody {
+    /// 본문의 메시지 (순서대로)
+    pub messages: Vec<GrpcMessage>,
+    /// gRPC-Web 본문에 포함된 트레일러 (gRPC는 HTTP 트레일러로 전달되므로 비어 있음)
+    pub trailers: HeaderMap,
+}
+
+impl GrpcBody {
+    /// 헤더(`content-type`, `grpc-encoding`)에 맞춰 본문을 메시지 단위로 나눔
+    ///
+    /// `application/grpc-web-text`면 base64 디코딩 후 나눕니다.
+    ///
+    /// # Errors
+    ///
+    /// 본문이 중간에 잘렸거나, 압축된 메시지의 `grpc-encoding`을 지원하지 않거나 압축 해제에
+    /// 실패하면 에러를 반환합니다. 압축을 푼 메시지의 합계가
+    /// [`DEFAULT_MAX_DECOMPRESSED_LEN`]을 넘어도 에러를 반환합니다.
+    pub fn decode(headers: &HeaderMap, body: &[u8]) -> Result<Self, GrpcError> {
+        Self::decode_with_limit(headers, body, DEFAULT_MAX_DECOMPRESSED_LEN)
+    }
+
+    /// 압축을 푼 메시지 합계의 최대 크기(`limit` bytes)를 지정해서 [`decode`](Self::decode)
+    ///
+    /// 작은 압축 메시지가 매우 큰 메시지로 풀리는 경우(압축 폭탄)에도 `limit`까지만 메모리를
+    /// 사용하고 [`GrpcError::TooLarge`]를 반환합니다.
+    pub fn decode_with_limit(
+        headers: &HeaderMap,
+        body: &[u8],
+        limit: usize,
+    ) -> Result<Self, GrpcError> {
+        let encoding = headers
+            .get("grpc-encoding")
+            .and_then(|encoding| encoding.to_str().ok());
+
+        if content_type(headers)
+            .is_some_and(|content_type| content_type.starts_with("application/grpc-web-text"))
+        {
+            let body = decode_base64_chunks(body)?;
+            Self::split(&body, encoding, limit)
+        } else {
+            Self::split(body, encoding, limit)
+        }
+    }
+
+    fn split(mut body: &[u8], encoding: Option<&str>, limit: usize) -> Result<Self, GrpcError> {
+        let mut grpc = Self::default();
+        // 압축을 풀 수 있는 남은 크기
+        let mut remaining = limit;
+
+        while !body.is_empty() {
+            let Some((prefix, rest)) = body.split_at_checked(PREFIX_LEN) else {
+                return Err(GrpcError::Truncated);
+            };
+
+            let flags = prefix[0];
+            let len = u32::from_be_bytes([prefix[1], prefix[2], prefix[3], prefix[4]]) as usize;
+            let Some((data, rest)) = rest.split_at_checked(len) else {
+                return Err(GrpcError::Truncated);
+            };
+            body = rest;
+
+            if flags & TRAILER_FLAG != 0 {
+                parse_trailers(data, &mut grpc.trailers);
+                continue;
+            }
+
+            let compressed = flags & COMPRESSED_FLAG != 0;
+            let data = if compressed {
+                let data = decompress(encoding.unwrap_or("identity"), data, remaining).map_err(
+                    |e| match e {
+                        GrpcError::TooLarge(_) => GrpcError::TooLarge(limit),
+                        e => e,
+                    },
+                )?;
+                remaining -= data.len();
+                data
+            } else {
+                Bytes::copy_from_slice(data)
+            };
+
+            grpc.messages.push(GrpcMessage { compressed, data });
+        }
+
+        Ok(grpc)
+    }
+
+    /// gRPC-Web 본문 트레일러의 상태 (없으면 None)
+    pub fn status(&self) -> Option<GrpcStatus> {
+        GrpcStatus::from_headers(&self.trailers)
+    }
+}
+
+/// 압축 해제 (`limit` bytes를 넘으면 더 읽지 않고 에러)
+fn decompress(encoding: &str, data: &[u8], limit: usize) -> Result<Bytes, GrpcError> {
+    let mut decompressed = Vec::new();
+    // 한도를 넘었는지 알 수 있도록 한 바이트 더 읽음
+    let max = limit as u64 + 1;
+    let result = match encoding.trim() {
+        "gzip" => GzDecoder::new(data)
+            .take(max)
+            .read_to_end(&mut decompressed),
+        "deflate" => ZlibDecoder::new(data)
+            .take(max)
+            .read_to_end(&mut decompressed),
+        other => return Err(GrpcError::UnsupportedEncoding(other.to_owned())),
+    };
+
+    result.map_err(GrpcError::Decompress)?;
+    if decompressed.len() > limit {
+        return Err(GrpcError::TooLarge(limit));
+    }
+    Ok(Bytes::from(decompressed))
+}
+
+/// 패딩으로 끝나는 base64 조각이 이어 붙은 grpc-web-text 본문 디코딩
+fn decode_base64_chunks(body: &[u8]) -> Result<Vec<u8>, GrpcError> {
+    let body: Vec<u8> = body
+        .iter()
+        .copied()
+        .filter(|b| !b.is_ascii_whitespace())
+        .collect();
+
+    let mut decoded = Vec::new();
+    let mut rest = body.as_slice();
+    while !rest.is_empty() {
+        let end = match rest.iter().position(|&b| b == b'=') {
+            Some(start) => start + rest[start..].iter().take_while(|&&b| b == b'=').count(),
+            None => rest.len(),
+        };
+
+        decoded.extend(STANDARD.decode(&rest[..end])?);
+        rest = &rest[end..];
+    }
+
+    Ok(decoded)
+}
+
+/// gRPC-Web 트레일러 프레임(`name: value` 줄 목록) 파싱
+fn parse_trailers(data: &[u8], trailers: &mut HeaderMap) {
+    for line in data.split(|&b| b == b'\n') {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        let Some(colon) = line.iter().position(|&b| b == b':') else {
+            continue;
+        };
+
+        let name = HeaderName::from_bytes(line[..colon].trim_ascii());
+        let value = HeaderValue::from_bytes(line[colon + 1..].trim_ascii());
+        if let (Ok(name), Ok(value)) = (name, value) {
+            trailers.append(name, value);
+        }
+    }
+}
+
+/// `grpc-message`의 퍼센트 인코딩 해제
+fn percent_decode(value: &[u8]) -> String {
+    let mut decoded = Vec::with_capacity(value.len());
+    let mut i = 0;
+    while i < value.len() {
+        let byte = value[i];
+        let hex = value
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match (byte, hex) {
+            (b'%', Some(hex)) => {
+                decoded.push(hex);
+                i += 3;
+            }
+            _ => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// 메시지를 JSON으로 변환하기 위한 protobuf 디스크립터 모음
+///
+/// 복제 비용이 작으므로 핸들러마다 복제해서 사용할 수 있습니다.
+#[derive(Clone, Debug)]
+pub struct ProtoDescriptors {
+    pool: DescriptorPool,
+}
+
+impl Default for ProtoDescriptors {
+    fn default() -> Self {
+        Self {
+            pool: DescriptorPool::new(),
+        }
+    }
+}
+
+impl ProtoDescriptors {
+    /// 빈 디스크립터 모음 생성
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 직렬화된 `FileDescriptorSet` 추가 (`protoc --include_imports -o` 출력 등)
+    ///
+    /// # Errors
+    ///
+    /// 디스크립터를 파싱할 수 없거나 이미 추가된 정의와 충돌하면 에러를 반환합니다.
+    pub fn add_file_descriptor_set(&mut self, bytes: &[u8]) -> Result<(), GrpcError> {
+        self.pool.decode_file_descriptor_set(bytes)?;
+        Ok(())
+    }
+
+    /// `.proto` 파일 추가
+    ///
+    /// `protoc`(또는 `PROTOC` 환경 변수로 지정한 실행 파일)로 `FileDescriptorSet`을 생성해
+    /// 추가하며, `include_paths`는 `import` 경로 검색에 사용됩니다.
+    ///
+    /// # Errors
+    ///
+    /// `protoc`를 실행할 수 없거나 컴파일에 실패하면 에러를 반환합니다.
+    pub fn add_proto_files(
+        &mut self,
+        files: &[impl AsRef<Path>],
+        include_paths: &[impl AsRef<Path>],
+    ) -> Result<(), GrpcError> {
+        static NEXT_OUTPUT: AtomicU64 = AtomicU64::new(0);
+
+        let output = std::env::temp_dir().join(format!(
+            "proxyapi-descriptors-{}-{}.pb",
+            std::process::id(),
+            NEXT_OUTPUT.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        let mut protoc =
+            Command::new(std::env::var_os("PROTOC").unwrap_or_else(|| "protoc".into()));
+        protoc.arg("--include_imports").arg("-o").arg(&output);
+        for include_path in include_paths {
+            protoc.arg("-I").arg(include_path.as_ref());
+        }
+        for file in files {
+            let file = file.as_ref();
+            // import 경로가 없으면 파일이 있는 디렉터리를 기준으로 컴파일
+            let dir = file
+                .parent()
+                .filter(|dir| include_paths.is_empty() && !dir.as_os_str().is_empty());
+            if let Some(dir) = dir {
+                protoc.arg("-I").arg(dir);
+            }
+            protoc.arg(file);
+        }
+
+        let result = protoc
+            .output()
+            .map_err(|e| GrpcError::Protoc(e.to_string()))
+            .and_then(|out| {
+                if out.status.success() {
+                    std::fs::read(&output).map_err(|e| GrpcError::Protoc(e.to_string()))
+                } else {
+                    Err(GrpcError::Protoc(
+                        String::from_utf8_lossy(&out.stderr).trim().to_owned(),
+                    ))
+                }
+            });
+        let _ = std::fs::remove_file(&output);
+
+        self.add_file_descriptor_set(&result?)
+    }
+
+    /// 등록된 서비스가 없는지 확인
+    pub fn is_empty(&self) -> bool {
+        self.pool.services().len() == 0
+    }
+
+    /// 요청 경로(`/package.Service/Method`)에 해당하는 메서드
+    pub fn method(&self, path: &str) -> Option<MethodDescriptor> {
+        let (service, method) = path.trim_start_matches('/').split_once('/')?;
+        self.pool
+            .get_service_by_name(service)?
+            .methods()
+            .find(|m| m.name() == method)
+    }
+
+    /// 요청 본문의 메시지를 JSON 배열로 변환
+    ///
+    /// # Errors
+    ///
+    /// 경로에 해당하는 메서드가 없거나 메시지를 디코딩할 수 없으면 에러를 반환합니다.
+    pub fn request_json(
+        &self,
+        path: &str,
+        body: &GrpcBody,
+    ) -> Result<serde_json::Value, GrpcError> {
+        let method = self
+            .method(path)
+            .ok_or_else(|| GrpcError::UnknownMethod(path.to_owned()))?;
+        to_json(method.input(), body)
+    }
+
+    /// 응답 본문의 메시지를 JSON 배열로 변환
+    ///
+    /// # Errors
+    ///
+    /// 경로에 해당하는 메서드가 없거나 메시지를 디코딩할 수 없으면 에러를 반환합니다.
+    pub fn response_json(
+        &self,
+        path: &str,
+        body: &GrpcBody,
+    ) -> Result<serde_json::Value, GrpcError> {
+        let method = self
+            .method(path)
+            .ok_or_else(|| GrpcError::UnknownMethod(path.to_owned()))?;
+        to_json(method.output(), body)
+    }
+}
+
+fn to_json(descriptor: MessageDescriptor, body: &GrpcBody) -> Result<serde_json::Value, GrpcError> {
+    body.messages
+        .iter()
+        .map(|message| {
+            let message = DynamicMessage::decode(descriptor.clone(), message.data.clone())?;
+            Ok(serde_json::to_value(&message)?)
+        })
+        .collect::<Result<Vec<_>, GrpcError>>()
+        .map(serde_json::Value::Array)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{Compression, write::GzEncoder};
+    use prost::Message;
+    use prost_types::{
+        DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+        MethodDescriptorProto, ServiceDescriptorProto, field_descriptor_proto::Type,
+    };
+    use std::io::Write;
+
+    fn frame(flags: u8, data: &[u8]) -> Vec<u8> {
+        let mut frame = vec![flags];
+        frame.extend((data.len() as u32).to_be_bytes());
+        frame.extend(data);
+        frame
+    }
+
+    fn headers(content_type: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, content_type.parse().unwrap());
+        headers
+    }
+
+    /// `test.Echo/Say(test.Text) returns (test.Text)`
+    fn descriptors() -> ProtoDescriptors {
+        let text = DescriptorProto {
+            name: Some("Text".into()),
+            field: vec![FieldDescriptorProto {
+                name: Some("text".into()),
+                number: Some(1),
+                r#type: Some(Type::String as i32),
+                json_name: Some("text".into()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let service = ServiceDescriptorProto {
+            name: Some("Echo".into()),
+            method: vec![MethodDescriptorProto {
+                name: Some("Say".into()),
+                input_type: Some(".test.Text".into()),
+                output_type: Some(".test.Text".into()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let set = FileDescriptorSet {
+            file: vec![FileDescriptorProto {
+                name: Some("test.proto".into()),
+                package: Some("test".into()),
+                message_type: vec![text],
+                service: vec![service],
+                syntax: Some("proto3".into()),
+                ..Default::default()
+            }],
+        };
+
+        let mut descriptors = ProtoDescriptors::new();
+        descriptors
+            .add_file_descriptor_set(&set.encode_to_vec())
+            .unwrap();
+        descriptors
+    }
+
+    #[test]
+    fn splits_messages() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"second").unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let mut body = frame(0, b"first");
+        body.extend(frame(COMPRESSED_FLAG, &compressed));
+
+        let mut headers = headers("application/grpc");
+        headers.insert("grpc-encoding", "gzip".parse().unwrap());
+
+        let grpc = GrpcBody::decode(&headers, &body).unwrap();
+        assert_eq!(grpc.messages.len(), 2);
+        assert_eq!(grpc.messages[0].data, "first");
+        assert!(grpc.messages[1].compressed);
+        assert_eq!(grpc.messages[1].data, "second");
+
+        assert!(matches!(
+            GrpcBody::decode(&headers, &body[..body.len() - 1]),
+            Err(GrpcError::Truncated)
+        ));
+    }
+
+    #[test]
+    fn limits_decompressed_size() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(&vec![0; 1024 * 1024]).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let body = frame(COMPRESSED_FLAG, &compressed);
+        let mut headers = headers("application/grpc");
+        headers.insert("grpc-encoding", "gzip".parse().unwrap());
+
+        assert!(matches!(
+            GrpcBody::decode_with_limit(&headers, &body, 1024),
+            Err(GrpcError::TooLarge(1024))
+        ));
+
+        // 한도는 본문의 모든 메시지 합계에 적용
+        let mut body = body.clone();
+        body.extend(frame(COMPRESSED_FLAG, &compressed));
+        assert!(matches!(
+            GrpcBody::decode_with_limit(&headers, &body, 1024 * 1024 + 1),
+            Err(GrpcError::TooLarge(_))
+        ));
+        assert_eq!(GrpcBody::decode(&headers, &body).unwrap().messages.len(), 2);
+    }
+
+    #[test]
+    fn reads_grpc_web_trailers() {
+        let mut body = frame(0, b"hello");
+        body.extend(frame(
+            TRAILER_FLAG,
+            b"grpc-status: 3\r\ngrpc-message: bad%20request\r\n",
+        ));
+
+        let grpc = GrpcBody::decode(&headers("application/grpc-web+proto"), &body).unwrap();
+        assert_eq!(grpc.messages.len(), 1);
+        assert_eq!(
+            grpc.status(),
+            Some(GrpcStatus {
+                code: 3,
+                message: Some("bad request".into()),
+            })
+        );
+
+        // grpc-web-text는 base64 조각이 이어 붙어 있을 수 있음
+        let mut text = STANDARD.encode(frame(0, b"a"));
+        text.push_str(&STANDARD.encode(frame(TRAILER_FLAG, b"grpc-status: 0\r\n")));
+        let grpc =
+            GrpcBody::decode(&headers("application/grpc-web-text"), text.as_bytes()).unwrap();
+        assert_eq!(grpc.messages[0].data, "a");
+        assert!(grpc.status().unwrap().is_ok());
+    }
+
+    #[test]
+    fn renders_messages_as_json() {
+        let descriptors = descriptors();
+        let body = frame(0, &[0x0a, 0x02, b'h', b'i']);
+        let grpc = GrpcBody::decode(&headers("application/grpc"), &body).unwrap();
+
+        assert_eq!(
+            descriptors.request_json("/test.Echo/Say", &grpc).unwrap(),
+            serde_json::json!([{ "text": "hi" }])
+        );
+        assert!(matches!(
+            descriptors.response_json("/test.Echo/Missing", &grpc),
+            Err(GrpcError::UnknownMethod(_))
+        ));
+    }
+}
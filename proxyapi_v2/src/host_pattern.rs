@@ -0,0 +1,50 @@
+//! 호스트 패턴 매칭
+//!
+//! Map Remote, 인증서 검증 예외, 클라이언트 인증서 선택, 상위 프록시 우회 목록에서 같은 규칙으로 호스트를
+//! 매칭합니다.
+
+/// 호스트가 호스트 패턴과 일치하는지 확인 (ASCII 대소문자 구분 없음)
+///
+/// `*`는 모든 호스트, `*.example.com`은 하위 도메인(`example.com` 자체는 제외), `.example.com`은
+/// `example.com`과 하위 도메인(`NO_PROXY` 형식), 그 밖의 패턴은 정확히 같은 호스트와 일치합니다.
+pub(crate) fn host_matches(pattern: &str, host: &str) -> bool {
+    match pattern.strip_prefix('*') {
+        Some("") => true,
+        Some(suffix) => suffix.starts_with('.') && is_subdomain(host, suffix),
+        None => match pattern.strip_prefix('.') {
+            Some(domain) => domain.eq_ignore_ascii_case(host) || is_subdomain(host, pattern),
+            None => pattern.eq_ignore_ascii_case(host),
+        },
+    }
+}
+
+/// 호스트가 `.`으로 시작하는 접미사의 하위 도메인인지 확인
+fn is_subdomain(host: &str, suffix: &str) -> bool {
+    host.len() > suffix.len()
+        && host.is_char_boundary(host.len() - suffix.len())
+        && host[host.len() - suffix.len()..].eq_ignore_ascii_case(suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_patterns() {
+        assert!(host_matches("*", "example.com"));
+        assert!(host_matches("example.com", "example.com"));
+        assert!(host_matches("Example.COM", "example.com"));
+        assert!(!host_matches("example.com", "api.example.com"));
+
+        assert!(host_matches("*.example.com", "api.example.com"));
+        assert!(host_matches("*.example.com", "API.Example.com"));
+        assert!(!host_matches("*.example.com", "example.com"));
+        assert!(!host_matches("*.example.com", "badexample.com"));
+        assert!(!host_matches("*.example.com", "example.org"));
+        assert!(!host_matches("*example.com", "badexample.com"));
+
+        assert!(host_matches(".example.com", "example.com"));
+        assert!(host_matches(".example.com", "www.Example.com"));
+        assert!(!host_matches(".example.com", "notexample.com"));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::TlsInfo;
 use crate::certificate_authority::CertificateAuthority;
 use crate::rewind::Rewind;
 use crate::tls_version_detector::TlsVersionDetector;
@@ -45,13 +46,16 @@ impl<CA: CertificateAuthority> HybridTlsHandler<CA> {
         })
     }
 
-    /// TLS 버전을 감지하고 적절한 TLS 핸들러를 선택합니다 (Upgraded 스트림 전용)
-    pub async fn handle_tls_connection_upgraded(
+    /// TLS 버전을 감지하고 적절한 TLS 핸들러를 선택합니다 (Upgraded 스트림, SOCKS5 스트림 등)
+    pub async fn handle_tls_connection_upgraded<S>(
         &self,
         authority: &Authority,
-        upgraded: Rewind<TokioIo<Upgraded>>,
+        upgraded: Rewind<S>,
         initial_buffer: &[u8],
-    ) -> Result<HybridTlsStream, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<HybridTlsStream<S>, Box<dyn std::error::Error + Send + Sync>>
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
         // TLS 버전 감지
         let tls_version = TlsVersionDetector::detect_tls_version(initial_buffer);
 
@@ -195,11 +199,14 @@ impl<CA: CertificateAuthority> HybridTlsHandler<CA> {
     }
 
     /// rustls로 Upgraded 스트림을 처리합니다
-    async fn handle_with_rustls_upgraded(
+    async fn handle_with_rustls_upgraded<S>(
         &self,
         authority: &Authority,
-        upgraded: Rewind<TokioIo<Upgraded>>,
-    ) -> Result<HybridTlsStream, Box<dyn std::error::Error + Send + Sync>> {
+        upgraded: Rewind<S>,
+    ) -> Result<HybridTlsStream<S>, Box<dyn std::error::Error + Send + Sync>>
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
         let server_config = self.ca.gen_server_config(authority).await;
         let acceptor = TlsAcceptor::from(server_config);
 
@@ -219,11 +226,14 @@ impl<CA: CertificateAuthority> HybridTlsHandler<CA> {
 
     /// native-tls로 Upgraded 스트림을 처리합니다
     #[cfg(feature = "native-tls-client")]
-    async fn handle_with_native_tls_upgraded(
+    async fn handle_with_native_tls_upgraded<S>(
         &self,
         authority: &Authority,
-        upgraded: Rewind<TokioIo<Upgraded>>,
-    ) -> Result<HybridTlsStream, Box<dyn std::error::Error + Send + Sync>> {
+        upgraded: Rewind<S>,
+    ) -> Result<HybridTlsStream<S>, Box<dyn std::error::Error + Send + Sync>>
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
         info!("🔧 native-tls로 TLS 연결 처리 시작: {}", authority);
 
         // PKCS12 인증서 생성
@@ -269,11 +279,14 @@ impl<CA: CertificateAuthority> HybridTlsHandler<CA> {
     }
 
     #[cfg(not(feature = "native-tls-client"))]
-    async fn handle_with_native_tls_upgraded(
+    async fn handle_with_native_tls_upgraded<S>(
         &self,
         _authority: &Authority,
-        _upgraded: Rewind<TokioIo<Upgraded>>,
-    ) -> Result<HybridTlsStream, Box<dyn std::error::Error + Send + Sync>> {
+        _upgraded: Rewind<S>,
+    ) -> Result<HybridTlsStream<S>, Box<dyn std::error::Error + Send + Sync>>
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
         error!("native-tls-client feature가 활성화되지 않음");
         Err("native-tls-client feature not enabled".into())
     }
@@ -294,13 +307,29 @@ impl<CA: CertificateAuthority> HybridTlsHandler<CA> {
 }
 
 /// 하이브리드 TLS 스트림 - rustls 또는 native-tls 스트림을 래핑
-pub enum HybridTlsStream {
-    Rustls(tokio_rustls::TlsStream<Rewind<TokioIo<Upgraded>>>),
+pub enum HybridTlsStream<S = TokioIo<Upgraded>> {
+    Rustls(tokio_rustls::TlsStream<Rewind<S>>),
     #[cfg(feature = "native-tls-client")]
-    NativeTls(NativeTlsStream<Rewind<TokioIo<Upgraded>>>),
+    NativeTls(NativeTlsStream<Rewind<S>>),
+}
+
+impl<S> HybridTlsStream<S> {
+    /// 클라이언트와 협상한 TLS 세션 정보
+    ///
+    /// native-tls는 협상 결과를 제공하지 않으므로 빈 정보를 반환합니다.
+    pub fn tls_info(&self) -> TlsInfo {
+        match self {
+            HybridTlsStream::Rustls(tokio_rustls::TlsStream::Server(stream)) => {
+                TlsInfo::from_rustls(stream.get_ref().1)
+            }
+            HybridTlsStream::Rustls(tokio_rustls::TlsStream::Client(_)) => TlsInfo::default(),
+            #[cfg(feature = "native-tls-client")]
+            HybridTlsStream::NativeTls(_) => TlsInfo::default(),
+        }
+    }
 }
 
-impl AsyncRead for HybridTlsStream {
+impl<S: AsyncRead + AsyncWrite + Unpin> AsyncRead for HybridTlsStream<S> {
     fn poll_read(
         self: std::pin::Pin<&mut Self>,
         cx: &mut std::task::Context<'_>,
@@ -314,7 +343,7 @@ impl AsyncRead for HybridTlsStream {
     }
 }
 
-impl AsyncWrite for HybridTlsStream {
+impl<S: AsyncRead + AsyncWrite + Unpin> AsyncWrite for HybridTlsStream<S> {
     fn poll_write(
         self: std::pin::Pin<&mut Self>,
         cx: &mut std::task::Context<'_>,
@@ -10,41 +10,67 @@
 //!
 //! - `decoder`: Enables [`decode_request`] and [`decode_response`] helpers (enabled by default).
 //! - `full`: Enables all features.
+//! - `grpc`: Enables [`grpc`] message decoding with protobuf descriptor support.
 //! - `http2`: Enables HTTP/2 support.
+//! - `map-local`: Enables [`map_local`] for answering requests from local files.
 //! - `native-tls-client`: Enables [`ProxyBuilder::with_native_tls_client`](builder::ProxyBuilder::with_native_tls_client).
 //! - `openssl-ca`: Enables [`OpensslAuthority`](certificate_authority::OpensslAuthority).
 //! - `rcgen-ca`: Enables [`RcgenAuthority`](certificate_authority::RcgenAuthority) (enabled by default).
+//! - `replay`: Enables the [`replay`] client for resending captured requests.
+//! - `rules`: Enables the [`rules`] request/response rewrite engine.
 //! - `rustls-client`: Enables [`ProxyBuilder::with_rustls_client`](builder::ProxyBuilder::with_rustls_client) (enabled by default).
 
 mod body;
 #[cfg(feature = "decoder")]
 mod decoder;
 mod error;
+mod host_pattern;
 mod noop;
 mod proxy;
 mod rewind;
 
 pub mod certificate_authority;
+pub mod client_identity;
+#[cfg(feature = "grpc")]
+pub mod grpc;
 pub mod hybrid_tls_handler;
+#[cfg(feature = "map-local")]
+pub mod map_local;
+pub mod map_remote;
+#[cfg(feature = "replay")]
+pub mod replay;
+#[cfg(feature = "rules")]
+pub mod rules;
+pub mod sse;
+pub mod timings;
 pub mod tls_version_detector;
+pub mod upstream_proxy;
+pub mod upstream_tls;
+pub mod websocket;
 
 use futures::{Sink, SinkExt, Stream, StreamExt};
+use http::uri::{Authority, Scheme};
 use hyper::{Request, Response, StatusCode, Uri};
 use std::net::SocketAddr;
+use timings::Timings;
 use tokio_tungstenite::tungstenite::{self, Message};
 use tracing::error;
+use upstream_tls::{UpstreamSession, UpstreamTls};
+use websocket::WebSocketDirection;
 
 pub use futures;
 pub use hyper;
 pub use hyper_util;
 #[cfg(feature = "openssl-ca")]
 pub use openssl;
+#[cfg(feature = "grpc")]
+pub use prost_reflect;
 #[cfg(feature = "rcgen-ca")]
 pub use rcgen;
 pub use tokio_rustls::rustls;
 pub use tokio_tungstenite;
 
-pub use body::Body;
+pub use body::{Body, BodyTap, TapFrame};
 #[cfg(feature = "decoder")]
 pub use decoder::{decode_request, decode_response};
 pub use error::Error;
@@ -80,6 +106,51 @@ impl From<Response<Body>> for RequestOrResponse {
 pub struct HttpContext {
     /// Address of the client that is sending the request.
     pub client_addr: SocketAddr,
+    /// 요청/응답 쌍을 식별하는 ID (프로세스 안에서 고유)
+    ///
+    /// 같은 교환의 `handle_request`, `handle_response`, `handle_error`에는 같은 값이 전달되며,
+    /// HTTP/2 연결에서는 스트림마다 별도의 교환으로 처리됩니다.
+    pub exchange_id: u64,
+    /// 클라이언트 연결 ID (프로세스 안에서 고유)
+    ///
+    /// CONNECT 터널, TLS 세션, HTTP/2 스트림 등 같은 TCP 연결로 들어온 요청은 같은 값을 가집니다.
+    pub connection_id: u64,
+    /// 가로챈 CONNECT(SOCKS5, 투명 프록시 포함) 대상 주소 (일반 프록시 요청이면 None)
+    pub authority: Option<Authority>,
+    /// 클라이언트와의 연결 스킴 (가로챈 TLS 연결이면 HTTPS)
+    pub scheme: Scheme,
+    /// 가로챈 TLS 세션 정보 (TLS가 아니면 None)
+    pub tls: Option<TlsInfo>,
+    /// 구간별 소요 시간 (응답을 받은 뒤 `handle_response`에서만 채워짐)
+    pub timings: Timings,
+    /// 업스트림 서버 인증서 검증 결과 (TLS 연결로 응답을 받은 뒤 `handle_response`에서만 채워짐)
+    pub upstream_tls: Option<UpstreamTls>,
+    /// 업스트림 TLS 세션 정보 (TLS 연결로 응답을 받은 뒤 `handle_response`에서만 채워짐)
+    pub upstream_session: Option<UpstreamSession>,
+    /// 업스트림 연결에 선택된 클라이언트 인증서 이름 (응답을 받은 뒤 `handle_response`에서만 채워짐)
+    pub client_identity: Option<String>,
+}
+
+/// 클라이언트와 협상한 TLS 세션 정보
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct TlsInfo {
+    /// 협상된 TLS 버전
+    pub version: Option<TlsVersion>,
+    /// 협상된 ALPN 프로토콜 (예: `h2`, `http/1.1`)
+    pub alpn: Option<Vec<u8>>,
+    /// 클라이언트가 보낸 SNI
+    pub sni: Option<String>,
+}
+
+impl TlsInfo {
+    pub(crate) fn from_rustls(conn: &rustls::ServerConnection) -> Self {
+        Self {
+            version: conn.protocol_version().and_then(TlsVersion::from_rustls),
+            alpn: conn.alpn_protocol().map(<[u8]>::to_vec),
+            sni: conn.server_name().map(str::to_owned),
+        }
+    }
 }
 
 /// Context for websocket messages.
@@ -91,6 +162,8 @@ pub enum WebSocketContext {
         src: SocketAddr,
         /// URI of the server.
         dst: Uri,
+        /// WebSocket 연결 ID (업그레이드 요청의 [`HttpContext::exchange_id`]와 같음)
+        connection_id: u64,
     },
     #[non_exhaustive]
     ServerToClient {
@@ -98,9 +171,31 @@ pub enum WebSocketContext {
         src: Uri,
         /// Address of the client.
         dst: SocketAddr,
+        /// WebSocket 연결 ID (업그레이드 요청의 [`HttpContext::exchange_id`]와 같음)
+        connection_id: u64,
     },
 }
 
+impl WebSocketContext {
+    /// 메시지가 속한 WebSocket 연결 ID
+    ///
+    /// 양방향 메시지가 같은 값을 가지므로 연결 단위로 묶을 때 사용합니다.
+    pub fn connection_id(&self) -> u64 {
+        match self {
+            Self::ClientToServer { connection_id, .. }
+            | Self::ServerToClient { connection_id, .. } => *connection_id,
+        }
+    }
+
+    /// 메시지 방향
+    pub fn direction(&self) -> WebSocketDirection {
+        match self {
+            Self::ClientToServer { .. } => WebSocketDirection::ClientToServer,
+            Self::ServerToClient { .. } => WebSocketDirection::ServerToClient,
+        }
+    }
+}
+
 /// Handler for HTTP requests and responses.
 ///
 /// Each request/response pair is passed to the same instance of the handler.
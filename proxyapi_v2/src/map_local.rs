@@ -0,0 +1,447 @@
+//! Map Local - 로컬 파일로 응답
+//!
+//! 매칭되는 요청에 대해 업스트림에 연결하지 않고 로컬 파일 또는 디렉터리의 파일로 응답합니다.
+//! [`HttpHandler::handle_request`](crate::HttpHandler::handle_request)에서 [`MapLocal::serve`]의
+//! 결과를 [`RequestOrResponse::Response`](crate::RequestOrResponse::Response)로 반환하면 됩니다.
+
+use crate::{
+    Body,
+    map_remote::{Location, MapRemoteError},
+};
+use http::{HeaderMap, HeaderValue, Method, Request, Response, StatusCode, header};
+use http_body_util::Full;
+use hyper::body::Bytes;
+use proxy_v2_models::{DataType, detect_data_type};
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+};
+use tracing::{debug, warn};
+
+/// 디렉터리 요청 시 기본으로 찾는 인덱스 파일
+const DEFAULT_INDEX_FILES: [&str; 2] = ["index.html", "index.htm"];
+
+/// Map Local 규칙
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MapLocalRule {
+    from: Location,
+    target: PathBuf,
+    index_files: Vec<String>,
+}
+
+impl MapLocalRule {
+    /// 새 규칙 생성
+    ///
+    /// `target`이 파일이면 매칭되는 모든 요청에 해당 파일로 응답하고, 디렉터리면 매칭 위치 이후의
+    /// 경로를 디렉터리 아래 경로로 사용합니다 (`/static` → `dir`이면 `/static/js/app.js` → `dir/js/app.js`).
+    ///
+    /// # Errors
+    ///
+    /// 매칭 위치 형식이 잘못되면 오류를 반환합니다.
+    pub fn new(from: &str, target: impl Into<PathBuf>) -> Result<Self, MapRemoteError> {
+        Ok(Self {
+            from: from.parse()?,
+            target: target.into(),
+            index_files: DEFAULT_INDEX_FILES.iter().map(|s| s.to_string()).collect(),
+        })
+    }
+
+    /// 디렉터리 요청 시 찾을 인덱스 파일 목록 설정 (기본값: `index.html`, `index.htm`)
+    pub fn index_files(mut self, index_files: Vec<String>) -> Self {
+        self.index_files = index_files;
+        self
+    }
+
+    /// 요청 경로에 해당하는 로컬 경로 (경로 탈출 시도는 None)
+    fn resolve(&self, path: &str) -> Option<PathBuf> {
+        let rest = &path[self.from.path.len()..];
+        let mut resolved = self.target.clone();
+
+        for segment in rest.split('/').filter(|s| !s.is_empty()) {
+            let segment = percent_decode(segment)?;
+            if segment == "."
+                || segment == ".."
+                || segment.contains(['/', '\\', '\0'])
+                || Path::new(&segment).is_absolute()
+            {
+                return None;
+            }
+            resolved.push(segment);
+        }
+
+        Some(resolved)
+    }
+}
+
+/// Map Local 규칙 목록
+///
+/// 복제본끼리 규칙 목록을 공유하므로 프록시 실행 중에도 [`MapLocal::set_rules`]로 교체할 수 있습니다.
+#[derive(Clone, Debug, Default)]
+pub struct MapLocal {
+    rules: Arc<RwLock<Vec<MapLocalRule>>>,
+}
+
+impl MapLocal {
+    /// 규칙 목록으로 생성
+    pub fn new(rules: Vec<MapLocalRule>) -> Self {
+        Self {
+            rules: Arc::new(RwLock::new(rules)),
+        }
+    }
+
+    /// 규칙 목록 교체
+    pub fn set_rules(&self, rules: Vec<MapLocalRule>) {
+        *self.rules.write().expect("map local lock poisoned") = rules;
+    }
+
+    /// 규칙이 없는지 확인
+    pub fn is_empty(&self) -> bool {
+        self.rules
+            .read()
+            .expect("map local lock poisoned")
+            .is_empty()
+    }
+
+    /// 매칭되는 규칙이 있으면 로컬 파일로 응답 생성
+    ///
+    /// 매칭되는 규칙이 없으면 None을 반환합니다. 파일이 없으면 404 응답을 반환합니다.
+    pub async fn serve<T>(&self, req: &Request<T>) -> Option<Response<Body>> {
+        let rule = self
+            .rules
+            .read()
+            .expect("map local lock poisoned")
+            .iter()
+            .find(|rule| rule.from.matches(req.uri()))
+            .cloned()?;
+
+        // 대상이 파일이면 경로와 관계없이 해당 파일로 응답
+        let path = match tokio::fs::metadata(&rule.target).await {
+            Ok(metadata) if metadata.is_file() => rule.target.clone(),
+            _ => match rule.resolve(req.uri().path()) {
+                Some(path) => path,
+                None => return Some(status_response(StatusCode::FORBIDDEN)),
+            },
+        };
+
+        let (path, contents) = match read_with_index(&path, &rule.index_files).await {
+            Ok(found) => found,
+            Err(e) => {
+                warn!("Map local file not available {}: {}", path.display(), e);
+                return Some(status_response(match e.kind() {
+                    ErrorKind::NotFound => StatusCode::NOT_FOUND,
+                    ErrorKind::PermissionDenied => StatusCode::FORBIDDEN,
+                    _ => StatusCode::INTERNAL_SERVER_ERROR,
+                }));
+            }
+        };
+
+        debug!("Serving {} from {}", req.uri(), path.display());
+        Some(file_response(
+            req.method(),
+            req.headers(),
+            &path,
+            Bytes::from(contents),
+        ))
+    }
+}
+
+/// 파일 읽기 (디렉터리면 인덱스 파일을 순서대로 시도)
+async fn read_with_index(
+    path: &Path,
+    index_files: &[String],
+) -> std::io::Result<(PathBuf, Vec<u8>)> {
+    if !tokio::fs::metadata(path).await?.is_dir() {
+        return Ok((path.to_path_buf(), tokio::fs::read(path).await?));
+    }
+
+    for index in index_files {
+        let index_path = path.join(index);
+        match tokio::fs::read(&index_path).await {
+            Ok(contents) => return Ok((index_path, contents)),
+            Err(e) if e.kind() == ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Err(ErrorKind::NotFound.into())
+}
+
+/// 파일 내용으로 응답 생성 (Range 요청 지원)
+fn file_response(
+    method: &Method,
+    headers: &HeaderMap,
+    path: &Path,
+    contents: Bytes,
+) -> Response<Body> {
+    let total = contents.len() as u64;
+    let content_type = content_type(path, &contents);
+
+    let mut builder = Response::builder()
+        .header(header::CONTENT_TYPE, content_type)
+        .header(header::ACCEPT_RANGES, "bytes");
+
+    let range = headers
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| parse_range(value, total));
+
+    let body = match range {
+        Some(Err(())) => {
+            return Response::builder()
+                .status(StatusCode::RANGE_NOT_SATISFIABLE)
+                .header(header::CONTENT_RANGE, format!("bytes */{}", total))
+                .body(Body::empty())
+                .expect("Failed to build response");
+        }
+        Some(Ok(Some((start, end)))) => {
+            builder = builder.status(StatusCode::PARTIAL_CONTENT).header(
+                header::CONTENT_RANGE,
+                format!("bytes {}-{}/{}", start, end, total),
+            );
+            contents.slice(start as usize..=end as usize)
+        }
+        _ => contents,
+    };
+
+    builder = builder.header(header::CONTENT_LENGTH, body.len());
+    let body = if method == Method::HEAD {
+        Body::empty()
+    } else {
+        Body::from(Full::new(body))
+    };
+
+    builder.body(body).expect("Failed to build response")
+}
+
+/// `Range` 헤더 파싱
+///
+/// 단일 범위만 지원하며, 여러 범위나 다른 단위는 `Ok(None)`(전체 응답)으로 처리합니다.
+/// 만족할 수 없는 범위는 `Err`를 반환합니다.
+fn parse_range(value: &str, total: u64) -> Result<Option<(u64, u64)>, ()> {
+    let Some(spec) = value.trim().strip_prefix("bytes=") else {
+        return Ok(None);
+    };
+    if spec.contains(',') {
+        return Ok(None);
+    }
+
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return Err(());
+    };
+
+    let (start, end) = match (start.trim(), end.trim()) {
+        ("", "") => return Err(()),
+        // 마지막 N 바이트
+        ("", suffix) => {
+            let suffix: u64 = suffix.parse().map_err(|_| ())?;
+            if suffix == 0 || total == 0 {
+                return Err(());
+            }
+            (total.saturating_sub(suffix), total - 1)
+        }
+        (start, "") => (start.parse().map_err(|_| ())?, total.saturating_sub(1)),
+        (start, end) => {
+            let start: u64 = start.parse().map_err(|_| ())?;
+            let end: u64 = end.parse().map_err(|_| ())?;
+            (start, end.min(total.saturating_sub(1)))
+        }
+    };
+
+    if start >= total || start > end {
+        return Err(());
+    }
+
+    Ok(Some((start, end)))
+}
+
+/// 파일 내용으로 Content-Type 추론
+///
+/// 내용 분석([`detect_data_type`])으로 구체적인 타입을 알 수 없으면 확장자로 추론합니다.
+fn content_type(path: &Path, contents: &Bytes) -> HeaderValue {
+    let data_type = detect_data_type(&HeaderMap::new(), contents);
+
+    let mime = match data_type {
+        DataType::Json
+        | DataType::Xml
+        | DataType::Html
+        | DataType::Css
+        | DataType::Javascript
+        | DataType::Document
+        | DataType::Archive => data_type.to_mime_type(),
+        _ => extension_mime_type(path).unwrap_or(match data_type {
+            DataType::Text => "text/plain; charset=utf-8",
+            _ => "application/octet-stream",
+        }),
+    };
+
+    HeaderValue::from_static(mime)
+}
+
+fn extension_mime_type(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+
+    Some(match extension.as_str() {
+        "html" | "htm" => "text/html; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "js" | "mjs" => "application/javascript",
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "txt" => "text/plain; charset=utf-8",
+        "csv" => "text/csv",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "ico" => "image/x-icon",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "wasm" => "application/wasm",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        _ => return None,
+    })
+}
+
+/// 경로 세그먼트의 퍼센트 인코딩 해제 (잘못된 인코딩이나 UTF-8이 아니면 None)
+fn percent_decode(segment: &str) -> Option<String> {
+    let bytes = segment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = segment.get(i + 1..i + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    String::from_utf8(decoded).ok()
+}
+
+fn status_response(status: StatusCode) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .body(Body::empty())
+        .expect("Failed to build response")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http_body_util::BodyExt;
+
+    fn request(uri: &str) -> Request<()> {
+        Request::builder().uri(uri).body(()).unwrap()
+    }
+
+    async fn body(res: Response<Body>) -> Bytes {
+        res.into_body().collect().await.unwrap().to_bytes()
+    }
+
+    #[test]
+    fn parses_ranges() {
+        assert_eq!(parse_range("bytes=0-4", 10), Ok(Some((0, 4))));
+        assert_eq!(parse_range("bytes=5-", 10), Ok(Some((5, 9))));
+        assert_eq!(parse_range("bytes=-3", 10), Ok(Some((7, 9))));
+        assert_eq!(parse_range("bytes=8-100", 10), Ok(Some((8, 9))));
+        assert_eq!(parse_range("bytes=0-1,4-5", 10), Ok(None));
+        assert_eq!(parse_range("items=0-1", 10), Ok(None));
+        assert_eq!(parse_range("bytes=10-", 10), Err(()));
+        assert_eq!(parse_range("bytes=5-2", 10), Err(()));
+    }
+
+    #[test]
+    fn rejects_path_traversal() {
+        let rule = MapLocalRule::new("example.com/static", "/srv/www").unwrap();
+
+        assert_eq!(
+            rule.resolve("/static/js/app%20v2.js"),
+            Some(PathBuf::from("/srv/www/js/app v2.js"))
+        );
+        assert_eq!(rule.resolve("/static/../secret"), None);
+        assert_eq!(rule.resolve("/static/%2e%2e/secret"), None);
+        assert_eq!(rule.resolve("/static/a%2fb"), None);
+    }
+
+    #[tokio::test]
+    async fn serves_files_from_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        tokio::fs::write(dir.path().join("data.json"), r#"{"ok":true}"#)
+            .await
+            .unwrap();
+        tokio::fs::create_dir(dir.path().join("docs"))
+            .await
+            .unwrap();
+        tokio::fs::write(dir.path().join("docs/index.html"), "<h1>docs</h1>")
+            .await
+            .unwrap();
+
+        let map_local = MapLocal::new(vec![
+            MapLocalRule::new("api.example.com/v1", dir.path()).unwrap(),
+        ]);
+
+        let res = map_local
+            .serve(&request("https://api.example.com/v1/data.json"))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers()[header::CONTENT_TYPE], "application/json");
+        assert_eq!(body(res).await, r#"{"ok":true}"#);
+
+        let res = map_local
+            .serve(&request("https://api.example.com/v1/docs/"))
+            .await
+            .unwrap();
+        assert_eq!(
+            res.headers()[header::CONTENT_TYPE],
+            "text/html; charset=utf-8"
+        );
+        assert_eq!(body(res).await, "<h1>docs</h1>");
+
+        let res = map_local
+            .serve(&request("https://api.example.com/v1/missing.json"))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+        assert!(
+            map_local
+                .serve(&request("https://api.example.com/v2/data.json"))
+                .await
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn serves_byte_ranges() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("video.bin");
+        tokio::fs::write(&file, b"0123456789").await.unwrap();
+
+        let map_local = MapLocal::new(vec![MapLocalRule::new("example.com", &file).unwrap()]);
+
+        let mut req = request("http://example.com/anything");
+        req.headers_mut()
+            .insert(header::RANGE, HeaderValue::from_static("bytes=2-5"));
+        let res = map_local.serve(&req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(res.headers()[header::CONTENT_RANGE], "bytes 2-5/10");
+        assert_eq!(body(res).await, "2345");
+
+        req.headers_mut()
+            .insert(header::RANGE, HeaderValue::from_static("bytes=20-"));
+        let res = map_local.serve(&req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+        assert_eq!(res.headers()[header::CONTENT_RANGE], "bytes */10");
+    }
+}
@@ -0,0 +1,401 @@
+//! Map Remote - 요청을 다른 업스트림으로 전달
+//!
+//! [`MapRemote`]는 [`ProxyBuilder::with_map_remote`](crate::builder::ProxyBuilder::with_map_remote)로
+//! 등록하며, [`HttpHandler::handle_request`](crate::HttpHandler::handle_request) 호출 전에 요청 URI의
+//! scheme/authority/경로 접두사를 교체합니다. 원래 URI는 요청 확장(extension)의
+//! [`RemoteMapping`]으로 확인할 수 있습니다.
+
+use crate::host_pattern::host_matches;
+use http::{
+    HeaderValue, Request, Uri, header,
+    uri::{PathAndQuery, Scheme},
+};
+use std::{
+    str::FromStr,
+    sync::{Arc, RwLock},
+};
+use thiserror::Error;
+use tracing::debug;
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum MapRemoteError {
+    #[error("invalid map remote location '{0}'")]
+    InvalidLocation(String),
+}
+
+/// `scheme://host:port/path` 형태의 위치
+///
+/// scheme과 port는 생략할 수 있으며, 매칭 위치의 호스트에는 `*.example.com` 같은 와일드카드를 쓸 수 있습니다.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Location {
+    scheme: Option<Scheme>,
+    host: String,
+    port: Option<u16>,
+    pub(crate) path: String,
+}
+
+impl FromStr for Location {
+    type Err = MapRemoteError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || MapRemoteError::InvalidLocation(s.to_string());
+
+        let (scheme, rest) = match s.trim().split_once("://") {
+            Some((scheme, rest)) => {
+                let scheme = match scheme.to_ascii_lowercase().as_str() {
+                    "http" => Scheme::HTTP,
+                    "https" => Scheme::HTTPS,
+                    _ => return Err(error()),
+                };
+                (Some(scheme), rest)
+            }
+            None => (None, s.trim()),
+        };
+
+        let (authority, path) = match rest.find('/') {
+            Some(index) => rest.split_at(index),
+            None => (rest, ""),
+        };
+
+        if path.contains(['?', '#']) {
+            return Err(error());
+        }
+
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) if !authority.ends_with(']') => {
+                (host, Some(port.parse::<u16>().map_err(|_| error())?))
+            }
+            _ => (authority, None),
+        };
+
+        if host.is_empty() {
+            return Err(error());
+        }
+
+        Ok(Self {
+            scheme,
+            host: host.to_ascii_lowercase(),
+            port,
+            path: path.trim_end_matches('/').to_string(),
+        })
+    }
+}
+
+impl Location {
+    pub(crate) fn matches(&self, uri: &Uri) -> bool {
+        if self.scheme.is_some() && uri.scheme() != self.scheme.as_ref() {
+            return false;
+        }
+
+        let Some(host) = uri.host() else {
+            return false;
+        };
+        if !host_matches(&self.host, host) {
+            return false;
+        }
+
+        if let Some(port) = self.port {
+            let uri_port = uri.port_u16().or_else(|| match uri.scheme_str() {
+                Some("https") => Some(443),
+                Some("http") => Some(80),
+                _ => None,
+            });
+            if uri_port != Some(port) {
+                return false;
+            }
+        }
+
+        path_matches(&self.path, uri.path())
+    }
+}
+
+/// 경로 접두사 매칭 (`/v1`은 `/v1`, `/v1/users`와 매칭되지만 `/v10`과는 매칭되지 않음)
+fn path_matches(prefix: &str, path: &str) -> bool {
+    match path.strip_prefix(prefix) {
+        Some(rest) => rest.is_empty() || rest.starts_with('/'),
+        None => false,
+    }
+}
+
+/// Map Remote 규칙
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MapRemoteRule {
+    from: Location,
+    to: Location,
+    preserve_host: bool,
+}
+
+impl MapRemoteRule {
+    /// 새 규칙 생성
+    ///
+    /// # Errors
+    ///
+    /// 위치 형식이 잘못되었거나 대상 호스트에 와일드카드가 있으면 오류를 반환합니다.
+    pub fn new(from: &str, to: &str) -> Result<Self, MapRemoteError> {
+        let from: Location = from.parse()?;
+        let to_location: Location = to.parse()?;
+
+        if to_location.host.contains('*') {
+            return Err(MapRemoteError::InvalidLocation(to.to_string()));
+        }
+
+        Ok(Self {
+            from,
+            to: to_location,
+            preserve_host: false,
+        })
+    }
+
+    /// 원래 Host 헤더를 유지할지 설정 (기본값: 대상 호스트로 교체)
+    pub fn preserve_host(mut self, preserve_host: bool) -> Self {
+        self.preserve_host = preserve_host;
+        self
+    }
+
+    /// URI가 규칙과 매칭되면 대상 URI 반환
+    pub fn map_uri(&self, uri: &Uri) -> Option<Uri> {
+        if !self.from.matches(uri) {
+            return None;
+        }
+
+        // 대상 scheme이 없으면 원래 scheme과 포트를 유지
+        let scheme = self
+            .to
+            .scheme
+            .clone()
+            .or_else(|| uri.scheme().cloned())
+            .unwrap_or(Scheme::HTTP);
+        let port = match (&self.to.scheme, self.to.port) {
+            (_, Some(port)) => Some(port),
+            (None, None) => uri.port_u16(),
+            (Some(_), None) => None,
+        };
+        let authority = match port {
+            Some(port) => format!("{}:{}", self.to.host, port),
+            None => self.to.host.clone(),
+        };
+
+        let rest = &uri.path()[self.from.path.len()..];
+        let mut path = format!("{}{}", self.to.path, rest);
+        if path.is_empty() {
+            path.push('/');
+        }
+        if let Some(query) = uri.query() {
+            path = format!("{}?{}", path, query);
+        }
+
+        Uri::builder()
+            .scheme(scheme)
+            .authority(authority)
+            .path_and_query(PathAndQuery::try_from(path).ok()?)
+            .build()
+            .ok()
+    }
+}
+
+/// Map Remote가 적용된 요청에 추가되는 확장(extension)
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RemoteMapping {
+    /// 교체되기 전 원래 URI
+    pub original_uri: Uri,
+    /// 원래 Host 헤더를 유지하는지 여부
+    pub preserve_host: bool,
+}
+
+/// Map Remote 규칙 목록
+///
+/// 복제본끼리 규칙 목록을 공유하므로 프록시 실행 중에도 [`MapRemote::set_rules`]로 교체할 수 있습니다.
+#[derive(Clone, Debug, Default)]
+pub struct MapRemote {
+    rules: Arc<RwLock<Vec<MapRemoteRule>>>,
+}
+
+impl MapRemote {
+    /// 규칙 목록으로 생성
+    pub fn new(rules: Vec<MapRemoteRule>) -> Self {
+        Self {
+            rules: Arc::new(RwLock::new(rules)),
+        }
+    }
+
+    /// 규칙 목록 교체
+    pub fn set_rules(&self, rules: Vec<MapRemoteRule>) {
+        *self.rules.write().expect("map remote lock poisoned") = rules;
+    }
+
+    /// 규칙이 없는지 확인
+    pub fn is_empty(&self) -> bool {
+        self.rules
+            .read()
+            .expect("map remote lock poisoned")
+            .is_empty()
+    }
+
+    /// 첫 번째로 매칭되는 규칙으로 요청 URI 교체
+    ///
+    /// 교체되면 [`RemoteMapping`] 확장을 추가하고 `true`를 반환합니다.
+    pub fn apply<T>(&self, req: &mut Request<T>) -> bool {
+        if req.extensions().get::<RemoteMapping>().is_some() {
+            return false;
+        }
+
+        let mapped = self
+            .rules
+            .read()
+            .expect("map remote lock poisoned")
+            .iter()
+            .find_map(|rule| Some((rule.map_uri(req.uri())?, rule.preserve_host)));
+
+        let Some((uri, preserve_host)) = mapped else {
+            return false;
+        };
+
+        debug!("Mapping {} to {}", req.uri(), uri);
+        let original_uri = std::mem::replace(req.uri_mut(), uri);
+
+        if !preserve_host {
+            if let Some(host) = req
+                .uri()
+                .authority()
+                .and_then(|authority| HeaderValue::from_str(authority.as_str()).ok())
+            {
+                req.headers_mut().insert(header::HOST, host);
+            }
+        }
+
+        req.extensions_mut().insert(RemoteMapping {
+            original_uri,
+            preserve_host,
+        });
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(from: &str, to: &str, uri: &str) -> Option<String> {
+        MapRemoteRule::new(from, to)
+            .unwrap()
+            .map_uri(&uri.parse().unwrap())
+            .map(|uri| uri.to_string())
+    }
+
+    #[test]
+    fn parses_locations() {
+        let location: Location = "https://*.example.com:8443/api/".parse().unwrap();
+        assert_eq!(location.scheme, Some(Scheme::HTTPS));
+        assert_eq!(location.host, "*.example.com");
+        assert_eq!(location.port, Some(8443));
+        assert_eq!(location.path, "/api");
+
+        assert!("ftp://example.com".parse::<Location>().is_err());
+        assert!("example.com:port".parse::<Location>().is_err());
+        assert!("https:///path".parse::<Location>().is_err());
+        assert!(MapRemoteRule::new("example.com", "*.example.com").is_err());
+    }
+
+    #[test]
+    fn maps_host_and_path_prefix() {
+        assert_eq!(
+            map(
+                "https://api.prod.example.com",
+                "http://localhost:8080",
+                "https://api.prod.example.com/users?id=1"
+            )
+            .as_deref(),
+            Some("http://localhost:8080/users?id=1")
+        );
+        assert_eq!(
+            map(
+                "api.example.com/v1",
+                "staging.example.com/api/v2",
+                "https://api.example.com/v1/users"
+            )
+            .as_deref(),
+            Some("https://staging.example.com/api/v2/users")
+        );
+        assert_eq!(
+            map("*.example.com", "localhost", "http://a.example.com:3000/").as_deref(),
+            Some("http://localhost:3000/")
+        );
+    }
+
+    #[test]
+    fn skips_non_matching_requests() {
+        assert_eq!(
+            map(
+                "api.example.com/v1",
+                "localhost",
+                "http://api.example.com/v10"
+            ),
+            None
+        );
+        assert_eq!(
+            map(
+                "https://api.example.com",
+                "localhost",
+                "http://api.example.com/"
+            ),
+            None
+        );
+        assert_eq!(
+            map(
+                "api.example.com:8443",
+                "localhost",
+                "https://api.example.com/"
+            ),
+            None
+        );
+        assert_eq!(
+            map("*.example.com", "localhost", "http://example.org/"),
+            None
+        );
+    }
+
+    #[test]
+    fn apply_records_original_uri() {
+        let map_remote = MapRemote::new(vec![
+            MapRemoteRule::new("api.example.com", "http://localhost:8080").unwrap(),
+            MapRemoteRule::new("web.example.com", "http://localhost:3000")
+                .unwrap()
+                .preserve_host(true),
+        ]);
+
+        let mut req = Request::builder()
+            .uri("https://api.example.com/")
+            .header(header::HOST, "api.example.com")
+            .body(())
+            .unwrap();
+        assert!(map_remote.apply(&mut req));
+        assert_eq!(req.uri(), "http://localhost:8080/");
+        assert_eq!(req.headers()[header::HOST], "localhost:8080");
+        assert_eq!(
+            req.extensions()
+                .get::<RemoteMapping>()
+                .unwrap()
+                .original_uri,
+            "https://api.example.com/"
+        );
+
+        // 이미 교체된 요청은 다시 교체하지 않음
+        assert!(!map_remote.apply(&mut req));
+
+        let mut req = Request::builder()
+            .uri("https://web.example.com/")
+            .header(header::HOST, "web.example.com")
+            .body(())
+            .unwrap();
+        assert!(map_remote.apply(&mut req));
+        assert_eq!(req.headers()[header::HOST], "web.example.com");
+        assert!(
+            req.extensions()
+                .get::<RemoteMapping>()
+                .unwrap()
+                .preserve_host
+        );
+    }
+}
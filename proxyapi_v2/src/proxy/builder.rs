@@ -1,6 +1,11 @@
 use crate::{
     Body, HttpHandler, NoopHandler, Proxy, WebSocketHandler,
     certificate_authority::CertificateAuthority,
+    client_identity::ClientIdentities,
+    map_remote::MapRemote,
+    timings::TimingConnector,
+    upstream_proxy::{UpstreamConnector, UpstreamProxy},
+    websocket::WebSocketInjector,
 };
 use hyper_util::{
     client::legacy::{Client, connect::Connect},
@@ -112,7 +117,12 @@ pub struct WantsCa {
 impl ProxyBuilder<WantsCa> {
     /// Set the certificate authority to use.
     pub fn with_ca<CA: CertificateAuthority>(self, ca: CA) -> ProxyBuilder<WantsClient<CA>> {
-        ProxyBuilder(WantsClient { al: self.0.al, ca })
+        ProxyBuilder(WantsClient {
+            al: self.0.al,
+            ca,
+            upstream_proxy: None,
+            client_identities: ClientIdentities::new(),
+        })
     }
 }
 
@@ -121,9 +131,35 @@ impl ProxyBuilder<WantsCa> {
 pub struct WantsClient<CA> {
     al: AddrOrListener,
     ca: CA,
+    upstream_proxy: Option<Arc<UpstreamProxy>>,
+    client_identities: ClientIdentities,
 }
 
 impl<CA: CertificateAuthority> ProxyBuilder<WantsClient<CA>> {
+    /// 상위(upstream) 프록시 설정
+    ///
+    /// 이후에 만드는 rustls/native-tls 클라이언트, CONNECT 터널, WebSocket 연결이 모두 상위
+    /// 프록시를 거칩니다. [`with_client`](Self::with_client)로 직접 만든 클라이언트에는 적용되지
+    /// 않으므로 [`UpstreamConnector`]로 커넥터를 감싸야 합니다.
+    pub fn with_upstream_proxy(self, upstream_proxy: UpstreamProxy) -> Self {
+        ProxyBuilder(WantsClient {
+            upstream_proxy: Some(Arc::new(upstream_proxy)),
+            ..self.0
+        })
+    }
+
+    /// 호스트별 클라이언트 인증서(mTLS) 설정
+    ///
+    /// 이후에 만드는 rustls 클라이언트가 연결할 호스트에 맞는 인증서를 서버가 요청할 때 보냅니다.
+    /// native-tls 클라이언트, WebSocket 연결과 [`with_client`](Self::with_client)로 직접 만든 클라이언트에는
+    /// 적용되지 않습니다.
+    pub fn with_client_identities(self, client_identities: ClientIdentities) -> Self {
+        ProxyBuilder(WantsClient {
+            client_identities,
+            ..self.0
+        })
+    }
+
     /// Use a hyper-rustls connector.
     #[cfg(feature = "rustls-client")]
     pub fn with_rustls_client(
@@ -213,20 +249,32 @@ impl<CA: CertificateAuthority> ProxyBuilder<WantsClient<CA>> {
                     websocket_handler: NoopHandler::new(),
                     websocket_connector: None,
                     server: None,
+                    map_remote: MapRemote::default(),
+                    websocket_injector: WebSocketInjector::default(),
+                    upstream_proxy: self.0.upstream_proxy,
+                    socks5: None,
+                    transparent: None,
                     graceful_shutdown: pending(),
                 });
             }
         };
 
-        let https = hyper_rustls::HttpsConnectorBuilder::new()
-            .with_tls_config(rustls_config.clone())
-            .https_or_http()
-            .enable_http1();
+        let upstream = UpstreamConnector::shared(self.0.upstream_proxy.clone());
+        let https = self
+            .0
+            .client_identities
+            .connector(rustls_config.clone(), |config| {
+                let https = hyper_rustls::HttpsConnectorBuilder::new()
+                    .with_tls_config(config)
+                    .https_or_http()
+                    .enable_http1();
 
-        #[cfg(feature = "http2")]
-        let https = https.enable_http2();
+                #[cfg(feature = "http2")]
+                let https = https.enable_http2();
 
-        let https = https.build();
+                https.wrap_connector(upstream.clone())
+            });
+        let https = TimingConnector::new(https);
 
         ProxyBuilder(WantsHandlers {
             al: self.0.al,
@@ -239,6 +287,11 @@ impl<CA: CertificateAuthority> ProxyBuilder<WantsClient<CA>> {
             websocket_handler: NoopHandler::new(),
             websocket_connector: Some(Connector::Rustls(Arc::new(rustls_config))),
             server: None,
+            map_remote: MapRemote::default(),
+            websocket_injector: WebSocketInjector::default(),
+            upstream_proxy: self.0.upstream_proxy,
+            socks5: None,
+            transparent: None,
             graceful_shutdown: pending(),
         })
     }
@@ -249,8 +302,6 @@ impl<CA: CertificateAuthority> ProxyBuilder<WantsClient<CA>> {
         self,
     ) -> ProxyBuilder<WantsHandlers<CA, impl Connect + Clone, NoopHandler, NoopHandler, Pending<()>>>
     {
-        use hyper_util::client::legacy::connect::HttpConnector;
-
         let tls_connector = match hyper_tls::native_tls::TlsConnector::new() {
             Ok(tls_connector) => tls_connector,
             Err(e) => {
@@ -262,13 +313,22 @@ impl<CA: CertificateAuthority> ProxyBuilder<WantsClient<CA>> {
                     websocket_handler: NoopHandler::new(),
                     websocket_connector: None,
                     server: None,
+                    map_remote: MapRemote::default(),
+                    websocket_injector: WebSocketInjector::default(),
+                    upstream_proxy: self.0.upstream_proxy,
+                    socks5: None,
+                    transparent: None,
                     graceful_shutdown: pending(),
                 });
             }
         };
 
         let tokio_tls_connector = tokio_native_tls::TlsConnector::from(tls_connector.clone());
-        let https = hyper_tls::HttpsConnector::from((HttpConnector::new(), tokio_tls_connector));
+        let https = hyper_tls::HttpsConnector::from((
+            UpstreamConnector::shared(self.0.upstream_proxy.clone()),
+            tokio_tls_connector,
+        ));
+        let https = TimingConnector::new(https);
 
         ProxyBuilder(WantsHandlers {
             al: self.0.al,
@@ -281,6 +341,11 @@ impl<CA: CertificateAuthority> ProxyBuilder<WantsClient<CA>> {
             websocket_handler: NoopHandler::new(),
             websocket_connector: Some(Connector::NativeTls(tls_connector)),
             server: None,
+            map_remote: MapRemote::default(),
+            websocket_injector: WebSocketInjector::default(),
+            upstream_proxy: self.0.upstream_proxy,
+            socks5: None,
+            transparent: None,
             graceful_shutdown: pending(),
         })
     }
@@ -301,6 +366,11 @@ impl<CA: CertificateAuthority> ProxyBuilder<WantsClient<CA>> {
             websocket_handler: NoopHandler::new(),
             websocket_connector: None,
             server: None,
+            map_remote: MapRemote::default(),
+            websocket_injector: WebSocketInjector::default(),
+            upstream_proxy: self.0.upstream_proxy,
+            socks5: None,
+            transparent: None,
             graceful_shutdown: pending(),
         })
     }
@@ -315,6 +385,11 @@ pub struct WantsHandlers<CA, C, H, W, F> {
     websocket_handler: W,
     websocket_connector: Option<Connector>,
     server: Option<Builder<TokioExecutor>>,
+    map_remote: MapRemote,
+    websocket_injector: WebSocketInjector,
+    upstream_proxy: Option<Arc<UpstreamProxy>>,
+    socks5: Option<AddrOrListener>,
+    transparent: Option<AddrOrListener>,
     graceful_shutdown: F,
 }
 
@@ -332,6 +407,11 @@ impl<CA, C, H, W, F> ProxyBuilder<WantsHandlers<CA, C, H, W, F>> {
             websocket_handler: self.0.websocket_handler,
             websocket_connector: self.0.websocket_connector,
             server: self.0.server,
+            map_remote: self.0.map_remote,
+            websocket_injector: self.0.websocket_injector,
+            upstream_proxy: self.0.upstream_proxy,
+            socks5: self.0.socks5,
+            transparent: self.0.transparent,
             graceful_shutdown: self.0.graceful_shutdown,
         })
     }
@@ -349,6 +429,11 @@ impl<CA, C, H, W, F> ProxyBuilder<WantsHandlers<CA, C, H, W, F>> {
             websocket_handler,
             websocket_connector: self.0.websocket_connector,
             server: self.0.server,
+            map_remote: self.0.map_remote,
+            websocket_injector: self.0.websocket_injector,
+            upstream_proxy: self.0.upstream_proxy,
+            socks5: self.0.socks5,
+            transparent: self.0.transparent,
             graceful_shutdown: self.0.graceful_shutdown,
         })
     }
@@ -369,6 +454,61 @@ impl<CA, C, H, W, F> ProxyBuilder<WantsHandlers<CA, C, H, W, F>> {
         })
     }
 
+    /// Map Remote 규칙 설정 (핸들러 호출 전에 요청 URI를 교체)
+    pub fn with_map_remote(self, map_remote: MapRemote) -> Self {
+        ProxyBuilder(WantsHandlers {
+            map_remote,
+            ..self.0
+        })
+    }
+
+    /// WebSocket 메시지 주입 핸들 설정 (복제본으로 실행 중인 연결에 메시지를 보낼 수 있음)
+    pub fn with_websocket_injector(self, websocket_injector: WebSocketInjector) -> Self {
+        ProxyBuilder(WantsHandlers {
+            websocket_injector,
+            ..self.0
+        })
+    }
+
+    /// SOCKS5 리스너 주소 설정
+    ///
+    /// HTTP 프록시 리스너와 함께 SOCKS5 CONNECT 요청을 받습니다. 연결된 스트림은 HTTP CONNECT와
+    /// 같은 방식(TLS 가로채기, 평문 HTTP 처리, 그 외 터널링)으로 처리되며, 인증은 지원하지 않습니다.
+    pub fn with_socks5_addr(self, addr: SocketAddr) -> Self {
+        ProxyBuilder(WantsHandlers {
+            socks5: Some(AddrOrListener::Addr(addr)),
+            ..self.0
+        })
+    }
+
+    /// SOCKS5 리스너 설정 ([`with_socks5_addr`](Self::with_socks5_addr) 참고)
+    pub fn with_socks5_listener(self, listener: TcpListener) -> Self {
+        ProxyBuilder(WantsHandlers {
+            socks5: Some(AddrOrListener::Listener(listener)),
+            ..self.0
+        })
+    }
+
+    /// 투명 프록시 리스너 주소 설정
+    ///
+    /// iptables 등으로 리다이렉트된 연결을 CONNECT 없이 받습니다. TLS는 ClientHello의 SNI,
+    /// 평문 HTTP는 `Host` 헤더로 대상 호스트를 찾으며, Linux에서는 `SO_ORIGINAL_DST`로 얻은
+    /// 원래 목적지의 포트를 사용합니다 (호스트를 찾지 못하면 원래 목적지 주소로 연결).
+    pub fn with_transparent_addr(self, addr: SocketAddr) -> Self {
+        ProxyBuilder(WantsHandlers {
+            transparent: Some(AddrOrListener::Addr(addr)),
+            ..self.0
+        })
+    }
+
+    /// 투명 프록시 리스너 설정 ([`with_transparent_addr`](Self::with_transparent_addr) 참고)
+    pub fn with_transparent_listener(self, listener: TcpListener) -> Self {
+        ProxyBuilder(WantsHandlers {
+            transparent: Some(AddrOrListener::Listener(listener)),
+            ..self.0
+        })
+    }
+
     /// Set a future that when ready will gracefully shutdown the proxy server.
     pub fn with_graceful_shutdown<F2: Future<Output = ()> + Send + 'static>(
         self,
@@ -382,6 +522,11 @@ impl<CA, C, H, W, F> ProxyBuilder<WantsHandlers<CA, C, H, W, F>> {
             websocket_handler: self.0.websocket_handler,
             websocket_connector: self.0.websocket_connector,
             server: self.0.server,
+            map_remote: self.0.map_remote,
+            websocket_injector: self.0.websocket_injector,
+            upstream_proxy: self.0.upstream_proxy,
+            socks5: self.0.socks5,
+            transparent: self.0.transparent,
             graceful_shutdown,
         })
     }
@@ -396,6 +541,11 @@ impl<CA, C, H, W, F> ProxyBuilder<WantsHandlers<CA, C, H, W, F>> {
             websocket_handler: self.0.websocket_handler,
             websocket_connector: self.0.websocket_connector,
             server: self.0.server,
+            map_remote: self.0.map_remote,
+            websocket_injector: self.0.websocket_injector,
+            upstream_proxy: self.0.upstream_proxy,
+            socks5: self.0.socks5,
+            transparent: self.0.transparent,
             graceful_shutdown: self.0.graceful_shutdown,
         })
     }
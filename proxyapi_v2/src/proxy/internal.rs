@@ -1,7 +1,20 @@
+use super::{
+    socks5::{self, Reply},
+    transparent,
+};
 use crate::{
-    HttpContext, HttpHandler, RequestOrResponse, WebSocketContext, WebSocketHandler, body::Body,
-    certificate_authority::CertificateAuthority, hybrid_tls_handler::HybridTlsHandler,
-    rewind::Rewind, tls_version_detector::TlsVersionDetector,
+    HttpContext, HttpHandler, RequestOrResponse, TlsInfo, WebSocketContext, WebSocketHandler,
+    body::Body,
+    certificate_authority::CertificateAuthority,
+    client_identity::UsedClientIdentity,
+    hybrid_tls_handler::HybridTlsHandler,
+    map_remote::{MapRemote, RemoteMapping},
+    rewind::Rewind,
+    timings::Timings,
+    tls_version_detector::TlsVersionDetector,
+    upstream_proxy::{ProxyStream, UpstreamProxy},
+    upstream_tls::{UpstreamSession, UpstreamTls},
+    websocket::{WebSocketDirection, WebSocketInjector},
 };
 use futures::{Sink, Stream, StreamExt};
 use http::uri::{Authority, Scheme};
@@ -17,11 +30,23 @@ use hyper_util::{
     rt::{TokioExecutor, TokioIo},
     server,
 };
-use std::{convert::Infallible, net::SocketAddr, sync::Arc};
-use tokio::{io::AsyncReadExt, net::TcpStream, task::JoinHandle};
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Instant,
+};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite},
+    net::TcpStream,
+    task::JoinHandle,
+};
 use tokio_rustls::TlsAcceptor;
 use tokio_tungstenite::{
-    Connector, WebSocketStream,
+    Connector, MaybeTlsStream, WebSocketStream,
     tungstenite::{self, Message, protocol::WebSocketConfig},
 };
 use tracing::{Instrument, Span, error, info, info_span, instrument, warn};
@@ -33,6 +58,33 @@ fn bad_request() -> Response<Body> {
         .expect("Failed to build response")
 }
 
+/// SOCKS5, 투명 프록시 연결을 핸들러에 전달하기 위한 CONNECT 요청
+fn connect_request(authority: &Authority) -> Request<Body> {
+    Request::builder()
+        .method(Method::CONNECT)
+        .uri(authority.as_str())
+        .header(hyper::header::HOST, authority.as_str())
+        .body(Body::empty())
+        .expect("Failed to build request")
+}
+
+/// 평문 HTTP 요청의 시작인지 확인
+fn is_http_request(buffer: &[u8]) -> bool {
+    const METHODS: [&[u8]; 8] = [
+        b"GET ", b"POST", b"PUT ", b"HEAD", b"DELE", b"OPTI", b"PATC", b"TRAC",
+    ];
+    // HTTP/2 prior knowledge(h2c) 연결은 `PRI * HTTP/2.0` 서문으로 시작
+    METHODS.contains(&buffer) || (cfg!(feature = "http2") && buffer == b"PRI ")
+}
+
+static NEXT_CONNECTION_ID: AtomicU64 = AtomicU64::new(1);
+static NEXT_EXCHANGE_ID: AtomicU64 = AtomicU64::new(1);
+
+/// 새 클라이언트 연결 ID 발급
+pub(crate) fn next_connection_id() -> u64 {
+    NEXT_CONNECTION_ID.fetch_add(1, Ordering::Relaxed)
+}
+
 fn spawn_with_trace<T: Send + Sync + 'static>(
     fut: impl Future<Output = T> + Send + 'static,
     span: Span,
@@ -47,7 +99,14 @@ pub(crate) struct InternalProxy<C, CA, H, W> {
     pub http_handler: H,
     pub websocket_handler: W,
     pub websocket_connector: Option<Connector>,
+    pub map_remote: MapRemote,
+    pub websocket_injector: WebSocketInjector,
+    pub upstream_proxy: Option<Arc<UpstreamProxy>>,
     pub client_addr: SocketAddr,
+    pub connection_id: u64,
+    pub authority: Option<Authority>,
+    pub scheme: Scheme,
+    pub tls: Option<TlsInfo>,
 }
 
 impl<C, CA, H, W> Clone for InternalProxy<C, CA, H, W>
@@ -64,7 +123,14 @@ where
             http_handler: self.http_handler.clone(),
             websocket_handler: self.websocket_handler.clone(),
             websocket_connector: self.websocket_connector.clone(),
+            map_remote: self.map_remote.clone(),
+            websocket_injector: self.websocket_injector.clone(),
+            upstream_proxy: self.upstream_proxy.clone(),
             client_addr: self.client_addr,
+            connection_id: self.connection_id,
+            authority: self.authority.clone(),
+            scheme: self.scheme.clone(),
+            tls: self.tls.clone(),
         }
     }
 }
@@ -79,6 +145,15 @@ where
     fn context(&self) -> HttpContext {
         HttpContext {
             client_addr: self.client_addr,
+            exchange_id: NEXT_EXCHANGE_ID.fetch_add(1, Ordering::Relaxed),
+            connection_id: self.connection_id,
+            authority: self.authority.clone(),
+            scheme: self.scheme.clone(),
+            tls: self.tls.clone(),
+            timings: Timings::default(),
+            upstream_tls: None,
+            upstream_session: None,
+            client_identity: None,
         }
     }
 
@@ -89,6 +164,7 @@ where
             method = %req.method(),
             uri=%req.uri(),
             client_addr = %self.client_addr,
+            connection_id = self.connection_id,
         )
     )]
     pub(crate) async fn proxy(
@@ -97,9 +173,14 @@ where
     ) -> Result<Response<Body>, Infallible> {
         let ctx = self.context();
 
+        let mut req = req.map(Body::from);
+        if req.method() != Method::CONNECT {
+            self.map_remote.apply(&mut req);
+        }
+
         let req = match self
             .http_handler
-            .handle_request(&ctx, req.map(Body::from))
+            .handle_request(&ctx, req)
             .instrument(info_span!("handle_request"))
             .await
         {
@@ -110,10 +191,14 @@ where
         if req.method() == Method::CONNECT {
             Ok(self.process_connect(req))
         } else if hyper_tungstenite::is_upgrade_request(&req) {
-            Ok(self.upgrade_websocket(req))
+            Ok(self.upgrade_websocket(req, ctx.exchange_id))
         } else {
-            let normalized_req = normalize_request(req);
+            let mut normalized_req = normalize_request(req);
+            if let Some(upstream_proxy) = &self.upstream_proxy {
+                upstream_proxy.authorize(&mut normalized_req);
+            }
 
+            let sent = Instant::now();
             let res = self
                 .client
                 .request(normalized_req)
@@ -121,11 +206,23 @@ where
                 .await;
 
             match res {
-                Ok(res) => Ok(self
-                    .http_handler
-                    .handle_response(&ctx, res.map(Body::from))
-                    .instrument(info_span!("handle_response"))
-                    .await),
+                Ok(res) => {
+                    let ctx = HttpContext {
+                        timings: Timings::from_response(sent, &res),
+                        upstream_tls: res.extensions().get::<UpstreamTls>().cloned(),
+                        upstream_session: res.extensions().get::<UpstreamSession>().cloned(),
+                        client_identity: res
+                            .extensions()
+                            .get::<UsedClientIdentity>()
+                            .map(|used| used.0.clone()),
+                        ..ctx
+                    };
+                    Ok(self
+                        .http_handler
+                        .handle_response(&ctx, res.map(Body::from))
+                        .instrument(info_span!("handle_response"))
+                        .await)
+                }
                 Err(err) => {
                     println!("❌ 업스트림 서버 연결 실패");
                     println!("   - 오류: {}", err);
@@ -144,183 +241,13 @@ where
     fn process_connect(mut self, mut req: Request<Body>) -> Response<Body> {
         match req.uri().authority().cloned() {
             Some(authority) => {
+                self.authority = Some(authority.clone());
                 let span = info_span!("process_connect");
                 let fut = async move {
                     match hyper::upgrade::on(&mut req).await {
                         Ok(upgraded) => {
-                            let mut upgraded = TokioIo::new(upgraded);
-                            let mut buffer = [0; 4];
-                            let bytes_read = match upgraded.read(&mut buffer).await {
-                                Ok(bytes_read) => bytes_read,
-                                Err(e) => {
-                                    error!("Failed to read from upgraded connection: {}", e);
-                                    return;
-                                }
-                            };
-
-                            let mut upgraded = Rewind::new(
-                                upgraded,
-                                Bytes::copy_from_slice(buffer[..bytes_read].as_ref()),
-                            );
-
-                            if self
-                                .http_handler
-                                .should_intercept(&self.context(), &req)
-                                .await
-                            {
-                                if buffer == *b"GET " {
-                                    if let Err(e) = self
-                                        .serve_stream(
-                                            TokioIo::new(upgraded),
-                                            Scheme::HTTP,
-                                            authority,
-                                        )
-                                        .await
-                                    {
-                                        error!("WebSocket connect error: {}", e);
-                                    }
-
-                                    return;
-                                } else if buffer[..2] == *b"\x16\x03" {
-                                    // TLS 버전 감지
-                                    let tls_version =
-                                        TlsVersionDetector::detect_tls_version(&buffer);
-
-                                    match tls_version {
-                                        Some(version) => {
-                                            info!(
-                                                "🔍 TLS 버전 감지: {} - 하이브리드 핸들러 사용",
-                                                version
-                                            );
-
-                                            // HybridTlsHandler 생성
-                                            let hybrid_handler =
-                                                match HybridTlsHandler::new(Arc::clone(&self.ca))
-                                                    .await
-                                                {
-                                                    Ok(handler) => handler,
-                                                    Err(e) => {
-                                                        error!(
-                                                            "❌ HybridTlsHandler 생성 실패: {}",
-                                                            e
-                                                        );
-                                                        return;
-                                                    }
-                                                };
-
-                                            // 하이브리드 TLS 연결 처리
-                                            match hybrid_handler
-                                                .handle_tls_connection_upgraded(
-                                                    &authority, upgraded, &buffer,
-                                                )
-                                                .await
-                                            {
-                                                Ok(hybrid_stream) => {
-                                                    info!(
-                                                        "✅ 하이브리드 TLS 연결 성공: {}",
-                                                        version
-                                                    );
-                                                    let stream = TokioIo::new(hybrid_stream);
-
-                                                    if let Err(e) = self
-                                                        .serve_stream(
-                                                            stream,
-                                                            Scheme::HTTPS,
-                                                            authority.clone(),
-                                                        )
-                                                        .await
-                                                    {
-                                                        if !e.to_string().starts_with(
-                                                            "error shutting down connection",
-                                                        ) {
-                                                            error!("HTTPS connect error: {}", e);
-                                                        }
-                                                    }
-                                                }
-                                                Err(e) => {
-                                                    error!("❌ 하이브리드 TLS 연결 실패: {}", e);
-                                                    println!("   - TLS 버전: {}", version);
-                                                    println!("   - 대상 서버: {}", authority);
-                                                    println!("   - 오류: {}", e);
-                                                    return;
-                                                }
-                                            }
-                                        }
-                                        None => {
-                                            warn!(
-                                                "⚠️ TLS 버전을 감지할 수 없음, 기존 rustls로 시도"
-                                            );
-
-                                            // 기존 rustls 로직 사용
-                                            let server_config = self
-                                                .ca
-                                                .gen_server_config(&authority)
-                                                .instrument(info_span!("gen_server_config"))
-                                                .await;
-
-                                            let stream = match TlsAcceptor::from(server_config)
-                                                .accept(upgraded)
-                                                .await
-                                            {
-                                                Ok(stream) => TokioIo::new(stream),
-                                                Err(e) => {
-                                                    error!(
-                                                        "Failed to establish TLS connection: {}",
-                                                        e
-                                                    );
-                                                    println!("❌ TLS 핸드셰이크 실패");
-                                                    println!("   - 대상 서버: {}", authority);
-                                                    println!("   - 오류: {}", e);
-                                                    return;
-                                                }
-                                            };
-
-                                            if let Err(e) = self
-                                                .serve_stream(
-                                                    stream,
-                                                    Scheme::HTTPS,
-                                                    authority.clone(),
-                                                )
-                                                .await
-                                            {
-                                                if !e
-                                                    .to_string()
-                                                    .starts_with("error shutting down connection")
-                                                {
-                                                    error!("HTTPS connect error: {}", e);
-                                                }
-                                            }
-                                        }
-                                    }
-
-                                    return;
-                                } else {
-                                    warn!(
-                                        "Unknown protocol, read '{:02X?}' from upgraded connection",
-                                        &buffer[..bytes_read]
-                                    );
-                                }
-                            }
-
-                            let mut server = match TcpStream::connect(authority.as_ref()).await {
-                                Ok(server) => server,
-                                Err(e) => {
-                                    error!("Failed to connect to {}: {}", authority, e);
-                                    println!("❌ 업스트림 서버 연결 실패");
-                                    println!("   - 대상 서버: {}", authority);
-                                    println!("   - 오류: {}", e);
-                                    return;
-                                }
-                            };
-
-                            if let Err(e) =
-                                tokio::io::copy_bidirectional(&mut upgraded, &mut server).await
-                            {
-                                error!("Failed to tunnel to {}: {}", authority, e);
-                                println!("❌ 터널링 실패");
-                                println!("   - 대상 서버: {}", authority);
-                                println!("   - 오류: {}", e);
-                            }
+                            self.serve_tunnel(TokioIo::new(upgraded), req, authority)
+                                .await;
                         }
                         Err(e) => {
                             error!("Upgrade error: {}", e);
@@ -337,8 +264,255 @@ where
         }
     }
 
+    /// SOCKS5 연결 처리
+    ///
+    /// CONNECT 요청을 HTTP CONNECT 요청으로 바꿔 핸들러에 전달한 뒤, 연결된 스트림을 HTTP
+    /// CONNECT와 같은 방식으로 처리합니다. 핸들러가 응답을 반환하면 연결을 거부합니다.
+    #[instrument(skip_all, fields(client_addr = %self.client_addr))]
+    pub(crate) async fn serve_socks5(mut self, mut stream: TcpStream) {
+        let authority = match socks5::read_request(&mut stream).await {
+            Ok(authority) => authority,
+            Err(e) => {
+                error!("SOCKS5 handshake failed: {}", e);
+                return;
+            }
+        };
+
+        let ctx = self.context();
+        let req = match self
+            .http_handler
+            .handle_request(&ctx, connect_request(&authority))
+            .instrument(info_span!("handle_request"))
+            .await
+        {
+            RequestOrResponse::Request(req) => req,
+            RequestOrResponse::Response(_) => {
+                let _ = socks5::write_reply(&mut stream, Reply::NotAllowed).await;
+                return;
+            }
+        };
+
+        let Some(authority) = req.uri().authority().cloned() else {
+            let _ = socks5::write_reply(&mut stream, Reply::GeneralFailure).await;
+            return;
+        };
+
+        if let Err(e) = socks5::write_reply(&mut stream, Reply::Succeeded).await {
+            error!("Failed to write SOCKS5 reply: {}", e);
+            return;
+        }
+
+        self.serve_tunnel(stream, req, authority).await;
+    }
+
+    /// 투명 프록시 연결 처리
+    ///
+    /// 첫 데이터(TLS SNI 또는 `Host` 헤더)와 원래 목적지로 대상 주소를 찾은 뒤, CONNECT 요청과
+    /// 같은 방식으로 핸들러에 전달하고 처리합니다.
+    #[instrument(skip_all, fields(client_addr = %self.client_addr))]
+    pub(crate) async fn serve_transparent(mut self, mut stream: TcpStream) {
+        let original_dst = transparent::original_destination(&stream);
+        let initial = match transparent::read_initial(&mut stream).await {
+            Ok(initial) => initial,
+            Err(e) => {
+                error!("Failed to read from transparent connection: {}", e);
+                return;
+            }
+        };
+
+        let Some(authority) = transparent::resolve_authority(&initial, original_dst) else {
+            warn!(
+                "Unable to determine destination of transparent connection from {}",
+                self.client_addr
+            );
+            return;
+        };
+
+        let ctx = self.context();
+        let req = match self
+            .http_handler
+            .handle_request(&ctx, connect_request(&authority))
+            .instrument(info_span!("handle_request"))
+            .await
+        {
+            RequestOrResponse::Request(req) => req,
+            RequestOrResponse::Response(_) => return,
+        };
+
+        let Some(authority) = req.uri().authority().cloned() else {
+            return;
+        };
+
+        self.serve_tunnel(Rewind::new(stream, initial), req, authority)
+            .await;
+    }
+
+    /// CONNECT 또는 SOCKS5로 연결된 스트림의 프로토콜을 감지하여 가로채거나 그대로 터널링
+    async fn serve_tunnel<S>(mut self, mut upgraded: S, req: Request<Body>, authority: Authority)
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        self.authority = Some(authority.clone());
+
+        let mut buffer = [0; 4];
+        let bytes_read = match upgraded.read(&mut buffer).await {
+            Ok(bytes_read) => bytes_read,
+            Err(e) => {
+                error!("Failed to read from upgraded connection: {}", e);
+                return;
+            }
+        };
+
+        let mut upgraded = Rewind::new(
+            upgraded,
+            Bytes::copy_from_slice(buffer[..bytes_read].as_ref()),
+        );
+
+        if self
+            .http_handler
+            .should_intercept(&self.context(), &req)
+            .await
+        {
+            if is_http_request(&buffer[..bytes_read]) {
+                if let Err(e) = self
+                    .serve_stream(TokioIo::new(upgraded), Scheme::HTTP, authority, None)
+                    .await
+                {
+                    error!("WebSocket connect error: {}", e);
+                }
+
+                return;
+            } else if buffer[..2] == *b"\x16\x03" {
+                // TLS 버전 감지
+                let tls_version = TlsVersionDetector::detect_tls_version(&buffer);
+
+                match tls_version {
+                    Some(version) => {
+                        info!("🔍 TLS 버전 감지: {} - 하이브리드 핸들러 사용", version);
+
+                        // HybridTlsHandler 생성
+                        let hybrid_handler = match HybridTlsHandler::new(Arc::clone(&self.ca)).await
+                        {
+                            Ok(handler) => handler,
+                            Err(e) => {
+                                error!("❌ HybridTlsHandler 생성 실패: {}", e);
+                                return;
+                            }
+                        };
+
+                        // 하이브리드 TLS 연결 처리
+                        match hybrid_handler
+                            .handle_tls_connection_upgraded(&authority, upgraded, &buffer)
+                            .await
+                        {
+                            Ok(hybrid_stream) => {
+                                info!("✅ 하이브리드 TLS 연결 성공: {}", version);
+                                let tls = hybrid_stream.tls_info();
+                                let stream = TokioIo::new(hybrid_stream);
+
+                                if let Err(e) = self
+                                    .serve_stream(
+                                        stream,
+                                        Scheme::HTTPS,
+                                        authority.clone(),
+                                        Some(tls),
+                                    )
+                                    .await
+                                {
+                                    if !e.to_string().starts_with("error shutting down connection")
+                                    {
+                                        error!("HTTPS connect error: {}", e);
+                                    }
+                                }
+                            }
+                            Err(e) => {
+                                error!("❌ 하이브리드 TLS 연결 실패: {}", e);
+                                println!("   - TLS 버전: {}", version);
+                                println!("   - 대상 서버: {}", authority);
+                                println!("   - 오류: {}", e);
+                                return;
+                            }
+                        }
+                    }
+                    None => {
+                        warn!("⚠️ TLS 버전을 감지할 수 없음, 기존 rustls로 시도");
+
+                        // 기존 rustls 로직 사용
+                        let server_config = self
+                            .ca
+                            .gen_server_config(&authority)
+                            .instrument(info_span!("gen_server_config"))
+                            .await;
+
+                        let stream = match TlsAcceptor::from(server_config).accept(upgraded).await {
+                            Ok(stream) => stream,
+                            Err(e) => {
+                                error!("Failed to establish TLS connection: {}", e);
+                                println!("❌ TLS 핸드셰이크 실패");
+                                println!("   - 대상 서버: {}", authority);
+                                println!("   - 오류: {}", e);
+                                return;
+                            }
+                        };
+
+                        let tls = TlsInfo::from_rustls(stream.get_ref().1);
+                        if let Err(e) = self
+                            .serve_stream(
+                                TokioIo::new(stream),
+                                Scheme::HTTPS,
+                                authority.clone(),
+                                Some(tls),
+                            )
+                            .await
+                        {
+                            if !e.to_string().starts_with("error shutting down connection") {
+                                error!("HTTPS connect error: {}", e);
+                            }
+                        }
+                    }
+                }
+
+                return;
+            } else {
+                warn!(
+                    "Unknown protocol, read '{:02X?}' from upgraded connection",
+                    &buffer[..bytes_read]
+                );
+            }
+        }
+
+        let server = match &self.upstream_proxy {
+            Some(upstream_proxy) => {
+                upstream_proxy
+                    .connect(authority.host(), authority.port_u16().unwrap_or(443))
+                    .await
+            }
+            None => TcpStream::connect(authority.as_ref())
+                .await
+                .map(ProxyStream::Tcp),
+        };
+        let mut server = match server {
+            Ok(server) => server,
+            Err(e) => {
+                error!("Failed to connect to {}: {}", authority, e);
+                println!("❌ 업스트림 서버 연결 실패");
+                println!("   - 대상 서버: {}", authority);
+                println!("   - 오류: {}", e);
+                return;
+            }
+        };
+
+        if let Err(e) = tokio::io::copy_bidirectional(&mut upgraded, &mut server).await {
+            error!("Failed to tunnel to {}: {}", authority, e);
+            println!("❌ 터널링 실패");
+            println!("   - 대상 서버: {}", authority);
+            println!("   - 오류: {}", e);
+        }
+    }
+
+    /// WebSocket 업그레이드 처리 (`connection_id`는 업그레이드 요청의 교환 ID)
     #[instrument(skip_all)]
-    fn upgrade_websocket(self, req: Request<Body>) -> Response<Body> {
+    fn upgrade_websocket(self, req: Request<Body>, connection_id: u64) -> Response<Body> {
         // WebSocket 업그레이드 요청을 원본 핸들러로 전달
         let mut req = {
             let (mut parts, _) = req.into_parts();
@@ -386,7 +560,9 @@ where
                 let fut = async move {
                     match websocket.await {
                         Ok(ws) => {
-                            if let Err(e) = self.handle_websocket_tunnel(ws, req).await {
+                            if let Err(e) =
+                                self.handle_websocket_tunnel(ws, req, connection_id).await
+                            {
                                 error!("Failed to handle WebSocket tunnel: {}", e);
                             }
                         }
@@ -411,22 +587,13 @@ where
         self,
         client_socket: WebSocketStream<TokioIo<Upgraded>>,
         req: Request<()>,
+        connection_id: u64,
     ) -> Result<(), tungstenite::Error> {
         // WebSocket 터널링 구현
         let uri = req.uri().clone();
 
         // 서버에 WebSocket 연결
-        #[cfg(any(feature = "rustls-client", feature = "native-tls-client"))]
-        let (server_socket, _) = tokio_tungstenite::connect_async_tls_with_config(
-            req,
-            None,
-            false,
-            self.websocket_connector,
-        )
-        .await?;
-
-        #[cfg(not(any(feature = "rustls-client", feature = "native-tls-client")))]
-        let (server_socket, _) = tokio_tungstenite::connect_async(req).await?;
+        let server_socket = self.connect_websocket(req).await?;
 
         // WebSocket 핸들러를 사용하여 터널링 구현
         let (server_sink, server_stream) = server_socket.split();
@@ -438,22 +605,32 @@ where
 
         // WebSocket 핸들러를 사용하여 메시지 전달
         spawn_message_forwarder(
-            server_stream,
+            self.websocket_injector.attach(
+                connection_id,
+                WebSocketDirection::ServerToClient,
+                server_stream,
+            ),
             client_sink,
             websocket_handler.clone(),
             WebSocketContext::ServerToClient {
                 src: uri.clone(),
                 dst: self.client_addr,
+                connection_id,
             },
         );
 
         spawn_message_forwarder(
-            client_stream,
+            self.websocket_injector.attach(
+                connection_id,
+                WebSocketDirection::ClientToServer,
+                client_stream,
+            ),
             server_sink,
             websocket_handler,
             WebSocketContext::ClientToServer {
                 src: self.client_addr,
                 dst: uri,
+                connection_id,
             },
         );
 
@@ -465,20 +642,11 @@ where
         self,
         client_socket: WebSocketStream<TokioIo<Upgraded>>,
         req: Request<()>,
+        connection_id: u64,
     ) -> Result<(), tungstenite::Error> {
         let uri = req.uri().clone();
 
-        #[cfg(any(feature = "rustls-client", feature = "native-tls-client"))]
-        let (server_socket, _) = tokio_tungstenite::connect_async_tls_with_config(
-            req,
-            None,
-            false,
-            self.websocket_connector,
-        )
-        .await?;
-
-        #[cfg(not(any(feature = "rustls-client", feature = "native-tls-client")))]
-        let (server_socket, _) = tokio_tungstenite::connect_async(req).await?;
+        let server_socket = self.connect_websocket(req).await?;
 
         let (server_sink, server_stream) = server_socket.split();
         let (client_sink, client_stream) = client_socket.split();
@@ -488,38 +656,93 @@ where
         } = self;
 
         spawn_message_forwarder(
-            server_stream,
+            self.websocket_injector.attach(
+                connection_id,
+                WebSocketDirection::ServerToClient,
+                server_stream,
+            ),
             client_sink,
             websocket_handler.clone(),
             WebSocketContext::ServerToClient {
                 src: uri.clone(),
                 dst: self.client_addr,
+                connection_id,
             },
         );
 
         spawn_message_forwarder(
-            client_stream,
+            self.websocket_injector.attach(
+                connection_id,
+                WebSocketDirection::ClientToServer,
+                client_stream,
+            ),
             server_sink,
             websocket_handler,
             WebSocketContext::ClientToServer {
                 src: self.client_addr,
                 dst: uri,
+                connection_id,
             },
         );
 
         Ok(())
     }
 
+    /// 서버에 WebSocket 연결 (상위 프록시가 있으면 프록시를 거쳐 연결)
+    async fn connect_websocket(
+        &self,
+        req: Request<()>,
+    ) -> Result<WebSocketStream<MaybeTlsStream<ProxyStream>>, tungstenite::Error> {
+        let host = req
+            .uri()
+            .host()
+            .unwrap_or_default()
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .to_string();
+        let port = req
+            .uri()
+            .port_u16()
+            .unwrap_or(match req.uri().scheme_str() {
+                Some("wss") => 443,
+                _ => 80,
+            });
+        let stream = match &self.upstream_proxy {
+            Some(upstream_proxy) => upstream_proxy.connect(&host, port).await?,
+            None => ProxyStream::Tcp(TcpStream::connect((host.as_str(), port)).await?),
+        };
+
+        #[cfg(any(feature = "rustls-client", feature = "native-tls-client"))]
+        let (server_socket, _) = tokio_tungstenite::client_async_tls_with_config(
+            req,
+            stream,
+            None,
+            self.websocket_connector.clone(),
+        )
+        .await?;
+
+        #[cfg(not(any(feature = "rustls-client", feature = "native-tls-client")))]
+        let (server_socket, _) =
+            tokio_tungstenite::client_async_with_config(req, MaybeTlsStream::Plain(stream), None)
+                .await?;
+
+        Ok(server_socket)
+    }
+
     #[instrument(skip_all)]
     async fn serve_stream<I>(
-        self,
+        mut self,
         stream: I,
         scheme: Scheme,
         authority: Authority,
+        tls: Option<TlsInfo>,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
     where
         I: hyper::rt::Read + hyper::rt::Write + Unpin + Send + 'static,
     {
+        self.scheme = scheme.clone();
+        self.tls = tls;
+
         let service = service_fn(|mut req| {
             if req.version() == hyper::Version::HTTP_10 || req.version() == hyper::Version::HTTP_11
             {
@@ -558,7 +781,18 @@ fn spawn_message_forwarder(
 #[instrument(skip_all)]
 fn normalize_request<T>(mut req: Request<T>) -> Request<T> {
     // Hyper will automatically add a Host header if needed.
-    req.headers_mut().remove(hyper::header::HOST);
+    let host = req.headers_mut().remove(hyper::header::HOST);
+
+    // Map Remote에서 원래 Host 헤더를 유지하도록 설정한 경우 복원
+    if req
+        .extensions()
+        .get::<RemoteMapping>()
+        .is_some_and(|mapping| mapping.preserve_host)
+    {
+        if let Some(host) = host {
+            req.headers_mut().insert(hyper::header::HOST, host);
+        }
+    }
 
     // HTTP/2 supports multiple cookie headers, but HTTP/1.x only supports one.
     if let Entry::Occupied(mut cookies) = req.headers_mut().entry(hyper::header::COOKIE) {
@@ -582,6 +816,15 @@ mod tests {
         async fn gen_server_config(&self, _authority: &Authority) -> Arc<ServerConfig> {
             unimplemented!();
         }
+
+        fn get_ca_cert_der(&self) -> Option<Vec<u8>> {
+            unimplemented!();
+        }
+
+        #[cfg(feature = "native-tls-client")]
+        async fn gen_pkcs12_identity(&self, _authority: &Authority) -> Option<Vec<u8>> {
+            unimplemented!();
+        }
     }
 
     fn build_proxy() -> InternalProxy<HttpConnector, CA, crate::NoopHandler, crate::NoopHandler> {
@@ -592,7 +835,47 @@ mod tests {
             http_handler: crate::NoopHandler::new(),
             websocket_handler: crate::NoopHandler::new(),
             websocket_connector: None,
+            map_remote: MapRemote::default(),
+            websocket_injector: WebSocketInjector::default(),
+            upstream_proxy: None,
             client_addr: "127.0.0.1:8080".parse().unwrap(),
+            connection_id: next_connection_id(),
+            authority: None,
+            scheme: Scheme::HTTP,
+            tls: None,
+        }
+    }
+
+    mod context {
+        use super::*;
+
+        #[test]
+        fn assigns_unique_exchange_ids() {
+            let proxy = build_proxy();
+
+            let first = proxy.context();
+            let second = proxy.context();
+
+            assert_ne!(first.exchange_id, second.exchange_id);
+            assert_eq!(first.connection_id, second.connection_id);
+        }
+
+        #[test]
+        fn carries_connection_metadata() {
+            let mut proxy = build_proxy();
+            proxy.authority = Some(Authority::from_static("example.com:443"));
+            proxy.scheme = Scheme::HTTPS;
+            proxy.tls = Some(TlsInfo {
+                sni: Some("example.com".to_owned()),
+                ..TlsInfo::default()
+            });
+
+            let ctx = proxy.clone().context();
+
+            assert_ne!(ctx.connection_id, build_proxy().connection_id);
+            assert_eq!(ctx.authority, proxy.authority);
+            assert_eq!(ctx.scheme, Scheme::HTTPS);
+            assert_eq!(ctx.tls.unwrap().sni.as_deref(), Some("example.com"));
         }
     }
 
@@ -622,6 +905,30 @@ mod tests {
             assert_eq!(req.headers().get(hyper::header::HOST), None);
         }
 
+        #[test]
+        fn keeps_host_header_for_preserved_mapping() {
+            let map_remote = MapRemote::new(vec![
+                crate::map_remote::MapRemoteRule::new("example.com", "http://localhost:8080")
+                    .unwrap()
+                    .preserve_host(true),
+            ]);
+
+            let mut req = Request::builder()
+                .uri("http://example.com/")
+                .header(hyper::header::HOST, "example.com")
+                .body(())
+                .unwrap();
+            assert!(map_remote.apply(&mut req));
+
+            let req = normalize_request(req);
+
+            assert_eq!(req.uri(), "http://localhost:8080/");
+            assert_eq!(
+                req.headers().get(hyper::header::HOST),
+                Some(&"example.com".parse().unwrap())
+            );
+        }
+
         #[test]
         fn joins_cookies() {
             let req = Request::builder()
@@ -675,7 +982,7 @@ mod tests {
                 .body(Body::empty())
                 .unwrap();
 
-            let res = proxy.upgrade_websocket(req);
+            let res = proxy.upgrade_websocket(req, 1);
 
             assert_eq!(res.status(), StatusCode::BAD_REQUEST)
         }
@@ -689,7 +996,7 @@ mod tests {
                 .body(Body::empty())
                 .unwrap();
 
-            let res = proxy.upgrade_websocket(req);
+            let res = proxy.upgrade_websocket(req, 1);
 
             assert_eq!(res.status(), StatusCode::BAD_REQUEST)
         }
@@ -1,12 +1,16 @@
 mod internal;
+mod socks5;
+mod transparent;
 
 pub mod builder;
 
 use crate::{
     Body, Error, HttpHandler, WebSocketHandler, builder::ProxyBuilder,
-    certificate_authority::CertificateAuthority,
+    certificate_authority::CertificateAuthority, map_remote::MapRemote,
+    upstream_proxy::UpstreamProxy, websocket::WebSocketInjector,
 };
 use builder::{AddrOrListener, WantsAddr};
+use http::uri::Scheme;
 use hyper::service::service_fn;
 use hyper_util::{
     client::legacy::{Client, connect::Connect},
@@ -14,8 +18,8 @@ use hyper_util::{
     server::conn::auto::{self, Builder},
 };
 use internal::InternalProxy;
-use std::sync::Arc;
-use tokio::net::TcpListener;
+use std::{net::SocketAddr, sync::Arc};
+use tokio::net::{TcpListener, TcpStream};
 use tokio_graceful::Shutdown;
 use tokio_tungstenite::Connector;
 use tracing::error;
@@ -77,6 +81,11 @@ pub struct Proxy<C, CA, H, W, F> {
     websocket_handler: W,
     websocket_connector: Option<Connector>,
     server: Option<Builder<TokioExecutor>>,
+    map_remote: MapRemote,
+    websocket_injector: WebSocketInjector,
+    upstream_proxy: Option<Arc<UpstreamProxy>>,
+    socks5: Option<AddrOrListener>,
+    transparent: Option<AddrOrListener>,
     graceful_shutdown: F,
 }
 
@@ -107,6 +116,12 @@ where
                 .http1()
                 .title_case_headers(true)
                 .preserve_header_case(true);
+            // HTTP/2 연결은 연결 서문(preface)으로 자동 감지되며, 스트림마다 별도의 요청으로 처리됨
+            #[cfg(feature = "http2")]
+            builder
+                .http2()
+                .timer(hyper_util::rt::TokioTimer::new())
+                .keep_alive_interval(std::time::Duration::from_secs(30));
             builder
         });
 
@@ -115,6 +130,18 @@ where
             AddrOrListener::Listener(listener) => listener,
         };
 
+        let socks5_listener = match self.socks5 {
+            Some(AddrOrListener::Addr(addr)) => Some(TcpListener::bind(addr).await?),
+            Some(AddrOrListener::Listener(listener)) => Some(listener),
+            None => None,
+        };
+
+        let transparent_listener = match self.transparent {
+            Some(AddrOrListener::Addr(addr)) => Some(TcpListener::bind(addr).await?),
+            Some(AddrOrListener::Listener(listener)) => Some(listener),
+            None => None,
+        };
+
         let shutdown = Shutdown::new(self.graceful_shutdown);
         let guard = shutdown.guard_weak();
 
@@ -135,6 +162,10 @@ where
                     let http_handler = self.http_handler.clone();
                     let websocket_handler = self.websocket_handler.clone();
                     let websocket_connector = self.websocket_connector.clone();
+                    let map_remote = self.map_remote.clone();
+                    let websocket_injector = self.websocket_injector.clone();
+                    let upstream_proxy = self.upstream_proxy.clone();
+                    let connection_id = internal::next_connection_id();
 
                     shutdown.spawn_task_fn(move |guard| async move {
                         let conn = server.serve_connection_with_upgrades(
@@ -147,7 +178,14 @@ where
                                     http_handler: http_handler.clone(),
                                     websocket_handler: websocket_handler.clone(),
                                     websocket_connector: websocket_connector.clone(),
+                                    map_remote: map_remote.clone(),
+                                    websocket_injector: websocket_injector.clone(),
+                                    upstream_proxy: upstream_proxy.clone(),
                                     client_addr,
+                                    connection_id,
+                                    authority: None,
+                                    scheme: Scheme::HTTP,
+                                    tls: None,
                                 }
                                 .proxy(req)
                             }),
@@ -166,6 +204,72 @@ where
                         }
                     });
                 }
+                res = accept_optional(socks5_listener.as_ref()) => {
+                    let (tcp, client_addr) = match res {
+                        Ok((tcp, client_addr)) => (tcp, client_addr),
+                        Err(e) => {
+                            error!("Failed to accept incoming SOCKS5 connection: {}", e);
+                            continue;
+                        }
+                    };
+
+                    let internal_proxy = InternalProxy {
+                        ca: Arc::clone(&self.ca),
+                        client: self.client.clone(),
+                        server: server.clone(),
+                        http_handler: self.http_handler.clone(),
+                        websocket_handler: self.websocket_handler.clone(),
+                        websocket_connector: self.websocket_connector.clone(),
+                        map_remote: self.map_remote.clone(),
+                        websocket_injector: self.websocket_injector.clone(),
+                        upstream_proxy: self.upstream_proxy.clone(),
+                        client_addr,
+                        connection_id: internal::next_connection_id(),
+                        authority: None,
+                        scheme: Scheme::HTTP,
+                        tls: None,
+                    };
+
+                    shutdown.spawn_task_fn(move |guard| async move {
+                        tokio::select! {
+                            _ = internal_proxy.serve_socks5(tcp) => {}
+                            _ = guard.cancelled() => {}
+                        }
+                    });
+                }
+                res = accept_optional(transparent_listener.as_ref()) => {
+                    let (tcp, client_addr) = match res {
+                        Ok((tcp, client_addr)) => (tcp, client_addr),
+                        Err(e) => {
+                            error!("Failed to accept incoming transparent connection: {}", e);
+                            continue;
+                        }
+                    };
+
+                    let internal_proxy = InternalProxy {
+                        ca: Arc::clone(&self.ca),
+                        client: self.client.clone(),
+                        server: server.clone(),
+                        http_handler: self.http_handler.clone(),
+                        websocket_handler: self.websocket_handler.clone(),
+                        websocket_connector: self.websocket_connector.clone(),
+                        map_remote: self.map_remote.clone(),
+                        websocket_injector: self.websocket_injector.clone(),
+                        upstream_proxy: self.upstream_proxy.clone(),
+                        client_addr,
+                        connection_id: internal::next_connection_id(),
+                        authority: None,
+                        scheme: Scheme::HTTP,
+                        tls: None,
+                    };
+
+                    shutdown.spawn_task_fn(move |guard| async move {
+                        tokio::select! {
+                            _ = internal_proxy.serve_transparent(tcp) => {}
+                            _ = guard.cancelled() => {}
+                        }
+                    });
+                }
                 _ = guard.cancelled() => {
                     break;
                 }
@@ -177,3 +281,13 @@ where
         Ok(())
     }
 }
+
+/// 추가 리스너(SOCKS5, 투명 프록시)에서 연결 수락 (리스너가 없으면 완료되지 않음)
+async fn accept_optional(
+    listener: Option<&TcpListener>,
+) -> std::io::Result<(TcpStream, SocketAddr)> {
+    match listener {
+        Some(listener) => listener.accept().await,
+        None => std::future::pending().await,
+    }
+}
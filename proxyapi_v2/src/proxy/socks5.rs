@@ -0,0 +1,173 @@
+//! SOCKS5 서버 핸드셰이크 (RFC 1928, 인증 없음 + CONNECT만 지원)
+
+use http::uri::Authority;
+use std::{
+    io,
+    net::{Ipv4Addr, Ipv6Addr},
+};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+const VERSION: u8 = 0x05;
+const NO_AUTHENTICATION: u8 = 0x00;
+const NO_ACCEPTABLE_METHODS: u8 = 0xFF;
+const CMD_CONNECT: u8 = 0x01;
+const ATYP_IPV4: u8 = 0x01;
+const ATYP_DOMAIN: u8 = 0x03;
+const ATYP_IPV6: u8 = 0x04;
+
+/// SOCKS5 응답 코드
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub(crate) enum Reply {
+    Succeeded = 0x00,
+    GeneralFailure = 0x01,
+    NotAllowed = 0x02,
+    CommandNotSupported = 0x07,
+    AddressTypeNotSupported = 0x08,
+}
+
+/// 인사와 CONNECT 요청을 읽고 대상 주소 반환
+///
+/// 지원하지 않는 요청이면 오류 응답을 보낸 뒤 오류를 반환합니다. 성공 응답은 호출자가
+/// [`write_reply`]로 보냅니다.
+pub(crate) async fn read_request<S>(stream: &mut S) -> io::Result<Authority>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut header = [0; 2];
+    stream.read_exact(&mut header).await?;
+    if header[0] != VERSION {
+        return Err(invalid_data("unsupported SOCKS version"));
+    }
+
+    let mut methods = vec![0; header[1] as usize];
+    stream.read_exact(&mut methods).await?;
+    if !methods.contains(&NO_AUTHENTICATION) {
+        stream.write_all(&[VERSION, NO_ACCEPTABLE_METHODS]).await?;
+        return Err(invalid_data("no acceptable SOCKS5 authentication method"));
+    }
+    stream.write_all(&[VERSION, NO_AUTHENTICATION]).await?;
+
+    let mut request = [0; 4];
+    stream.read_exact(&mut request).await?;
+    if request[0] != VERSION {
+        return Err(invalid_data("unsupported SOCKS version"));
+    }
+
+    let host = match request[3] {
+        ATYP_IPV4 => {
+            let mut octets = [0; 4];
+            stream.read_exact(&mut octets).await?;
+            Ipv4Addr::from(octets).to_string()
+        }
+        ATYP_IPV6 => {
+            let mut octets = [0; 16];
+            stream.read_exact(&mut octets).await?;
+            format!("[{}]", Ipv6Addr::from(octets))
+        }
+        ATYP_DOMAIN => {
+            let len = stream.read_u8().await? as usize;
+            let mut domain = vec![0; len];
+            stream.read_exact(&mut domain).await?;
+            String::from_utf8(domain).map_err(|_| invalid_data("invalid SOCKS5 domain name"))?
+        }
+        _ => {
+            write_reply(stream, Reply::AddressTypeNotSupported).await?;
+            return Err(invalid_data("unsupported SOCKS5 address type"));
+        }
+    };
+    let port = stream.read_u16().await?;
+
+    if request[1] != CMD_CONNECT {
+        write_reply(stream, Reply::CommandNotSupported).await?;
+        return Err(invalid_data("unsupported SOCKS5 command"));
+    }
+
+    match format!("{}:{}", host, port).parse() {
+        Ok(authority) => Ok(authority),
+        Err(_) => {
+            write_reply(stream, Reply::GeneralFailure).await?;
+            Err(invalid_data("invalid SOCKS5 destination"))
+        }
+    }
+}
+
+/// 응답 전송 (바인드 주소는 0.0.0.0:0)
+pub(crate) async fn write_reply<S>(stream: &mut S, reply: Reply) -> io::Result<()>
+where
+    S: AsyncWrite + Unpin,
+{
+    stream
+        .write_all(&[VERSION, reply as u8, 0x00, ATYP_IPV4, 0, 0, 0, 0, 0, 0])
+        .await
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn reads_connect_request() {
+        let (mut client, mut server) = tokio::io::duplex(64);
+
+        client.write_all(&[0x05, 0x02, 0x02, 0x00]).await.unwrap();
+        client
+            .write_all(&[0x05, 0x01, 0x00, 0x03, 11])
+            .await
+            .unwrap();
+        client.write_all(b"example.com").await.unwrap();
+        client.write_all(&443u16.to_be_bytes()).await.unwrap();
+
+        let authority = read_request(&mut server).await.unwrap();
+        assert_eq!(authority, "example.com:443");
+
+        let mut method = [0; 2];
+        client.read_exact(&mut method).await.unwrap();
+        assert_eq!(method, [0x05, 0x00]);
+    }
+
+    #[tokio::test]
+    async fn reads_ipv6_destination() {
+        let (mut client, mut server) = tokio::io::duplex(64);
+
+        client.write_all(&[0x05, 0x01, 0x00]).await.unwrap();
+        client.write_all(&[0x05, 0x01, 0x00, 0x04]).await.unwrap();
+        client
+            .write_all(&Ipv6Addr::LOCALHOST.octets())
+            .await
+            .unwrap();
+        client.write_all(&8080u16.to_be_bytes()).await.unwrap();
+
+        let authority = read_request(&mut server).await.unwrap();
+        assert_eq!(authority, "[::1]:8080");
+    }
+
+    #[tokio::test]
+    async fn rejects_unsupported_requests() {
+        // 인증 없음 방식을 지원하지 않는 클라이언트
+        let (mut client, mut server) = tokio::io::duplex(64);
+        client.write_all(&[0x05, 0x01, 0x02]).await.unwrap();
+        assert!(read_request(&mut server).await.is_err());
+
+        let mut method = [0; 2];
+        client.read_exact(&mut method).await.unwrap();
+        assert_eq!(method, [0x05, 0xFF]);
+
+        // BIND 명령
+        let (mut client, mut server) = tokio::io::duplex(64);
+        client.write_all(&[0x05, 0x01, 0x00]).await.unwrap();
+        client
+            .write_all(&[0x05, 0x02, 0x00, 0x01, 127, 0, 0, 1, 0, 80])
+            .await
+            .unwrap();
+        assert!(read_request(&mut server).await.is_err());
+
+        let mut reply = [0; 12];
+        client.read_exact(&mut reply).await.unwrap();
+        assert_eq!(reply[2..4], [0x05, Reply::CommandNotSupported as u8]);
+    }
+}
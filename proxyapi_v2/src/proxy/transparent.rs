@@ -0,0 +1,328 @@
+//! 투명 프록시 - CONNECT 없이 들어온 연결의 대상 주소 복원
+//!
+//! TLS는 ClientHello의 SNI, 평문 HTTP는 `Host` 헤더로 대상 호스트를 찾고, Linux에서는
+//! `SO_ORIGINAL_DST`로 iptables 리다이렉트 전의 원래 목적지를 함께 사용합니다.
+
+use http::uri::Authority;
+use hyper::body::Bytes;
+use std::{io, net::SocketAddr};
+use tokio::{io::AsyncReadExt, net::TcpStream};
+
+/// TLS 레코드 최대 크기 (헤더 포함)
+const MAX_TLS_RECORD: usize = 5 + 16 * 1024 + 2048;
+/// 평문 HTTP 요청 헤더 최대 크기
+const MAX_HTTP_HEADER: usize = 16 * 1024;
+
+/// 대상 주소를 찾는 데 필요한 첫 데이터 읽기
+///
+/// TLS면 첫 레코드(ClientHello) 전체를, 그 외에는 HTTP 요청 헤더 끝까지 읽습니다.
+/// 읽은 데이터는 [`Rewind`](crate::rewind::Rewind)로 스트림에 되돌려야 합니다.
+pub(crate) async fn read_initial(stream: &mut TcpStream) -> io::Result<Bytes> {
+    let mut buffer = Vec::with_capacity(4096);
+
+    loop {
+        if buffer.first() == Some(&0x16) {
+            if buffer.len() >= 5 {
+                let record_len = 5 + u16::from_be_bytes([buffer[3], buffer[4]]) as usize;
+                if buffer.len() >= record_len || buffer.len() >= MAX_TLS_RECORD {
+                    break;
+                }
+            }
+        } else if !buffer.is_empty()
+            && (buffer.windows(4).any(|w| w == b"\r\n\r\n") || buffer.len() >= MAX_HTTP_HEADER)
+        {
+            break;
+        }
+
+        if stream.read_buf(&mut buffer).await? == 0 {
+            break;
+        }
+    }
+
+    Ok(Bytes::from(buffer))
+}
+
+/// 첫 데이터와 원래 목적지로 대상 authority 결정
+///
+/// SNI 또는 `Host` 헤더의 호스트를 우선 사용하고, 포트는 원래 목적지의 포트(없으면 443/80)를
+/// 사용합니다. 호스트를 찾지 못하면 원래 목적지 주소를 사용합니다.
+pub(crate) fn resolve_authority(
+    initial: &[u8],
+    original_dst: Option<SocketAddr>,
+) -> Option<Authority> {
+    let is_tls = initial.first() == Some(&0x16);
+    let default_port = original_dst
+        .map(|dst| dst.port())
+        .unwrap_or(if is_tls { 443 } else { 80 });
+
+    let authority = if is_tls {
+        parse_sni(initial).map(|host| format!("{}:{}", host, default_port))
+    } else {
+        http_host(initial).map(|host| match host.parse::<Authority>() {
+            Ok(authority) if authority.port().is_some() => host,
+            _ => format!("{}:{}", host, default_port),
+        })
+    };
+
+    authority
+        .or_else(|| original_dst.map(|dst| dst.to_string()))
+        .and_then(|authority| authority.parse().ok())
+}
+
+/// TLS ClientHello에서 SNI 호스트 이름 추출
+pub(crate) fn parse_sni(record: &[u8]) -> Option<String> {
+    // 레코드 헤더(5) + 핸드셰이크 헤더(4)
+    if record.len() < 9 || record[0] != 0x16 || record[5] != 0x01 {
+        return None;
+    }
+    let mut reader = Reader(&record[9..]);
+
+    // version(2) + random(32)
+    reader.skip(34)?;
+    let session_id_len = reader.u8()? as usize;
+    reader.skip(session_id_len)?;
+    let cipher_suites_len = reader.u16()? as usize;
+    reader.skip(cipher_suites_len)?;
+    let compression_len = reader.u8()? as usize;
+    reader.skip(compression_len)?;
+
+    let extensions_len = reader.u16()? as usize;
+    let mut extensions = Reader(reader.take(extensions_len)?);
+    while let (Some(ext_type), Some(ext_len)) = (extensions.u16(), extensions.u16()) {
+        let data = extensions.take(ext_len as usize)?;
+        if ext_type != 0x0000 {
+            continue;
+        }
+
+        let mut server_names = Reader(data);
+        let list_len = server_names.u16()? as usize;
+        let mut list = Reader(server_names.take(list_len)?);
+        while let Some(name_type) = list.u8() {
+            let name_len = list.u16()? as usize;
+            let name = list.take(name_len)?;
+            if name_type == 0x00 {
+                return String::from_utf8(name.to_vec()).ok();
+            }
+        }
+        return None;
+    }
+
+    None
+}
+
+/// 평문 HTTP 요청 헤더에서 `Host` 값 추출
+pub(crate) fn http_host(initial: &[u8]) -> Option<String> {
+    let header_end = initial
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .unwrap_or(initial.len());
+    let headers = std::str::from_utf8(&initial[..header_end]).ok()?;
+
+    headers.split("\r\n").skip(1).find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim()
+            .eq_ignore_ascii_case("host")
+            .then(|| value.trim().to_string())
+            .filter(|host| !host.is_empty())
+    })
+}
+
+/// iptables 리다이렉트 전의 원래 목적지 (`SO_ORIGINAL_DST`)
+///
+/// 리다이렉트되지 않은 연결이면 None을 반환합니다.
+#[cfg(target_os = "linux")]
+pub(crate) fn original_destination(stream: &TcpStream) -> Option<SocketAddr> {
+    use std::{
+        mem,
+        net::{Ipv4Addr, Ipv6Addr},
+        os::fd::AsRawFd,
+    };
+
+    let fd = stream.as_raw_fd();
+    let local = stream.local_addr().ok()?;
+
+    // SAFETY: getsockopt에 크기가 맞는 sockaddr 구조체와 길이를 전달함
+    let original = unsafe {
+        if local.is_ipv4() {
+            let mut addr: libc::sockaddr_in = mem::zeroed();
+            let mut len = mem::size_of::<libc::sockaddr_in>() as libc::socklen_t;
+            let result = libc::getsockopt(
+                fd,
+                libc::SOL_IP,
+                libc::SO_ORIGINAL_DST,
+                &mut addr as *mut _ as *mut libc::c_void,
+                &mut len,
+            );
+            (result == 0).then(|| {
+                SocketAddr::from((
+                    Ipv4Addr::from(u32::from_be(addr.sin_addr.s_addr)),
+                    u16::from_be(addr.sin_port),
+                ))
+            })
+        } else {
+            let mut addr: libc::sockaddr_in6 = mem::zeroed();
+            let mut len = mem::size_of::<libc::sockaddr_in6>() as libc::socklen_t;
+            let result = libc::getsockopt(
+                fd,
+                libc::SOL_IPV6,
+                libc::IP6T_SO_ORIGINAL_DST,
+                &mut addr as *mut _ as *mut libc::c_void,
+                &mut len,
+            );
+            (result == 0).then(|| {
+                SocketAddr::from((
+                    Ipv6Addr::from(addr.sin6_addr.s6_addr),
+                    u16::from_be(addr.sin6_port),
+                ))
+            })
+        }
+    };
+
+    original.filter(|original| *original != local)
+}
+
+/// iptables 리다이렉트 전의 원래 목적지 (Linux 외에는 지원하지 않음)
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn original_destination(_stream: &TcpStream) -> Option<SocketAddr> {
+    None
+}
+
+/// 바이트 슬라이스 순차 읽기
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.0.len() < len {
+            return None;
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Some(head)
+    }
+
+    fn skip(&mut self, len: usize) -> Option<()> {
+        self.take(len).map(|_| ())
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.take(1).map(|b| b[0])
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.take(2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client_hello(server_name: Option<&str>) -> Vec<u8> {
+        let mut extensions = Vec::new();
+        // supported_versions 확장 (SNI 앞에 다른 확장이 있는 경우)
+        extensions.extend_from_slice(&[0x00, 0x2b, 0x00, 0x03, 0x02, 0x03, 0x04]);
+        if let Some(name) = server_name {
+            let name = name.as_bytes();
+            let list_len = 3 + name.len() as u16;
+            extensions.extend_from_slice(&[0x00, 0x00]);
+            extensions.extend_from_slice(&(list_len + 2).to_be_bytes());
+            extensions.extend_from_slice(&list_len.to_be_bytes());
+            extensions.push(0x00);
+            extensions.extend_from_slice(&(name.len() as u16).to_be_bytes());
+            extensions.extend_from_slice(name);
+        }
+
+        let mut body = vec![0x03, 0x03];
+        body.extend_from_slice(&[0; 32]);
+        body.push(0);
+        body.extend_from_slice(&[0x00, 0x02, 0x13, 0x01]);
+        body.extend_from_slice(&[0x01, 0x00]);
+        body.extend_from_slice(&(extensions.len() as u16).to_be_bytes());
+        body.extend_from_slice(&extensions);
+
+        let mut handshake = vec![0x01];
+        handshake.extend_from_slice(&(body.len() as u32).to_be_bytes()[1..]);
+        handshake.extend_from_slice(&body);
+
+        let mut record = vec![0x16, 0x03, 0x01];
+        record.extend_from_slice(&(handshake.len() as u16).to_be_bytes());
+        record.extend_from_slice(&handshake);
+        record
+    }
+
+    #[test]
+    fn parses_sni() {
+        assert_eq!(
+            parse_sni(&client_hello(Some("api.example.com"))).as_deref(),
+            Some("api.example.com")
+        );
+        assert_eq!(parse_sni(&client_hello(None)), None);
+
+        // 잘린 레코드
+        let record = client_hello(Some("api.example.com"));
+        assert_eq!(parse_sni(&record[..record.len() - 4]), None);
+    }
+
+    #[test]
+    fn parses_http_host() {
+        let request = b"POST /upload HTTP/1.1\r\nContent-Type: text/plain\r\nhost: example.com:8080\r\n\r\nbody";
+        assert_eq!(http_host(request).as_deref(), Some("example.com:8080"));
+        assert_eq!(http_host(b"GET / HTTP/1.0\r\n\r\n"), None);
+    }
+
+    #[test]
+    fn resolves_authority() {
+        let original_dst: SocketAddr = "93.184.216.34:8443".parse().unwrap();
+
+        let hello = client_hello(Some("api.example.com"));
+        assert_eq!(
+            resolve_authority(&hello, Some(original_dst)).unwrap(),
+            "api.example.com:8443"
+        );
+        assert_eq!(
+            resolve_authority(&hello, None).unwrap(),
+            "api.example.com:443"
+        );
+        assert_eq!(
+            resolve_authority(&client_hello(None), Some(original_dst)).unwrap(),
+            "93.184.216.34:8443"
+        );
+
+        let request = b"GET / HTTP/1.1\r\nHost: example.com\r\n\r\n";
+        assert_eq!(resolve_authority(request, None).unwrap(), "example.com:80");
+        let request = b"GET / HTTP/1.1\r\nHost: example.com:3000\r\n\r\n";
+        assert_eq!(
+            resolve_authority(request, Some(original_dst)).unwrap(),
+            "example.com:3000"
+        );
+        assert!(resolve_authority(b"\x00\x01", None).is_none());
+    }
+
+    #[tokio::test]
+    async fn reads_whole_client_hello() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let hello = client_hello(Some("api.example.com"));
+
+        let client = {
+            let hello = hello.clone();
+            tokio::spawn(async move {
+                use tokio::io::AsyncWriteExt;
+
+                let mut stream = TcpStream::connect(addr).await.unwrap();
+                // 여러 조각으로 나눠서 전송
+                for chunk in hello.chunks(7) {
+                    stream.write_all(chunk).await.unwrap();
+                    stream.flush().await.unwrap();
+                }
+                stream
+            })
+        };
+
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let initial = read_initial(&mut stream).await.unwrap();
+        assert_eq!(&initial[..], &hello[..]);
+        assert_eq!(original_destination(&stream), None);
+        drop(client.await.unwrap());
+    }
+}
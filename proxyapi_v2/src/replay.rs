@@ -0,0 +1,848 @@
+//! 캡처한 요청 재전송
+//!
+//! [`ReplayClient`]는 프록시의 업스트림 클라이언트와 같은 방식([`TlsVerificationPolicy`]에 따른 인증서 검증,
+//! 호스트별 클라이언트 인증서, ALPN으로 h2/HTTP/1.1 협상, 상위 프록시 경유)으로 연결해 캡처한(또는 편집한) 요청을 다시 보냅니다. [`hybrid_client`]는
+//! 프록시의 업스트림 클라이언트로도 사용할 수 있습니다.
+//!
+//! `legacy-tls-client` 기능을 켜면 rustls가 지원하지 않는 TLS 1.0/1.1과 오래된 암호 스위트만 사용하는
+//! 서버에 OpenSSL로 연결하는 [`ReplayClient::legacy`]도 사용할 수 있습니다.
+
+#[cfg(feature = "legacy-tls-client")]
+use crate::upstream_tls::LegacyTlsConnector;
+use crate::{
+    Body,
+    client_identity::{ClientIdentities, IdentityConnector, UsedClientIdentity},
+    timings::{TimingConnector, Timings},
+    upstream_proxy::{UpstreamConnector, UpstreamProxy},
+    upstream_tls::{
+        TlsPolicyError, TlsVerificationPolicy, UpstreamSession, UpstreamTls, VerifyingConnector,
+    },
+};
+use futures::{Stream, StreamExt, stream};
+use http::{HeaderMap, Request, Response, Version, header};
+use http_body_util::BodyExt;
+use hyper::body::Bytes;
+use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+use hyper_util::{client::legacy::Client, rt::TokioExecutor};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+use thiserror::Error;
+use tokio_rustls::rustls::{self, ClientConfig, crypto::CryptoProvider};
+
+/// 인증서 검증 정책을 적용하는 업스트림 클라이언트
+pub type HybridClient = Client<
+    TimingConnector<IdentityConnector<VerifyingConnector<HttpsConnector<UpstreamConnector>>>>,
+    Body,
+>;
+
+/// OpenSSL로 오래된 TLS 버전과 암호 스위트까지 협상하는 업스트림 클라이언트
+#[cfg(feature = "legacy-tls-client")]
+pub type LegacyClient =
+    Client<TimingConnector<IdentityConnector<LegacyTlsConnector<UpstreamConnector>>>, Body>;
+
+/// 레거시 클라이언트가 허용하는 암호 스위트 (TLS 1.2 이하, 인증 없는 암호 스위트 제외)
+#[cfg(feature = "legacy-tls-client")]
+const LEGACY_CIPHERS: &str = "ALL:!aNULL";
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ReplayError {
+    #[error("Failed to build TLS configuration: {0}")]
+    Tls(#[from] rustls::Error),
+    #[error("Failed to build certificate verifier: {0}")]
+    Policy(#[from] TlsPolicyError),
+    #[cfg(feature = "legacy-tls-client")]
+    #[error("Failed to build OpenSSL connector: {0}")]
+    OpenSsl(#[from] openssl::error::ErrorStack),
+    #[error("Failed to send request: {0}")]
+    Request(#[from] hyper_util::client::legacy::Error),
+    #[error("Failed to read response body: {0}")]
+    Body(#[from] crate::Error),
+}
+
+/// 인증서를 정책에 따라 검증하는 클라이언트 생성 (상위 프록시가 있으면 프록시를 거쳐 연결)
+///
+/// 서버가 h2를 지원하면 ALPN으로 HTTP/2를 협상하고(`http2` 기능), 아니면 HTTP/1.1을 사용합니다.
+/// 연결마다의 인증서 검증 결과는 응답 extension의 [`UpstreamTls`](crate::upstream_tls::UpstreamTls)로,
+/// 호스트에 맞는 클라이언트 인증서를 선택했으면 [`UsedClientIdentity`]로 기록됩니다.
+pub fn hybrid_client(
+    provider: CryptoProvider,
+    verification: &TlsVerificationPolicy,
+    identities: &ClientIdentities,
+    upstream_proxy: Option<UpstreamProxy>,
+) -> Result<HybridClient, ReplayError> {
+    let verifier = verification.verifier(&provider)?;
+    let rustls_config = ClientConfig::builder_with_provider(Arc::new(provider))
+        .with_safe_default_protocol_versions()?
+        .dangerous()
+        .with_custom_certificate_verifier(verifier.clone())
+        .with_no_client_auth();
+
+    // ALPN 프로토콜 목록은 활성화한 HTTP 버전에 따라 커넥터가 설정
+    let upstream = UpstreamConnector::new(upstream_proxy);
+    let connector = identities.connector(rustls_config, |config| {
+        let https = HttpsConnectorBuilder::new()
+            .with_tls_config(config)
+            .https_or_http()
+            .enable_http1();
+        #[cfg(feature = "http2")]
+        let https = https.enable_http2();
+        VerifyingConnector::new(https.wrap_connector(upstream.clone()), verifier.clone())
+    });
+
+    Ok(Client::builder(TokioExecutor::new())
+        .http1_title_case_headers(true)
+        .http1_preserve_header_case(true)
+        .build(TimingConnector::new(connector)))
+}
+
+/// 인증서를 정책에 따라 검증하고 TLS 1.0과 오래된 암호 스위트까지 협상하는 OpenSSL 클라이언트 생성
+///
+/// OpenSSL 보안 수준을 0으로 낮춰 최신 라이브러리가 기본으로 거부하는 TLS 1.0/1.1, SHA-1 서명, 짧은 키,
+/// 3DES 등 오래된 암호 스위트도 허용합니다(빌드한 OpenSSL이 지원하는 범위에서). 인증서는 OpenSSL 기본
+/// 신뢰 저장소, 운영체제 신뢰 저장소와 정책의 추가 CA로 검증하며, 검증 예외 호스트에만 유효하지 않은
+/// 인증서를 허용합니다. 검증 결과, 협상한 세션 정보와 선택한 클라이언트 인증서는 [`hybrid_client`]와 같이
+/// 응답 extension으로 기록됩니다. ALPN을 사용하지 않으므로 HTTP/1.1로만 연결합니다.
+#[cfg(feature = "legacy-tls-client")]
+pub fn legacy_client(
+    provider: CryptoProvider,
+    verification: &TlsVerificationPolicy,
+    identities: &ClientIdentities,
+    upstream_proxy: Option<UpstreamProxy>,
+) -> Result<LegacyClient, ReplayError> {
+    use openssl::{
+        pkey::PKey,
+        ssl::{SslConnector, SslMethod, SslVerifyMode, SslVersion},
+        x509::X509,
+    };
+
+    let verifier = verification.verifier(&provider)?;
+    // 운영체제 인증서 중 OpenSSL이 읽지 못하는 것은 건너뜀 (rustls 검증기와 같은 방식)
+    let mut roots = rustls_native_certs::load_native_certs()
+        .certs
+        .iter()
+        .filter_map(|certificate| X509::from_der(certificate).ok())
+        .collect::<Vec<_>>();
+    for certificate in verification.extra_roots() {
+        roots.push(X509::from_der(certificate)?);
+    }
+
+    let upstream = UpstreamConnector::new(upstream_proxy);
+    let connector = identities.try_connector(|identity| {
+        let tls = |insecure: bool| {
+            let mut builder = SslConnector::builder(SslMethod::tls_client())?;
+            builder.set_min_proto_version(Some(SslVersion::TLS1))?;
+            builder.set_security_level(0);
+            builder.set_cipher_list(LEGACY_CIPHERS)?;
+            if insecure {
+                builder.set_verify(SslVerifyMode::NONE);
+            }
+            for root in &roots {
+                // 기본 신뢰 저장소에 이미 있는 인증서는 무시
+                let _ = builder.cert_store_mut().add_cert(root.clone());
+            }
+            if let Some(identity) = identity {
+                let (certificate, chain) = identity
+                    .certificates()
+                    .split_first()
+                    .expect("client identity has a certificate");
+                let certificate = X509::from_der(certificate)?;
+                builder.set_certificate(&certificate)?;
+                for certificate in chain {
+                    builder.add_extra_chain_cert(X509::from_der(certificate)?)?;
+                }
+                let private_key = PKey::private_key_from_der(identity.private_key().secret_der())?;
+                builder.set_private_key(&private_key)?;
+            }
+            Ok::<_, ReplayError>(builder.build())
+        };
+        Ok::<_, ReplayError>(LegacyTlsConnector::new(
+            upstream.clone(),
+            tls(false)?,
+            tls(true)?,
+            verifier.clone(),
+        ))
+    })?;
+
+    Ok(Client::builder(TokioExecutor::new())
+        .http1_title_case_headers(true)
+        .http1_preserve_header_case(true)
+        .build(TimingConnector::new(connector)))
+}
+
+/// 반복 재전송 옵션
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReplayOptions {
+    /// 보낼 횟수
+    pub count: usize,
+    /// 동시에 보낼 최대 요청 수
+    pub concurrency: usize,
+    /// 요청을 시작하는 간격
+    pub delay: Duration,
+}
+
+impl Default for ReplayOptions {
+    fn default() -> Self {
+        Self {
+            count: 1,
+            concurrency: 1,
+            delay: Duration::ZERO,
+        }
+    }
+}
+
+/// 재전송한 요청의 응답
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ReplayResponse {
+    /// 요청을 보낸 시각
+    pub sent_at: SystemTime,
+    /// 응답 (본문은 받은 그대로, 압축되어 있으면 압축된 상태)
+    pub response: Response<Bytes>,
+    /// 본문 뒤에 전달된 트레일러
+    pub trailers: Option<HeaderMap>,
+    /// 응답 헤더까지의 구간별 소요 시간
+    pub timings: Timings,
+    /// 응답 본문 수신 시간
+    pub receive: Duration,
+    /// 업스트림 인증서 검증 결과 (TLS로 연결하지 않았으면 None)
+    pub upstream_tls: Option<UpstreamTls>,
+    /// 업스트림 TLS 세션 정보 (TLS로 연결하지 않았으면 None)
+    pub upstream_session: Option<UpstreamSession>,
+    /// 연결에 선택된 클라이언트 인증서 이름
+    pub client_identity: Option<String>,
+}
+
+/// 캡처한 요청을 다시 보내는 클라이언트
+///
+/// 연결 풀을 공유하므로 한 번 만들어 여러 요청에 재사용합니다.
+#[derive(Clone, Debug)]
+pub struct ReplayClient {
+    client: UpstreamClient,
+    upstream_proxy: Option<UpstreamProxy>,
+}
+
+#[derive(Clone, Debug)]
+enum UpstreamClient {
+    Rustls(HybridClient),
+    #[cfg(feature = "legacy-tls-client")]
+    OpenSsl(LegacyClient),
+}
+
+impl ReplayClient {
+    pub fn new(
+        provider: CryptoProvider,
+        verification: &TlsVerificationPolicy,
+        identities: &ClientIdentities,
+        upstream_proxy: Option<UpstreamProxy>,
+    ) -> Result<Self, ReplayError> {
+        Ok(Self {
+            client: UpstreamClient::Rustls(hybrid_client(
+                provider,
+                verification,
+                identities,
+                upstream_proxy.clone(),
+            )?),
+            upstream_proxy,
+        })
+    }
+
+    /// [`legacy_client`]로 연결하는 클라이언트 생성
+    ///
+    /// rustls 핸드셰이크가 실패하는 오래된 서버에 요청을 다시 보낼 때 사용합니다.
+    #[cfg(feature = "legacy-tls-client")]
+    pub fn legacy(
+        provider: CryptoProvider,
+        verification: &TlsVerificationPolicy,
+        identities: &ClientIdentities,
+        upstream_proxy: Option<UpstreamProxy>,
+    ) -> Result<Self, ReplayError> {
+        Ok(Self {
+            client: UpstreamClient::OpenSsl(legacy_client(
+                provider,
+                verification,
+                identities,
+                upstream_proxy.clone(),
+            )?),
+            upstream_proxy,
+        })
+    }
+
+    /// 요청을 한 번 보내고 응답 본문까지 받기
+    ///
+    /// 본문을 편집했을 수 있으므로 `content-length`는 본문에 맞게 다시 계산하고, HTTP 버전은 ALPN으로
+    /// 다시 협상합니다.
+    pub async fn send(&self, req: Request<Bytes>) -> Result<ReplayResponse, ReplayError> {
+        let (mut parts, body) = req.into_parts();
+        parts.version = Version::HTTP_11;
+        parts.headers.remove(header::TRANSFER_ENCODING);
+        if parts.headers.contains_key(header::CONTENT_LENGTH) || !body.is_empty() {
+            parts
+                .headers
+                .insert(header::CONTENT_LENGTH, body.len().into());
+        }
+        let mut req = Request::from_parts(parts, Body::from(http_body_util::Full::new(body)));
+        if let Some(upstream_proxy) = &self.upstream_proxy {
+            upstream_proxy.authorize(&mut req);
+        }
+
+        let sent_at = SystemTime::now();
+        let sent = Instant::now();
+        let res = match &self.client {
+            UpstreamClient::Rustls(client) => client.request(req).await?,
+            #[cfg(feature = "legacy-tls-client")]
+            UpstreamClient::OpenSsl(client) => client.request(req).await?,
+        };
+        let timings = Timings::from_response(sent, &res);
+        let upstream_tls = res.extensions().get::<UpstreamTls>().cloned();
+        let upstream_session = res.extensions().get::<UpstreamSession>().cloned();
+        let client_identity = res
+            .extensions()
+            .get::<UsedClientIdentity>()
+            .map(|used| used.0.clone());
+
+        let receive_started = Instant::now();
+        let (parts, body) = res.into_parts();
+        let collected = Body::from(body).collect().await?;
+        let receive = receive_started.elapsed();
+        let trailers = collected.trailers().cloned();
+
+        Ok(ReplayResponse {
+            sent_at,
+            response: Response::from_parts(parts, collected.to_bytes()),
+            trailers,
+            timings,
+            receive,
+            upstream_tls,
+            upstream_session,
+            client_identity,
+        })
+    }
+
+    /// 요청을 옵션에 따라 반복해서 보내고, 응답을 받는 대로 `(순번, 결과)`로 반환
+    ///
+    /// 요청은 `delay` 간격으로 시작하며, 동시에 `concurrency`개까지 진행합니다.
+    pub fn replay(
+        &self,
+        req: Request<Bytes>,
+        options: ReplayOptions,
+    ) -> impl Stream<Item = (usize, Result<ReplayResponse, ReplayError>)> + Send + '_ {
+        stream::iter(0..options.count.max(1))
+            .then(move |index| async move {
+                if index > 0 && !options.delay.is_zero() {
+                    tokio::time::sleep(options.delay).await;
+                }
+                index
+            })
+            .map(move |index| {
+                let req = clone_request(&req);
+                async move { (index, self.send(req).await) }
+            })
+            .buffer_unordered(options.concurrency.max(1))
+    }
+}
+
+fn clone_request(req: &Request<Bytes>) -> Request<Bytes> {
+    let mut cloned = Request::new(req.body().clone());
+    *cloned.method_mut() = req.method().clone();
+    *cloned.uri_mut() = req.uri().clone();
+    *cloned.version_mut() = req.version();
+    *cloned.headers_mut() = req.headers().clone();
+    cloned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyper::{server::conn::http1, service::service_fn};
+    use hyper_util::rt::TokioIo;
+    use std::{
+        convert::Infallible,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+    use tokio::net::TcpListener;
+    use tokio_rustls::rustls::crypto::aws_lc_rs;
+
+    /// 받은 본문 크기와 요청 순번을 돌려주는 서버
+    async fn start_server() -> (std::net::SocketAddr, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&hits);
+
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let counter = Arc::clone(&counter);
+                tokio::spawn(async move {
+                    let service = service_fn(move |req: Request<hyper::body::Incoming>| {
+                        let counter = Arc::clone(&counter);
+                        async move {
+                            let length = req.headers()[header::CONTENT_LENGTH].clone();
+                            let body = req.into_body().collect().await.unwrap().to_bytes();
+                            counter.fetch_add(1, Ordering::SeqCst);
+                            let res = Response::builder()
+                                .header("x-content-length", length)
+                                .body(http_body_util::Full::new(body))
+                                .unwrap();
+                            Ok::<_, Infallible>(res)
+                        }
+                    });
+                    let _ = http1::Builder::new()
+                        .serve_connection(TokioIo::new(stream), service)
+                        .await;
+                });
+            }
+        });
+
+        (addr, hits)
+    }
+
+    fn request(addr: std::net::SocketAddr, body: &'static str) -> Request<Bytes> {
+        Request::builder()
+            .method("POST")
+            .uri(format!("http://{}/echo", addr))
+            .version(Version::HTTP_2)
+            .header(header::CONTENT_LENGTH, "1")
+            .body(Bytes::from_static(body.as_bytes()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn send_fixes_content_length() {
+        let (addr, _) = start_server().await;
+        let client = ReplayClient::new(
+            aws_lc_rs::default_provider(),
+            &TlsVerificationPolicy::new(),
+            &ClientIdentities::new(),
+            None,
+        )
+        .unwrap();
+
+        let res = client.send(request(addr, "edited body")).await.unwrap();
+
+        assert_eq!(res.response.headers()["x-content-length"], "11");
+        assert_eq!(res.response.body(), "edited body");
+    }
+
+    #[tokio::test]
+    async fn replay_repeats_request() {
+        let (addr, hits) = start_server().await;
+        let client = ReplayClient::new(
+            aws_lc_rs::default_provider(),
+            &TlsVerificationPolicy::new(),
+            &ClientIdentities::new(),
+            None,
+        )
+        .unwrap();
+        let options = ReplayOptions {
+            count: 5,
+            concurrency: 2,
+            delay: Duration::from_millis(1),
+        };
+
+        let mut indexes: Vec<_> = client
+            .replay(request(addr, "hello"), options)
+            .map(|(index, res)| {
+                assert_eq!(res.unwrap().response.body(), "hello");
+                index
+            })
+            .collect()
+            .await;
+        indexes.sort();
+
+        assert_eq!(indexes, vec![0, 1, 2, 3, 4]);
+        assert_eq!(hits.load(Ordering::SeqCst), 5);
+    }
+
+    /// 테스트 CA가 발급한 `localhost` 인증서로 응답하는 HTTPS 서버
+    ///
+    /// `client_ca`가 있으면 그 CA가 발급한 클라이언트 인증서를 요구합니다.
+    #[cfg(feature = "rcgen-ca")]
+    async fn start_tls_server(
+        client_ca: Option<tokio_rustls::rustls::pki_types::CertificateDer<'static>>,
+    ) -> (
+        std::net::SocketAddr,
+        tokio_rustls::rustls::pki_types::CertificateDer<'static>,
+    ) {
+        use rcgen::{BasicConstraints, CertificateParams, DnType, IsCa, KeyPair};
+        use tokio_rustls::{
+            TlsAcceptor,
+            rustls::{
+                RootCertStore, ServerConfig, pki_types::PrivatePkcs8KeyDer,
+                server::WebPkiClientVerifier,
+            },
+        };
+
+        let ca_key = KeyPair::generate().unwrap();
+        let mut ca_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        ca_params
+            .distinguished_name
+            .push(DnType::CommonName, "Replay Test CA");
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let ca = ca_params.self_signed(&ca_key).unwrap();
+
+        let key = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::new(vec!["localhost".to_string()]).unwrap();
+        params
+            .distinguished_name
+            .push(DnType::CommonName, "Replay Test Server");
+        let certificate = params.signed_by(&key, &ca, &ca_key).unwrap();
+
+        let provider = Arc::new(aws_lc_rs::default_provider());
+        let config = ServerConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()
+            .unwrap();
+        let config = match client_ca {
+            Some(client_ca) => {
+                let mut roots = RootCertStore::empty();
+                roots.add(client_ca).unwrap();
+                let verifier =
+                    WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                        .build()
+                        .unwrap();
+                config.with_client_cert_verifier(verifier)
+            }
+            None => config.with_no_client_auth(),
+        };
+        let config = config
+            .with_single_cert(
+                vec![certificate.der().clone()],
+                PrivatePkcs8KeyDer::from(key.serialize_der()).into(),
+            )
+            .unwrap();
+        let acceptor = TlsAcceptor::from(Arc::new(config));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let acceptor = acceptor.clone();
+                tokio::spawn(async move {
+                    let Ok(stream) = acceptor.accept(stream).await else {
+                        return;
+                    };
+                    let service = service_fn(|_: Request<hyper::body::Incoming>| async {
+                        Ok::<_, Infallible>(Response::new(http_body_util::Full::new(
+                            Bytes::from_static(b"secure"),
+                        )))
+                    });
+                    let _ = http1::Builder::new()
+                        .serve_connection(TokioIo::new(stream), service)
+                        .await;
+                });
+            }
+        });
+
+        (addr, ca.der().clone())
+    }
+
+    #[cfg(feature = "rcgen-ca")]
+    #[tokio::test]
+    async fn send_records_upstream_tls() {
+        use crate::{
+            TlsVersion,
+            upstream_tls::{CertificateStatus, UpstreamTls},
+        };
+
+        let (addr, ca) = start_tls_server(None).await;
+        let req = || {
+            Request::builder()
+                .uri(format!("https://localhost:{}/", addr.port()))
+                .body(Bytes::new())
+                .unwrap()
+        };
+
+        let client = ReplayClient::new(
+            aws_lc_rs::default_provider(),
+            &TlsVerificationPolicy::new().with_extra_ca(ca),
+            &ClientIdentities::new(),
+            None,
+        )
+        .unwrap();
+        let res = client.send(req()).await.unwrap();
+
+        assert_eq!(res.response.body(), "secure");
+        let tls = res.response.extensions().get::<UpstreamTls>().unwrap();
+        assert_eq!(tls.status, CertificateStatus::Valid);
+        let session = res.upstream_session.unwrap();
+        assert_eq!(session.version, Some(TlsVersion::Tls13));
+        assert!(session.cipher_suite.unwrap().starts_with("TLS13_"));
+        assert_eq!(session.certificates.len(), 1);
+
+        // 신뢰하지 않는 CA가 발급한 인증서는 예외 호스트가 아니면 연결하지 않음
+        let client = ReplayClient::new(
+            aws_lc_rs::default_provider(),
+            &TlsVerificationPolicy::new(),
+            &ClientIdentities::new(),
+            None,
+        )
+        .unwrap();
+        let ReplayError::Request(error) = client.send(req()).await.unwrap_err() else {
+            panic!("expected request error");
+        };
+        assert_eq!(
+            crate::upstream_tls::verification_error(&error),
+            Some(CertificateStatus::UnknownIssuer)
+        );
+    }
+
+    #[cfg(feature = "rcgen-ca")]
+    #[tokio::test]
+    async fn send_uses_client_identity() {
+        use crate::client_identity::ClientIdentity;
+        use rcgen::{BasicConstraints, CertificateParams, DnType, IsCa, KeyPair};
+
+        let client_ca_key = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        params
+            .distinguished_name
+            .push(DnType::CommonName, "Replay Test Client CA");
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let client_ca = params.self_signed(&client_ca_key).unwrap();
+
+        let key = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        params
+            .distinguished_name
+            .push(DnType::CommonName, "Replay Test Client");
+        let certificate = params.signed_by(&key, &client_ca, &client_ca_key).unwrap();
+        let identity = ClientIdentity::from_pem(
+            "internal",
+            certificate.pem().as_bytes(),
+            key.serialize_pem().as_bytes(),
+            &aws_lc_rs::default_provider(),
+        )
+        .unwrap();
+
+        let (addr, ca) = start_tls_server(Some(client_ca.der().clone())).await;
+        let policy = TlsVerificationPolicy::new().with_extra_ca(ca);
+        let req = || {
+            Request::builder()
+                .uri(format!("https://localhost:{}/", addr.port()))
+                .body(Bytes::new())
+                .unwrap()
+        };
+        let client = |identities: &ClientIdentities| {
+            ReplayClient::new(aws_lc_rs::default_provider(), &policy, identities, None).unwrap()
+        };
+
+        // 호스트에 맞는 인증서를 보내고 응답에 인증서 이름 기록
+        let identities = ClientIdentities::new().with_identity("localhost", identity.clone());
+        let res = client(&identities).send(req()).await.unwrap();
+        assert_eq!(res.response.body(), "secure");
+        assert_eq!(res.client_identity.as_deref(), Some("internal"));
+
+        // 다른 호스트용 인증서는 보내지 않으므로 서버가 연결을 거부
+        let identities = ClientIdentities::new().with_identity("*.example.com", identity);
+        assert!(client(&identities).send(req()).await.is_err());
+        assert!(client(&ClientIdentities::new()).send(req()).await.is_err());
+    }
+
+    #[cfg(all(feature = "rcgen-ca", feature = "legacy-tls-client"))]
+    #[tokio::test]
+    async fn legacy_send_applies_policy() {
+        use crate::{
+            client_identity::ClientIdentity,
+            upstream_tls::{CertificateStatus, UpstreamTls},
+        };
+        use rcgen::{CertificateParams, DnType, KeyPair};
+
+        let (addr, ca) = start_tls_server(None).await;
+        let req = || {
+            Request::builder()
+                .uri(format!("https://localhost:{}/", addr.port()))
+                .body(Bytes::new())
+                .unwrap()
+        };
+        let client = |policy: &TlsVerificationPolicy, identities: &ClientIdentities| {
+            ReplayClient::legacy(aws_lc_rs::default_provider(), policy, identities, None).unwrap()
+        };
+
+        // 추가 CA로 검증한 연결은 유효한 인증서로 기록
+        let res = client(
+            &TlsVerificationPolicy::new().with_extra_ca(ca.clone()),
+            &ClientIdentities::new(),
+        )
+        .send(req())
+        .await
+        .unwrap();
+        assert_eq!(res.response.body(), "secure");
+        assert_eq!(
+            res.upstream_tls,
+            Some(UpstreamTls {
+                status: CertificateStatus::Valid,
+                insecure: false,
+            })
+        );
+
+        // 신뢰하지 않는 CA가 발급한 인증서는 예외 호스트에만 연결하고 검증 결과 기록
+        let strict = client(&TlsVerificationPolicy::new(), &ClientIdentities::new());
+        assert!(strict.send(req()).await.is_err());
+        let insecure = client(
+            &TlsVerificationPolicy::new().with_insecure_host("localhost"),
+            &ClientIdentities::new(),
+        );
+        let res = insecure.send(req()).await.unwrap();
+        assert_eq!(
+            res.upstream_tls,
+            Some(UpstreamTls {
+                status: CertificateStatus::UnknownIssuer,
+                insecure: true,
+            })
+        );
+
+        // 호스트에 맞는 클라이언트 인증서 사용
+        let key = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        params
+            .distinguished_name
+            .push(DnType::CommonName, "Replay Test Client");
+        let certificate = params.self_signed(&key).unwrap();
+        let identity = ClientIdentity::from_pem(
+            "internal",
+            certificate.pem().as_bytes(),
+            key.serialize_pem().as_bytes(),
+            &aws_lc_rs::default_provider(),
+        )
+        .unwrap();
+        let (addr, ca) = start_tls_server(Some(certificate.der().clone())).await;
+        let req = Request::builder()
+            .uri(format!("https://localhost:{}/", addr.port()))
+            .body(Bytes::new())
+            .unwrap();
+        let res = client(
+            &TlsVerificationPolicy::new().with_extra_ca(ca),
+            &ClientIdentities::new().with_identity("localhost", identity),
+        )
+        .send(req)
+        .await
+        .unwrap();
+        assert_eq!(res.response.body(), "secure");
+        assert_eq!(res.client_identity.as_deref(), Some("internal"));
+    }
+
+    /// TLS 1.0과 `ECDHE-RSA-AES128-SHA`만 허용하는 HTTPS 서버
+    ///
+    /// TLS 1.0의 키 교환 서명(MD5+SHA-1)은 OpenSSL 보안 수준 0에서만 허용됩니다.
+    #[cfg(all(feature = "rcgen-ca", feature = "legacy-tls-client"))]
+    async fn start_tls10_server() -> (
+        std::net::SocketAddr,
+        tokio_rustls::rustls::pki_types::CertificateDer<'static>,
+    ) {
+        use openssl::{
+            pkey::PKey,
+            rsa::Rsa,
+            ssl::{Ssl, SslContext, SslMethod, SslVersion},
+            x509::X509,
+        };
+        use rcgen::{CertificateParams, DnType, KeyPair};
+
+        // RSA 서명 암호 스위트에는 RSA 서버 키가 필요
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let key_pem = String::from_utf8(key.private_key_to_pem_pkcs8().unwrap()).unwrap();
+        let key_pair = KeyPair::from_pem(&key_pem).unwrap();
+        let mut params = CertificateParams::new(vec!["localhost".to_string()]).unwrap();
+        params
+            .distinguished_name
+            .push(DnType::CommonName, "Legacy Test Server");
+        let certificate = params.self_signed(&key_pair).unwrap();
+
+        let mut context = SslContext::builder(SslMethod::tls_server()).unwrap();
+        context.set_security_level(0);
+        context
+            .set_min_proto_version(Some(SslVersion::TLS1))
+            .unwrap();
+        context
+            .set_max_proto_version(Some(SslVersion::TLS1))
+            .unwrap();
+        context.set_cipher_list("ECDHE-RSA-AES128-SHA").unwrap();
+        context
+            .set_certificate(&X509::from_der(certificate.der()).unwrap())
+            .unwrap();
+        context.set_private_key(&key).unwrap();
+        let context = context.build();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let ssl = Ssl::new(&context).unwrap();
+                tokio::spawn(async move {
+                    let mut stream = tokio_openssl::SslStream::new(ssl, stream).unwrap();
+                    if std::pin::Pin::new(&mut stream).accept().await.is_err() {
+                        return;
+                    }
+                    let service = service_fn(|_: Request<hyper::body::Incoming>| async {
+                        Ok::<_, Infallible>(Response::new(http_body_util::Full::new(
+                            Bytes::from_static(b"legacy"),
+                        )))
+                    });
+                    let _ = http1::Builder::new()
+                        .serve_connection(TokioIo::new(stream), service)
+                        .await;
+                });
+            }
+        });
+
+        (addr, certificate.der().clone())
+    }
+
+    #[cfg(all(feature = "rcgen-ca", feature = "legacy-tls-client"))]
+    #[tokio::test]
+    async fn legacy_connects_to_tls10_server() {
+        use crate::{
+            TlsVersion,
+            upstream_tls::{CertificateStatus, UpstreamTls, is_handshake_failure},
+        };
+
+        let (addr, certificate) = start_tls10_server().await;
+        let policy = TlsVerificationPolicy::new().with_extra_ca(certificate);
+        let req = || {
+            Request::builder()
+                .uri(format!("https://localhost:{}/", addr.port()))
+                .body(Bytes::new())
+                .unwrap()
+        };
+
+        // rustls는 TLS 1.0을 지원하지 않으므로 핸드셰이크 실패
+        let client = ReplayClient::new(
+            aws_lc_rs::default_provider(),
+            &policy,
+            &ClientIdentities::new(),
+            None,
+        )
+        .unwrap();
+        let ReplayError::Request(error) = client.send(req()).await.unwrap_err() else {
+            panic!("expected request error");
+        };
+        assert!(is_handshake_failure(&error));
+
+        let client = ReplayClient::legacy(
+            aws_lc_rs::default_provider(),
+            &policy,
+            &ClientIdentities::new(),
+            None,
+        )
+        .unwrap();
+        let res = client.send(req()).await.unwrap();
+
+        assert_eq!(res.response.body(), "legacy");
+        assert_eq!(
+            res.upstream_tls,
+            Some(UpstreamTls {
+                status: CertificateStatus::Valid,
+                insecure: false,
+            })
+        );
+        let session = res.upstream_session.unwrap();
+        assert_eq!(session.version, Some(TlsVersion::Tls10));
+        assert_eq!(session.cipher_suite.as_deref(), Some("ECDHE-RSA-AES128-SHA"));
+        assert_eq!(session.certificates.len(), 1);
+    }
+}
@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+
+/// JSON Patch (RFC 6902) 연산
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+    Move { from: String, path: String },
+    Copy { from: String, path: String },
+    Test { path: String, value: Value },
+}
+
+/// JSON Patch 적용 오류
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PatchError {
+    path: String,
+    reason: &'static str,
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "JSON patch failed at '{}': {}", self.path, self.reason)
+    }
+}
+
+impl std::error::Error for PatchError {}
+
+fn patch_error(path: &str, reason: &'static str) -> PatchError {
+    PatchError {
+        path: path.to_string(),
+        reason,
+    }
+}
+
+/// JSON 문서에 패치를 적용합니다.
+///
+/// 하나라도 실패하면 문서는 변경되지 않습니다.
+pub fn apply_patch(document: &mut Value, operations: &[PatchOperation]) -> Result<(), PatchError> {
+    let mut patched = document.clone();
+
+    for operation in operations {
+        match operation {
+            PatchOperation::Add { path, value } => add(&mut patched, path, value.clone())?,
+            PatchOperation::Remove { path } => {
+                remove(&mut patched, path)?;
+            }
+            PatchOperation::Replace { path, value } => {
+                let target = patched
+                    .pointer_mut(path)
+                    .ok_or_else(|| patch_error(path, "path does not exist"))?;
+                *target = value.clone();
+            }
+            PatchOperation::Move { from, path } => {
+                let value = remove(&mut patched, from)?;
+                add(&mut patched, path, value)?;
+            }
+            PatchOperation::Copy { from, path } => {
+                let value = patched
+                    .pointer(from)
+                    .cloned()
+                    .ok_or_else(|| patch_error(from, "path does not exist"))?;
+                add(&mut patched, path, value)?;
+            }
+            PatchOperation::Test { path, value } => {
+                if patched.pointer(path) != Some(value) {
+                    return Err(patch_error(path, "test failed"));
+                }
+            }
+        }
+    }
+
+    *document = patched;
+    Ok(())
+}
+
+/// JSON Pointer를 부모 경로와 마지막 토큰으로 분리
+fn split_pointer(path: &str) -> Result<(&str, String), PatchError> {
+    let index = path
+        .rfind('/')
+        .ok_or_else(|| patch_error(path, "invalid JSON pointer"))?;
+    let token = path[index + 1..].replace("~1", "/").replace("~0", "~");
+    Ok((&path[..index], token))
+}
+
+fn add(document: &mut Value, path: &str, value: Value) -> Result<(), PatchError> {
+    if path.is_empty() {
+        *document = value;
+        return Ok(());
+    }
+
+    let (parent, token) = split_pointer(path)?;
+    match document.pointer_mut(parent) {
+        Some(Value::Object(map)) => {
+            map.insert(token, value);
+            Ok(())
+        }
+        Some(Value::Array(items)) => {
+            if token == "-" {
+                items.push(value);
+                return Ok(());
+            }
+            let index: usize = token
+                .parse()
+                .map_err(|_| patch_error(path, "invalid array index"))?;
+            if index > items.len() {
+                return Err(patch_error(path, "array index out of bounds"));
+            }
+            items.insert(index, value);
+            Ok(())
+        }
+        Some(_) => Err(patch_error(path, "parent is not a container")),
+        None => Err(patch_error(path, "parent does not exist")),
+    }
+}
+
+fn remove(document: &mut Value, path: &str) -> Result<Value, PatchError> {
+    let (parent, token) = split_pointer(path)?;
+    match document.pointer_mut(parent) {
+        Some(Value::Object(map)) => map
+            .remove(&token)
+            .ok_or_else(|| patch_error(path, "path does not exist")),
+        Some(Value::Array(items)) => {
+            let index: usize = token
+                .parse()
+                .map_err(|_| patch_error(path, "invalid array index"))?;
+            if index >= items.len() {
+                return Err(patch_error(path, "array index out of bounds"));
+            }
+            Ok(items.remove(index))
+        }
+        _ => Err(patch_error(path, "path does not exist")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn applies_operations_in_order() {
+        let mut document = json!({"a": 1, "list": [1, 2]});
+        let operations: Vec<PatchOperation> = serde_json::from_value(json!([
+            {"op": "replace", "path": "/a", "value": 2},
+            {"op": "add", "path": "/list/-", "value": 3},
+            {"op": "copy", "from": "/a", "path": "/b"},
+            {"op": "move", "from": "/list/0", "path": "/first"},
+            {"op": "remove", "path": "/list/0"},
+            {"op": "test", "path": "/b", "value": 2}
+        ]))
+        .unwrap();
+
+        apply_patch(&mut document, &operations).unwrap();
+
+        assert_eq!(document, json!({"a": 2, "b": 2, "first": 1, "list": [3]}));
+    }
+
+    #[test]
+    fn failed_patch_leaves_document_untouched() {
+        let mut document = json!({"a": 1});
+        let operations = vec![
+            PatchOperation::Replace {
+                path: "/a".to_string(),
+                value: json!(5),
+            },
+            PatchOperation::Remove {
+                path: "/missing".to_string(),
+            },
+        ];
+
+        assert!(apply_patch(&mut document, &operations).is_err());
+        assert_eq!(document, json!({"a": 1}));
+    }
+}
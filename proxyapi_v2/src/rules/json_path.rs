@@ -0,0 +1,170 @@
+use serde_json::Value;
+use std::fmt;
+
+/// 간단한 JSONPath 구현
+///
+/// 지원하는 문법: `$`, `.name`, `['name']`, `[0]`, `[-1]`, `[*]`, `.*`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JsonPath {
+    segments: Vec<Segment>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Segment {
+    Key(String),
+    Index(i64),
+    Wildcard,
+}
+
+/// JSONPath 파싱 오류
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JsonPathError {
+    path: String,
+    position: usize,
+}
+
+impl fmt::Display for JsonPathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid JSONPath '{}' at position {}",
+            self.path, self.position
+        )
+    }
+}
+
+impl std::error::Error for JsonPathError {}
+
+impl JsonPath {
+    /// JSONPath 문자열 파싱
+    pub fn parse(path: &str) -> Result<Self, JsonPathError> {
+        let error = |position| JsonPathError {
+            path: path.to_string(),
+            position,
+        };
+
+        let chars: Vec<char> = path.trim().chars().collect();
+        let mut pos = 0;
+
+        if chars.first() == Some(&'$') {
+            pos += 1;
+        }
+
+        let mut segments = Vec::new();
+
+        while pos < chars.len() {
+            match chars[pos] {
+                '.' => {
+                    pos += 1;
+                    let start = pos;
+                    while pos < chars.len() && chars[pos] != '.' && chars[pos] != '[' {
+                        pos += 1;
+                    }
+                    let key: String = chars[start..pos].iter().collect();
+                    match key.as_str() {
+                        "" => return Err(error(start)),
+                        "*" => segments.push(Segment::Wildcard),
+                        _ => segments.push(Segment::Key(key)),
+                    }
+                }
+                '[' => {
+                    pos += 1;
+                    let start = pos;
+                    let end = chars[start..]
+                        .iter()
+                        .position(|c| *c == ']')
+                        .map(|offset| start + offset)
+                        .ok_or_else(|| error(start))?;
+                    let inner: String = chars[start..end].iter().collect();
+                    let inner = inner.trim();
+
+                    let segment = if inner == "*" {
+                        Segment::Wildcard
+                    } else if let Some(quoted) = inner
+                        .strip_prefix('\'')
+                        .and_then(|s| s.strip_suffix('\''))
+                        .or_else(|| inner.strip_prefix('"').and_then(|s| s.strip_suffix('"')))
+                    {
+                        Segment::Key(quoted.to_string())
+                    } else {
+                        Segment::Index(inner.parse().map_err(|_| error(start))?)
+                    };
+
+                    segments.push(segment);
+                    pos = end + 1;
+                }
+                _ if pos == 0 => {
+                    // `$` 없이 시작하는 경우 (`a.b`) 첫 키로 처리
+                    let start = pos;
+                    while pos < chars.len() && chars[pos] != '.' && chars[pos] != '[' {
+                        pos += 1;
+                    }
+                    segments.push(Segment::Key(chars[start..pos].iter().collect()));
+                }
+                _ => return Err(error(pos)),
+            }
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// JSON 값에서 경로에 해당하는 모든 값 선택
+    pub fn select<'a>(&self, value: &'a Value) -> Vec<&'a Value> {
+        let mut current = vec![value];
+
+        for segment in &self.segments {
+            let mut next = Vec::new();
+            for value in current {
+                match (segment, value) {
+                    (Segment::Key(key), Value::Object(map)) => next.extend(map.get(key)),
+                    (Segment::Index(index), Value::Array(items)) => {
+                        let index = if *index < 0 {
+                            items.len() as i64 + index
+                        } else {
+                            *index
+                        };
+                        if index >= 0 {
+                            next.extend(items.get(index as usize));
+                        }
+                    }
+                    (Segment::Wildcard, Value::Object(map)) => next.extend(map.values()),
+                    (Segment::Wildcard, Value::Array(items)) => next.extend(items.iter()),
+                    _ => {}
+                }
+            }
+            current = next;
+        }
+
+        current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn selects_nested_values() {
+        let value = json!({"user": {"roles": ["admin", "dev"], "name": "cheolsu"}});
+
+        let path = JsonPath::parse("$.user.name").unwrap();
+        assert_eq!(path.select(&value), vec![&json!("cheolsu")]);
+
+        let path = JsonPath::parse("$.user.roles[-1]").unwrap();
+        assert_eq!(path.select(&value), vec![&json!("dev")]);
+
+        let path = JsonPath::parse("$['user'].roles[*]").unwrap();
+        assert_eq!(path.select(&value).len(), 2);
+
+        let path = JsonPath::parse("user.missing").unwrap();
+        assert!(path.select(&value).is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_paths() {
+        assert!(JsonPath::parse("$.").is_err());
+        assert!(JsonPath::parse("$.a[").is_err());
+        assert!(JsonPath::parse("$.a[x]").is_err());
+    }
+}
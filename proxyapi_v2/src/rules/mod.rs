@@ -0,0 +1,987 @@
+//! 요청/응답 재작성 규칙 엔진
+//!
+//! 규칙은 매처(메서드, 호스트 glob, 경로 정규식, 쿼리, 헤더, JSONPath 본문 조건)와
+//! 액션 목록(헤더 설정/삭제, 상태 코드 변경, 본문 교체, JSON Patch, 리다이렉트, 지연)으로 구성됩니다.
+//! [`RuleEngine`]은 복제해도 같은 규칙 목록을 공유하므로 실행 중에 [`RuleEngine::set_rules`]로
+//! 규칙을 교체(핫 리로드)할 수 있습니다. WebSocket 메시지 규칙은 [`WebSocketRuleEngine`]을 사용합니다.
+
+mod json_patch;
+mod json_path;
+mod websocket;
+
+pub use json_patch::{PatchError, PatchOperation, apply_patch};
+pub use json_path::{JsonPath, JsonPathError};
+pub use websocket::{
+    CompiledWebSocketRule, WebSocketAction, WebSocketMatcher, WebSocketRule, WebSocketRuleEngine,
+};
+
+use http::{
+    HeaderMap, HeaderName, HeaderValue, Method, Request, Response, StatusCode, Uri, header,
+    request::Parts, uri::Authority,
+};
+use hyper::body::Bytes;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+use tracing::{debug, warn};
+
+/// 재작성 규칙
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Rule {
+    /// 규칙 식별자
+    #[serde(default)]
+    pub id: String,
+    /// 표시용 이름
+    #[serde(default)]
+    pub name: String,
+    /// 비활성화된 규칙은 매칭되지 않음
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    /// 요청 매칭 조건
+    #[serde(rename = "match", default)]
+    pub matcher: RuleMatcher,
+    /// 매칭 시 순서대로 적용할 액션
+    #[serde(default)]
+    pub actions: Vec<RuleAction>,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+/// 요청 매칭 조건 (지정된 조건을 모두 만족해야 매칭)
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RuleMatcher {
+    /// HTTP 메서드 목록 (비어 있으면 모든 메서드)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub methods: Vec<String>,
+    /// 호스트 glob 패턴 (예: `*.example.com`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    /// 경로 정규식
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// 전체 URL에 포함되어야 하는 문자열
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url_contains: Option<String>,
+    /// 쿼리 파라미터 조건
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub query: Vec<ValueMatcher>,
+    /// 헤더 조건
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<ValueMatcher>,
+    /// JSON 본문 조건
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub body: Vec<BodyMatcher>,
+}
+
+/// 이름/값 조건 (`value`가 없으면 존재 여부만 확인, 있으면 정규식 매칭)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ValueMatcher {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+/// JSONPath 본문 조건 (`equals`가 없으면 존재 여부만 확인)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BodyMatcher {
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub equals: Option<Value>,
+}
+
+/// 액션 적용 대상
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleTarget {
+    #[default]
+    Request,
+    Response,
+}
+
+/// 규칙이 매칭되었을 때 적용할 액션
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RuleAction {
+    /// 헤더 설정 (기존 값 덮어쓰기)
+    SetHeader {
+        #[serde(default)]
+        target: RuleTarget,
+        name: String,
+        value: String,
+    },
+    /// 헤더 삭제
+    RemoveHeader {
+        #[serde(default)]
+        target: RuleTarget,
+        name: String,
+    },
+    /// 응답 상태 코드 변경
+    SetStatus { status: u16 },
+    /// 본문 교체
+    ReplaceBody {
+        #[serde(default)]
+        target: RuleTarget,
+        body: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        content_type: Option<String>,
+    },
+    /// JSON 본문에 JSON Patch(RFC 6902) 적용
+    JsonPatch {
+        #[serde(default)]
+        target: RuleTarget,
+        patch: Vec<PatchOperation>,
+    },
+    /// 다른 호스트로 요청 전달
+    Redirect {
+        host: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        scheme: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        port: Option<u16>,
+    },
+    /// 지연 주입
+    Delay {
+        #[serde(default)]
+        target: RuleTarget,
+        millis: u64,
+    },
+    /// 응답 전체 교체 (상태 코드, 헤더, 본문)
+    ReplaceResponse {
+        #[serde(default = "default_status")]
+        status: u16,
+        #[serde(default)]
+        headers: BTreeMap<String, String>,
+        #[serde(default)]
+        body: String,
+    },
+}
+
+fn default_status() -> u16 {
+    200
+}
+
+impl RuleAction {
+    /// 요청 본문을 수정하는 액션인지 확인
+    fn modifies_request_body(&self) -> bool {
+        matches!(
+            self,
+            RuleAction::ReplaceBody {
+                target: RuleTarget::Request,
+                ..
+            } | RuleAction::JsonPatch {
+                target: RuleTarget::Request,
+                ..
+            }
+        )
+    }
+
+    /// 응답 본문을 수정하는 액션인지 확인
+    fn modifies_response_body(&self) -> bool {
+        matches!(
+            self,
+            RuleAction::ReplaceBody {
+                target: RuleTarget::Response,
+                ..
+            } | RuleAction::JsonPatch {
+                target: RuleTarget::Response,
+                ..
+            } | RuleAction::ReplaceResponse { .. }
+        )
+    }
+}
+
+/// 규칙 컴파일 오류
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RuleError {
+    pub rule_id: String,
+    pub message: String,
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid rule '{}': {}", self.rule_id, self.message)
+    }
+}
+
+impl std::error::Error for RuleError {}
+
+/// 정규식 등을 미리 컴파일한 규칙
+#[derive(Debug)]
+pub struct CompiledRule {
+    rule: Rule,
+    methods: Vec<Method>,
+    host: Option<String>,
+    path: Option<Regex>,
+    query: Vec<(String, Option<Regex>)>,
+    headers: Vec<(HeaderName, Option<Regex>)>,
+    body: Vec<(JsonPath, Option<Value>)>,
+}
+
+impl CompiledRule {
+    /// 규칙을 검증하고 컴파일
+    pub fn compile(rule: Rule) -> Result<Self, RuleError> {
+        let error = |message: String| RuleError {
+            rule_id: rule.id.clone(),
+            message,
+        };
+
+        let matcher = &rule.matcher;
+
+        let methods = matcher
+            .methods
+            .iter()
+            .map(|method| {
+                Method::from_bytes(method.to_uppercase().as_bytes())
+                    .map_err(|_| error(format!("invalid method '{}'", method)))
+            })
+            .collect::<Result<_, _>>()?;
+
+        let path = matcher
+            .path
+            .as_deref()
+            .map(|path| Regex::new(path).map_err(|e| error(e.to_string())))
+            .transpose()?;
+
+        let query = matcher
+            .query
+            .iter()
+            .map(|m| Ok((m.name.clone(), compile_value_regex(m, &error)?)))
+            .collect::<Result<_, RuleError>>()?;
+
+        let headers = matcher
+            .headers
+            .iter()
+            .map(|m| {
+                let name = HeaderName::from_bytes(m.name.as_bytes())
+                    .map_err(|_| error(format!("invalid header name '{}'", m.name)))?;
+                Ok((name, compile_value_regex(m, &error)?))
+            })
+            .collect::<Result<_, RuleError>>()?;
+
+        let body = matcher
+            .body
+            .iter()
+            .map(|m| {
+                let path = JsonPath::parse(&m.path).map_err(|e| error(e.to_string()))?;
+                Ok((path, m.equals.clone()))
+            })
+            .collect::<Result<_, RuleError>>()?;
+
+        for action in &rule.actions {
+            validate_action(action).map_err(error)?;
+        }
+
+        Ok(Self {
+            methods,
+            host: matcher.host.as_ref().map(|host| host.to_lowercase()),
+            path,
+            query,
+            headers,
+            body,
+            rule,
+        })
+    }
+
+    /// 원본 규칙
+    pub fn rule(&self) -> &Rule {
+        &self.rule
+    }
+
+    /// 요청 본문 조건이 있는 활성 규칙인지 확인 (없으면 본문 없이 매칭 가능)
+    pub fn matches_body(&self) -> bool {
+        self.rule.enabled && !self.body.is_empty()
+    }
+
+    /// 요청이 규칙과 매칭되는지 확인
+    pub fn matches(&self, parts: &Parts, body: &[u8]) -> bool {
+        if !self.rule.enabled {
+            return false;
+        }
+
+        if !self.methods.is_empty() && !self.methods.contains(&parts.method) {
+            return false;
+        }
+
+        if let Some(pattern) = &self.host {
+            let host = request_host(parts).unwrap_or_default().to_lowercase();
+            if !glob_match(pattern, &host) {
+                return false;
+            }
+        }
+
+        if let Some(path) = &self.path {
+            if !path.is_match(parts.uri.path()) {
+                return false;
+            }
+        }
+
+        if let Some(needle) = &self.rule.matcher.url_contains {
+            if !parts.uri.to_string().contains(needle.as_str()) {
+                return false;
+            }
+        }
+
+        let query_matches = self.query.iter().all(|(name, value)| {
+            query_pairs(&parts.uri).any(|(key, actual)| {
+                key == name && value.as_ref().is_none_or(|v| v.is_match(actual))
+            })
+        });
+        if !query_matches {
+            return false;
+        }
+
+        let headers_match = self.headers.iter().all(|(name, value)| {
+            parts
+                .headers
+                .get_all(name)
+                .iter()
+                .any(|actual| match value {
+                    Some(value) => actual.to_str().is_ok_and(|actual| value.is_match(actual)),
+                    None => true,
+                })
+        });
+        if !headers_match {
+            return false;
+        }
+
+        if !self.body.is_empty() {
+            let Ok(json) = serde_json::from_slice::<Value>(body) else {
+                return false;
+            };
+
+            let body_matches = self.body.iter().all(|(path, expected)| {
+                let selected = path.select(&json);
+                match expected {
+                    Some(expected) => selected.contains(&expected),
+                    None => !selected.is_empty(),
+                }
+            });
+            if !body_matches {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+fn compile_value_regex(
+    matcher: &ValueMatcher,
+    error: &impl Fn(String) -> RuleError,
+) -> Result<Option<Regex>, RuleError> {
+    matcher
+        .value
+        .as_deref()
+        .map(|value| Regex::new(value).map_err(|e| error(e.to_string())))
+        .transpose()
+}
+
+fn validate_action(action: &RuleAction) -> Result<(), String> {
+    match action {
+        RuleAction::SetHeader { name, value, .. } => {
+            HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| format!("invalid header name '{}'", name))?;
+            HeaderValue::from_str(value)
+                .map_err(|_| format!("invalid header value for '{}'", name))?;
+        }
+        RuleAction::RemoveHeader { name, .. } => {
+            HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| format!("invalid header name '{}'", name))?;
+        }
+        RuleAction::SetStatus { status } => {
+            StatusCode::from_u16(*status).map_err(|_| format!("invalid status {}", status))?;
+        }
+        RuleAction::ReplaceBody {
+            content_type: Some(content_type),
+            ..
+        } => {
+            HeaderValue::from_str(content_type)
+                .map_err(|_| format!("invalid content type '{}'", content_type))?;
+        }
+        RuleAction::Redirect { host, scheme, port } => {
+            let authority = match port {
+                Some(port) => format!("{}:{}", host, port),
+                None => host.clone(),
+            };
+            authority
+                .parse::<Authority>()
+                .map_err(|_| format!("invalid redirect host '{}'", authority))?;
+            if let Some(scheme) = scheme {
+                if scheme != "http" && scheme != "https" {
+                    return Err(format!("invalid redirect scheme '{}'", scheme));
+                }
+            }
+        }
+        RuleAction::ReplaceResponse {
+            status, headers, ..
+        } => {
+            StatusCode::from_u16(*status).map_err(|_| format!("invalid status {}", status))?;
+            for (name, value) in headers {
+                HeaderName::from_bytes(name.as_bytes())
+                    .map_err(|_| format!("invalid header name '{}'", name))?;
+                HeaderValue::from_str(value)
+                    .map_err(|_| format!("invalid header value for '{}'", name))?;
+            }
+        }
+        RuleAction::ReplaceBody { .. }
+        | RuleAction::JsonPatch { .. }
+        | RuleAction::Delay { .. } => {}
+    }
+
+    Ok(())
+}
+
+/// 요청의 호스트 (절대 URI가 아니면 Host 헤더 사용)
+fn request_host(parts: &Parts) -> Option<&str> {
+    parts.uri.host().or_else(|| {
+        parts
+            .headers
+            .get(header::HOST)
+            .and_then(|host| host.to_str().ok())
+            .map(|host| host.split(':').next().unwrap_or(host))
+    })
+}
+
+fn query_pairs(uri: &Uri) -> impl Iterator<Item = (&str, &str)> {
+    uri.query()
+        .unwrap_or_default()
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| pair.split_once('=').unwrap_or((pair, "")))
+}
+
+/// `*`(임의 문자열)와 `?`(임의 한 글자)를 지원하는 glob 매칭
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = backtrack {
+            p = star_p + 1;
+            t = star_t + 1;
+            backtrack = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// 규칙 목록을 보관하고 요청과 매칭하는 엔진
+///
+/// 복제본끼리 규칙 목록을 공유하므로 핸들러에 복제해서 넘긴 뒤에도 규칙을 교체할 수 있습니다.
+#[derive(Clone, Debug, Default)]
+pub struct RuleEngine {
+    rules: Arc<RwLock<Vec<Arc<CompiledRule>>>>,
+}
+
+impl RuleEngine {
+    /// 빈 규칙 엔진 생성
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 규칙 목록 교체
+    ///
+    /// 유효하지 않은 규칙은 건너뛰고 해당 오류 목록을 반환합니다.
+    pub fn set_rules(&self, rules: Vec<Rule>) -> Vec<RuleError> {
+        let mut compiled = Vec::with_capacity(rules.len());
+        let mut errors = Vec::new();
+
+        for rule in rules {
+            match CompiledRule::compile(rule) {
+                Ok(rule) => compiled.push(Arc::new(rule)),
+                Err(e) => {
+                    warn!("Skipping rule: {}", e);
+                    errors.push(e);
+                }
+            }
+        }
+
+        debug!("Loaded {} rules", compiled.len());
+        *self.rules.write().expect("rule lock poisoned") = compiled;
+        errors
+    }
+
+    /// 현재 규칙 수
+    pub fn len(&self) -> usize {
+        self.rules.read().expect("rule lock poisoned").len()
+    }
+
+    /// 규칙이 없는지 확인
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 요청 본문 조건이 있는 규칙이 있는지 확인 (없으면 요청 본문을 모으지 않고 매칭 가능)
+    pub fn matches_request_body(&self) -> bool {
+        self.rules
+            .read()
+            .expect("rule lock poisoned")
+            .iter()
+            .any(|rule| rule.matches_body())
+    }
+
+    /// 요청과 매칭되는 규칙 목록 반환 (규칙 순서 유지)
+    pub fn match_request(&self, parts: &Parts, body: &[u8]) -> MatchedRules {
+        let rules = self.rules.read().expect("rule lock poisoned");
+        MatchedRules(
+            rules
+                .iter()
+                .filter(|rule| rule.matches(parts, body))
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
+/// 하나의 요청에 매칭된 규칙 목록
+///
+/// 요청 단계에서 매칭한 결과를 응답 단계까지 보관해서 응답 액션을 적용합니다.
+#[derive(Clone, Debug, Default)]
+pub struct MatchedRules(Vec<Arc<CompiledRule>>);
+
+impl MatchedRules {
+    /// 매칭된 규칙이 없는지 확인
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// 매칭된 규칙 ID 목록
+    pub fn rule_ids(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(|rule| rule.rule.id.as_str())
+    }
+
+    /// 업스트림 요청 없이 응답 전체를 교체하는 규칙이 있는지 확인
+    pub fn replaces_response(&self) -> bool {
+        self.actions()
+            .any(|action| matches!(action, RuleAction::ReplaceResponse { .. }))
+    }
+
+    /// 요청 본문을 수정하는 규칙이 있는지 확인 (없으면 요청 본문을 모으지 않고 그대로 전달 가능)
+    pub fn modifies_request_body(&self) -> bool {
+        self.actions().any(RuleAction::modifies_request_body)
+    }
+
+    /// 응답 본문을 수정하는 규칙이 있는지 확인 (없으면 응답 본문을 모으지 않고 그대로 전달 가능)
+    pub fn modifies_response_body(&self) -> bool {
+        self.actions().any(RuleAction::modifies_response_body)
+    }
+
+    fn actions(&self) -> impl Iterator<Item = &RuleAction> {
+        self.0.iter().flat_map(|rule| rule.rule.actions.iter())
+    }
+
+    /// 요청 대상 액션 적용 (지연 액션이 있으면 적용 후 대기)
+    pub async fn apply_request(&self, req: Request<Bytes>) -> Request<Bytes> {
+        let (mut parts, mut body) = req.into_parts();
+        let mut delay = Duration::ZERO;
+
+        for action in self.actions() {
+            match action {
+                RuleAction::SetHeader {
+                    target: RuleTarget::Request,
+                    name,
+                    value,
+                } => set_header(&mut parts.headers, name, value),
+                RuleAction::RemoveHeader {
+                    target: RuleTarget::Request,
+                    name,
+                } => remove_header(&mut parts.headers, name),
+                RuleAction::ReplaceBody {
+                    target: RuleTarget::Request,
+                    body: new_body,
+                    content_type,
+                } => replace_body(
+                    &mut parts.headers,
+                    &mut body,
+                    Bytes::from(new_body.clone()),
+                    content_type.as_deref(),
+                ),
+                RuleAction::JsonPatch {
+                    target: RuleTarget::Request,
+                    patch,
+                } => patch_body(&mut parts.headers, &mut body, patch),
+                RuleAction::Redirect { host, scheme, port } => {
+                    redirect(&mut parts, host, scheme.as_deref(), *port)
+                }
+                RuleAction::Delay {
+                    target: RuleTarget::Request,
+                    millis,
+                } => delay += Duration::from_millis(*millis),
+                _ => {}
+            }
+        }
+
+        // 응답 본문을 수정해야 하면 업스트림이 압축하지 않도록 요청
+        if self.modifies_response_body() {
+            parts.headers.remove(header::ACCEPT_ENCODING);
+        }
+
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+
+        Request::from_parts(parts, body)
+    }
+
+    /// 응답 대상 액션 적용 (지연 액션이 있으면 적용 후 대기)
+    pub async fn apply_response(&self, res: Response<Bytes>) -> Response<Bytes> {
+        let (mut parts, mut body) = res.into_parts();
+        let mut delay = Duration::ZERO;
+
+        for action in self.actions() {
+            match action {
+                RuleAction::SetHeader {
+                    target: RuleTarget::Response,
+                    name,
+                    value,
+                } => set_header(&mut parts.headers, name, value),
+                RuleAction::RemoveHeader {
+                    target: RuleTarget::Response,
+                    name,
+                } => remove_header(&mut parts.headers, name),
+                RuleAction::SetStatus { status } => {
+                    if let Ok(status) = StatusCode::from_u16(*status) {
+                        parts.status = status;
+                    }
+                }
+                RuleAction::ReplaceBody {
+                    target: RuleTarget::Response,
+                    body: new_body,
+                    content_type,
+                } => replace_body(
+                    &mut parts.headers,
+                    &mut body,
+                    Bytes::from(new_body.clone()),
+                    content_type.as_deref(),
+                ),
+                RuleAction::JsonPatch {
+                    target: RuleTarget::Response,
+                    patch,
+                } => patch_body(&mut parts.headers, &mut body, patch),
+                RuleAction::Delay {
+                    target: RuleTarget::Response,
+                    millis,
+                } => delay += Duration::from_millis(*millis),
+                RuleAction::ReplaceResponse {
+                    status,
+                    headers,
+                    body: new_body,
+                } => {
+                    parts.status = StatusCode::from_u16(*status).unwrap_or(StatusCode::OK);
+                    parts.headers = headers
+                        .iter()
+                        .filter_map(|(name, value)| {
+                            Some((
+                                HeaderName::from_bytes(name.as_bytes()).ok()?,
+                                HeaderValue::from_str(value).ok()?,
+                            ))
+                        })
+                        .collect();
+                    // content-length는 실제 본문 길이에 맞게 자동 설정됨
+                    parts.headers.remove(header::CONTENT_LENGTH);
+                    body = Bytes::from(new_body.clone());
+                }
+                _ => {}
+            }
+        }
+
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+
+        Response::from_parts(parts, body)
+    }
+}
+
+fn set_header(headers: &mut HeaderMap, name: &str, value: &str) {
+    if let (Ok(name), Ok(value)) = (
+        HeaderName::from_bytes(name.as_bytes()),
+        HeaderValue::from_str(value),
+    ) {
+        headers.insert(name, value);
+    }
+}
+
+fn remove_header(headers: &mut HeaderMap, name: &str) {
+    if let Ok(name) = HeaderName::from_bytes(name.as_bytes()) {
+        headers.remove(name);
+    }
+}
+
+/// 본문을 교체하고 본문 관련 헤더를 새 본문에 맞게 정리
+fn replace_body(
+    headers: &mut HeaderMap,
+    body: &mut Bytes,
+    new_body: Bytes,
+    content_type: Option<&str>,
+) {
+    headers.remove(header::CONTENT_ENCODING);
+    if headers.contains_key(header::CONTENT_LENGTH) {
+        headers.insert(header::CONTENT_LENGTH, HeaderValue::from(new_body.len()));
+    }
+    if let Some(content_type) = content_type.and_then(|v| HeaderValue::from_str(v).ok()) {
+        headers.insert(header::CONTENT_TYPE, content_type);
+    }
+    *body = new_body;
+}
+
+fn patch_body(headers: &mut HeaderMap, body: &mut Bytes, patch: &[PatchOperation]) {
+    if headers.contains_key(header::CONTENT_ENCODING) {
+        warn!("Skipping JSON patch on encoded body");
+        return;
+    }
+
+    let mut json = match serde_json::from_slice::<Value>(body) {
+        Ok(json) => json,
+        Err(e) => {
+            warn!("Skipping JSON patch on non-JSON body: {}", e);
+            return;
+        }
+    };
+
+    match apply_patch(&mut json, patch) {
+        Ok(()) => match serde_json::to_vec(&json) {
+            Ok(patched) => replace_body(headers, body, Bytes::from(patched), None),
+            Err(e) => warn!("Failed to serialize patched body: {}", e),
+        },
+        Err(e) => warn!("{}", e),
+    }
+}
+
+fn redirect(parts: &mut Parts, host: &str, scheme: Option<&str>, port: Option<u16>) {
+    let mut uri_parts = parts.uri.clone().into_parts();
+
+    let port = port.or_else(|| {
+        uri_parts
+            .authority
+            .as_ref()
+            .and_then(|authority| authority.port_u16())
+    });
+    let authority = match port {
+        Some(port) => format!("{}:{}", host, port),
+        None => host.to_string(),
+    };
+
+    let Ok(authority) = authority.parse::<Authority>() else {
+        return;
+    };
+    uri_parts.authority = Some(authority.clone());
+
+    if let Some(scheme) = scheme.and_then(|scheme| scheme.parse().ok()) {
+        uri_parts.scheme = Some(scheme);
+    } else if uri_parts.scheme.is_none() {
+        uri_parts.scheme = Some(http::uri::Scheme::HTTP);
+    }
+    if uri_parts.path_and_query.is_none() {
+        uri_parts.path_and_query = Some(http::uri::PathAndQuery::from_static("/"));
+    }
+
+    match Uri::from_parts(uri_parts) {
+        Ok(uri) => {
+            debug!("Redirecting {} to {}", parts.uri, uri);
+            parts.uri = uri;
+            if let Ok(host) = HeaderValue::from_str(authority.as_str()) {
+                parts.headers.insert(header::HOST, host);
+            }
+        }
+        Err(e) => warn!("Failed to build redirect URI: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rule(value: Value) -> Rule {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn request(method: &str, uri: &str, body: &str) -> Request<Bytes> {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("x-client", "mobile")
+            .header(header::ACCEPT_ENCODING, "gzip")
+            .body(Bytes::from(body.to_string()))
+            .unwrap()
+    }
+
+    fn matches(engine: &RuleEngine, req: &Request<Bytes>) -> MatchedRules {
+        let (parts, body) = req.clone().into_parts();
+        engine.match_request(&parts, &body)
+    }
+
+    #[test]
+    fn glob_matching() {
+        assert!(glob_match("*.example.com", "api.example.com"));
+        assert!(glob_match("api.example.???", "api.example.com"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("*.example.com", "example.org"));
+        assert!(!glob_match("api.*", "web.example.com"));
+    }
+
+    #[test]
+    fn matches_all_conditions() {
+        let engine = RuleEngine::new();
+        let errors = engine.set_rules(vec![rule(json!({
+            "id": "orders",
+            "match": {
+                "methods": ["post"],
+                "host": "*.example.com",
+                "path": "^/orders/\\d+$",
+                "query": [{"name": "debug"}],
+                "headers": [{"name": "X-Client", "value": "^mob"}],
+                "body": [{"path": "$.items[0].sku", "equals": "A-1"}]
+            },
+            "actions": []
+        }))]);
+        assert!(errors.is_empty());
+
+        let body = r#"{"items":[{"sku":"A-1"}]}"#;
+        let hit = request("POST", "https://api.example.com/orders/7?debug", body);
+        assert_eq!(
+            matches(&engine, &hit).rule_ids().collect::<Vec<_>>(),
+            ["orders"]
+        );
+
+        let wrong_method = request("GET", "https://api.example.com/orders/7?debug", body);
+        assert!(matches(&engine, &wrong_method).is_empty());
+
+        let wrong_body = request("POST", "https://api.example.com/orders/7?debug", "{}");
+        assert!(matches(&engine, &wrong_body).is_empty());
+
+        let missing_query = request("POST", "https://api.example.com/orders/7", body);
+        assert!(matches(&engine, &missing_query).is_empty());
+    }
+
+    #[test]
+    fn invalid_rules_are_skipped() {
+        let engine = RuleEngine::new();
+        let errors = engine.set_rules(vec![
+            rule(json!({"id": "bad", "match": {"path": "("}})),
+            rule(json!({"id": "good", "match": {"url_contains": "/api"}})),
+        ]);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].rule_id, "bad");
+        assert_eq!(engine.len(), 1);
+    }
+
+    #[test]
+    fn disabled_rules_do_not_match() {
+        let engine = RuleEngine::new();
+        engine.set_rules(vec![rule(json!({"id": "off", "enabled": false}))]);
+
+        assert!(matches(&engine, &request("GET", "http://example.com/", "")).is_empty());
+    }
+
+    #[test]
+    fn reports_request_body_usage() {
+        let engine = RuleEngine::new();
+        engine.set_rules(vec![rule(json!({
+            "id": "headers",
+            "actions": [{"type": "replace_body", "target": "response", "body": "mocked"}]
+        }))]);
+        assert!(!engine.matches_request_body());
+
+        let req = request("POST", "http://example.com/", "{}");
+        let matched = matches(&engine, &req);
+        assert!(!matched.modifies_request_body());
+        assert!(matched.modifies_response_body());
+
+        engine.set_rules(vec![
+            rule(json!({
+                "id": "off",
+                "enabled": false,
+                "match": {"body": [{"path": "$.a"}]}
+            })),
+            rule(json!({
+                "id": "patch",
+                "actions": [{"type": "json_patch", "patch": []}]
+            })),
+        ]);
+        assert!(!engine.matches_request_body());
+        assert!(matches(&engine, &req).modifies_request_body());
+
+        engine.set_rules(vec![rule(json!({"match": {"body": [{"path": "$.a"}]}}))]);
+        assert!(engine.matches_request_body());
+    }
+
+    #[tokio::test]
+    async fn applies_request_actions() {
+        let engine = RuleEngine::new();
+        engine.set_rules(vec![rule(json!({
+            "id": "rewrite",
+            "actions": [
+                {"type": "set_header", "name": "x-debug", "value": "1"},
+                {"type": "remove_header", "name": "x-client"},
+                {"type": "json_patch", "patch": [{"op": "add", "path": "/patched", "value": true}]},
+                {"type": "redirect", "host": "localhost", "scheme": "http", "port": 8080},
+                {"type": "replace_body", "target": "response", "body": "mocked"}
+            ]
+        }))]);
+
+        let req = request("POST", "https://api.example.com/v1?x=1", r#"{"a":1}"#);
+        let req = matches(&engine, &req).apply_request(req).await;
+
+        assert_eq!(req.uri(), "http://localhost:8080/v1?x=1");
+        assert_eq!(req.headers()["x-debug"], "1");
+        assert!(!req.headers().contains_key("x-client"));
+        assert!(!req.headers().contains_key(header::ACCEPT_ENCODING));
+        assert_eq!(
+            serde_json::from_slice::<Value>(req.body()).unwrap(),
+            json!({"a": 1, "patched": true})
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_response_actions() {
+        let engine = RuleEngine::new();
+        engine.set_rules(vec![rule(json!({
+            "id": "mock",
+            "actions": [
+                {"type": "set_status", "status": 503},
+                {"type": "set_header", "target": "response", "name": "retry-after", "value": "5"},
+                {"type": "replace_body", "target": "response", "body": "{}", "content_type": "application/json"}
+            ]
+        }))]);
+
+        let req = request("GET", "http://example.com/", "");
+        let res = Response::builder()
+            .header(header::CONTENT_ENCODING, "gzip")
+            .header(header::CONTENT_LENGTH, "100")
+            .body(Bytes::from_static(b"\x1f\x8b"))
+            .unwrap();
+        let matched = matches(&engine, &req);
+        assert!(matched.modifies_response_body());
+        let res = matched.apply_response(res).await;
+
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(res.headers()["retry-after"], "5");
+        assert_eq!(res.headers()[header::CONTENT_TYPE], "application/json");
+        assert_eq!(res.headers()[header::CONTENT_LENGTH], "2");
+        assert!(!res.headers().contains_key(header::CONTENT_ENCODING));
+        assert_eq!(res.body(), "{}");
+    }
+}
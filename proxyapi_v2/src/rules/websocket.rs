@@ -0,0 +1,411 @@
+//! WebSocket 메시지 규칙
+//!
+//! 규칙은 매처(방향, 호스트 glob, 텍스트 정규식, JSONPath 조건)와 액션 목록(텍스트 교체,
+//! JSON Patch, 삭제)으로 구성되며, [`WebSocketRuleEngine::apply`]로
+//! [`WebSocketHandler::handle_message`](crate::WebSocketHandler::handle_message)에서 적용합니다.
+
+use super::{BodyMatcher, JsonPath, PatchOperation, RuleError, apply_patch, glob_match};
+use crate::{WebSocketContext, websocket::WebSocketDirection};
+use http::Uri;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::{Arc, RwLock};
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{debug, warn};
+
+/// WebSocket 메시지 규칙
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WebSocketRule {
+    /// 규칙 식별자
+    #[serde(default)]
+    pub id: String,
+    /// 표시용 이름
+    #[serde(default)]
+    pub name: String,
+    /// 비활성화된 규칙은 매칭되지 않음
+    #[serde(default = "super::enabled_by_default")]
+    pub enabled: bool,
+    /// 메시지 매칭 조건
+    #[serde(rename = "match", default)]
+    pub matcher: WebSocketMatcher,
+    /// 매칭 시 순서대로 적용할 액션
+    #[serde(default)]
+    pub actions: Vec<WebSocketAction>,
+}
+
+/// 메시지 매칭 조건 (지정된 조건을 모두 만족해야 매칭)
+///
+/// 텍스트/JSON 조건은 텍스트 메시지에만 매칭됩니다.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct WebSocketMatcher {
+    /// 메시지 방향 (없으면 양방향)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub direction: Option<WebSocketDirection>,
+    /// 서버 호스트 glob 패턴 (예: `*.example.com`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    /// 텍스트 정규식
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// JSON 메시지 조건
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub json: Vec<BodyMatcher>,
+}
+
+/// 규칙이 매칭되었을 때 적용할 액션
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WebSocketAction {
+    /// 텍스트 교체 (`pattern`이 있으면 일치하는 부분을 정규식 치환, 없으면 메시지 전체 교체)
+    Replace {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pattern: Option<String>,
+        text: String,
+    },
+    /// JSON 메시지에 JSON Patch(RFC 6902) 적용
+    JsonPatch { patch: Vec<PatchOperation> },
+    /// 메시지를 전달하지 않음
+    Drop,
+}
+
+#[derive(Debug)]
+enum CompiledAction {
+    Replace {
+        pattern: Option<Regex>,
+        text: String,
+    },
+    JsonPatch(Vec<PatchOperation>),
+    Drop,
+}
+
+/// 정규식 등을 미리 컴파일한 WebSocket 규칙
+#[derive(Debug)]
+pub struct CompiledWebSocketRule {
+    rule: WebSocketRule,
+    host: Option<String>,
+    text: Option<Regex>,
+    json: Vec<(JsonPath, Option<Value>)>,
+    actions: Vec<CompiledAction>,
+}
+
+impl CompiledWebSocketRule {
+    /// 규칙을 검증하고 컴파일
+    pub fn compile(rule: WebSocketRule) -> Result<Self, RuleError> {
+        let error = |message: String| RuleError {
+            rule_id: rule.id.clone(),
+            message,
+        };
+        let regex = |pattern: &str| Regex::new(pattern).map_err(|e| error(e.to_string()));
+
+        let matcher = &rule.matcher;
+        let text = matcher.text.as_deref().map(regex).transpose()?;
+
+        let json = matcher
+            .json
+            .iter()
+            .map(|m| {
+                let path = JsonPath::parse(&m.path).map_err(|e| error(e.to_string()))?;
+                Ok((path, m.equals.clone()))
+            })
+            .collect::<Result<_, RuleError>>()?;
+
+        let actions = rule
+            .actions
+            .iter()
+            .map(|action| {
+                Ok(match action {
+                    WebSocketAction::Replace { pattern, text } => CompiledAction::Replace {
+                        pattern: pattern.as_deref().map(regex).transpose()?,
+                        text: text.clone(),
+                    },
+                    WebSocketAction::JsonPatch { patch } => {
+                        CompiledAction::JsonPatch(patch.clone())
+                    }
+                    WebSocketAction::Drop => CompiledAction::Drop,
+                })
+            })
+            .collect::<Result<_, RuleError>>()?;
+
+        Ok(Self {
+            host: matcher.host.as_ref().map(|host| host.to_lowercase()),
+            text,
+            json,
+            actions,
+            rule,
+        })
+    }
+
+    /// 원본 규칙
+    pub fn rule(&self) -> &WebSocketRule {
+        &self.rule
+    }
+
+    /// 메시지가 규칙과 매칭되는지 확인
+    pub fn matches(&self, ctx: &WebSocketContext, message: &Message) -> bool {
+        if !self.rule.enabled {
+            return false;
+        }
+
+        if self
+            .rule
+            .matcher
+            .direction
+            .is_some_and(|direction| direction != ctx.direction())
+        {
+            return false;
+        }
+
+        if let Some(pattern) = &self.host {
+            let host = server_uri(ctx).host().unwrap_or_default().to_lowercase();
+            if !glob_match(pattern, &host) {
+                return false;
+            }
+        }
+
+        if self.text.is_none() && self.json.is_empty() {
+            return true;
+        }
+
+        let Message::Text(text) = message else {
+            return false;
+        };
+
+        if let Some(pattern) = &self.text {
+            if !pattern.is_match(text.as_str()) {
+                return false;
+            }
+        }
+
+        if !self.json.is_empty() {
+            let Ok(json) = serde_json::from_str::<Value>(text.as_str()) else {
+                return false;
+            };
+
+            return self.json.iter().all(|(path, expected)| {
+                let selected = path.select(&json);
+                match expected {
+                    Some(expected) => selected.contains(&expected),
+                    None => !selected.is_empty(),
+                }
+            });
+        }
+
+        true
+    }
+
+    /// 액션 적용 (메시지를 삭제하면 None)
+    fn apply(&self, mut message: Message) -> Option<Message> {
+        for action in &self.actions {
+            message = match (action, message) {
+                (CompiledAction::Drop, _) => return None,
+                (
+                    CompiledAction::Replace {
+                        pattern: None,
+                        text,
+                    },
+                    _,
+                ) => Message::text(text.clone()),
+                (
+                    CompiledAction::Replace {
+                        pattern: Some(pattern),
+                        text,
+                    },
+                    Message::Text(current),
+                ) => Message::text(pattern.replace_all(current.as_str(), text).into_owned()),
+                (CompiledAction::JsonPatch(patch), Message::Text(current)) => {
+                    patch_text(current.as_str(), patch)
+                        .map(Message::text)
+                        .unwrap_or(Message::Text(current))
+                }
+                (_, message) => message,
+            };
+        }
+
+        Some(message)
+    }
+}
+
+/// 컨텍스트의 서버 URI
+fn server_uri(ctx: &WebSocketContext) -> &Uri {
+    match ctx {
+        WebSocketContext::ClientToServer { dst, .. } => dst,
+        WebSocketContext::ServerToClient { src, .. } => src,
+    }
+}
+
+fn patch_text(text: &str, patch: &[PatchOperation]) -> Option<String> {
+    let mut json = serde_json::from_str::<Value>(text).ok()?;
+    if let Err(e) = apply_patch(&mut json, patch) {
+        warn!("Skipping WebSocket JSON patch: {}", e);
+        return None;
+    }
+    serde_json::to_string(&json).ok()
+}
+
+/// WebSocket 규칙 목록을 보관하고 메시지에 적용하는 엔진
+///
+/// 복제본끼리 규칙 목록을 공유하므로 핸들러에 복제해서 넘긴 뒤에도 규칙을 교체할 수 있습니다.
+#[derive(Clone, Debug, Default)]
+pub struct WebSocketRuleEngine {
+    rules: Arc<RwLock<Vec<Arc<CompiledWebSocketRule>>>>,
+}
+
+impl WebSocketRuleEngine {
+    /// 빈 규칙 엔진 생성
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 규칙 목록 교체
+    ///
+    /// 유효하지 않은 규칙은 건너뛰고 해당 오류 목록을 반환합니다.
+    pub fn set_rules(&self, rules: Vec<WebSocketRule>) -> Vec<RuleError> {
+        let mut compiled = Vec::with_capacity(rules.len());
+        let mut errors = Vec::new();
+
+        for rule in rules {
+            match CompiledWebSocketRule::compile(rule) {
+                Ok(rule) => compiled.push(Arc::new(rule)),
+                Err(e) => {
+                    warn!("Skipping WebSocket rule: {}", e);
+                    errors.push(e);
+                }
+            }
+        }
+
+        debug!("Loaded {} WebSocket rules", compiled.len());
+        *self.rules.write().expect("rule lock poisoned") = compiled;
+        errors
+    }
+
+    /// 현재 규칙 수
+    pub fn len(&self) -> usize {
+        self.rules.read().expect("rule lock poisoned").len()
+    }
+
+    /// 규칙이 없는지 확인
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 매칭되는 규칙의 액션을 규칙 순서대로 적용 (메시지를 삭제하면 None)
+    ///
+    /// 각 규칙은 앞선 규칙이 수정한 메시지와 매칭됩니다.
+    pub fn apply(&self, ctx: &WebSocketContext, mut message: Message) -> Option<Message> {
+        let rules = self.rules.read().expect("rule lock poisoned").clone();
+
+        for rule in rules {
+            if rule.matches(ctx, &message) {
+                debug!("Applying WebSocket rule '{}'", rule.rule.id);
+                message = rule.apply(message)?;
+            }
+        }
+
+        Some(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn engine(rules: Value) -> WebSocketRuleEngine {
+        let engine = WebSocketRuleEngine::new();
+        assert!(
+            engine
+                .set_rules(serde_json::from_value(rules).unwrap())
+                .is_empty()
+        );
+        engine
+    }
+
+    fn client_to_server() -> WebSocketContext {
+        WebSocketContext::ClientToServer {
+            src: "127.0.0.1:1234".parse().unwrap(),
+            dst: "wss://chat.example.com/socket".parse().unwrap(),
+            connection_id: 1,
+        }
+    }
+
+    fn server_to_client() -> WebSocketContext {
+        WebSocketContext::ServerToClient {
+            src: "wss://chat.example.com/socket".parse().unwrap(),
+            dst: "127.0.0.1:1234".parse().unwrap(),
+            connection_id: 1,
+        }
+    }
+
+    #[test]
+    fn replaces_matching_text() {
+        let engine = engine(json!([{
+            "id": "mask",
+            "match": {"direction": "client_to_server", "host": "*.example.com", "text": "token"},
+            "actions": [{"type": "replace", "pattern": "token=\\w+", "text": "token=***"}]
+        }]));
+
+        let message = Message::text("hello token=abc");
+        assert_eq!(
+            engine.apply(&client_to_server(), message.clone()),
+            Some(Message::text("hello token=***"))
+        );
+        // 방향이 다르면 그대로 전달
+        assert_eq!(
+            engine.apply(&server_to_client(), message.clone()),
+            Some(message)
+        );
+    }
+
+    #[test]
+    fn patches_and_drops_json_messages() {
+        let engine = engine(json!([
+            {
+                "id": "patch",
+                "match": {"json": [{"path": "$.type", "equals": "price"}]},
+                "actions": [{"type": "json_patch", "patch": [
+                    {"op": "replace", "path": "/value", "value": 0}
+                ]}]
+            },
+            {
+                "id": "drop",
+                "match": {"direction": "server_to_client", "json": [{"path": "$.type", "equals": "ping"}]},
+                "actions": [{"type": "drop"}]
+            }
+        ]));
+
+        assert_eq!(
+            engine.apply(
+                &server_to_client(),
+                Message::text(r#"{"type":"price","value":10}"#)
+            ),
+            Some(Message::text(r#"{"type":"price","value":0}"#))
+        );
+        assert_eq!(
+            engine.apply(&server_to_client(), Message::text(r#"{"type":"ping"}"#)),
+            None
+        );
+        // 바이너리 메시지는 텍스트 조건과 매칭되지 않음
+        let binary = Message::binary(b"{\"type\":\"ping\"}".to_vec());
+        assert_eq!(
+            engine.apply(&server_to_client(), binary.clone()),
+            Some(binary)
+        );
+    }
+
+    #[test]
+    fn invalid_rules_are_skipped() {
+        let engine = WebSocketRuleEngine::new();
+        let errors = engine.set_rules(
+            serde_json::from_value(json!([
+                {"id": "bad", "match": {"text": "("}, "actions": [{"type": "drop"}]},
+                {"id": "good", "actions": [{"type": "drop"}]}
+            ]))
+            .unwrap(),
+        );
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].rule_id, "bad");
+        assert_eq!(engine.len(), 1);
+    }
+}
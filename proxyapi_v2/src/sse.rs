@@ -0,0 +1,190 @@
+//! Server-Sent Events(`text/event-stream`) 파싱
+//!
+//! [`SseParser`]에 본문 조각을 도착하는 순서대로 넣으면 완성된 이벤트를 [`SseEvent`]로 돌려줍니다.
+//! 줄바꿈(CRLF/LF/CR)이 조각 경계에 걸쳐 있어도 이어서 파싱합니다.
+
+use http::{HeaderMap, header::CONTENT_TYPE};
+
+/// `text/event-stream` 응답인지 확인
+pub fn is_event_stream(headers: &HeaderMap) -> bool {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("text/event-stream"))
+}
+
+/// 하나의 Server-Sent Event
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SseEvent {
+    /// 마지막으로 받은 `id` 필드 (이후 이벤트에도 유지)
+    pub id: Option<String>,
+    /// `event` 필드 (없으면 기본 이벤트 `message`)
+    pub event: Option<String>,
+    /// `data` 필드 (여러 줄이면 `\n`으로 연결)
+    pub data: String,
+    /// 이 이벤트에 포함된 `retry` 필드 (밀리초)
+    pub retry: Option<u64>,
+}
+
+/// 증분 SSE 파서
+#[derive(Debug, Default)]
+pub struct SseParser {
+    line: Vec<u8>,
+    /// 직전 조각이 CR로 끝나 다음 LF를 건너뛰어야 하는지
+    pending_cr: bool,
+    /// 스트림 시작 부분(BOM 제거 전)인지
+    started: bool,
+    last_id: Option<String>,
+    event: Option<String>,
+    data: Option<String>,
+    retry: Option<u64>,
+}
+
+impl SseParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 본문 조각을 파싱해 완성된 이벤트 반환
+    ///
+    /// 끝나지 않은 줄이나 빈 줄로 닫히지 않은 이벤트는 다음 조각과 이어서 파싱합니다.
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        let mut events = Vec::new();
+        let mut chunk = chunk;
+
+        if !self.started && !chunk.is_empty() {
+            self.started = true;
+            chunk = chunk.strip_prefix("\u{feff}".as_bytes()).unwrap_or(chunk);
+        }
+
+        for &byte in chunk {
+            let pending_cr = std::mem::take(&mut self.pending_cr);
+            match byte {
+                b'\n' if pending_cr => {}
+                b'\r' | b'\n' => {
+                    self.pending_cr = byte == b'\r';
+                    let line = std::mem::take(&mut self.line);
+                    events.extend(self.process_line(&line));
+                }
+                _ => self.line.push(byte),
+            }
+        }
+
+        events
+    }
+
+    fn process_line(&mut self, line: &[u8]) -> Option<SseEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+
+        let line = String::from_utf8_lossy(line);
+        if line.starts_with(':') {
+            return None;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line.as_ref(), ""),
+        };
+
+        match field {
+            "data" => {
+                let data = self.data.get_or_insert_with(String::new);
+                data.push_str(value);
+                data.push('\n');
+            }
+            "event" => self.event = Some(value.to_owned()),
+            "id" if !value.contains('\0') => self.last_id = Some(value.to_owned()),
+            "retry" if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) => {
+                self.retry = value.parse().ok();
+            }
+            _ => {}
+        }
+
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        let event = self.event.take();
+        let retry = self.retry.take();
+        let mut data = self.data.take()?;
+        // 마지막 줄바꿈 제거
+        data.pop();
+
+        Some(SseEvent {
+            id: self.last_id.clone(),
+            event,
+            data,
+            retry,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_events_across_chunks() {
+        let mut parser = SseParser::new();
+
+        assert!(parser.push(b"id: 1\r\nevent: tick\r\nda").is_empty());
+        let events = parser.push(b"ta: hello\r\n\r\ndata: next\n\n");
+
+        assert_eq!(
+            events,
+            vec![
+                SseEvent {
+                    id: Some("1".to_owned()),
+                    event: Some("tick".to_owned()),
+                    data: "hello".to_owned(),
+                    retry: None,
+                },
+                SseEvent {
+                    id: Some("1".to_owned()),
+                    event: None,
+                    data: "next".to_owned(),
+                    retry: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn joins_multiline_data_and_skips_comments() {
+        let mut parser = SseParser::new();
+        let events = parser.push(b": keep-alive\ndata: a\ndata:b\ndata\nretry: 3000\n\n");
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].data, "a\nb\n");
+        assert_eq!(events[0].retry, Some(3000));
+    }
+
+    #[test]
+    fn handles_split_crlf_and_empty_events() {
+        let mut parser = SseParser::new();
+
+        assert!(parser.push(b"event: ignored\r").is_empty());
+        assert!(parser.push(b"\n\r").is_empty());
+        let events = parser.push(b"\ndata: x\r\r");
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event, None);
+        assert_eq!(events[0].data, "x");
+    }
+
+    #[test]
+    fn detects_event_stream() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CONTENT_TYPE,
+            "text/event-stream; charset=utf-8".parse().unwrap(),
+        );
+        assert!(is_event_stream(&headers));
+
+        headers.insert(CONTENT_TYPE, "text/plain".parse().unwrap());
+        assert!(!is_event_stream(&headers));
+    }
+}
@@ -0,0 +1,291 @@
+//! 교환별 구간 소요 시간 측정
+//!
+//! 새 연결의 DNS 조회와 TCP 연결 시간은 [`UpstreamConnector`](crate::upstream_proxy::UpstreamConnector)가,
+//! TLS 핸드셰이크까지 포함한 연결 완료 시각은 [`TimingConnector`]가 연결 정보에 기록합니다.
+//! hyper 클라이언트가 이 정보를 응답 확장(extension)에 넣어 주면 프록시가 [`Timings`]로 계산해
+//! [`HttpContext::timings`](crate::HttpContext::timings)로 핸들러에 전달합니다.
+
+use http::{Response, Uri, uri::Scheme};
+use hyper::rt::{Read, ReadBufCursor, Write};
+use hyper_util::client::legacy::connect::{Connected, Connection};
+use std::{
+    future::Future,
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tower_service::Service;
+
+/// 교환 하나의 구간별 소요 시간
+///
+/// 연결 단계(`dns`, `connect`, `tls`)는 이 요청을 위해 새로 연결한 경우에만 채워지며, 풀에서
+/// 재사용한 연결이면 None입니다. 응답 본문 수신 시간은 본문을 읽는 핸들러가 측정해야 합니다.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct Timings {
+    /// DNS 조회 (IP 주소로 연결하거나 상위 프록시를 거친 경우 None)
+    pub dns: Option<Duration>,
+    /// TCP 연결 (상위 프록시를 거친 경우 터널 연결까지 포함)
+    pub connect: Option<Duration>,
+    /// TLS 핸드셰이크
+    pub tls: Option<Duration>,
+    /// 연결 준비 후 응답 헤더를 받을 때까지 (요청 전송 포함, time to first byte)
+    pub wait: Option<Duration>,
+}
+
+impl Timings {
+    /// 응답 확장에 기록된 연결 정보로 계산
+    ///
+    /// `sent`는 클라이언트에 요청을 넘긴 시각이며, 응답 헤더를 방금 받았다고 가정합니다.
+    pub(crate) fn from_response<B>(sent: Instant, res: &Response<B>) -> Self {
+        let extensions = res.extensions();
+        // 요청 전에 시작된 연결은 풀에서 재사용된 연결
+        let tcp = extensions
+            .get::<TcpTiming>()
+            .filter(|tcp| tcp.started >= sent);
+        let connect = extensions
+            .get::<ConnectTiming>()
+            .filter(|connect| connect.started >= sent);
+
+        let established = tcp.map(TcpTiming::established);
+        let ready = connect
+            .map(|connect| connect.finished)
+            .or(established)
+            .unwrap_or(sent);
+
+        let tls = match (connect, established) {
+            (Some(connect), Some(established)) if connect.tls => {
+                Some(connect.finished.saturating_duration_since(established))
+            }
+            _ => None,
+        };
+
+        Self {
+            dns: tcp.and_then(|tcp| tcp.dns),
+            connect: tcp.map(|tcp| tcp.connect),
+            tls,
+            wait: Some(Instant::now().saturating_duration_since(ready)),
+        }
+    }
+}
+
+/// 새 TCP 연결의 DNS 조회/연결 시간 (연결 정보의 extra로 기록됨)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TcpTiming {
+    /// 연결을 시작한 시각
+    pub started: Instant,
+    /// DNS 조회 시간
+    pub dns: Option<Duration>,
+    /// TCP 연결 시간
+    pub connect: Duration,
+}
+
+impl TcpTiming {
+    /// TCP 연결이 완료된 시각
+    pub fn established(&self) -> Instant {
+        self.started + self.dns.unwrap_or_default() + self.connect
+    }
+}
+
+/// TLS 핸드셰이크를 포함한 연결 완료 시각 (연결 정보의 extra로 기록됨)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ConnectTiming {
+    /// 연결을 시작한 시각
+    pub started: Instant,
+    /// 연결(및 TLS 핸드셰이크)이 완료된 시각
+    pub finished: Instant,
+    /// TLS 연결 여부
+    pub tls: bool,
+}
+
+/// 연결 시간을 연결 정보에 기록하는 스트림
+#[derive(Debug)]
+pub struct TimedStream<T, E> {
+    inner: T,
+    timing: E,
+}
+
+impl<T, E> TimedStream<T, E> {
+    pub(crate) fn new(inner: T, timing: E) -> Self {
+        Self { inner, timing }
+    }
+}
+
+impl<T, E> Connection for TimedStream<T, E>
+where
+    T: Connection,
+    E: Clone + Send + Sync + 'static,
+{
+    fn connected(&self) -> Connected {
+        self.inner.connected().extra(self.timing.clone())
+    }
+}
+
+impl<T: Read + Unpin, E: Unpin> Read for TimedStream<T, E> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: ReadBufCursor<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl<T: Write + Unpin, E: Unpin> Write for TimedStream<T, E> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write_vectored(cx, bufs)
+    }
+}
+
+/// TLS 핸드셰이크를 포함한 연결 시간을 기록하는 커넥터
+///
+/// TLS 커넥터(`hyper_rustls::HttpsConnector` 등)를 감싸서 사용합니다. 안쪽 커넥터가
+/// [`UpstreamConnector`](crate::upstream_proxy::UpstreamConnector)이면 TCP 연결 이후의 시간을
+/// TLS 핸드셰이크 시간으로 계산합니다.
+#[derive(Clone, Debug)]
+pub struct TimingConnector<C> {
+    inner: C,
+}
+
+impl<C> TimingConnector<C> {
+    /// 커넥터 감싸기
+    pub fn new(inner: C) -> Self {
+        Self { inner }
+    }
+}
+
+impl<C> Service<Uri> for TimingConnector<C>
+where
+    C: Service<Uri>,
+    C::Future: Send + 'static,
+{
+    type Response = TimedStream<C::Response, ConnectTiming>;
+    type Error = C::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let tls = uri.scheme() == Some(&Scheme::HTTPS);
+        let started = Instant::now();
+        let connecting = self.inner.call(uri);
+
+        Box::pin(async move {
+            let stream = connecting.await?;
+            Ok(TimedStream::new(
+                stream,
+                ConnectTiming {
+                    started,
+                    finished: Instant::now(),
+                    tls,
+                },
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(tcp: Option<TcpTiming>, connect: Option<ConnectTiming>) -> Response<()> {
+        let mut res = Response::new(());
+        if let Some(tcp) = tcp {
+            res.extensions_mut().insert(tcp);
+        }
+        if let Some(connect) = connect {
+            res.extensions_mut().insert(connect);
+        }
+        res
+    }
+
+    #[test]
+    fn splits_new_connection_phases() {
+        let sent = Instant::now();
+        let tcp = TcpTiming {
+            started: sent,
+            dns: Some(Duration::from_millis(5)),
+            connect: Duration::from_millis(10),
+        };
+        let connect = ConnectTiming {
+            started: sent,
+            finished: sent + Duration::from_millis(40),
+            tls: true,
+        };
+
+        let timings = Timings::from_response(sent, &response(Some(tcp), Some(connect)));
+        assert_eq!(timings.dns, Some(Duration::from_millis(5)));
+        assert_eq!(timings.connect, Some(Duration::from_millis(10)));
+        assert_eq!(timings.tls, Some(Duration::from_millis(25)));
+        assert!(timings.wait.is_some());
+    }
+
+    #[test]
+    fn ignores_reused_connection() {
+        let earlier = Instant::now();
+        let tcp = TcpTiming {
+            started: earlier,
+            dns: None,
+            connect: Duration::from_millis(10),
+        };
+        let sent = earlier + Duration::from_millis(100);
+
+        let timings = Timings::from_response(sent, &response(Some(tcp), None));
+        assert_eq!(timings.connect, None);
+        assert_eq!(timings.tls, None);
+        assert!(timings.wait.is_some());
+    }
+
+    #[tokio::test]
+    async fn records_connection_timings() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let accept = tokio::spawn(async move { listener.accept().await.unwrap() });
+
+        let mut connector =
+            TimingConnector::new(crate::upstream_proxy::UpstreamConnector::new(None));
+        let uri: Uri = format!("http://localhost:{}/", port).parse().unwrap();
+        let sent = Instant::now();
+        let stream = connector.call(uri).await.unwrap();
+        let _ = accept.await.unwrap();
+
+        let mut res = Response::new(());
+        stream.connected().get_extras(res.extensions_mut());
+
+        let tcp = res.extensions().get::<TcpTiming>().unwrap();
+        assert!(tcp.dns.is_some());
+        let connect = res.extensions().get::<ConnectTiming>().unwrap();
+        assert!(!connect.tls);
+
+        let timings = Timings::from_response(sent, &res);
+        assert!(timings.dns.is_some() && timings.connect.is_some());
+        assert_eq!(timings.tls, None);
+    }
+}
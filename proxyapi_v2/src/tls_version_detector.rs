@@ -1,5 +1,6 @@
 use std::io;
 use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio_rustls::rustls::ProtocolVersion;
 
 /// TLS 버전을 감지하는 유틸리티
 pub struct TlsVersionDetector;
@@ -56,7 +57,7 @@ impl TlsVersionDetector {
 }
 
 /// 지원되는 TLS 버전
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TlsVersion {
     Tls10,
     Tls11,
@@ -65,6 +66,17 @@ pub enum TlsVersion {
 }
 
 impl TlsVersion {
+    /// rustls가 협상한 프로토콜 버전을 변환합니다
+    pub fn from_rustls(version: ProtocolVersion) -> Option<Self> {
+        match version {
+            ProtocolVersion::TLSv1_0 => Some(TlsVersion::Tls10),
+            ProtocolVersion::TLSv1_1 => Some(TlsVersion::Tls11),
+            ProtocolVersion::TLSv1_2 => Some(TlsVersion::Tls12),
+            ProtocolVersion::TLSv1_3 => Some(TlsVersion::Tls13),
+            _ => None,
+        }
+    }
+
     /// TLS 버전을 문자열로 반환합니다
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -140,6 +152,15 @@ mod tests {
         assert!(TlsVersionDetector::is_rustls_supported(TlsVersion::Tls13));
     }
 
+    #[test]
+    fn test_from_rustls() {
+        assert_eq!(
+            TlsVersion::from_rustls(ProtocolVersion::TLSv1_3),
+            Some(TlsVersion::Tls13)
+        );
+        assert_eq!(TlsVersion::from_rustls(ProtocolVersion::SSLv3), None);
+    }
+
     #[test]
     fn test_openssl_support() {
         assert!(TlsVersionDetector::is_openssl_supported(TlsVersion::Tls10));
@@ -0,0 +1,904 @@
+//! 업스트림 서버 인증서 검증
+//!
+//! [`TlsVerificationPolicy`]는 운영체제 신뢰 저장소(rustls-native-certs)와 사용자가 추가한 CA로 업스트림
+//! 인증서를 검증하고, 예외로 지정한 호스트는 검증에 실패해도 연결합니다. 연결마다의 검증 결과는
+//! [`UpstreamTls`]로 연결 정보에 기록되어 [`HttpContext::upstream_tls`](crate::HttpContext::upstream_tls)로
+//! 핸들러에 전달되며, 검증에 실패해 연결하지 못한 경우에는 [`verification_error`]로 오류에서 꺼낼 수 있습니다.
+//!
+//! 협상한 TLS 버전, 암호 스위트, ALPN과 서버가 보낸 인증서 체인은 [`UpstreamSession`]으로 함께 기록되어
+//! [`HttpContext::upstream_session`](crate::HttpContext::upstream_session)으로 전달됩니다.
+
+use crate::{TlsVersion, host_pattern::host_matches};
+use std::{error::Error as StdError, fmt, io, sync::Arc};
+use thiserror::Error;
+use tokio_rustls::rustls::{
+    self, AlertDescription, CertificateError, DigitallySignedStruct, OtherError, RootCertStore,
+    SignatureScheme,
+    client::{
+        WebPkiServerVerifier,
+        danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    },
+    crypto::{
+        CryptoProvider, WebPkiSupportedAlgorithms, verify_tls12_signature, verify_tls13_signature,
+    },
+    pki_types::{CertificateDer, ServerName, UnixTime, pem::PemObject},
+};
+
+/// 업스트림 인증서 검증 결과
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum CertificateStatus {
+    /// 신뢰할 수 있는 인증서
+    Valid,
+    /// 유효 기간이 지난 인증서
+    Expired,
+    /// 아직 유효 기간이 시작되지 않은 인증서
+    NotYetValid,
+    /// 접속한 호스트 이름과 맞지 않는 인증서
+    HostnameMismatch,
+    /// 자체 서명 인증서
+    SelfSigned,
+    /// 신뢰 저장소에 없는 CA가 발급한 인증서
+    UnknownIssuer,
+    /// 폐기된 인증서
+    Revoked,
+    /// 그 밖의 이유로 유효하지 않은 인증서
+    Invalid(String),
+}
+
+impl CertificateStatus {
+    /// 신뢰할 수 있는 인증서인지 여부
+    pub fn is_valid(&self) -> bool {
+        *self == Self::Valid
+    }
+
+    fn from_error(error: &rustls::Error) -> Self {
+        let rustls::Error::InvalidCertificate(error) = error else {
+            return Self::Invalid(error.to_string());
+        };
+
+        match error {
+            CertificateError::Expired | CertificateError::ExpiredContext { .. } => Self::Expired,
+            CertificateError::NotValidYet | CertificateError::NotValidYetContext { .. } => {
+                Self::NotYetValid
+            }
+            CertificateError::NotValidForName | CertificateError::NotValidForNameContext { .. } => {
+                Self::HostnameMismatch
+            }
+            CertificateError::UnknownIssuer => Self::UnknownIssuer,
+            CertificateError::Revoked => Self::Revoked,
+            CertificateError::Other(OtherError(other)) if other.is::<SelfSigned>() => {
+                Self::SelfSigned
+            }
+            error => Self::Invalid(error.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for CertificateStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Valid => f.write_str("valid"),
+            Self::Expired => f.write_str("expired"),
+            Self::NotYetValid => f.write_str("not yet valid"),
+            Self::HostnameMismatch => f.write_str("hostname mismatch"),
+            Self::SelfSigned => f.write_str("self-signed"),
+            Self::UnknownIssuer => f.write_str("unknown issuer"),
+            Self::Revoked => f.write_str("revoked"),
+            Self::Invalid(reason) => write!(f, "invalid ({})", reason),
+        }
+    }
+}
+
+/// 자체 서명 인증서를 검증 오류로 전달하기 위한 표시
+#[derive(Debug, Error)]
+#[error("self-signed certificate")]
+struct SelfSigned;
+
+/// 업스트림 TLS 연결의 인증서 검증 정보
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct UpstreamTls {
+    /// 인증서 검증 결과
+    pub status: CertificateStatus,
+    /// 검증 예외 호스트로 지정되어 검증 결과와 관계없이 연결했는지 여부
+    pub insecure: bool,
+}
+
+/// 업스트림 TLS 연결에서 협상한 세션 정보
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct UpstreamSession {
+    /// 협상된 TLS 버전
+    pub version: Option<TlsVersion>,
+    /// 협상된 암호 스위트 (예: `TLS13_AES_128_GCM_SHA256`)
+    pub cipher_suite: Option<String>,
+    /// 협상된 ALPN 프로토콜 (예: `h2`, `http/1.1`)
+    pub alpn: Option<Vec<u8>>,
+    /// 서버가 보낸 인증서 체인 (DER, 서버 인증서부터)
+    pub certificates: Vec<CertificateDer<'static>>,
+}
+
+impl UpstreamSession {
+    #[cfg(feature = "rustls-client")]
+    pub(crate) fn from_rustls(conn: &rustls::ClientConnection) -> Self {
+        Self {
+            version: conn.protocol_version().and_then(TlsVersion::from_rustls),
+            cipher_suite: conn
+                .negotiated_cipher_suite()
+                .map(|suite| format!("{:?}", suite.suite())),
+            alpn: conn.alpn_protocol().map(<[u8]>::to_vec),
+            certificates: conn
+                .peer_certificates()
+                .map(|certificates| {
+                    certificates
+                        .iter()
+                        .map(|certificate| certificate.clone().into_owned())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    #[cfg(feature = "legacy-tls-client")]
+    pub(crate) fn from_openssl(ssl: &openssl::ssl::SslRef) -> Self {
+        use openssl::ssl::SslVersion;
+
+        let version = ssl.version2().and_then(|version| {
+            [
+                (SslVersion::TLS1, TlsVersion::Tls10),
+                (SslVersion::TLS1_1, TlsVersion::Tls11),
+                (SslVersion::TLS1_2, TlsVersion::Tls12),
+                (SslVersion::TLS1_3, TlsVersion::Tls13),
+            ]
+            .into_iter()
+            .find_map(|(ssl_version, tls_version)| (ssl_version == version).then_some(tls_version))
+        });
+
+        Self {
+            version,
+            cipher_suite: ssl.current_cipher().map(|cipher| cipher.name().to_string()),
+            alpn: ssl.selected_alpn_protocol().map(<[u8]>::to_vec),
+            // 클라이언트 쪽 체인은 서버 인증서부터 시작
+            certificates: ssl
+                .peer_cert_chain()
+                .map(|chain| {
+                    chain
+                        .iter()
+                        .filter_map(|certificate| certificate.to_der().ok())
+                        .map(CertificateDer::from)
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// 업스트림 연결 오류에서 인증서 검증 결과 꺼내기 (인증서 때문에 실패한 게 아니면 None)
+pub fn verification_error(error: &(dyn StdError + 'static)) -> Option<CertificateStatus> {
+    rustls_errors(error).find_map(|error| match error {
+        rustls::Error::InvalidCertificate(_) => Some(CertificateStatus::from_error(error)),
+        _ => None,
+    })
+}
+
+/// 업스트림 연결 오류가 서버와 TLS 버전이나 암호 스위트를 협상하지 못한 핸드셰이크 실패인지 확인
+///
+/// TLS 1.0/1.1만 지원하거나 오래된 암호 스위트만 사용하는 서버에 연결할 때 발생합니다.
+pub fn is_handshake_failure(error: &(dyn StdError + 'static)) -> bool {
+    rustls_errors(error).any(|error| {
+        matches!(
+            error,
+            rustls::Error::AlertReceived(
+                AlertDescription::HandshakeFailure | AlertDescription::ProtocolVersion
+            ) | rustls::Error::PeerIncompatible(_)
+        )
+    })
+}
+
+/// 오류 체인에 있는 rustls 오류
+fn rustls_errors<'a>(
+    error: &'a (dyn StdError + 'static),
+) -> impl Iterator<Item = &'a rustls::Error> {
+    std::iter::successors(Some(error), |&error| {
+        match error.downcast_ref::<io::Error>() {
+            // io::Error의 source()는 감싼 오류를 건너뛰므로 직접 꺼냄
+            Some(error) => error
+                .get_ref()
+                .map(|inner| inner as &(dyn StdError + 'static)),
+            None => error.source(),
+        }
+    })
+    .filter_map(|error| error.downcast_ref::<rustls::Error>())
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum TlsPolicyError {
+    #[error("Failed to parse CA certificate: {0}")]
+    Pem(#[from] rustls::pki_types::pem::Error),
+    #[error("Invalid CA certificate: {0}")]
+    Certificate(#[from] rustls::Error),
+}
+
+/// 업스트림 인증서 검증 정책
+///
+/// 기본값은 운영체제 신뢰 저장소로 모든 호스트를 검증합니다. 예외 호스트 패턴은 정확한 호스트 이름,
+/// 하위 도메인 와일드카드(`*.example.com`) 또는 모든 호스트(`*`)를 지원합니다.
+#[derive(Clone, Debug, Default)]
+pub struct TlsVerificationPolicy {
+    extra_roots: Vec<CertificateDer<'static>>,
+    insecure_hosts: Vec<String>,
+}
+
+impl TlsVerificationPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 모든 호스트를 검증 예외로 처리하는 정책 (검증 결과는 그대로 기록)
+    pub fn insecure() -> Self {
+        Self::new().with_insecure_host("*")
+    }
+
+    /// 신뢰할 CA 인증서 추가 (DER)
+    pub fn with_extra_ca(mut self, certificate: CertificateDer<'static>) -> Self {
+        self.extra_roots.push(certificate);
+        self
+    }
+
+    /// PEM 파일 내용의 CA 인증서를 모두 추가
+    pub fn with_extra_ca_pem(mut self, pem: &[u8]) -> Result<Self, TlsPolicyError> {
+        for certificate in CertificateDer::pem_slice_iter(pem) {
+            self.extra_roots.push(certificate?);
+        }
+        Ok(self)
+    }
+
+    /// 검증에 실패해도 연결할 호스트 패턴 추가
+    pub fn with_insecure_host(mut self, pattern: impl Into<String>) -> Self {
+        self.insecure_hosts
+            .push(pattern.into().to_ascii_lowercase());
+        self
+    }
+
+    /// 운영체제 신뢰 저장소 외에 신뢰할 CA 인증서
+    #[cfg(feature = "legacy-tls-client")]
+    pub(crate) fn extra_roots(&self) -> &[CertificateDer<'static>] {
+        &self.extra_roots
+    }
+
+    /// 호스트가 검증 예외인지 확인
+    pub fn is_insecure(&self, host: &str) -> bool {
+        self.insecure_hosts
+            .iter()
+            .any(|pattern| host_matches(pattern, host))
+    }
+
+    /// 정책에 따라 인증서를 검증하는 rustls 검증기 생성
+    ///
+    /// `ClientConfig`의 `with_custom_certificate_verifier`에 사용하며, 연결마다의 검증 결과를 기록하려면
+    /// 같은 검증기로 [`VerifyingConnector`](crate::upstream_tls::VerifyingConnector)를 만듭니다.
+    pub fn verifier(
+        &self,
+        provider: &CryptoProvider,
+    ) -> Result<Arc<PolicyVerifier>, TlsPolicyError> {
+        let mut roots = RootCertStore::empty();
+        let native = rustls_native_certs::load_native_certs();
+        for error in &native.errors {
+            tracing::warn!("Failed to load native certificate: {}", error);
+        }
+        roots.add_parsable_certificates(native.certs);
+        for certificate in &self.extra_roots {
+            roots.add(certificate.clone())?;
+        }
+
+        // 신뢰할 CA가 하나도 없으면 모든 인증서를 알 수 없는 발급자로 처리
+        let supported = provider.signature_verification_algorithms;
+        let inner = (!roots.is_empty())
+            .then(|| {
+                WebPkiServerVerifier::builder_with_provider(
+                    Arc::new(roots),
+                    Arc::new(provider.clone()),
+                )
+                .build()
+                .ok()
+            })
+            .flatten();
+
+        Ok(Arc::new(PolicyVerifier {
+            inner,
+            supported,
+            policy: self.clone(),
+        }))
+    }
+}
+
+/// [`TlsVerificationPolicy`]를 적용하는 인증서 검증기
+#[derive(Debug)]
+pub struct PolicyVerifier {
+    inner: Option<Arc<WebPkiServerVerifier>>,
+    supported: WebPkiSupportedAlgorithms,
+    policy: TlsVerificationPolicy,
+}
+
+impl PolicyVerifier {
+    /// 예외와 관계없이 인증서 검증
+    fn verify(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        now: UnixTime,
+    ) -> Result<(), rustls::Error> {
+        let result = match &self.inner {
+            Some(inner) => inner
+                .verify_server_cert(end_entity, intermediates, server_name, &[], now)
+                .map(|_| ()),
+            None => Err(CertificateError::UnknownIssuer.into()),
+        };
+
+        match result {
+            Err(rustls::Error::InvalidCertificate(CertificateError::UnknownIssuer))
+                if is_self_signed(end_entity) =>
+            {
+                Err(CertificateError::Other(OtherError(Arc::new(SelfSigned))).into())
+            }
+            result => result,
+        }
+    }
+
+    /// 연결된 서버의 인증서 체인 검증 결과 (체인이 비어 있거나 호스트 이름이 잘못되었으면 None)
+    pub fn inspect(&self, host: &str, certificates: &[CertificateDer<'_>]) -> Option<UpstreamTls> {
+        let (end_entity, intermediates) = certificates.split_first()?;
+        let server_name = ServerName::try_from(host).ok()?;
+        let status = match self.verify(end_entity, intermediates, &server_name, UnixTime::now()) {
+            Ok(()) => CertificateStatus::Valid,
+            Err(error) => CertificateStatus::from_error(&error),
+        };
+
+        Some(UpstreamTls {
+            status,
+            insecure: self.policy.is_insecure(host),
+        })
+    }
+}
+
+impl ServerCertVerifier for PolicyVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        match self.verify(end_entity, intermediates, server_name, now) {
+            Ok(()) => Ok(ServerCertVerified::assertion()),
+            Err(_) if self.policy.is_insecure(&server_name.to_str()) => {
+                Ok(ServerCertVerified::assertion())
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(message, cert, dss, &self.supported)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(message, cert, dss, &self.supported)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.supported.supported_schemes()
+    }
+}
+
+/// DER 값 하나를 읽어 (태그, 내용, 나머지) 반환
+fn der_read(input: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, rest) = input.split_first()?;
+    let (&first, rest) = rest.split_first()?;
+    let (length, rest) = if first & 0x80 == 0 {
+        (first as usize, rest)
+    } else {
+        let octets = (first & 0x7f) as usize;
+        if octets == 0 || octets > 4 || rest.len() < octets {
+            return None;
+        }
+        let length = rest[..octets]
+            .iter()
+            .fold(0usize, |length, &byte| (length << 8) | byte as usize);
+        (length, &rest[octets..])
+    };
+    if rest.len() < length {
+        return None;
+    }
+    Some((tag, &rest[..length], &rest[length..]))
+}
+
+/// 발급자와 주체가 같은 인증서인지 확인
+fn is_self_signed(certificate: &[u8]) -> bool {
+    let names = || {
+        let (_, certificate, _) = der_read(certificate)?;
+        let (_, tbs, _) = der_read(certificate)?;
+        // version([0], 생략 가능)과 serialNumber 건너뛰기
+        let (tag, _, mut rest) = der_read(tbs)?;
+        if tag == 0xa0 {
+            rest = der_read(rest)?.2;
+        }
+        let (_, _, rest) = der_read(rest)?; // signature
+        let (_, issuer, rest) = der_read(rest)?;
+        let (_, _, rest) = der_read(rest)?; // validity
+        let (_, subject, _) = der_read(rest)?;
+        Some(issuer == subject)
+    };
+    names().unwrap_or(false)
+}
+
+pub use connector::VerifiedStream;
+#[cfg(feature = "rustls-client")]
+pub use connector::VerifyingConnector;
+#[cfg(feature = "legacy-tls-client")]
+pub use connector::{LegacyTlsConnector, LegacyTlsStream};
+
+mod connector {
+    use super::{UpstreamSession, UpstreamTls};
+    use hyper::rt::{Read, ReadBufCursor, Write};
+    use hyper_util::client::legacy::connect::{Connected, Connection};
+    use std::{
+        io,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    /// TLS 연결의 인증서 검증 결과와 세션 정보를 연결 정보에 기록하는 스트림
+    #[derive(Debug)]
+    pub struct VerifiedStream<S> {
+        inner: S,
+        tls: Option<UpstreamTls>,
+        session: Option<UpstreamSession>,
+    }
+
+    impl<S: Connection> Connection for VerifiedStream<S> {
+        fn connected(&self) -> Connected {
+            let mut connected = self.inner.connected();
+            if let Some(tls) = &self.tls {
+                connected = connected.extra(tls.clone());
+            }
+            if let Some(session) = &self.session {
+                connected = connected.extra(session.clone());
+            }
+            connected
+        }
+    }
+
+    impl<S: Read + Unpin> Read for VerifiedStream<S> {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: ReadBufCursor<'_>,
+        ) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.inner).poll_read(cx, buf)
+        }
+    }
+
+    impl<S: Write + Unpin> Write for VerifiedStream<S> {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            Pin::new(&mut self.inner).poll_write(cx, buf)
+        }
+
+        fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.inner).poll_flush(cx)
+        }
+
+        fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.inner).poll_shutdown(cx)
+        }
+
+        fn is_write_vectored(&self) -> bool {
+            self.inner.is_write_vectored()
+        }
+
+        fn poll_write_vectored(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            bufs: &[io::IoSlice<'_>],
+        ) -> Poll<io::Result<usize>> {
+            Pin::new(&mut self.inner).poll_write_vectored(cx, bufs)
+        }
+    }
+
+    #[cfg(feature = "rustls-client")]
+    pub use rustls_connector::VerifyingConnector;
+
+    #[cfg(feature = "rustls-client")]
+    mod rustls_connector {
+        use super::*;
+        use crate::upstream_tls::PolicyVerifier;
+        use http::Uri;
+        use hyper_rustls::MaybeHttpsStream;
+        use std::{future::Future, sync::Arc};
+        use tower_service::Service;
+
+        /// 업스트림 인증서 검증 결과와 세션 정보를 기록하는 커넥터
+        ///
+        /// `hyper_rustls::HttpsConnector`를 감싸며, 안쪽 커넥터의 TLS 설정은 같은 정책의 검증기를 사용해야 합니다.
+        #[derive(Clone, Debug)]
+        pub struct VerifyingConnector<C> {
+            inner: C,
+            verifier: Arc<PolicyVerifier>,
+        }
+
+        impl<C> VerifyingConnector<C> {
+            pub fn new(inner: C, verifier: Arc<PolicyVerifier>) -> Self {
+                Self { inner, verifier }
+            }
+        }
+
+        impl<C, T> Service<Uri> for VerifyingConnector<C>
+        where
+            C: Service<Uri, Response = MaybeHttpsStream<T>>,
+            C::Future: Send + 'static,
+            T: Send + 'static,
+        {
+            type Response = VerifiedStream<MaybeHttpsStream<T>>;
+            type Error = C::Error;
+            type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+            fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+                self.inner.poll_ready(cx)
+            }
+
+            fn call(&mut self, uri: Uri) -> Self::Future {
+                // IPv6 주소는 대괄호 없이 검증
+                let host = uri.host().map(|host| {
+                    host.trim_start_matches('[')
+                        .trim_end_matches(']')
+                        .to_string()
+                });
+                let verifier = self.verifier.clone();
+                let connecting = self.inner.call(uri);
+
+                Box::pin(async move {
+                    let inner = connecting.await?;
+                    let (tls, session) = match &inner {
+                        MaybeHttpsStream::Https(stream) => {
+                            let (_, connection) = stream.inner().get_ref();
+                            let tls = connection.peer_certificates().and_then(|certificates| {
+                                verifier.inspect(host.as_deref()?, certificates)
+                            });
+                            (tls, Some(UpstreamSession::from_rustls(connection)))
+                        }
+                        MaybeHttpsStream::Http(_) => (None, None),
+                    };
+                    Ok(VerifiedStream {
+                        inner,
+                        tls,
+                        session,
+                    })
+                })
+            }
+        }
+    }
+
+    #[cfg(feature = "legacy-tls-client")]
+    pub use openssl_connector::{LegacyTlsConnector, LegacyTlsStream};
+
+    #[cfg(feature = "legacy-tls-client")]
+    mod openssl_connector {
+        use super::*;
+        use crate::upstream_tls::{CertificateStatus, PolicyVerifier};
+        use http::{Uri, uri::Scheme};
+        use hyper_util::rt::TokioIo;
+        use openssl::ssl::SslConnector;
+        use std::{error::Error as StdError, future::Future, sync::Arc};
+        use tokio_openssl::SslStream;
+        use tower_service::Service;
+
+        /// OpenSSL로 업스트림에 TLS 연결하고 인증서 검증 정책을 적용하는 커넥터
+        ///
+        /// 인증서를 검증하는 `SslConnector`와 모든 인증서를 허용하는 `SslConnector`를 받아 검증 예외 호스트에만
+        /// 후자를 사용합니다. 검증한 연결은 유효한 인증서로 기록하고, 예외 호스트는 서버 인증서 체인을 같은
+        /// 정책의 검증기로 검사해 기록합니다. ALPN을 설정하지 않으므로 HTTP/1.1로만 연결합니다.
+        #[derive(Clone, Debug)]
+        pub struct LegacyTlsConnector<C> {
+            inner: C,
+            verified: SslConnector,
+            insecure: SslConnector,
+            verifier: Arc<PolicyVerifier>,
+        }
+
+        impl<C> LegacyTlsConnector<C> {
+            pub fn new(
+                inner: C,
+                verified: SslConnector,
+                insecure: SslConnector,
+                verifier: Arc<PolicyVerifier>,
+            ) -> Self {
+                Self {
+                    inner,
+                    verified,
+                    insecure,
+                    verifier,
+                }
+            }
+        }
+
+        impl<C, T> Service<Uri> for LegacyTlsConnector<C>
+        where
+            C: Service<Uri, Response = T>,
+            C::Error: Into<Box<dyn StdError + Send + Sync>>,
+            C::Future: Send + 'static,
+            T: Read + Write + Connection + Unpin + Send + 'static,
+        {
+            type Response = VerifiedStream<LegacyTlsStream<T>>;
+            type Error = Box<dyn StdError + Send + Sync>;
+            type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+            fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+                self.inner.poll_ready(cx).map_err(Into::into)
+            }
+
+            fn call(&mut self, uri: Uri) -> Self::Future {
+                let https = uri.scheme() == Some(&Scheme::HTTPS);
+                // IPv6 주소는 대괄호 없이 검증
+                let host = uri
+                    .host()
+                    .unwrap_or_default()
+                    .trim_start_matches('[')
+                    .trim_end_matches(']')
+                    .to_ascii_lowercase();
+                let insecure = self.verifier.policy.is_insecure(&host);
+                let connector = if insecure {
+                    self.insecure.clone()
+                } else {
+                    self.verified.clone()
+                };
+                let verifier = self.verifier.clone();
+                let connecting = self.inner.call(uri);
+
+                Box::pin(async move {
+                    let inner = connecting.await.map_err(Into::into)?;
+                    if !https {
+                        return Ok(VerifiedStream {
+                            inner: LegacyTlsStream::Http(inner),
+                            tls: None,
+                            session: None,
+                        });
+                    }
+
+                    let mut config = connector.configure()?;
+                    config.set_verify_hostname(!insecure);
+                    let mut stream = SslStream::new(config.into_ssl(&host)?, TokioIo::new(inner))?;
+                    Pin::new(&mut stream).connect().await?;
+
+                    let session = UpstreamSession::from_openssl(stream.ssl());
+                    let tls = if insecure {
+                        verifier.inspect(&host, &session.certificates)
+                    } else {
+                        Some(UpstreamTls {
+                            status: CertificateStatus::Valid,
+                            insecure: false,
+                        })
+                    };
+                    Ok(VerifiedStream {
+                        inner: LegacyTlsStream::Https(TokioIo::new(stream)),
+                        tls,
+                        session: Some(session),
+                    })
+                })
+            }
+        }
+
+        /// [`LegacyTlsConnector`]가 연결한 평문 또는 OpenSSL TLS 스트림
+        #[derive(Debug)]
+        pub enum LegacyTlsStream<T> {
+            Http(T),
+            Https(TokioIo<SslStream<TokioIo<T>>>),
+        }
+
+        impl<T: Connection> Connection for LegacyTlsStream<T> {
+            fn connected(&self) -> Connected {
+                match self {
+                    Self::Http(stream) => stream.connected(),
+                    Self::Https(stream) => stream.inner().get_ref().inner().connected(),
+                }
+            }
+        }
+
+        impl<T: Read + Write + Unpin> Read for LegacyTlsStream<T> {
+            fn poll_read(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+                buf: ReadBufCursor<'_>,
+            ) -> Poll<io::Result<()>> {
+                match self.get_mut() {
+                    Self::Http(stream) => Pin::new(stream).poll_read(cx, buf),
+                    Self::Https(stream) => Pin::new(stream).poll_read(cx, buf),
+                }
+            }
+        }
+
+        impl<T: Read + Write + Unpin> Write for LegacyTlsStream<T> {
+            fn poll_write(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+                buf: &[u8],
+            ) -> Poll<io::Result<usize>> {
+                match self.get_mut() {
+                    Self::Http(stream) => Pin::new(stream).poll_write(cx, buf),
+                    Self::Https(stream) => Pin::new(stream).poll_write(cx, buf),
+                }
+            }
+
+            fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+                match self.get_mut() {
+                    Self::Http(stream) => Pin::new(stream).poll_flush(cx),
+                    Self::Https(stream) => Pin::new(stream).poll_flush(cx),
+                }
+            }
+
+            fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+                match self.get_mut() {
+                    Self::Http(stream) => Pin::new(stream).poll_shutdown(cx),
+                    Self::Https(stream) => Pin::new(stream).poll_shutdown(cx),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "rcgen-ca"))]
+mod tests {
+    use super::*;
+    use rcgen::{
+        BasicConstraints, Certificate, CertificateParams, DistinguishedName, DnType, IsCa, KeyPair,
+    };
+    use tokio_rustls::rustls::crypto::aws_lc_rs;
+
+    fn params(common_name: &str, names: &[&str]) -> CertificateParams {
+        let names = names
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>();
+        let mut params = CertificateParams::new(names).unwrap();
+        let mut distinguished_name = DistinguishedName::new();
+        distinguished_name.push(DnType::CommonName, common_name);
+        params.distinguished_name = distinguished_name;
+        params
+    }
+
+    fn ca() -> (Certificate, KeyPair) {
+        let key_pair = KeyPair::generate().unwrap();
+        let mut params = params("Upstream TLS Test CA", &[]);
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        (params.self_signed(&key_pair).unwrap(), key_pair)
+    }
+
+    fn leaf(issuer: Option<&(Certificate, KeyPair)>) -> CertificateDer<'static> {
+        let key_pair = KeyPair::generate().unwrap();
+        let params = params("Upstream TLS Test Server", &["localhost"]);
+        let certificate = match issuer {
+            Some((issuer, issuer_key)) => params.signed_by(&key_pair, issuer, issuer_key),
+            None => params.self_signed(&key_pair),
+        };
+        certificate.unwrap().der().clone()
+    }
+
+    fn inspect(verifier: &PolicyVerifier, host: &str, cert: &CertificateDer<'_>) -> UpstreamTls {
+        verifier.inspect(host, std::slice::from_ref(cert)).unwrap()
+    }
+
+    #[test]
+    fn matches_insecure_host_patterns() {
+        let policy = TlsVerificationPolicy::new()
+            .with_insecure_host("*.Internal.test")
+            .with_insecure_host("legacy.example.com");
+
+        assert!(policy.is_insecure("api.internal.test"));
+        assert!(!policy.is_insecure("internal.test"));
+        assert!(policy.is_insecure("LEGACY.example.com"));
+        assert!(!policy.is_insecure("example.com"));
+        assert!(TlsVerificationPolicy::insecure().is_insecure("anything"));
+    }
+
+    #[test]
+    fn classifies_certificates() {
+        let ca = ca();
+        let issued = leaf(Some(&ca));
+        let self_signed = leaf(None);
+        let provider = aws_lc_rs::default_provider();
+
+        let untrusted = TlsVerificationPolicy::new().verifier(&provider).unwrap();
+        assert_eq!(
+            inspect(&untrusted, "localhost", &self_signed).status,
+            CertificateStatus::SelfSigned
+        );
+        assert_eq!(
+            inspect(&untrusted, "localhost", &issued).status,
+            CertificateStatus::UnknownIssuer
+        );
+
+        let trusted = TlsVerificationPolicy::new()
+            .with_extra_ca(ca.0.der().clone())
+            .with_insecure_host("other.test")
+            .verifier(&provider)
+            .unwrap();
+        assert_eq!(
+            inspect(&trusted, "localhost", &issued),
+            UpstreamTls {
+                status: CertificateStatus::Valid,
+                insecure: false,
+            }
+        );
+        assert_eq!(
+            inspect(&trusted, "other.test", &issued),
+            UpstreamTls {
+                status: CertificateStatus::HostnameMismatch,
+                insecure: true,
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_unless_insecure() {
+        let self_signed = leaf(None);
+        let provider = aws_lc_rs::default_provider();
+        let server_name = ServerName::try_from("localhost").unwrap();
+
+        let strict = TlsVerificationPolicy::new().verifier(&provider).unwrap();
+        let error = strict
+            .verify_server_cert(&self_signed, &[], &server_name, &[], UnixTime::now())
+            .unwrap_err();
+        let error = io::Error::new(io::ErrorKind::InvalidData, error);
+        assert_eq!(
+            verification_error(&error),
+            Some(CertificateStatus::SelfSigned)
+        );
+
+        let insecure = TlsVerificationPolicy::insecure()
+            .verifier(&provider)
+            .unwrap();
+        assert!(
+            insecure
+                .verify_server_cert(&self_signed, &[], &server_name, &[], UnixTime::now())
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn detects_handshake_failures() {
+        let wrapped = |error: rustls::Error| io::Error::new(io::ErrorKind::InvalidData, error);
+
+        assert!(is_handshake_failure(&wrapped(
+            rustls::Error::AlertReceived(AlertDescription::HandshakeFailure)
+        )));
+        assert!(is_handshake_failure(&wrapped(
+            rustls::Error::AlertReceived(AlertDescription::ProtocolVersion)
+        )));
+        assert!(is_handshake_failure(&wrapped(
+            rustls::Error::PeerIncompatible(rustls::PeerIncompatible::Tls12NotOffered)
+        )));
+        assert!(!is_handshake_failure(&wrapped(
+            rustls::Error::AlertReceived(AlertDescription::BadCertificate)
+        )));
+        assert!(!is_handshake_failure(&io::Error::other(
+            "HandshakeFailure in a message"
+        )));
+    }
+}
@@ -0,0 +1,198 @@
+//! WebSocket 메시지 주입
+//!
+//! [`WebSocketInjector`]를 [`ProxyBuilder::with_websocket_injector`](crate::builder::ProxyBuilder::with_websocket_injector)로
+//! 등록하면 가로챈 WebSocket 연결의 방향마다 주입 채널이 열리고, [`WebSocketInjector::inject`]로
+//! 연결 ID([`WebSocketContext::connection_id`](crate::WebSocketContext::connection_id))와 방향을
+//! 지정해 메시지를 보낼 수 있습니다. 주입한 메시지도 같은 방향의 다른 메시지처럼
+//! [`WebSocketHandler::handle_message`](crate::WebSocketHandler::handle_message)를 거쳐 전달됩니다.
+
+use futures::{Stream, StreamExt, channel::mpsc, stream};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    task::Poll,
+};
+use thiserror::Error;
+use tokio_tungstenite::tungstenite::{self, Message};
+
+/// WebSocket 메시지 방향
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "rules",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum WebSocketDirection {
+    /// 클라이언트 → 서버
+    ClientToServer,
+    /// 서버 → 클라이언트
+    ServerToClient,
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum InjectError {
+    #[error("WebSocket connection {0} is not open")]
+    NotConnected(u64),
+}
+
+type ChannelKey = (u64, WebSocketDirection);
+
+/// 실행 중인 WebSocket 연결에 메시지를 주입하는 핸들
+///
+/// 복제본끼리 연결 목록을 공유하므로 프록시에 등록한 뒤에도 복제본으로 주입할 수 있습니다.
+#[derive(Clone, Debug, Default)]
+pub struct WebSocketInjector {
+    channels: Arc<RwLock<HashMap<ChannelKey, mpsc::UnboundedSender<Message>>>>,
+}
+
+impl WebSocketInjector {
+    /// 빈 주입 핸들 생성
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 연결에 메시지 주입
+    ///
+    /// `ClientToServer`면 서버로, `ServerToClient`면 클라이언트로 전송됩니다.
+    pub fn inject(
+        &self,
+        connection_id: u64,
+        direction: WebSocketDirection,
+        message: Message,
+    ) -> Result<(), InjectError> {
+        let channels = self
+            .channels
+            .read()
+            .expect("websocket injector lock poisoned");
+
+        channels
+            .get(&(connection_id, direction))
+            .ok_or(InjectError::NotConnected(connection_id))?
+            .unbounded_send(message)
+            .map_err(|_| InjectError::NotConnected(connection_id))
+    }
+
+    /// 열려 있는 연결 ID 목록 (오름차순)
+    pub fn connections(&self) -> Vec<u64> {
+        let channels = self
+            .channels
+            .read()
+            .expect("websocket injector lock poisoned");
+
+        let mut ids: Vec<u64> = channels.keys().map(|(id, _)| *id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+
+    /// 한 방향의 메시지 스트림에 주입 채널을 합침
+    ///
+    /// 원래 스트림이 끝나면 합친 스트림도 끝나며, 스트림을 버리면 채널이 등록 해제됩니다.
+    pub(crate) fn attach<S>(
+        &self,
+        connection_id: u64,
+        direction: WebSocketDirection,
+        mut messages: S,
+    ) -> impl Stream<Item = Result<Message, tungstenite::Error>> + Unpin + Send + 'static
+    where
+        S: Stream<Item = Result<Message, tungstenite::Error>> + Unpin + Send + 'static,
+    {
+        let (tx, mut injected) = mpsc::unbounded();
+        let key = (connection_id, direction);
+        self.channels
+            .write()
+            .expect("websocket injector lock poisoned")
+            .insert(key, tx);
+
+        let registration = Registration {
+            injector: self.clone(),
+            key,
+        };
+
+        stream::poll_fn(move |cx| {
+            let _registration = &registration;
+
+            match messages.poll_next_unpin(cx) {
+                Poll::Pending => match injected.poll_next_unpin(cx) {
+                    Poll::Ready(Some(message)) => Poll::Ready(Some(Ok(message))),
+                    _ => Poll::Pending,
+                },
+                ready => ready,
+            }
+        })
+    }
+}
+
+/// 스트림과 함께 버려지면 주입 채널을 등록 해제
+struct Registration {
+    injector: WebSocketInjector,
+    key: ChannelKey,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        if let Ok(mut channels) = self.injector.channels.write() {
+            channels.remove(&self.key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn merges_injected_messages() {
+        let injector = WebSocketInjector::new();
+        let mut messages = injector.attach(
+            1,
+            WebSocketDirection::ClientToServer,
+            stream::pending::<Result<Message, tungstenite::Error>>(),
+        );
+
+        injector
+            .inject(1, WebSocketDirection::ClientToServer, Message::text("hi"))
+            .unwrap();
+
+        let message = messages.next().await.unwrap().unwrap();
+        assert_eq!(message, Message::text("hi"));
+        assert_eq!(injector.connections(), vec![1]);
+    }
+
+    #[tokio::test]
+    async fn ends_with_original_stream() {
+        let injector = WebSocketInjector::new();
+        let mut messages = injector.attach(
+            1,
+            WebSocketDirection::ServerToClient,
+            stream::iter(vec![Ok(Message::text("a"))]),
+        );
+
+        assert_eq!(messages.next().await.unwrap().unwrap(), Message::text("a"));
+        assert!(messages.next().await.is_none());
+    }
+
+    #[test]
+    fn unregisters_dropped_streams() {
+        let injector = WebSocketInjector::new();
+        let messages = injector.attach(
+            1,
+            WebSocketDirection::ServerToClient,
+            stream::pending::<Result<Message, tungstenite::Error>>(),
+        );
+
+        assert!(matches!(
+            injector.inject(1, WebSocketDirection::ClientToServer, Message::text("x")),
+            Err(InjectError::NotConnected(1))
+        ));
+
+        drop(messages);
+        assert!(injector.connections().is_empty());
+        assert!(
+            injector
+                .inject(1, WebSocketDirection::ServerToClient, Message::text("x"))
+                .is_err()
+        );
+    }
+}
@@ -19,8 +19,15 @@ use proxyapi_v2::{
     rustls,
     tokio_tungstenite::tungstenite::{Message, Utf8Bytes},
 };
+#[cfg(feature = "rcgen-ca")]
+use proxyapi_v2::{
+    certificate_authority::{RcgenAuthority, RootCa},
+    rustls::crypto::aws_lc_rs,
+};
 use reqwest::tls::Certificate;
 use rustls_pemfile as pemfile;
+#[cfg(feature = "rcgen-ca")]
+use std::sync::OnceLock;
 use std::{
     convert::Infallible,
     net::SocketAddr,
@@ -37,6 +44,32 @@ use tokio_util::io::ReaderStream;
 pub const HELLO_WORLD: &str = "Hello, World";
 pub const WORLD: Utf8Bytes = Utf8Bytes::from_static("world");
 
+/// 테스트 프로세스마다 새로 생성하는 루트 CA
+#[cfg(feature = "rcgen-ca")]
+pub fn root_ca() -> &'static RootCa {
+    static ROOT_CA: OnceLock<RootCa> = OnceLock::new();
+
+    ROOT_CA.get_or_init(|| RootCa::generate("proxyapi_v2 test CA").expect("Failed to generate CA"))
+}
+
+/// 테스트용 루트 CA로 서명하는 RcgenAuthority
+#[cfg(feature = "rcgen-ca")]
+pub fn build_ca() -> RcgenAuthority {
+    RcgenAuthority::from_root_ca(root_ca(), 1000, aws_lc_rs::default_provider())
+        .expect("Failed to build CA")
+}
+
+/// 테스트 클라이언트가 신뢰할 CA 인증서 (PEM)
+#[cfg(feature = "rcgen-ca")]
+pub fn ca_cert_pem() -> &'static [u8] {
+    root_ca().cert_pem().as_bytes()
+}
+
+#[cfg(not(feature = "rcgen-ca"))]
+pub fn ca_cert_pem() -> &'static [u8] {
+    include_bytes!("../../examples/ca/hudsucker.cer")
+}
+
 async fn test_server(req: Request<Incoming>) -> Result<Response<Body>, Infallible> {
     if hyper_tungstenite::is_upgrade_request(&req) {
         let (res, ws) = hyper_tungstenite::upgrade(req, None).unwrap();
@@ -162,8 +195,7 @@ fn rustls_client_config() -> rustls::ClientConfig {
         roots.add(cert.clone()).unwrap();
     }
 
-    let mut ca_cert_bytes: &[u8] =
-        include_bytes!("../../src/certificate_authority/cheolsu-proxy.cer");
+    let mut ca_cert_bytes = ca_cert_pem();
     let ca_cert = pemfile::certs(&mut ca_cert_bytes)
         .next()
         .unwrap()
@@ -194,10 +226,7 @@ pub fn rustls_client() -> Client<hyper_rustls::HttpsConnector<HttpConnector>, Bo
 }
 
 fn native_tls_connector() -> native_tls::TlsConnector {
-    let ca_cert = native_tls::Certificate::from_pem(include_bytes!(
-        "../../src/certificate_authority/cheolsu-proxy.cer"
-    ))
-    .unwrap();
+    let ca_cert = native_tls::Certificate::from_pem(ca_cert_pem()).unwrap();
 
     native_tls::TlsConnector::builder()
         .add_root_certificate(ca_cert)
@@ -327,10 +356,7 @@ where
 
 pub fn build_client(proxy: &str) -> reqwest::Client {
     let proxy = reqwest::Proxy::all(proxy).unwrap();
-    let ca_cert = Certificate::from_pem(include_bytes!(
-        "../../src/certificate_authority/cheolsu-proxy.cer"
-    ))
-    .unwrap();
+    let ca_cert = Certificate::from_pem(ca_cert_pem()).unwrap();
 
     reqwest::Client::builder()
         .proxy(proxy)
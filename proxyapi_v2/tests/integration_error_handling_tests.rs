@@ -360,7 +360,8 @@ async fn start_proxy_server(
     let (tx, rx) = tokio::sync::oneshot::channel();
 
     // CA 인증서 생성
-    let ca = build_ca()?;
+    let ca_dir = tempfile::tempdir()?;
+    let ca = build_ca(ca_dir.path())?;
 
     // 하이브리드 클라이언트 생성 (모든 인증서 허용)
     let hybrid_client = create_hybrid_client()?;
@@ -1,11 +1,6 @@
 use async_http_proxy::http_connect_tokio;
 use futures::{SinkExt, StreamExt};
-use proxyapi_v2::{
-    certificate_authority::RcgenAuthority,
-    rcgen::{CertificateParams, KeyPair},
-    rustls::crypto::aws_lc_rs,
-    tokio_tungstenite::tungstenite::{Message, Utf8Bytes},
-};
+use proxyapi_v2::tokio_tungstenite::tungstenite::{Message, Utf8Bytes};
 use std::sync::atomic::Ordering;
 use tokio::net::TcpStream;
 
@@ -14,23 +9,11 @@ mod common;
 
 const HELLO: Utf8Bytes = Utf8Bytes::from_static("hello");
 
-fn build_ca() -> RcgenAuthority {
-    let key_pair = include_str!("../src/certificate_authority/cheolsu-proxy.key");
-    let ca_cert = include_str!("../src/certificate_authority/cheolsu-proxy.cer");
-    let key_pair = KeyPair::from_pem(key_pair).expect("Failed to parse private key");
-    let ca_cert = CertificateParams::from_ca_cert_pem(ca_cert)
-        .expect("Failed to parse CA certificate")
-        .self_signed(&key_pair)
-        .expect("Failed to sign CA certificate");
-
-    RcgenAuthority::new(key_pair, ca_cert, 1000, aws_lc_rs::default_provider())
-}
-
 #[tokio::test]
 async fn http() {
     // 실제 Tauri 환경과 동일하게 WebSocket 핸들러 포함해서 테스트
     let (proxy_addr, handler, stop_proxy) = common::start_proxy(
-        build_ca(),
+        common::build_ca(),
         common::native_tls_client(),
         common::native_tls_websocket_connector(),
     )
@@ -72,14 +55,16 @@ async fn http() {
 #[tokio::test]
 async fn https_rustls() {
     let (proxy_addr, handler, stop_proxy) = common::start_proxy(
-        build_ca(),
+        common::build_ca(),
         common::rustls_client(),
         common::rustls_websocket_connector(),
     )
     .await
     .unwrap();
 
-    let (server_addr, stop_server) = common::start_https_server(build_ca()).await.unwrap();
+    let (server_addr, stop_server) = common::start_https_server(common::build_ca())
+        .await
+        .unwrap();
 
     let mut stream = TcpStream::connect(proxy_addr).await.unwrap();
     http_connect_tokio(&mut stream, "localhost", server_addr.port())
@@ -109,14 +94,16 @@ async fn https_rustls() {
 #[tokio::test]
 async fn https_native_tls() {
     let (proxy_addr, handler, stop_proxy) = common::start_proxy(
-        build_ca(),
+        common::build_ca(),
         common::native_tls_client(),
         common::native_tls_websocket_connector(),
     )
     .await
     .unwrap();
 
-    let (server_addr, stop_server) = common::start_https_server(build_ca()).await.unwrap();
+    let (server_addr, stop_server) = common::start_https_server(common::build_ca())
+        .await
+        .unwrap();
 
     let mut stream = TcpStream::connect(proxy_addr).await.unwrap();
     http_connect_tokio(&mut stream, "localhost", server_addr.port())
@@ -146,7 +133,7 @@ async fn https_native_tls() {
 #[tokio::test]
 async fn without_intercept() {
     let (proxy_addr, handler, stop_proxy) = common::start_proxy_without_intercept(
-        build_ca(),
+        common::build_ca(),
         common::http_client(),
         common::plain_websocket_connector(),
     )
@@ -181,7 +168,7 @@ async fn without_intercept() {
 
 #[tokio::test]
 async fn noop() {
-    let (proxy_addr, stop_proxy) = common::start_noop_proxy(build_ca()).await.unwrap();
+    let (proxy_addr, stop_proxy) = common::start_noop_proxy(common::build_ca()).await.unwrap();
     let (server_addr, stop_server) = common::start_http_server().await.unwrap();
 
     let mut stream = TcpStream::connect(proxy_addr).await.unwrap();
@@ -0,0 +1,464 @@
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use proxyapi_v2::{
+    decode_response,
+    hyper::http::{header, request::Parts, HeaderMap, HeaderName, HeaderValue, StatusCode},
+    hyper::{Method, Request, Response, Uri},
+    rules::{CompiledRule, Rule, RuleError, RuleMatcher},
+    Body,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tauri::{AppHandle, Runtime, State};
+use tauri_plugin_store::StoreExt;
+use tokio::sync::{mpsc, oneshot, Mutex};
+
+/// 브레이크포인트 응답 대기 기본 시간 (초)
+const DEFAULT_TIMEOUT_SECS: u64 = 300;
+
+/// 브레이크포인트 정의 (스토어의 `breakpoints` 키)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Breakpoint {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    /// 요청 매칭 조건 (규칙 엔진과 동일한 형식)
+    #[serde(rename = "match", default)]
+    pub matcher: RuleMatcher,
+    /// 요청을 업스트림으로 보내기 전에 멈춤
+    #[serde(default = "enabled_by_default")]
+    pub request: bool,
+    /// 응답을 클라이언트로 보내기 전에 멈춤
+    #[serde(default)]
+    pub response: bool,
+    /// 사용자 응답 대기 시간 (초과 시 수정 없이 계속 진행)
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+fn default_timeout_secs() -> u64 {
+    DEFAULT_TIMEOUT_SECS
+}
+
+/// 매처를 컴파일한 브레이크포인트
+#[derive(Debug)]
+pub struct CompiledBreakpoint {
+    breakpoint: Breakpoint,
+    rule: CompiledRule,
+}
+
+impl CompiledBreakpoint {
+    fn compile(breakpoint: Breakpoint) -> Result<Self, RuleError> {
+        let rule = CompiledRule::compile(Rule {
+            id: breakpoint.id.clone(),
+            name: String::new(),
+            enabled: breakpoint.enabled,
+            matcher: breakpoint.matcher.clone(),
+            actions: Vec::new(),
+        })?;
+        Ok(Self { breakpoint, rule })
+    }
+
+    /// 요청 단계에서 멈추는지 확인
+    pub fn on_request(&self) -> bool {
+        self.breakpoint.request
+    }
+
+    /// 응답 단계에서 멈추는지 확인
+    pub fn on_response(&self) -> bool {
+        self.breakpoint.response
+    }
+}
+
+/// 멈춘 단계
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BreakpointStage {
+    Request,
+    Response,
+}
+
+/// UI로 전송되는 멈춘 메시지 (편집 가능한 형태)
+#[derive(Clone, Debug, Serialize)]
+pub struct BreakpointHit {
+    pub pause_id: String,
+    pub breakpoint_id: String,
+    pub stage: BreakpointStage,
+    pub method: String,
+    pub url: String,
+    pub status: Option<u16>,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+    /// 본문이 UTF-8이 아니면 true (이 경우 body는 표시용)
+    pub body_is_binary: bool,
+    pub timeout_secs: u64,
+}
+
+/// 브레이크포인트 이벤트 (setup에서 Tauri 이벤트로 전송)
+#[derive(Clone, Debug)]
+pub enum BreakpointEvent {
+    /// 메시지가 멈춤 (`breakpoint_hit`)
+    Hit(BreakpointHit),
+    /// 재개 또는 타임아웃으로 해제됨 (`breakpoint_released`)
+    Released { pause_id: String, timed_out: bool },
+}
+
+/// 사용자가 편집한 메시지 (지정한 항목만 교체)
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct EditedMessage {
+    #[serde(default)]
+    pub method: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub status: Option<u16>,
+    #[serde(default)]
+    pub headers: Option<Vec<(String, String)>>,
+    #[serde(default)]
+    pub body: Option<String>,
+}
+
+/// `resume_breakpoint` 명령으로 전달되는 처리 방법
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum BreakpointResolution {
+    /// 계속 진행 (편집 내용이 있으면 반영)
+    Continue {
+        #[serde(default)]
+        edit: Option<EditedMessage>,
+    },
+    /// 지정한 상태 코드로 중단
+    Abort {
+        #[serde(default = "default_abort_status")]
+        status: u16,
+        #[serde(default)]
+        body: String,
+    },
+}
+
+fn default_abort_status() -> u16 {
+    StatusCode::BAD_GATEWAY.as_u16()
+}
+
+/// 브레이크포인트 목록과 멈춘 메시지를 관리
+#[derive(Clone)]
+pub struct BreakpointManager {
+    breakpoints: Arc<RwLock<Vec<Arc<CompiledBreakpoint>>>>,
+    pending: Arc<Mutex<HashMap<String, oneshot::Sender<BreakpointResolution>>>>,
+    next_id: Arc<AtomicU64>,
+    events: mpsc::UnboundedSender<BreakpointEvent>,
+}
+
+impl BreakpointManager {
+    pub fn new(events: mpsc::UnboundedSender<BreakpointEvent>) -> Self {
+        Self {
+            breakpoints: Arc::new(RwLock::new(Vec::new())),
+            pending: Arc::new(Mutex::new(HashMap::new())),
+            next_id: Arc::new(AtomicU64::new(1)),
+            events,
+        }
+    }
+
+    /// 브레이크포인트 목록 교체 (유효하지 않은 항목은 건너뛰고 오류 반환)
+    pub fn set_breakpoints(&self, breakpoints: Vec<Breakpoint>) -> Vec<RuleError> {
+        let mut compiled = Vec::new();
+        let mut errors = Vec::new();
+
+        for breakpoint in breakpoints {
+            match CompiledBreakpoint::compile(breakpoint) {
+                Ok(breakpoint) => compiled.push(Arc::new(breakpoint)),
+                Err(e) => errors.push(e),
+            }
+        }
+
+        *self.breakpoints.write().expect("breakpoint lock poisoned") = compiled;
+        errors
+    }
+
+    /// 요청 본문 조건이 있는 브레이크포인트가 있는지 확인 (없으면 요청 본문 없이 매칭 가능)
+    pub fn matches_request_body(&self) -> bool {
+        self.breakpoints
+            .read()
+            .expect("breakpoint lock poisoned")
+            .iter()
+            .any(|breakpoint| breakpoint.rule.matches_body())
+    }
+
+    /// 요청과 매칭되는 첫 번째 브레이크포인트 반환
+    pub fn match_request(&self, parts: &Parts, body: &[u8]) -> Option<Arc<CompiledBreakpoint>> {
+        self.breakpoints
+            .read()
+            .expect("breakpoint lock poisoned")
+            .iter()
+            .find(|breakpoint| breakpoint.rule.matches(parts, body))
+            .cloned()
+    }
+
+    /// 요청을 멈추고 사용자 처리를 기다림
+    ///
+    /// 중단(abort)을 선택하면 클라이언트로 보낼 응답을 함께 반환합니다.
+    pub async fn pause_request(
+        &self,
+        breakpoint: &CompiledBreakpoint,
+        req: Request<Bytes>,
+    ) -> (Request<Bytes>, Option<Response<Bytes>>) {
+        let (mut parts, mut body) = req.into_parts();
+        let hit = self.new_hit(
+            breakpoint,
+            BreakpointStage::Request,
+            &parts.method,
+            &parts.uri,
+            None,
+            &parts.headers,
+            &body,
+        );
+
+        match self.wait(hit, breakpoint.breakpoint.timeout_secs).await {
+            Some(BreakpointResolution::Continue { edit: Some(edit) }) => {
+                if let Some(method) = edit
+                    .method
+                    .and_then(|m| Method::from_bytes(m.to_uppercase().as_bytes()).ok())
+                {
+                    parts.method = method;
+                }
+                if let Some(uri) = edit.url.and_then(|url| url.parse::<Uri>().ok()) {
+                    parts.uri = uri;
+                }
+                apply_edit(&mut parts.headers, &mut body, edit.headers, edit.body);
+                (Request::from_parts(parts, body), None)
+            }
+            Some(BreakpointResolution::Abort {
+                status,
+                body: abort_body,
+            }) => (
+                Request::from_parts(parts, body),
+                Some(abort_response(status, abort_body)),
+            ),
+            _ => (Request::from_parts(parts, body), None),
+        }
+    }
+
+    /// 응답을 멈추고 사용자 처리를 기다림 (압축된 본문은 해제 후 전달)
+    pub async fn pause_response(
+        &self,
+        breakpoint: &CompiledBreakpoint,
+        method: &Method,
+        uri: &Uri,
+        res: Response<Bytes>,
+    ) -> Response<Bytes> {
+        let (mut parts, mut body) = decode(res).await.into_parts();
+        let hit = self.new_hit(
+            breakpoint,
+            BreakpointStage::Response,
+            method,
+            uri,
+            Some(parts.status),
+            &parts.headers,
+            &body,
+        );
+
+        match self.wait(hit, breakpoint.breakpoint.timeout_secs).await {
+            Some(BreakpointResolution::Continue { edit: Some(edit) }) => {
+                if let Some(status) = edit.status.and_then(|s| StatusCode::from_u16(s).ok()) {
+                    parts.status = status;
+                }
+                apply_edit(&mut parts.headers, &mut body, edit.headers, edit.body);
+                Response::from_parts(parts, body)
+            }
+            Some(BreakpointResolution::Abort { status, body }) => abort_response(status, body),
+            _ => Response::from_parts(parts, body),
+        }
+    }
+
+    /// 멈춘 메시지 재개
+    pub async fn resume(
+        &self,
+        pause_id: &str,
+        resolution: BreakpointResolution,
+    ) -> Result<(), String> {
+        let sender = self
+            .pending
+            .lock()
+            .await
+            .remove(pause_id)
+            .ok_or_else(|| format!("대기 중인 브레이크포인트가 없습니다: {}", pause_id))?;
+
+        sender
+            .send(resolution)
+            .map_err(|_| format!("브레이크포인트 재개 실패 (이미 종료됨): {}", pause_id))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn new_hit(
+        &self,
+        breakpoint: &CompiledBreakpoint,
+        stage: BreakpointStage,
+        method: &Method,
+        uri: &Uri,
+        status: Option<StatusCode>,
+        headers: &HeaderMap,
+        body: &Bytes,
+    ) -> BreakpointHit {
+        let (body, body_is_binary) = match std::str::from_utf8(body) {
+            Ok(body) => (body.to_string(), false),
+            Err(_) => (String::from_utf8_lossy(body).into_owned(), true),
+        };
+
+        BreakpointHit {
+            pause_id: format!("bp-{}", self.next_id.fetch_add(1, Ordering::Relaxed)),
+            breakpoint_id: breakpoint.breakpoint.id.clone(),
+            stage,
+            method: method.to_string(),
+            url: uri.to_string(),
+            status: status.map(|status| status.as_u16()),
+            headers: headers
+                .iter()
+                .map(|(k, v)| {
+                    (
+                        k.as_str().to_string(),
+                        String::from_utf8_lossy(v.as_bytes()).into_owned(),
+                    )
+                })
+                .collect(),
+            body,
+            body_is_binary,
+            timeout_secs: breakpoint.breakpoint.timeout_secs,
+        }
+    }
+
+    /// UI에 멈춘 메시지를 알리고 처리 방법을 기다림 (타임아웃 시 None)
+    async fn wait(&self, hit: BreakpointHit, timeout_secs: u64) -> Option<BreakpointResolution> {
+        let pause_id = hit.pause_id.clone();
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().await.insert(pause_id.clone(), tx);
+
+        println!(
+            "⏸️ 브레이크포인트 [{}] {} {}",
+            pause_id, hit.method, hit.url
+        );
+        if self.events.send(BreakpointEvent::Hit(hit)).is_err() {
+            // UI 이벤트 루프가 없으면 멈추지 않고 진행
+            self.pending.lock().await.remove(&pause_id);
+            return None;
+        }
+
+        let result = tokio::time::timeout(Duration::from_secs(timeout_secs), rx).await;
+        let timed_out = result.is_err();
+        if timed_out {
+            self.pending.lock().await.remove(&pause_id);
+            println!("⏰ 브레이크포인트 [{}] 타임아웃 - 수정 없이 진행", pause_id);
+        }
+
+        let _ = self.events.send(BreakpointEvent::Released {
+            pause_id,
+            timed_out,
+        });
+
+        result.ok().and_then(Result::ok)
+    }
+}
+
+/// 편집된 헤더/본문 반영
+fn apply_edit(
+    headers: &mut HeaderMap,
+    body: &mut Bytes,
+    edited_headers: Option<Vec<(String, String)>>,
+    edited_body: Option<String>,
+) {
+    if let Some(edited_headers) = edited_headers {
+        *headers = edited_headers
+            .iter()
+            .filter_map(|(name, value)| {
+                Some((
+                    HeaderName::from_bytes(name.as_bytes()).ok()?,
+                    HeaderValue::from_str(value).ok()?,
+                ))
+            })
+            .collect();
+    }
+
+    if let Some(edited_body) = edited_body {
+        *body = Bytes::from(edited_body);
+    }
+
+    // content-length는 실제 본문 길이에 맞춤
+    if headers.contains_key(header::CONTENT_LENGTH) {
+        headers.insert(header::CONTENT_LENGTH, HeaderValue::from(body.len()));
+    }
+}
+
+/// 중단 시 클라이언트로 보낼 응답 생성
+fn abort_response(status: u16, body: String) -> Response<Bytes> {
+    let mut response = Response::new(Bytes::from(body));
+    *response.status_mut() = StatusCode::from_u16(status).unwrap_or(StatusCode::BAD_GATEWAY);
+    response.headers_mut().insert(
+        "x-cheolsu-proxy-breakpoint",
+        HeaderValue::from_static("aborted"),
+    );
+    response
+}
+
+/// 압축된 응답 본문 해제 (실패 시 원본 유지)
+async fn decode(res: Response<Bytes>) -> Response<Bytes> {
+    let (parts, body) = res.into_parts();
+    let status = parts.status;
+    let version = parts.version;
+    let headers = parts.headers.clone();
+
+    let decoded = decode_response(Response::from_parts(
+        parts,
+        Body::from(Full::new(body.clone())),
+    ));
+    let decoded = match decoded {
+        Ok(decoded) => {
+            let (parts, decoded_body) = decoded.into_parts();
+            match decoded_body.collect().await {
+                Ok(collected) => Some(Response::from_parts(parts, collected.to_bytes())),
+                Err(_) => None,
+            }
+        }
+        Err(_) => None,
+    };
+
+    decoded.unwrap_or_else(|| {
+        let mut response = Response::new(body);
+        *response.status_mut() = status;
+        *response.version_mut() = version;
+        *response.headers_mut() = headers;
+        response
+    })
+}
+
+/// 스토어에서 브레이크포인트 목록 로드
+pub fn load_breakpoints<R: Runtime>(app: &AppHandle<R>) -> Result<Vec<Breakpoint>, String> {
+    let store = app
+        .store("session.json")
+        .map_err(|e| format!("세션 스토어 로드 실패: {}", e))?;
+
+    let breakpoints = match store.get("breakpoints") {
+        Some(value) => {
+            serde_json::from_value(value).map_err(|e| format!("브레이크포인트 파싱 실패: {}", e))?
+        }
+        None => Vec::new(),
+    };
+
+    Ok(breakpoints)
+}
+
+/// 멈춘 요청/응답 재개 (편집 반영 또는 중단)
+#[tauri::command]
+pub async fn resume_breakpoint(
+    breakpoints: State<'_, BreakpointManager>,
+    pause_id: String,
+    resolution: BreakpointResolution,
+) -> Result<(), String> {
+    breakpoints.resume(&pause_id, resolution).await
+}
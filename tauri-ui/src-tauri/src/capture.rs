@@ -0,0 +1,127 @@
+use crate::capture_policy::remove_spill_files;
+use crate::storage::{run_blocking, TrafficStore};
+use proxy_v2_models::{Har, RequestInfo};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Runtime, State};
+use tokio::sync::Mutex;
+
+/// 메모리에 보관하는 최대 교환(요청/응답 쌍) 수
+const MAX_CAPTURED_EXCHANGES: usize = 10_000;
+
+/// 현재 캡처된 교환 목록 (HAR 내보내기 등에 사용)
+#[derive(Clone, Default)]
+pub struct CaptureState {
+    exchanges: Arc<Mutex<VecDeque<RequestInfo>>>,
+}
+
+impl CaptureState {
+    /// 교환을 캡처 목록에 추가 (최대 개수를 넘으면 가장 오래된 항목 제거)
+    pub async fn push(&self, info: RequestInfo) {
+        let mut exchanges = self.exchanges.lock().await;
+        if exchanges.len() >= MAX_CAPTURED_EXCHANGES {
+            exchanges.pop_front();
+        }
+        exchanges.push_back(info);
+    }
+
+    /// 캡처 목록을 HAR 로그로 변환
+    pub async fn to_har(&self) -> Har {
+        let exchanges = self.exchanges.lock().await;
+        Har::from_request_infos(exchanges.iter())
+    }
+
+    /// 캡처 목록 비우기 (비운 교환 반환)
+    pub async fn clear(&self) -> Vec<RequestInfo> {
+        self.exchanges.lock().await.drain(..).collect()
+    }
+}
+
+/// 새 교환을 캡처 목록과 디스크 저장소에 기록하고 UI에 전달
+pub async fn publish_exchange<R: Runtime>(
+    app: &AppHandle<R>,
+    capture: &CaptureState,
+    storage: &TrafficStore,
+    info: RequestInfo,
+) {
+    // HAR 내보내기를 위해 캡처 목록에도 보관
+    capture.push(info.clone()).await;
+    // 재시작 후에도 조회할 수 있도록 디스크 저장소에 저장 (SQLite 쓰기와 정리는 블로킹 스레드에서)
+    let stored = info.clone();
+    if let Err(e) = run_blocking(storage, move |storage| storage.insert(&stored)).await {
+        eprintln!("⚠️ {}", e);
+    }
+    let _ = app.emit("proxy_event", info);
+}
+
+/// 현재 캡처된 트래픽을 HAR 파일로 내보내기
+#[tauri::command]
+pub async fn export_har(capture: State<'_, CaptureState>, path: String) -> Result<usize, String> {
+    let har = capture.to_har().await;
+    let entry_count = har.log.entries.len();
+
+    let json = har
+        .to_json()
+        .map_err(|e| format!("HAR 직렬화 실패: {}", e))?;
+    tokio::fs::write(&path, json)
+        .await
+        .map_err(|e| format!("HAR 파일 저장 실패 ({}): {}", path, e))?;
+
+    println!("✅ HAR 내보내기 완료: {} 개의 항목 → {}", entry_count, path);
+    Ok(entry_count)
+}
+
+/// HAR 파일을 가져와서 UI 목록에 추가
+#[tauri::command]
+pub async fn import_har<R: Runtime>(
+    app: AppHandle<R>,
+    capture: State<'_, CaptureState>,
+    path: String,
+) -> Result<usize, String> {
+    let json = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("HAR 파일 읽기 실패 ({}): {}", path, e))?;
+    let har = Har::from_json(&json).map_err(|e| format!("HAR 파싱 실패: {}", e))?;
+    let infos = har
+        .to_request_infos()
+        .map_err(|e| format!("HAR 변환 실패: {}", e))?;
+
+    let entry_count = infos.len();
+    for info in infos {
+        capture.push(info.clone()).await;
+        // 프록시로 캡처된 항목과 동일한 이벤트로 UI 목록에 추가
+        let _ = app.emit("proxy_event", info);
+    }
+
+    println!("✅ HAR 가져오기 완료: {} 개의 항목 ← {}", entry_count, path);
+    Ok(entry_count)
+}
+
+/// 캡처된 트래픽 목록 비우기
+#[tauri::command]
+pub async fn clear_capture(
+    capture: State<'_, CaptureState>,
+    storage: State<'_, TrafficStore>,
+) -> Result<(), String> {
+    let cleared = capture.clear().await;
+
+    // 저장소에 남아 있는 교환의 본문 임시 파일은 저장소에서 삭제할 때 정리
+    run_blocking(&storage, move |storage| {
+        let referenced = storage.spill_paths()?;
+        remove_spill_files(
+            cleared
+                .iter()
+                .flat_map(|RequestInfo(req, res)| {
+                    [
+                        req.as_ref().and_then(|req| req.truncated()),
+                        res.as_ref().and_then(|res| res.truncated()),
+                    ]
+                })
+                .flatten()
+                .filter_map(|truncated| truncated.spill_path.clone())
+                .filter(|path| !referenced.contains(path)),
+        );
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,499 @@
+use proxy_v2_models::{BodyTruncation, Bytes, HeaderMap};
+use proxyapi_v2::hyper::header::CONTENT_TYPE;
+use proxyapi_v2::rules::glob_match;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::{JsonValue, StoreExt};
+use tokio::io::AsyncWriteExt;
+
+/// 캡처 정책 설정이 저장된 스토어 파일
+const SETTINGS_STORE: &str = "session.json";
+
+/// 본문 임시 파일 확장자
+const SPILL_EXTENSION: &str = "body";
+
+/// 한도를 넘은 본문 처리 방식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Overflow {
+    /// 한도까지만 기록
+    #[default]
+    Truncate,
+    /// 한도까지만 메모리에 기록하고 전체 본문은 임시 파일에 저장
+    Spill,
+}
+
+/// 본문 캡처 정책 (스토어의 `capturePolicy` 키)
+///
+/// 프록시는 정책과 관계없이 본문 전체를 전달하며, 정책은 기록(UI, 저장소, 내보내기)에만 적용됩니다.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CapturePolicy {
+    /// 메모리에 기록할 본문 최대 크기 (bytes)
+    pub max_body_bytes: usize,
+    /// 기록할 Content-Type (비어 있으면 모두, `image/*`처럼 와일드카드 사용 가능)
+    pub include_content_types: Vec<String>,
+    /// 기록하지 않을 Content-Type (본문 없이 크기만 기록)
+    pub exclude_content_types: Vec<String>,
+    /// 한도를 넘은 본문 처리 방식
+    pub overflow: Overflow,
+    /// 임시 파일을 저장할 디렉터리 (없으면 시스템 임시 디렉터리)
+    pub spill_dir: Option<PathBuf>,
+}
+
+impl Default for CapturePolicy {
+    fn default() -> Self {
+        Self {
+            max_body_bytes: 16 * 1024 * 1024,
+            include_content_types: Vec::new(),
+            exclude_content_types: Vec::new(),
+            overflow: Overflow::default(),
+            spill_dir: None,
+        }
+    }
+}
+
+impl CapturePolicy {
+    /// Content-Type이 기록 대상인지 확인 (Content-Type이 없으면 include 목록이 비어 있을 때만 기록)
+    fn captures(&self, headers: &HeaderMap) -> bool {
+        let mime = headers
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(';').next())
+            .map(|mime| mime.trim().to_ascii_lowercase())
+            .unwrap_or_default();
+        let matches = |patterns: &[String]| {
+            patterns
+                .iter()
+                .any(|pattern| glob_match(&pattern.to_ascii_lowercase(), &mime))
+        };
+
+        (self.include_content_types.is_empty() || matches(&self.include_content_types))
+            && !matches(&self.exclude_content_types)
+    }
+
+    /// 메모리에 기록할 본문 최대 크기 (기록 대상이 아니면 0)
+    pub fn limit(&self, headers: &HeaderMap) -> usize {
+        if self.captures(headers) {
+            self.max_body_bytes
+        } else {
+            0
+        }
+    }
+
+    /// 임시 파일을 저장할 디렉터리
+    pub fn spill_dir(&self) -> PathBuf {
+        self.spill_dir
+            .clone()
+            .unwrap_or_else(|| std::env::temp_dir().join("cheolsu-proxy"))
+    }
+
+    /// 임시 파일 디렉터리 안에 있는 본문 임시 파일인지 확인하고 실제 경로 반환
+    ///
+    /// UI에서 전달한 경로는 그대로 믿지 않고, 심볼릭 링크나 `..`를 풀어서 확인합니다.
+    pub async fn resolve_spill_path(&self, path: &str) -> Result<PathBuf, String> {
+        let dir = self.spill_dir();
+        let dir = tokio::fs::canonicalize(&dir)
+            .await
+            .map_err(|e| format!("본문 임시 디렉터리 확인 실패 ({}): {}", dir.display(), e))?;
+        let resolved = tokio::fs::canonicalize(path)
+            .await
+            .map_err(|e| format!("본문 임시 파일 확인 실패 ({}): {}", path, e))?;
+
+        let is_spill_file = resolved.parent() == Some(dir.as_path())
+            && resolved
+                .extension()
+                .is_some_and(|ext| ext == SPILL_EXTENSION);
+        if !is_spill_file {
+            return Err(format!(
+                "본문 임시 디렉터리 밖의 파일은 사용할 수 없습니다: {}",
+                path
+            ));
+        }
+        Ok(resolved)
+    }
+
+    /// 한도를 넘은 본문을 저장할 임시 파일 (저장하지 않는 정책이면 None)
+    ///
+    /// `name`은 파일 이름으로 사용하므로 교환마다 달라야 합니다.
+    pub fn spill(&self, headers: &HeaderMap, name: &str) -> Option<BodySpill> {
+        if self.overflow != Overflow::Spill || !self.captures(headers) {
+            return None;
+        }
+
+        Some(BodySpill {
+            limit: self.max_body_bytes as u64,
+            seen: 0,
+            path: self
+                .spill_dir()
+                .join(format!("{}.{}", name, SPILL_EXTENSION)),
+            file: None,
+            failed: false,
+        })
+    }
+
+    /// 이미 받은 본문에 정책을 적용해 기록할 본문 반환
+    pub async fn record(
+        &self,
+        headers: &HeaderMap,
+        name: &str,
+        body: &Bytes,
+    ) -> (Bytes, Option<BodyTruncation>) {
+        let limit = self.limit(headers);
+        if body.len() <= limit {
+            return (body.clone(), None);
+        }
+
+        let captured = body.slice(..limit);
+        let spill_path = match self.spill(headers, name) {
+            Some(mut spill) => {
+                spill.push(body, || captured.clone()).await;
+                spill.finish().await
+            }
+            None => None,
+        };
+        let truncated = BodyTruncation {
+            size: body.len() as u64,
+            spill_path,
+        };
+
+        (captured, Some(truncated))
+    }
+}
+
+/// 캡처 한도를 넘은 본문을 임시 파일로 저장
+///
+/// 한도를 넘는 순간 파일을 만들어 이미 기록한 앞부분과 나머지를 이어서 씁니다.
+pub struct BodySpill {
+    limit: u64,
+    seen: u64,
+    path: PathBuf,
+    file: Option<tokio::fs::File>,
+    failed: bool,
+}
+
+impl BodySpill {
+    /// 본문 조각 추가 (`captured`는 지금까지 메모리에 기록한 앞부분)
+    pub async fn push(&mut self, chunk: &[u8], captured: impl FnOnce() -> Bytes) {
+        let before = self.seen;
+        self.seen += chunk.len() as u64;
+        if self.failed || self.seen <= self.limit {
+            return;
+        }
+
+        if let Err(e) = self.write(chunk, before, captured).await {
+            eprintln!(
+                "⚠️ 본문 임시 파일 저장 실패 ({}): {}",
+                self.path.display(),
+                e
+            );
+            self.failed = true;
+            self.file = None;
+        }
+    }
+
+    async fn write(
+        &mut self,
+        chunk: &[u8],
+        before: u64,
+        captured: impl FnOnce() -> Bytes,
+    ) -> std::io::Result<()> {
+        let file = match &mut self.file {
+            Some(file) => file,
+            None => {
+                if let Some(dir) = self.path.parent() {
+                    tokio::fs::create_dir_all(dir).await?;
+                }
+                let mut file = tokio::fs::File::create(&self.path).await?;
+                file.write_all(&captured()).await?;
+                self.file.insert(file)
+            }
+        };
+
+        // 한도에 걸친 조각은 앞부분이 이미 기록되어 있으므로 나머지만 저장
+        let skip = self.limit.saturating_sub(before).min(chunk.len() as u64) as usize;
+        file.write_all(&chunk[skip..]).await
+    }
+
+    /// 저장을 마치고 임시 파일 경로 반환 (한도를 넘지 않았거나 저장에 실패했으면 None)
+    pub async fn finish(self) -> Option<String> {
+        let mut file = self.file?;
+        match file.flush().await {
+            Ok(()) => Some(self.path.to_string_lossy().into_owned()),
+            Err(e) => {
+                eprintln!(
+                    "⚠️ 본문 임시 파일 저장 실패 ({}): {}",
+                    self.path.display(),
+                    e
+                );
+                None
+            }
+        }
+    }
+}
+
+/// 본문 임시 파일 삭제 (이미 없는 파일은 무시)
+pub fn remove_spill_files(paths: impl IntoIterator<Item = String>) {
+    for path in paths {
+        match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                eprintln!("⚠️ 본문 임시 파일 삭제 실패 ({}): {}", path, e);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 저장된 교환이 참조하지 않는 본문 임시 파일 삭제 (삭제한 개수 반환)
+///
+/// 캡처만 하고 저장하지 못했거나 삭제 도중 종료되어 남은 파일을 정리합니다.
+pub fn remove_unreferenced_spill_files(dir: &Path, referenced: &HashSet<String>) -> usize {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return 0,
+        Err(e) => {
+            eprintln!("⚠️ 본문 임시 디렉터리 읽기 실패 ({}): {}", dir.display(), e);
+            return 0;
+        }
+    };
+
+    let unreferenced: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == SPILL_EXTENSION))
+        .map(|path| path.to_string_lossy().into_owned())
+        .filter(|path| !referenced.contains(path))
+        .collect();
+
+    let removed = unreferenced.len();
+    remove_spill_files(unreferenced);
+    removed
+}
+
+/// 스토어에서 본문 캡처 정책 로드 (설정이 없으면 기본값)
+pub fn load_capture_policy<R: Runtime>(app: &AppHandle<R>) -> Result<CapturePolicy, String> {
+    let store = app
+        .store(SETTINGS_STORE)
+        .map_err(|e| format!("세션 스토어 로드 실패: {}", e))?;
+
+    match store.get("capturePolicy") {
+        None | Some(JsonValue::Null) => Ok(CapturePolicy::default()),
+        Some(value) => {
+            serde_json::from_value(value).map_err(|e| format!("본문 캡처 정책 설정 오류: {}", e))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn policy_with(max_body_bytes: usize, overflow: Overflow, spill_dir: &Path) -> CapturePolicy {
+        CapturePolicy {
+            max_body_bytes,
+            overflow,
+            spill_dir: Some(spill_dir.to_path_buf()),
+            ..CapturePolicy::default()
+        }
+    }
+
+    fn content_type(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, value.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn limit_follows_content_type_filters() {
+        let dir = TempDir::new().unwrap();
+        let mut policy = policy_with(10, Overflow::Truncate, dir.path());
+        assert_eq!(policy.limit(&content_type("text/html")), 10);
+        assert_eq!(policy.limit(&HeaderMap::new()), 10);
+
+        policy.include_content_types = vec!["image/*".to_string(), "text/plain".to_string()];
+        assert_eq!(policy.limit(&content_type("image/png")), 10);
+        assert_eq!(policy.limit(&content_type("Text/Plain; charset=utf-8")), 10);
+        assert_eq!(policy.limit(&content_type("text/html")), 0);
+        // include 목록이 있으면 Content-Type이 없는 본문은 기록하지 않음
+        assert_eq!(policy.limit(&HeaderMap::new()), 0);
+
+        policy.exclude_content_types = vec!["image/svg*".to_string()];
+        assert_eq!(policy.limit(&content_type("image/png")), 10);
+        assert_eq!(policy.limit(&content_type("image/svg+xml")), 0);
+    }
+
+    #[tokio::test]
+    async fn record_keeps_bodies_within_limit() {
+        let dir = TempDir::new().unwrap();
+        let policy = policy_with(10, Overflow::Spill, dir.path());
+        let body = Bytes::from_static(b"0123456789");
+
+        let (captured, truncated) = policy.record(&HeaderMap::new(), "small", &body).await;
+
+        assert_eq!(captured, body);
+        assert_eq!(truncated, None);
+        assert!(!dir.path().join("small.body").exists());
+    }
+
+    #[tokio::test]
+    async fn record_truncates_without_spilling() {
+        let dir = TempDir::new().unwrap();
+        let policy = policy_with(4, Overflow::Truncate, dir.path());
+        let body = Bytes::from_static(b"0123456789");
+
+        let (captured, truncated) = policy.record(&HeaderMap::new(), "large", &body).await;
+
+        assert_eq!(captured.as_ref(), b"0123");
+        assert_eq!(
+            truncated,
+            Some(BodyTruncation {
+                size: 10,
+                spill_path: None
+            })
+        );
+        assert!(!dir.path().join("large.body").exists());
+    }
+
+    #[tokio::test]
+    async fn record_spills_full_body() {
+        let dir = TempDir::new().unwrap();
+        let policy = policy_with(4, Overflow::Spill, dir.path());
+        let body = Bytes::from_static(b"0123456789");
+
+        let (captured, truncated) = policy.record(&HeaderMap::new(), "large", &body).await;
+
+        assert_eq!(captured.as_ref(), b"0123");
+        let truncated = truncated.unwrap();
+        assert_eq!(truncated.size, 10);
+        let spill_path = truncated.spill_path.unwrap();
+        assert_eq!(
+            Path::new(&spill_path),
+            dir.path().join("large.body").as_path()
+        );
+        assert_eq!(std::fs::read(&spill_path).unwrap(), body.as_ref());
+    }
+
+    #[tokio::test]
+    async fn record_skips_excluded_content_types() {
+        let dir = TempDir::new().unwrap();
+        let mut policy = policy_with(4, Overflow::Spill, dir.path());
+        policy.exclude_content_types = vec!["video/*".to_string()];
+        let body = Bytes::from_static(b"0123456789");
+
+        let (captured, truncated) = policy
+            .record(&content_type("video/mp4"), "video", &body)
+            .await;
+
+        // 기록 대상이 아니면 본문 없이 크기만 기록
+        assert!(captured.is_empty());
+        assert_eq!(
+            truncated,
+            Some(BodyTruncation {
+                size: 10,
+                spill_path: None
+            })
+        );
+        assert!(!dir.path().join("video.body").exists());
+    }
+
+    #[tokio::test]
+    async fn spill_writes_chunks_straddling_limit_once() {
+        let dir = TempDir::new().unwrap();
+        let policy = policy_with(5, Overflow::Spill, dir.path());
+        let body = b"0123456789abcdef";
+        let mut spill = policy.spill(&HeaderMap::new(), "chunked").unwrap();
+
+        let mut captured = Vec::new();
+        // 프록시처럼 조각을 한도까지 메모리에 기록한 뒤 넘김
+        for chunk in body.chunks(3) {
+            let room = 5usize.saturating_sub(captured.len());
+            captured.extend_from_slice(&chunk[..room.min(chunk.len())]);
+            let snapshot = Bytes::from(captured.clone());
+            spill.push(chunk, || snapshot).await;
+        }
+        let spill_path = spill.finish().await.unwrap();
+
+        assert_eq!(captured, b"01234");
+        assert_eq!(std::fs::read(spill_path).unwrap(), body);
+    }
+
+    #[tokio::test]
+    async fn spill_without_overflow_creates_no_file() {
+        let dir = TempDir::new().unwrap();
+        let policy = policy_with(5, Overflow::Spill, dir.path());
+        let mut spill = policy.spill(&HeaderMap::new(), "small").unwrap();
+
+        spill.push(b"012", || unreachable!()).await;
+        spill.push(b"34", || unreachable!()).await;
+
+        assert_eq!(spill.finish().await, None);
+        assert!(!dir.path().join("small.body").exists());
+        assert!(policy_with(5, Overflow::Truncate, dir.path())
+            .spill(&HeaderMap::new(), "small")
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn resolve_spill_path_accepts_only_spill_files() {
+        let root = TempDir::new().unwrap();
+        let dir = root.path().join("spill");
+        std::fs::create_dir(&dir).unwrap();
+        let policy = policy_with(5, Overflow::Spill, &dir);
+
+        let spill_file = dir.join("exchange.body");
+        std::fs::write(&spill_file, b"body").unwrap();
+        let resolved = policy
+            .resolve_spill_path(&spill_file.to_string_lossy())
+            .await
+            .unwrap();
+        assert_eq!(resolved, spill_file.canonicalize().unwrap());
+
+        let outside = root.path().join("outside.body");
+        std::fs::write(&outside, b"secret").unwrap();
+        let not_body = dir.join("exchange.txt");
+        std::fs::write(&not_body, b"text").unwrap();
+        let nested = dir.join("nested");
+        std::fs::create_dir(&nested).unwrap();
+        std::fs::write(nested.join("inner.body"), b"body").unwrap();
+
+        for path in [
+            dir.join("../outside.body"),
+            outside,
+            not_body,
+            nested.join("inner.body"),
+            dir.join("missing.body"),
+        ] {
+            assert!(
+                policy
+                    .resolve_spill_path(&path.to_string_lossy())
+                    .await
+                    .is_err(),
+                "{}",
+                path.display()
+            );
+        }
+    }
+
+    #[test]
+    fn removes_only_unreferenced_spill_files() {
+        let dir = TempDir::new().unwrap();
+        let path = |name: &str| dir.path().join(name);
+        for name in ["kept.body", "orphan.body", "other.txt"] {
+            std::fs::write(path(name), b"body").unwrap();
+        }
+        let referenced = HashSet::from([path("kept.body").to_string_lossy().into_owned()]);
+
+        assert_eq!(remove_unreferenced_spill_files(dir.path(), &referenced), 1);
+
+        assert!(path("kept.body").exists());
+        assert!(!path("orphan.body").exists());
+        assert!(path("other.txt").exists());
+        assert_eq!(
+            remove_unreferenced_spill_files(&path("missing"), &referenced),
+            0
+        );
+    }
+}
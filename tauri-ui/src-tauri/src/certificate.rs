@@ -0,0 +1,92 @@
+use proxyapi_v2::certificate_authority::{LeafCertStore, RootCa, DEFAULT_CA_NAME};
+use serde::Deserialize;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager, Runtime};
+
+/// 내보낼 CA 인증서 형식
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CaExportFormat {
+    /// PEM 인증서
+    Pem,
+    /// DER 인증서
+    Der,
+    /// DER 인증서 (Windows/Android에서 설치하기 쉬운 `.crt` 확장자)
+    Crt,
+    /// Apple 구성 프로파일 (iOS/macOS)
+    Mobileconfig,
+}
+
+/// 설치별 루트 CA를 저장하는 앱 데이터 디렉터리
+fn ca_dir<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join("ca"))
+        .map_err(|e| format!("앱 데이터 디렉터리 확인 실패: {}", e))
+}
+
+/// 재시작 후에도 다시 서명하지 않도록 잎 인증서를 저장하는 앱 데이터 디렉터리
+pub(crate) fn leaf_cert_store<R: Runtime>(app: &AppHandle<R>) -> Result<LeafCertStore, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| LeafCertStore::new(dir.join("certs")))
+        .map_err(|e| format!("앱 데이터 디렉터리 확인 실패: {}", e))
+}
+
+/// 설치별 루트 CA 읽기 (처음 실행하면 새로 생성해서 저장)
+pub(crate) fn load_root_ca<R: Runtime>(app: &AppHandle<R>) -> Result<RootCa, String> {
+    let dir = ca_dir(app)?;
+    RootCa::load_or_generate(&dir, DEFAULT_CA_NAME)
+        .map_err(|e| format!("CA 로드 실패 ({}): {}", dir.display(), e))
+}
+
+/// 새 키 쌍으로 루트 CA를 다시 생성
+///
+/// 실행 중인 프록시는 기존 CA를 계속 사용하므로 프록시를 다시 시작해야 적용되며,
+/// 기기에 설치했던 이전 CA 인증서는 더 이상 신뢰되지 않으므로 새 인증서를 다시 설치해야 합니다.
+#[tauri::command]
+pub async fn regenerate_ca<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    let dir = ca_dir(&app)?;
+    let root_ca = RootCa::generate(DEFAULT_CA_NAME).map_err(|e| format!("CA 생성 실패: {}", e))?;
+    root_ca
+        .save(&dir)
+        .map_err(|e| format!("CA 저장 실패 ({}): {}", dir.display(), e))?;
+
+    // 이전 CA로 서명한 잎 인증서는 더 이상 사용되지 않으므로 삭제
+    match leaf_cert_store(&app) {
+        Ok(store) => {
+            if let Err(e) = store.clear() {
+                eprintln!("⚠️ 잎 인증서 저장소 삭제 실패: {}", e);
+            }
+        }
+        Err(e) => eprintln!("⚠️ {}", e),
+    }
+
+    println!("✅ 새 루트 CA 생성 완료 → {}", dir.display());
+    Ok(())
+}
+
+/// 루트 CA 인증서를 기기에 설치할 수 있는 형식으로 내보내기 (개인 키는 내보내지 않음)
+#[tauri::command]
+pub async fn export_ca_certificate<R: Runtime>(
+    app: AppHandle<R>,
+    path: String,
+    format: CaExportFormat,
+) -> Result<(), String> {
+    let root_ca = load_root_ca(&app)?;
+    let contents = match format {
+        CaExportFormat::Pem => Ok(root_ca.cert_pem().as_bytes().to_vec()),
+        CaExportFormat::Der | CaExportFormat::Crt => root_ca.cert_der(),
+        CaExportFormat::Mobileconfig => root_ca
+            .mobileconfig(DEFAULT_CA_NAME)
+            .map(String::into_bytes),
+    }
+    .map_err(|e| format!("CA 인증서 변환 실패: {}", e))?;
+
+    tokio::fs::write(&path, contents)
+        .await
+        .map_err(|e| format!("CA 인증서 저장 실패 ({}): {}", path, e))?;
+
+    println!("✅ CA 인증서 내보내기 완료: {:?} → {}", format, path);
+    Ok(())
+}
@@ -2,14 +2,32 @@
 
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
+mod breakpoints;
+mod capture;
+mod capture_policy;
+mod certificate;
 mod proxy;
 mod proxy_v2;
+mod replay;
+mod rules;
+mod snippet;
+mod storage;
+use breakpoints::{resume_breakpoint, BreakpointEvent, BreakpointManager};
+use capture::{clear_capture, export_har, import_har, CaptureState};
+use capture_policy::{load_capture_policy, remove_unreferenced_spill_files};
+use certificate::{export_ca_certificate, regenerate_ca};
 use proxy::{
     get_proxy_status_command, proxy_status, set_proxy, start_proxy, stop_proxy, store_changed,
     ProxyState,
 };
-use proxy_v2::{proxy_v2_status, start_proxy_v2, stop_proxy_v2, store_changed_v2, ProxyV2State};
-use tauri::Manager;
+use proxy_v2::{
+    inject_websocket_frame, proxy_v2_status, start_proxy_v2, stop_proxy_v2, store_changed_v2,
+    ProxyV2State,
+};
+use replay::replay_request;
+use snippet::{parse_curl_command, request_snippet};
+use storage::{clear_traffic, delete_traffic, get_traffic, list_traffic, TrafficStore};
+use tauri::{Emitter, Manager};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -35,6 +53,48 @@ pub fn run() {
                 app_handle.manage(Mutex::new(None) as ProxyState);
                 // 새로운 proxyapi_v2 프록시 상태
                 app_handle.manage(ProxyV2State::default());
+                // 캡처된 트래픽 (HAR 내보내기/가져오기)
+                app_handle.manage(CaptureState::default());
+                // 디스크 트래픽 저장소 (재시작 후에도 유지)
+                let storage = open_traffic_store(app_handle.handle());
+                app_handle.manage(storage.clone());
+                // 저장된 교환이 참조하지 않는 본문 임시 파일 정리
+                match load_capture_policy(app_handle.handle()) {
+                    Ok(policy) => {
+                        tauri::async_runtime::spawn_blocking(move || match storage.spill_paths() {
+                            Ok(referenced) => {
+                                let removed = remove_unreferenced_spill_files(
+                                    &policy.spill_dir(),
+                                    &referenced,
+                                );
+                                if removed > 0 {
+                                    println!("✅ 본문 임시 파일 {} 개 정리", removed);
+                                }
+                            }
+                            Err(e) => eprintln!("⚠️ {}", e),
+                        });
+                    }
+                    Err(e) => eprintln!("⚠️ {}", e),
+                }
+                // 브레이크포인트 (멈춘 메시지를 UI 이벤트로 전달)
+                let (breakpoint_tx, mut breakpoint_rx) = tokio::sync::mpsc::unbounded_channel();
+                app_handle.manage(BreakpointManager::new(breakpoint_tx));
+
+                let handle = app_handle.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    while let Some(event) = breakpoint_rx.recv().await {
+                        let _ = match event {
+                            BreakpointEvent::Hit(hit) => handle.emit("breakpoint_hit", hit),
+                            BreakpointEvent::Released {
+                                pause_id,
+                                timed_out,
+                            } => handle.emit(
+                                "breakpoint_released",
+                                serde_json::json!({ "pauseId": pause_id, "timedOut": timed_out }),
+                            ),
+                        };
+                    }
+                });
 
                 tauri::async_runtime::spawn(async {
                     if let Err(e) = set_proxy(true) {
@@ -61,9 +121,50 @@ pub fn run() {
                 stop_proxy_v2,
                 proxy_v2_status,
                 store_changed_v2,
+                inject_websocket_frame,
+                export_har,
+                import_har,
+                clear_capture,
+                replay_request,
+                request_snippet,
+                parse_curl_command,
+                resume_breakpoint,
+                list_traffic,
+                get_traffic,
+                delete_traffic,
+                clear_traffic,
+                regenerate_ca,
+                export_ca_certificate,
                 get_proxy_status_command
             ])
             .run(tauri::generate_context!())
             .expect("error while running tauri application");
     }
 }
+
+/// 앱 데이터 디렉터리의 트래픽 저장소 열기 (실패하면 메모리 저장소 사용)
+fn open_traffic_store<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> TrafficStore {
+    let store = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())
+        .and_then(|dir| {
+            std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+            TrafficStore::open(dir.join("traffic.db")).map_err(|e| e.to_string())
+        })
+        .unwrap_or_else(|e| {
+            eprintln!("⚠️ 트래픽 저장소 열기 실패, 메모리 저장소 사용: {}", e);
+            TrafficStore::open_in_memory().expect("Failed to open in-memory traffic store")
+        });
+
+    match storage::load_retention(app) {
+        Ok(retention) => {
+            if let Err(e) = store.set_retention(retention) {
+                eprintln!("⚠️ {}", e);
+            }
+        }
+        Err(e) => eprintln!("⚠️ {}", e),
+    }
+
+    store
+}
@@ -1,4 +1,4 @@
-use proxyapi::Proxy;
+use proxyapi::{ca::Ssl, Proxy};
 use std::process::Command;
 use std::{env, net::SocketAddr};
 use tauri_plugin_store::StoreExt;
@@ -8,6 +8,8 @@ use tauri::{async_runtime::Mutex, AppHandle, Emitter, Runtime, State};
 
 use proxyapi_models::RequestInfo;
 
+use crate::certificate::load_root_ca;
+
 pub type ProxyState = Mutex<Option<(Sender<()>, tauri::async_runtime::JoinHandle<()>, Proxy)>>;
 
 #[tauri::command]
@@ -22,7 +24,12 @@ pub async fn start_proxy<R: Runtime>(
     let store = app.store("session.json").map_err(|e| e.to_string())?;
     let sessions = store.get("sessions").unwrap_or_default();
 
-    let proxy_server = Proxy::new(addr, Some(tx.clone()), sessions);
+    // v2 프록시와 같은 설치별 루트 CA로 서명
+    let root_ca = load_root_ca(&app)?;
+    let ssl = Ssl::from_pem(root_ca.key_pem().as_bytes(), root_ca.cert_pem().as_bytes())
+        .map_err(|e| format!("CA 로드 실패: {}", e))?;
+
+    let proxy_server = Proxy::new(addr, Some(tx.clone()), sessions, ssl);
 
     let proxy_server_clone = proxy_server.clone();
 
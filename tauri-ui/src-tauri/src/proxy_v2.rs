@@ -1,119 +1,87 @@
+use crate::breakpoints::{load_breakpoints, BreakpointManager, CompiledBreakpoint};
+use crate::capture::{publish_exchange, CaptureState};
+use crate::capture_policy::{load_capture_policy, CapturePolicy};
+use crate::certificate::{leaf_cert_store, load_root_ca};
+use crate::rules::{
+    load_client_identities, load_map_local, load_map_remote, load_proto_descriptors, load_rules,
+    load_socks5_port, load_tls_verification, load_transparent_port, load_upstream_proxy,
+    load_websocket_rules,
+};
+use crate::storage::{load_retention, TrafficStore};
 use bytes::Bytes;
-use hyper_rustls::HttpsConnectorBuilder;
-use hyper_util::{
-    client::legacy::{connect::HttpConnector, Client},
-    rt::TokioExecutor,
+use futures_util::future::{BoxFuture, FutureExt, Shared};
+use proxy_v2_models::{
+    BodyTruncation, CertificateInfo, CertificateStatus, ProxiedRequest, ProxiedResponse,
+    RequestInfo, ServerSentEvent, Timings, UpstreamSession, UpstreamTls, WebSocketDirection,
+    WebSocketFrame, WebSocketOpcode,
 };
-use proxy_v2_models::{ProxiedRequest, ProxiedResponse, RequestInfo};
 use proxyapi_v2::{
     builder::ProxyBuilder,
-    certificate_authority::build_ca,
-    hyper::http::{HeaderMap, HeaderValue, StatusCode},
+    certificate_authority::RcgenAuthority,
+    grpc::{self, GrpcBody, ProtoDescriptors},
+    hyper::http::{HeaderMap, Method, StatusCode, Uri},
     hyper::{Request, Response},
+    map_local::{MapLocal, MapLocalRule},
+    map_remote::{MapRemote, MapRemoteRule, RemoteMapping},
+    replay::{hybrid_client, ReplayClient},
+    rules::{MatchedRules, Rule, RuleEngine, RuleError, WebSocketRule, WebSocketRuleEngine},
+    sse::{self, SseEvent, SseParser},
     tokio_tungstenite::tungstenite::Message,
-    Body, HttpContext, HttpHandler, RequestOrResponse, WebSocketContext, WebSocketHandler,
+    upstream_proxy::UpstreamProxy,
+    upstream_tls::{self, is_handshake_failure, verification_error},
+    websocket::{self, WebSocketInjector},
+    Body, HttpContext, HttpHandler, RequestOrResponse, TapFrame, WebSocketContext,
+    WebSocketHandler,
 };
+use std::collections::HashMap;
 use std::error::Error;
 use std::net::SocketAddr;
 use std::sync::mpsc;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter, Runtime, State};
-use tauri_plugin_store::{JsonValue, StoreExt};
 use tokio::net::TcpListener;
 use tokio::sync::oneshot::Sender;
 use tokio::sync::Mutex;
-use tokio_rustls::rustls::{crypto::aws_lc_rs, ClientConfig};
-
-/// 모든 인증서를 허용하는 위험한 인증서 검증기
-#[derive(Debug)]
-struct DangerousCertificateVerifier;
-
-impl tokio_rustls::rustls::client::danger::ServerCertVerifier for DangerousCertificateVerifier {
-    fn verify_server_cert(
-        &self,
-        _end_entity: &tokio_rustls::rustls::pki_types::CertificateDer<'_>,
-        _intermediates: &[tokio_rustls::rustls::pki_types::CertificateDer<'_>],
-        _server_name: &tokio_rustls::rustls::pki_types::ServerName<'_>,
-        _ocsp_response: &[u8],
-        _now: tokio_rustls::rustls::pki_types::UnixTime,
-    ) -> Result<tokio_rustls::rustls::client::danger::ServerCertVerified, tokio_rustls::rustls::Error>
-    {
-        // 모든 인증서를 허용
-        Ok(tokio_rustls::rustls::client::danger::ServerCertVerified::assertion())
-    }
+use tokio_rustls::rustls::crypto::aws_lc_rs;
+
+/// 본문 뒤에 트레일러를 붙인 Body 생성
+fn body_with_trailers(body: Bytes, trailers: HeaderMap) -> Body {
+    use http_body_util::{combinators::BoxBody, StreamBody};
+    use proxyapi_v2::hyper::body::Frame;
+
+    let frames = [
+        Ok::<_, proxyapi_v2::Error>(Frame::data(body)),
+        Ok(Frame::trailers(trailers)),
+    ];
+    Body::from(BoxBody::new(StreamBody::new(
+        proxyapi_v2::futures::stream::iter(frames),
+    )))
+}
 
-    fn verify_tls12_signature(
-        &self,
-        _message: &[u8],
-        _cert: &tokio_rustls::rustls::pki_types::CertificateDer<'_>,
-        _dss: &tokio_rustls::rustls::DigitallySignedStruct,
-    ) -> Result<
-        tokio_rustls::rustls::client::danger::HandshakeSignatureValid,
-        tokio_rustls::rustls::Error,
-    > {
-        // 모든 서명을 허용
-        Ok(tokio_rustls::rustls::client::danger::HandshakeSignatureValid::assertion())
-    }
-
-    fn verify_tls13_signature(
-        &self,
-        _message: &[u8],
-        _cert: &tokio_rustls::rustls::pki_types::CertificateDer<'_>,
-        _dss: &tokio_rustls::rustls::DigitallySignedStruct,
-    ) -> Result<
-        tokio_rustls::rustls::client::danger::HandshakeSignatureValid,
-        tokio_rustls::rustls::Error,
-    > {
-        // 모든 서명을 허용
-        Ok(tokio_rustls::rustls::client::danger::HandshakeSignatureValid::assertion())
-    }
-
-    fn supported_verify_schemes(&self) -> Vec<tokio_rustls::rustls::SignatureScheme> {
-        // 모든 서명 스키마 지원
-        vec![
-            tokio_rustls::rustls::SignatureScheme::RSA_PKCS1_SHA1,
-            tokio_rustls::rustls::SignatureScheme::ECDSA_SHA1_Legacy,
-            tokio_rustls::rustls::SignatureScheme::RSA_PKCS1_SHA256,
-            tokio_rustls::rustls::SignatureScheme::ECDSA_NISTP256_SHA256,
-            tokio_rustls::rustls::SignatureScheme::RSA_PKCS1_SHA384,
-            tokio_rustls::rustls::SignatureScheme::ECDSA_NISTP384_SHA384,
-            tokio_rustls::rustls::SignatureScheme::RSA_PKCS1_SHA512,
-            tokio_rustls::rustls::SignatureScheme::ECDSA_NISTP521_SHA512,
-            tokio_rustls::rustls::SignatureScheme::RSA_PSS_SHA256,
-            tokio_rustls::rustls::SignatureScheme::RSA_PSS_SHA384,
-            tokio_rustls::rustls::SignatureScheme::RSA_PSS_SHA512,
-            tokio_rustls::rustls::SignatureScheme::ED25519,
-            tokio_rustls::rustls::SignatureScheme::ED448,
-            tokio_rustls::rustls::SignatureScheme::ML_DSA_44,
-            tokio_rustls::rustls::SignatureScheme::ML_DSA_65,
-            tokio_rustls::rustls::SignatureScheme::ML_DSA_87,
-        ]
-    }
+/// 한도를 넘은 본문을 저장할 임시 파일 이름 (교환과 요청/응답 구분)
+fn spill_name(ctx: &HttpContext, part: &str) -> String {
+    format!(
+        "{}-{}-{}",
+        chrono::Local::now().timestamp_millis(),
+        ctx.exchange_id,
+        part
+    )
 }
 
-/// 하이브리드 클라이언트 생성 (모든 인증서 허용)
-fn create_hybrid_client(
-) -> Result<Client<hyper_rustls::HttpsConnector<HttpConnector>, Body>, Box<dyn std::error::Error>> {
-    // aws_lc_rs 프로바이더를 사용하되 모든 인증서를 허용하는 설정
-    let rustls_config =
-        ClientConfig::builder_with_provider(std::sync::Arc::new(aws_lc_rs::default_provider()))
-            .with_safe_default_protocol_versions()?
-            .dangerous()
-            .with_custom_certificate_verifier(std::sync::Arc::new(DangerousCertificateVerifier))
-            .with_no_client_auth();
-
-    // HTTP와 HTTPS를 모두 처리할 수 있는 커넥터 생성
-    let https = HttpsConnectorBuilder::new()
-        .with_tls_config(rustls_config)
-        .https_or_http() // HTTP와 HTTPS 모두 지원
-        .enable_http1() // HTTP/1.1 지원
-        // .enable_http2() // HTTP/2 지원 추가
-        .build();
-
-    Ok(Client::builder(TokioExecutor::new())
-        .http1_title_case_headers(true)
-        .http1_preserve_header_case(true)
-        .build(https))
+/// 구간별 소요 시간을 UI용(ms)으로 변환
+pub(crate) fn client_timings(
+    timings: &proxyapi_v2::timings::Timings,
+    receive: Duration,
+) -> Timings {
+    let millis = |duration: Duration| duration.as_secs_f64() * 1000.0;
+    Timings {
+        dns: timings.dns.map(millis),
+        connect: timings.connect.map(millis),
+        ssl: timings.tls.map(millis),
+        wait: timings.wait.map(millis),
+        receive: Some(millis(receive)),
+    }
 }
 
 /// HTTP 및 WebSocket 요청/응답을 로깅하는 핸들러
@@ -122,105 +90,256 @@ pub struct LoggingHandler {
     sender: mpsc::SyncSender<RequestInfo>,
     req: Option<ProxiedRequest>,
     res: Option<ProxiedResponse>,
-    sessions: Arc<Mutex<JsonValue>>,
+    rules: RuleEngine,
+    matched: MatchedRules,
+    breakpoints: BreakpointManager,
+    breakpoint: Option<Arc<CompiledBreakpoint>>,
+    map_remote: MapRemote,
+    map_local: MapLocal,
+    frames: tokio::sync::mpsc::UnboundedSender<WebSocketFrame>,
+    sse_events: tokio::sync::mpsc::UnboundedSender<ServerSentEvent>,
+    websockets: Arc<std::sync::Mutex<HashMap<u64, WebSocketLink>>>,
+    websocket_rules: WebSocketRuleEngine,
+    websocket_injector: WebSocketInjector,
+    proto_descriptors: Arc<std::sync::RwLock<ProtoDescriptors>>,
+    capture_policy: Arc<std::sync::RwLock<CapturePolicy>>,
+    legacy_client: Option<ReplayClient>,
+    upstream_request: Option<UpstreamRequest>,
+    request_body: Option<RequestBodyCapture>,
+}
+
+/// 모으지 않고 전달한 요청 본문의 캡처 결과 (본문이 끝나면 완료)
+type RequestBodyCapture = Shared<BoxFuture<'static, (Bytes, Option<BodyTruncation>)>>;
+
+/// TLS 핸드셰이크 실패 시 다시 보낼 수 있도록 보관한 업스트림 요청
+#[derive(Clone)]
+struct UpstreamRequest {
+    method: Method,
+    uri: Uri,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl UpstreamRequest {
+    fn new(req: &Request<Bytes>) -> Self {
+        Self {
+            method: req.method().clone(),
+            uri: req.uri().clone(),
+            headers: req.headers().clone(),
+            body: req.body().clone(),
+        }
+    }
+
+    fn to_request(&self) -> Request<Bytes> {
+        let mut req = Request::new(self.body.clone());
+        *req.method_mut() = self.method.clone();
+        *req.uri_mut() = self.uri.clone();
+        *req.headers_mut() = self.headers.clone();
+        req
+    }
+}
+
+/// WebSocket 연결과 업그레이드 요청의 연결 정보
+struct WebSocketLink {
+    request_id: String,
+    closing: bool,
 }
 
 impl LoggingHandler {
-    pub fn new(sender: mpsc::SyncSender<RequestInfo>) -> Self {
+    pub fn new(
+        sender: mpsc::SyncSender<RequestInfo>,
+        frames: tokio::sync::mpsc::UnboundedSender<WebSocketFrame>,
+        sse_events: tokio::sync::mpsc::UnboundedSender<ServerSentEvent>,
+        breakpoints: BreakpointManager,
+    ) -> Self {
         Self {
             sender,
             req: None,
             res: None,
-            sessions: Arc::new(Mutex::new(JsonValue::Array(Vec::new()))),
+            rules: RuleEngine::new(),
+            matched: MatchedRules::default(),
+            breakpoints,
+            breakpoint: None,
+            map_remote: MapRemote::default(),
+            map_local: MapLocal::default(),
+            frames,
+            sse_events,
+            websockets: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            websocket_rules: WebSocketRuleEngine::new(),
+            websocket_injector: WebSocketInjector::new(),
+            proto_descriptors: Arc::new(std::sync::RwLock::new(ProtoDescriptors::new())),
+            capture_policy: Arc::new(std::sync::RwLock::new(CapturePolicy::default())),
+            legacy_client: None,
+            upstream_request: None,
+            request_body: None,
         }
     }
 
-    /// 세션 데이터 업데이트
-    pub async fn update_sessions(&self, sessions: JsonValue) {
-        let mut sessions_guard = self.sessions.lock().await;
-        *sessions_guard = sessions;
+    /// rustls 핸드셰이크가 실패한 요청을 다시 보낼 레거시 TLS(OpenSSL) 클라이언트 설정
+    pub fn with_legacy_client(mut self, client: ReplayClient) -> Self {
+        self.legacy_client = Some(client);
+        self
+    }
+
+    /// 프록시에 등록할 Map Remote 규칙 목록 (핸들러와 공유)
+    pub fn map_remote(&self) -> MapRemote {
+        self.map_remote.clone()
     }
 
-    /// 요청 URL이 세션에 있는지 확인하고 매칭되는 세션 반환
-    async fn find_matching_session(&self, url: &str, method: &str) -> Option<JsonValue> {
-        let sessions_guard = self.sessions.lock().await;
-        if let JsonValue::Array(sessions) = &*sessions_guard {
-            sessions
-                .iter()
-                .find(|session| {
-                    let session_url = session.get("url").and_then(|v| v.as_str()).unwrap_or("");
-                    let session_method = session
-                        .get("method")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("GET");
+    /// Map Remote 규칙 업데이트 (실행 중인 프록시에 즉시 반영)
+    pub fn update_map_remote(&self, rules: Vec<MapRemoteRule>) {
+        self.map_remote.set_rules(rules);
+    }
 
-                    // URL과 메서드가 모두 매칭되는지 확인
-                    (url.contains(session_url) || session_url.contains(url))
-                        && session_method.to_uppercase() == method.to_uppercase()
-                })
-                .cloned()
+    /// Map Local 규칙 업데이트 (실행 중인 프록시에 즉시 반영)
+    pub fn update_map_local(&self, rules: Vec<MapLocalRule>) {
+        self.map_local.set_rules(rules);
+    }
+
+    /// 규칙 목록 업데이트 (실행 중인 프록시에 즉시 반영)
+    pub fn update_rules(&self, rules: Vec<Rule>) -> Vec<RuleError> {
+        self.rules.set_rules(rules)
+    }
+
+    /// WebSocket 메시지 규칙 목록 업데이트 (실행 중인 프록시에 즉시 반영)
+    pub fn update_websocket_rules(&self, rules: Vec<WebSocketRule>) -> Vec<RuleError> {
+        self.websocket_rules.set_rules(rules)
+    }
+
+    /// 프록시에 등록할 WebSocket 메시지 주입 핸들 (핸들러와 공유)
+    pub fn websocket_injector(&self) -> WebSocketInjector {
+        self.websocket_injector.clone()
+    }
+
+    /// gRPC 메시지 변환에 사용할 protobuf 디스크립터 업데이트 (실행 중인 프록시에 즉시 반영)
+    pub fn update_proto_descriptors(&self, descriptors: ProtoDescriptors) {
+        *self
+            .proto_descriptors
+            .write()
+            .unwrap_or_else(|e| e.into_inner()) = descriptors;
+    }
+
+    /// 본문 캡처 정책 업데이트 (실행 중인 프록시에 즉시 반영)
+    pub fn update_capture_policy(&self, policy: CapturePolicy) {
+        *self
+            .capture_policy
+            .write()
+            .unwrap_or_else(|e| e.into_inner()) = policy;
+    }
+
+    fn capture_policy(&self) -> CapturePolicy {
+        self.capture_policy
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// gRPC 메시지를 디스크립터로 JSON 변환 (gRPC가 아니거나 변환할 수 없으면 None)
+    fn grpc_json(
+        &self,
+        path: &str,
+        headers: &HeaderMap,
+        body: &GrpcBody,
+        request: bool,
+    ) -> Option<serde_json::Value> {
+        let descriptors = self
+            .proto_descriptors
+            .read()
+            .unwrap_or_else(|e| e.into_inner());
+        if descriptors.is_empty() || !grpc::is_grpc(headers) {
+            return None;
+        }
+
+        let json = if request {
+            descriptors.request_json(path, body)
         } else {
-            None
-        }
-    }
-
-    /// 세션 데이터로부터 HTTP 응답을 생성하는 메서드 (proxyapi와 동일한 로직)
-    fn create_response_from_session(&self, response_data: &JsonValue) -> Response<Body> {
-        // 상태 코드 추출
-        let status_code = response_data
-            .get("status")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(200) as u16;
-
-        // 헤더 추출 (content-length 제외)
-        let mut headers: HeaderMap = response_data
-            .get("headers")
-            .and_then(JsonValue::as_object)
-            .map(|obj| {
-                obj.iter()
-                    .filter_map(|(k, v)| {
-                        // content-length 헤더는 제외 (실제 본문 길이에 맞게 자동 설정됨)
-                        if k.to_lowercase() == "content-length" {
-                            return None;
-                        }
-                        Some((k.parse().ok()?, v.as_str()?.parse().ok()?))
-                    })
-                    .collect()
-            })
-            .unwrap_or_default();
+            descriptors.response_json(path, body)
+        };
+        json.map_err(|e| eprintln!("⚠️ gRPC 메시지 변환 실패 ({}): {}", path, e))
+            .ok()
+    }
+
+    /// gRPC 요청이면 메시지를 JSON으로 변환해 기록
+    fn decode_grpc_request(&self, proxied_request: ProxiedRequest) -> ProxiedRequest {
+        if !grpc::is_grpc(proxied_request.headers()) {
+            return proxied_request;
+        }
 
-        // 기본 Content-Type 헤더 설정 (없는 경우)
-        if !headers.contains_key("content-type") {
-            headers.insert("content-type", "application/json".parse().unwrap());
+        // 압축 폭탄으로 메모리를 다 쓰지 않도록 캡처 한도까지만 압축 해제
+        let limit = self.capture_policy().max_body_bytes;
+        let body = match GrpcBody::decode_with_limit(
+            proxied_request.headers(),
+            proxied_request.body(),
+            limit,
+        ) {
+            Ok(body) => body,
+            Err(e) => {
+                eprintln!("⚠️ gRPC 요청 본문 디코딩 실패: {}", e);
+                return proxied_request;
+            }
+        };
+        let path = proxied_request.uri().path().to_string();
+        match self.grpc_json(&path, proxied_request.headers(), &body, true) {
+            Some(json) => proxied_request.with_body_json(json),
+            None => proxied_request,
         }
+    }
 
-        // 세션 응답임을 나타내는 특별한 헤더 추가
-        headers.insert("x-cheolsu-proxy-session", "true".parse().unwrap());
-        headers.insert("x-cheolsu-proxy-version", "v2".parse().unwrap());
+    /// 트레일러를 기록하고, gRPC 응답이면 메시지를 JSON으로 변환해 기록
+    ///
+    /// gRPC-Web은 트레일러(`grpc-status` 등)가 본문에 포함되어 있으므로 본문에서 읽습니다.
+    fn decode_grpc_response(
+        &self,
+        mut proxied_response: ProxiedResponse,
+        trailers: Option<HeaderMap>,
+    ) -> ProxiedResponse {
+        if let Some(trailers) = trailers {
+            proxied_response = proxied_response.with_trailers(trailers);
+        }
+        if !grpc::is_grpc(proxied_response.headers()) {
+            return proxied_response;
+        }
 
-        // 응답 본문 생성
-        let body = if let Some(data) = response_data.get("data") {
-            match data {
-                JsonValue::String(s) => Body::from(s.clone()),
-                JsonValue::Object(_) | JsonValue::Array(_) => {
-                    let json_string = serde_json::to_string(data).unwrap_or_default();
-                    Body::from(json_string)
-                }
-                _ => {
-                    let string_data = data.to_string();
-                    Body::from(string_data)
-                }
+        // 압축 폭탄으로 메모리를 다 쓰지 않도록 캡처 한도까지만 압축 해제
+        let limit = self.capture_policy().max_body_bytes;
+        let body = match GrpcBody::decode_with_limit(
+            proxied_response.headers(),
+            proxied_response.body(),
+            limit,
+        ) {
+            Ok(body) => body,
+            Err(e) => {
+                eprintln!("⚠️ gRPC 응답 본문 디코딩 실패: {}", e);
+                return proxied_response;
             }
-        } else {
-            Body::empty()
         };
+        if proxied_response.trailers().is_none() && !body.trailers.is_empty() {
+            proxied_response = proxied_response.with_trailers(body.trailers.clone());
+        }
 
-        // 응답 생성
-        let mut response = Response::new(body);
-        *response.status_mut() = StatusCode::from_u16(status_code).unwrap_or(StatusCode::OK);
-        *response.headers_mut() = headers;
+        let path = match &self.req {
+            Some(req) => req.uri().path().to_string(),
+            None => return proxied_response,
+        };
+        match self.grpc_json(&path, proxied_response.headers(), &body, false) {
+            Some(json) => proxied_response.with_body_json(json),
+            None => proxied_response,
+        }
+    }
 
-        response
+    /// 파싱한 SSE 이벤트를 UI로 전송
+    fn send_sse_event(&self, event: SseEvent, index: usize) {
+        let Some(req) = &self.req else {
+            return;
+        };
+        let time = chrono::Local::now()
+            .timestamp_nanos_opt()
+            .unwrap_or_default();
+        let event = ServerSentEvent::new(req.id().clone(), index, event.data, time)
+            .with_id(event.id)
+            .with_event(event.event)
+            .with_retry(event.retry);
+        let _ = self.sse_events.send(event);
     }
 
     /// 요청과 응답을 묶어서 전송
@@ -238,6 +357,7 @@ impl LoggingHandler {
     /// Request를 ProxiedRequest로 변환하고 원본 요청을 복원 (비동기)
     async fn request_to_proxied_request(
         &self,
+        ctx: &HttpContext,
         mut req: Request<Body>,
     ) -> (ProxiedRequest, Request<Body>) {
         // 요청 body를 읽어서 Bytes로 변환
@@ -251,16 +371,30 @@ impl LoggingHandler {
         use http_body_util::Full;
         *body_mut = Body::from(Full::new(body_bytes.clone()));
 
-        let proxied_request = ProxiedRequest::new(
+        // 캡처 정책에 따라 기록할 본문 결정
+        let (captured, truncated) = self
+            .capture_policy()
+            .record(req.headers(), &spill_name(ctx, "request"), &body_bytes)
+            .await;
+
+        let mut proxied_request = ProxiedRequest::new(
             req.method().clone(),
             req.uri().clone(),
             req.version(),
             req.headers().clone(),
-            body_bytes.clone(),
+            captured,
             chrono::Local::now()
                 .timestamp_nanos_opt()
                 .unwrap_or_default(),
         );
+        if let Some(truncated) = truncated {
+            proxied_request = proxied_request.with_truncated(truncated);
+        }
+
+        // Map Remote가 적용된 경우 원래 URL도 함께 기록
+        if let Some(mapping) = req.extensions().get::<RemoteMapping>() {
+            proxied_request = proxied_request.with_original_uri(mapping.original_uri.clone());
+        }
 
         (proxied_request, req)
     }
@@ -268,6 +402,7 @@ impl LoggingHandler {
     /// Response를 ProxiedResponse로 변환하고 원본 응답을 복원
     async fn response_to_proxied_response(
         &self,
+        ctx: &HttpContext,
         mut res: Response<Body>,
     ) -> (ProxiedResponse, Response<Body>) {
         // 응답 body를 읽어서 Bytes로 변환
@@ -281,19 +416,131 @@ impl LoggingHandler {
         use http_body_util::Full;
         *body_mut = Body::from(Full::new(body_bytes.clone()));
 
-        let proxied_response = ProxiedResponse::new(
+        // 캡처 정책에 따라 기록할 본문 결정
+        let (captured, truncated) = self
+            .capture_policy()
+            .record(res.headers(), &spill_name(ctx, "response"), &body_bytes)
+            .await;
+
+        let mut proxied_response = ProxiedResponse::new(
             res.status(),
             res.version(),
             res.headers().clone(),
-            body_bytes.clone(),
+            captured,
             chrono::Local::now()
                 .timestamp_nanos_opt()
                 .unwrap_or_default(),
         );
+        if let Some(truncated) = truncated {
+            proxied_response = proxied_response.with_truncated(truncated);
+        }
 
         (proxied_response, res)
     }
 
+    /// 요청 본문을 모으지 않고 전달하면서 기록
+    ///
+    /// 본문은 캡처 정책의 한도까지만 기록하고, 본문이 끝나면 응답과 묶어서 전송합니다.
+    fn stream_request(
+        &mut self,
+        ctx: &HttpContext,
+        req: Request<Bytes>,
+        body: Body,
+    ) -> Request<Body> {
+        let (parts, _) = req.into_parts();
+        let policy = self.capture_policy();
+        let (body, mut tap) = body.tee(policy.limit(&parts.headers));
+        let mut spill = policy.spill(&parts.headers, &spill_name(ctx, "request"));
+
+        let mut proxied_request = ProxiedRequest::new(
+            parts.method.clone(),
+            parts.uri.clone(),
+            parts.version,
+            parts.headers.clone(),
+            Bytes::new(),
+            chrono::Local::now()
+                .timestamp_nanos_opt()
+                .unwrap_or_default(),
+        );
+        // Map Remote가 적용된 경우 원래 URL도 함께 기록
+        if let Some(mapping) = parts.extensions.get::<RemoteMapping>() {
+            proxied_request = proxied_request.with_original_uri(mapping.original_uri.clone());
+        }
+        self.req = Some(proxied_request);
+
+        let captured = tokio::spawn(async move {
+            use futures_util::StreamExt;
+
+            while let Some(frame) = tap.next().await {
+                if let (TapFrame::Data(data), Some(spill)) = (&frame, &mut spill) {
+                    spill.push(data, || tap.captured()).await;
+                }
+            }
+
+            let truncated = if tap.is_truncated() {
+                let spill_path = match spill {
+                    Some(spill) => spill.finish().await,
+                    None => None,
+                };
+                Some(BodyTruncation {
+                    size: tap.size(),
+                    spill_path,
+                })
+            } else {
+                None
+            };
+            (tap.captured(), truncated)
+        });
+        self.request_body = Some(
+            async move { captured.await.unwrap_or_default() }
+                .boxed()
+                .shared(),
+        );
+
+        Request::from_parts(parts, body)
+    }
+
+    /// 모으지 않고 전달한 요청 본문이 끝나면 기록 (gRPC 요청이면 메시지 변환)
+    async fn finish_request_body(&mut self) {
+        let Some(captured) = self.request_body.take() else {
+            return;
+        };
+        let (body, truncated) = captured.await;
+        let Some(mut req) = self.req.take().map(|req| req.with_body(body)) else {
+            return;
+        };
+        if let Some(truncated) = truncated {
+            req = req.with_truncated(truncated);
+        }
+        self.req = Some(self.decode_grpc_request(req));
+    }
+
+    /// 요청 본문이 끝난 뒤 요청과 응답을 묶어서 전송
+    ///
+    /// 모으지 않고 전달한 요청 본문은 응답을 막지 않도록 따로 기다립니다.
+    fn send_output_after_request_body(&mut self) {
+        if self.request_body.is_none() {
+            self.send_output();
+            return;
+        }
+
+        let mut handler = self.clone();
+        self.request_body = None;
+        tokio::spawn(async move {
+            handler.finish_request_body().await;
+            handler.send_output();
+        });
+    }
+
+    /// 모아 두지 않은 요청 본문이 있으면 모아서 요청 본문으로 설정
+    async fn collect_pending_body(req: &mut Request<Bytes>, pending_body: &mut Option<Body>) {
+        if let Some(mut body) = pending_body.take() {
+            *req.body_mut() = Self::body_to_bytes_from_mut(&mut body)
+                .await
+                .unwrap_or_default();
+        }
+    }
+
     /// BodyMut를 Bytes로 변환하는 헬퍼 함수 (기존 proxyapi 방식)
     async fn body_to_bytes_from_mut(
         body_mut: &mut Body,
@@ -302,6 +549,149 @@ impl LoggingHandler {
         let body_bytes = body_mut.collect().await?.to_bytes();
         Ok(body_bytes)
     }
+
+    /// 응답 본문을 모으지 않고 도착하는 대로 전달하면서 기록
+    ///
+    /// 본문은 캡처 정책의 한도까지만 기록하고, SSE 응답이면 이벤트를 받는 대로 UI로 보냅니다.
+    /// 요청과 응답은 본문이 끝나거나 클라이언트 연결이 끊긴 뒤에 묶어서 전송합니다.
+    async fn stream_response(&mut self, ctx: &HttpContext, res: Response<Body>) -> Response<Body> {
+        let receive_started = Instant::now();
+        let time = chrono::Local::now()
+            .timestamp_nanos_opt()
+            .unwrap_or_default();
+        let (parts, body) = res.into_parts();
+        // 규칙이 응답을 바꾸기 전에 업스트림과 실제로 사용한 HTTP 버전 기록
+        let upstream_version = parts.version;
+
+        // 헤더, 상태 코드, 지연 액션은 본문 없이 적용
+        let parts = if self.matched.is_empty() {
+            parts
+        } else {
+            let res = Response::from_parts(parts, Bytes::new());
+            self.matched.apply_response(res).await.into_parts().0
+        };
+
+        let policy = self.capture_policy();
+        let (body, mut tap) = body.tee(policy.limit(&parts.headers));
+        let mut spill = policy.spill(&parts.headers, &spill_name(ctx, "response"));
+        let (status, version, headers) = (parts.status, parts.version, parts.headers.clone());
+        let mut parser = sse::is_event_stream(&headers).then(SseParser::new);
+        let timings = ctx.timings;
+        let upstream_tls = ctx
+            .upstream_tls
+            .as_ref()
+            .map(|tls| client_upstream_tls(&tls.status, tls.insecure));
+        let upstream_session = ctx.upstream_session.as_ref().map(client_upstream_session);
+        let client_identity = ctx.client_identity.clone();
+        let mut handler = self.clone();
+
+        tokio::spawn(async move {
+            use futures_util::StreamExt;
+
+            let mut trailers = None;
+            let mut index = 0;
+            while let Some(frame) = tap.next().await {
+                match frame {
+                    TapFrame::Data(data) => {
+                        if let Some(spill) = &mut spill {
+                            spill.push(&data, || tap.captured()).await;
+                        }
+                        let Some(parser) = &mut parser else {
+                            continue;
+                        };
+                        for event in parser.push(&data) {
+                            handler.send_sse_event(event, index);
+                            index += 1;
+                        }
+                    }
+                    TapFrame::Trailers(headers) => trailers = Some(headers),
+                    _ => {}
+                }
+            }
+
+            let timings = client_timings(&timings, receive_started.elapsed());
+            handler.finish_request_body().await;
+            let mut proxied_response =
+                ProxiedResponse::new(status, version, headers, tap.captured(), time);
+            if tap.is_truncated() {
+                let spill_path = match spill {
+                    Some(spill) => spill.finish().await,
+                    None => None,
+                };
+                proxied_response = proxied_response.with_truncated(BodyTruncation {
+                    size: tap.size(),
+                    spill_path,
+                });
+            }
+            let proxied_response = handler.decode_grpc_response(proxied_response, trailers);
+            handler.res = Some(with_upstream_session(
+                proxied_response.with_timings(timings),
+                upstream_session,
+            ));
+            handler.req = handler.req.take().map(|req| {
+                with_client_identity(
+                    with_upstream_tls(
+                        req.with_timings(timings)
+                            .with_upstream_version(upstream_version),
+                        upstream_tls,
+                    ),
+                    client_identity,
+                )
+            });
+
+            // 요청과 응답을 묶어서 전송
+            handler.send_output();
+        });
+
+        Response::from_parts(parts, body)
+    }
+
+    /// rustls 핸드셰이크에 실패한 요청을 레거시 TLS(OpenSSL) 클라이언트로 다시 보내고 일반 응답처럼 처리
+    ///
+    /// 요청의 메서드, 헤더, 본문과 응답 본문(바이너리 포함)을 그대로 전달하며, 다시 보내지 못하면 None을 반환합니다.
+    async fn retry_with_legacy_client(&mut self, ctx: &HttpContext) -> Option<Response<Body>> {
+        use http_body_util::Full;
+
+        let client = self.legacy_client.clone()?;
+        let req = self.upstream_request.take()?;
+
+        eprintln!("🔄 TLS 핸드셰이크 실패: OpenSSL 레거시 TLS로 다시 요청 중...");
+        let replayed = match client.send(req.to_request()).await {
+            Ok(replayed) => replayed,
+            Err(e) => {
+                eprintln!("❌ 레거시 TLS 요청도 실패: {}", e);
+                return None;
+            }
+        };
+        eprintln!("✅ 레거시 TLS 요청 성공");
+
+        // 구간별 소요 시간과 인증서 검증 결과, TLS 세션 정보는 다시 보낸 요청 기준으로 기록
+        let mut ctx = ctx.clone();
+        ctx.timings = replayed.timings;
+        ctx.upstream_tls = replayed.upstream_tls;
+        ctx.upstream_session = replayed.upstream_session;
+        ctx.client_identity = replayed.client_identity;
+        let (parts, body) = replayed.response.into_parts();
+        let body = match replayed.trailers {
+            Some(trailers) => body_with_trailers(body, trailers),
+            None => Body::from(Full::new(body)),
+        };
+
+        Some(
+            self.handle_response(&ctx, Response::from_parts(parts, body))
+                .await,
+        )
+    }
+
+    /// 본문과 트레일러를 함께 읽는 헬퍼 함수
+    async fn body_and_trailers_from_mut(
+        body_mut: &mut Body,
+    ) -> Result<(Bytes, Option<HeaderMap>), Box<dyn std::error::Error + Send + Sync>> {
+        use http_body_util::BodyExt;
+        let collected = body_mut.collect().await?;
+        let trailers = collected.trailers().cloned();
+        Ok((collected.to_bytes(), trailers))
+    }
 }
 
 impl LoggingHandler {
@@ -337,66 +727,220 @@ impl LoggingHandler {
 }
 
 impl HttpHandler for LoggingHandler {
-    async fn handle_request(
-        &mut self,
-        _ctx: &HttpContext,
-        req: Request<Body>,
-    ) -> RequestOrResponse {
+    async fn handle_request(&mut self, ctx: &HttpContext, req: Request<Body>) -> RequestOrResponse {
+        use http_body_util::Full;
+        use proxyapi_v2::hyper::body::Body as _;
+
+        self.request_body = None;
+
+        // 본문 조건이 있는 규칙과 브레이크포인트가 없으면, 크기를 모르거나 캡처 한도보다 큰 본문은
+        // 모으지 않고 전달하면서 기록 (대용량 업로드, 스트리밍 요청)
+        let (parts, mut body) = req.into_parts();
+        let max_body_bytes = self.capture_policy().max_body_bytes as u64;
+        let streams_body = !self.rules.matches_request_body()
+            && !self.breakpoints.matches_request_body()
+            && body
+                .size_hint()
+                .exact()
+                .is_none_or(|len| len > max_body_bytes);
+        let (mut pending_body, body_bytes) = if streams_body {
+            (Some(body), Bytes::new())
+        } else {
+            let body_bytes = Self::body_to_bytes_from_mut(&mut body)
+                .await
+                .unwrap_or_default();
+            (None, body_bytes)
+        };
+
+        // 규칙 매칭 (본문을 모으지 않았으면 본문 조건이 있는 규칙이 없으므로 빈 본문으로 매칭)
+        self.matched = self.rules.match_request(&parts, &body_bytes);
+        let mut req = Request::from_parts(parts, body_bytes);
+        if !self.matched.is_empty() {
+            println!(
+                "📐 규칙 적용: {} ({})",
+                self.matched.rule_ids().collect::<Vec<_>>().join(", "),
+                req.uri()
+            );
+            if self.matched.modifies_request_body() {
+                Self::collect_pending_body(&mut req, &mut pending_body).await;
+            }
+            req = self.matched.apply_request(req).await;
+        }
+
+        // 브레이크포인트 매칭 (응답 단계에서도 사용)
+        let (parts, body_bytes) = req.into_parts();
+        self.breakpoint = self.breakpoints.match_request(&parts, &body_bytes);
+        let mut req = Request::from_parts(parts, body_bytes);
+        let mut early_response = None;
+        if let Some(breakpoint) = self.breakpoint.clone().filter(|bp| bp.on_request()) {
+            Self::collect_pending_body(&mut req, &mut pending_body).await;
+            let (paused_req, aborted) = self.breakpoints.pause_request(&breakpoint, req).await;
+            req = paused_req;
+            early_response = aborted;
+        }
+
+        // 중단된 요청이거나 응답 전체를 교체하는 규칙이면 업스트림 요청 없이 바로 응답
+        if early_response.is_none() && self.matched.replaces_response() {
+            early_response = Some(
+                self.matched
+                    .apply_response(Response::new(Bytes::new()))
+                    .await,
+            );
+        }
+        let early_response = match early_response {
+            Some(res) => Some(res.map(|body| Body::from(Full::new(body)))),
+            // Map Local 규칙과 매칭되면 로컬 파일로 응답
+            None => self.map_local.serve(&req).await,
+        };
+        if let Some(res) = early_response {
+            // 업스트림으로 보내지 않는 요청은 본문을 모아서 기록
+            Self::collect_pending_body(&mut req, &mut pending_body).await;
+            let (proxied_request, _) = self
+                .request_to_proxied_request(ctx, req.map(|body| Body::from(Full::new(body))))
+                .await;
+            self.req = Some(self.decode_grpc_request(proxied_request));
+            let (proxied_response, restored_res) =
+                self.response_to_proxied_response(ctx, res).await;
+            self.res = Some(proxied_response);
+
+            // 요청과 응답을 묶어서 전송
+            self.send_output();
+            return restored_res.into();
+        }
+
+        // TLS 핸드셰이크가 실패하면 다른 클라이언트로 다시 보낼 수 있도록 보관
+        // (모으지 않고 전달하는 본문은 다시 보낼 수 없음)
+        self.upstream_request = (self.legacy_client.is_some() && pending_body.is_none())
+            .then(|| UpstreamRequest::new(&req));
+
         // 요청 정보를 ProxiedRequest로 변환하고 원본 요청을 복원
-        let (proxied_request, restored_req) = self.request_to_proxied_request(req).await;
-        self.req = Some(proxied_request);
+        let restored_req = match pending_body {
+            Some(body) => self.stream_request(ctx, req, body),
+            None => {
+                let (proxied_request, restored_req) = self
+                    .request_to_proxied_request(ctx, req.map(|body| Body::from(Full::new(body))))
+                    .await;
+                self.req = Some(self.decode_grpc_request(proxied_request));
+                restored_req
+            }
+        };
+
+        // WebSocket 업그레이드는 응답 단계를 거치지 않으므로 요청만 전송하고 프레임과 연결
+        if is_websocket_upgrade(restored_req.headers()) {
+            if let Some(req) = &self.req {
+                self.websockets
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .insert(
+                        ctx.exchange_id,
+                        WebSocketLink {
+                            request_id: req.id().clone(),
+                            closing: false,
+                        },
+                    );
+            }
+            self.send_output();
+        }
 
         restored_req.into()
     }
 
-    async fn handle_response(&mut self, _ctx: &HttpContext, res: Response<Body>) -> Response<Body> {
-        // 일반 응답 처리 - 세션 매칭 확인
-        if let Some(req) = &self.req {
-            let url = req.uri().to_string();
-            let method = req.method().to_string();
-
-            if let Some(session) = self.find_matching_session(&url, &method).await {
-                // 세션의 response 데이터 추출
-                let default_response = JsonValue::Object(serde_json::Map::new());
-                let response_data = session.get("response").unwrap_or(&default_response);
-                let mut session_response = self.create_response_from_session(response_data);
-
-                // 세션 응답의 실제 본문을 가져와서 Bytes로 변환
-                let session_body_bytes =
-                    match Self::body_to_bytes_from_mut(&mut session_response.body_mut()).await {
-                        Ok(bytes) => bytes,
-                        Err(_) => Bytes::from("세션 응답 읽기 실패"),
-                    };
-
-                // 세션 응답을 ProxiedResponse로 변환하여 저장
-                let session_proxied_response = ProxiedResponse::new(
-                    session_response.status(),
-                    session_response.version(),
-                    session_response.headers().clone(),
-                    session_body_bytes.clone(),
-                    chrono::Local::now()
-                        .timestamp_nanos_opt()
-                        .unwrap_or_default(),
-                );
-
-                self.res = Some(session_proxied_response);
-
-                // 요청과 응답을 묶어서 전송
-                self.send_output();
-
-                // body를 다시 복원하여 반환
-                use http_body_util::Full;
-                *session_response.body_mut() = Body::from(Full::new(session_body_bytes));
-                return session_response;
-            }
+    async fn handle_response(&mut self, ctx: &HttpContext, res: Response<Body>) -> Response<Body> {
+        use http_body_util::Full;
+
+        // 응답 본문을 바꾸거나 멈출 필요가 없으면 본문을 모으지 않고 바로 전달
+        // (SSE, 롱 폴링, 대용량 다운로드가 본문 전체를 기다리지 않도록)
+        let pauses_response = self.breakpoint.as_ref().is_some_and(|bp| bp.on_response());
+        if !pauses_response && !self.matched.modifies_response_body() {
+            return self.stream_response(ctx, res).await;
         }
 
-        // 일반 응답 처리
-        let (proxied_response, restored_res) = self.response_to_proxied_response(res).await;
-        self.res = Some(proxied_response);
+        // 본문을 먼저 모두 받아서 수신 시간 측정 (이후 단계는 받은 본문을 사용)
+        // gRPC의 grpc-status처럼 본문 뒤에 오는 트레일러는 따로 보관했다가 다시 붙임
+        let receive_started = Instant::now();
+        let (parts, mut body) = res.into_parts();
+        let (body_bytes, trailers) = Self::body_and_trailers_from_mut(&mut body)
+            .await
+            .unwrap_or_default();
+        let timings = client_timings(&ctx.timings, receive_started.elapsed());
+        // 규칙이 응답을 바꾸기 전에 업스트림과 실제로 사용한 HTTP 버전 기록
+        let upstream_version = parts.version;
+        let res = Response::from_parts(parts, Body::from(Full::new(body_bytes)));
+
+        // 규칙의 응답 액션 적용
+        let res = if self.matched.is_empty() {
+            res
+        } else {
+            let (parts, mut body) = res.into_parts();
+            let body_bytes = Self::body_to_bytes_from_mut(&mut body)
+                .await
+                .unwrap_or_default();
+            self.matched
+                .apply_response(Response::from_parts(parts, body_bytes))
+                .await
+                .map(|body| Body::from(Full::new(body)))
+        };
+
+        // 응답 브레이크포인트
+        let res = match self.breakpoint.clone().filter(|bp| bp.on_response()) {
+            Some(breakpoint) => {
+                let (method, uri) = match &self.req {
+                    Some(req) => (req.method().clone(), req.uri().clone()),
+                    None => Default::default(),
+                };
+                let (parts, mut body) = res.into_parts();
+                let body_bytes = Self::body_to_bytes_from_mut(&mut body)
+                    .await
+                    .unwrap_or_default();
+                self.breakpoints
+                    .pause_response(
+                        &breakpoint,
+                        &method,
+                        &uri,
+                        Response::from_parts(parts, body_bytes),
+                    )
+                    .await
+                    .map(|body| Body::from(Full::new(body)))
+            }
+            None => res,
+        };
+
+        // 응답 정보를 ProxiedResponse로 변환하고 원본 응답을 복원
+        let (proxied_response, restored_res) = self.response_to_proxied_response(ctx, res).await;
+        let restored_res = match &trailers {
+            Some(trailers) => {
+                let (parts, mut body) = restored_res.into_parts();
+                let body_bytes = Self::body_to_bytes_from_mut(&mut body)
+                    .await
+                    .unwrap_or_default();
+                Response::from_parts(parts, body_with_trailers(body_bytes, trailers.clone()))
+            }
+            None => restored_res,
+        };
+        let proxied_response = self.decode_grpc_response(proxied_response, trailers);
+        let upstream_session = ctx.upstream_session.as_ref().map(client_upstream_session);
+        self.res = Some(with_upstream_session(
+            proxied_response.with_timings(timings),
+            upstream_session,
+        ));
+        let upstream_tls = ctx
+            .upstream_tls
+            .as_ref()
+            .map(|tls| client_upstream_tls(&tls.status, tls.insecure));
+        let client_identity = ctx.client_identity.clone();
+        self.req = self.req.take().map(|req| {
+            with_client_identity(
+                with_upstream_tls(
+                    req.with_timings(timings)
+                        .with_upstream_version(upstream_version),
+                    upstream_tls,
+                ),
+                client_identity,
+            )
+        });
 
         // 요청과 응답을 묶어서 전송
-        self.send_output();
+        self.send_output_after_request_body();
 
         // 원본 응답을 그대로 반환 (기존 proxyapi 방식)
         restored_res
@@ -404,7 +948,7 @@ impl HttpHandler for LoggingHandler {
 
     async fn handle_error(
         &mut self,
-        _ctx: &HttpContext,
+        ctx: &HttpContext,
         err: hyper_util::client::legacy::Error,
     ) -> Response<Body> {
         eprintln!("❌ [HANDLER] handle_error 호출됨 - 에러 발생!");
@@ -447,40 +991,29 @@ impl HttpHandler for LoggingHandler {
         eprintln!("   - 에러 타입: {:?}", err);
         eprintln!("   - 에러 메시지: {}", err);
 
-        // 에러 원인 분석 및 curl 백업 사용 여부 결정
-        let should_use_curl = if let Some(source) = err.source() {
+        if let Some(source) = err.source() {
             eprintln!("   - 원인: {}", source);
+        }
 
-            let source_str = source.to_string();
-            if source_str.contains("HandshakeFailure") {
-                eprintln!("   - TLS 핸드셰이크 실패 (curl 백업 사용)");
-                true
-            } else {
-                eprintln!("   - 기타 연결 오류 (curl 백업 사용 안함)");
-                false
+        // TLS 핸드셰이크 실패면 레거시 TLS 클라이언트로 다시 시도
+        if is_handshake_failure(&err) {
+            if let Some(res) = self.retry_with_legacy_client(ctx).await {
+                return res;
             }
-        } else {
-            eprintln!("   - 알 수 없는 오류 (curl 백업 사용 안함)");
-            false
-        };
+        }
 
-        // TLS 오류인 경우 curl 백업 사용
-        if should_use_curl {
-            if let Some(req) = &self.req {
-                eprintln!("🔄 TLS 오류: curl로 직접 요청 시도 중...");
-                match fallback_with_curl(req).await {
-                    Ok(response) => {
-                        eprintln!("✅ curl 직접 요청 성공 - 원본 응답 반환");
-                        return response;
-                    }
-                    Err(curl_err) => {
-                        eprintln!("❌ curl 직접 요청도 실패: {}", curl_err);
-                    }
-                }
-            }
+        // 인증서 검증에 실패했으면 검증 결과를 요청에 기록해서 응답 없이 전송
+        if let Some(status) = verification_error(&err) {
+            eprintln!("   - 🔒 업스트림 인증서 검증 실패: {}", status);
+            let upstream_tls = client_upstream_tls(&status, false);
+            self.req = self
+                .req
+                .take()
+                .map(|req| req.with_upstream_tls(upstream_tls));
+            self.send_output_after_request_body();
         }
 
-        // curl도 실패한 경우 기본 에러 응답
+        // 다시 시도하지 않았거나 실패한 경우 기본 에러 응답
         Response::builder()
             .status(StatusCode::BAD_GATEWAY)
             .body(Body::from(format!("Proxy Error: {}", err)))
@@ -488,131 +1021,150 @@ impl HttpHandler for LoggingHandler {
     }
 }
 
-/// curl을 사용해서 직접 요청을 보내고 응답을 받는 함수
-async fn fallback_with_curl(
-    req: &ProxiedRequest,
-) -> Result<Response<Body>, Box<dyn std::error::Error>> {
-    use std::process::Command;
-    use std::str;
-
-    let url = req.uri().to_string();
-    let method = req.method().to_string();
-
-    // curl 명령어 구성
-    let mut curl_cmd = Command::new("curl");
-    curl_cmd
-        .arg("-s") // silent mode
-        .arg("-i") // include headers
-        .arg("-X")
-        .arg(&method) // HTTP method
-        .arg("--max-time")
-        .arg("10") // 10초 타임아웃
-        .arg("--connect-timeout")
-        .arg("5") // 5초 연결 타임아웃
-        .arg("--insecure"); // SSL 인증서 검증 무시
-
-    // 헤더 추가
-    for (name, value) in req.headers() {
-        let name_str = name.as_str();
-        if let Ok(value_str) = value.to_str() {
-            // Host 헤더는 URL에서 자동으로 설정되므로 제외
-            if name_str.to_lowercase() != "host" {
-                curl_cmd
-                    .arg("-H")
-                    .arg(format!("{}: {}", name_str, value_str));
-            }
-        }
-    }
-
-    // URL 추가
-    curl_cmd.arg(&url);
-
-    eprintln!("🔧 curl 명령어 실행: {:?}", curl_cmd);
-
-    // curl 실행
-    let output = curl_cmd.output()?;
+/// 업스트림 인증서 검증 결과를 UI용으로 변환
+fn client_upstream_tls(status: &upstream_tls::CertificateStatus, insecure: bool) -> UpstreamTls {
+    use upstream_tls::CertificateStatus as Status;
+
+    let (status, detail) = match status {
+        Status::Valid => (CertificateStatus::Valid, None),
+        Status::Expired => (CertificateStatus::Expired, None),
+        Status::NotYetValid => (CertificateStatus::NotYetValid, None),
+        Status::HostnameMismatch => (CertificateStatus::HostnameMismatch, None),
+        Status::SelfSigned => (CertificateStatus::SelfSigned, None),
+        Status::UnknownIssuer => (CertificateStatus::UnknownIssuer, None),
+        Status::Revoked => (CertificateStatus::Revoked, None),
+        Status::Invalid(reason) => (CertificateStatus::Invalid, Some(reason.clone())),
+        status => (CertificateStatus::Invalid, Some(status.to_string())),
+    };
 
-    if !output.status.success() {
-        return Err(format!("curl 실행 실패: {}", output.status).into());
+    UpstreamTls {
+        status,
+        insecure,
+        detail,
     }
-
-    let response_text = str::from_utf8(&output.stdout)?;
-    eprintln!("📥 curl 응답 길이: {} bytes", response_text.len());
-
-    // HTTP 응답 파싱
-    parse_curl_response(response_text)
 }
 
-/// curl 응답을 HTTP Response로 파싱하는 함수
-fn parse_curl_response(response_text: &str) -> Result<Response<Body>, Box<dyn std::error::Error>> {
-    let lines: Vec<&str> = response_text.lines().collect();
-    if lines.is_empty() {
-        return Err("빈 응답".into());
+/// 업스트림 인증서 검증 결과가 있으면 요청에 기록
+fn with_upstream_tls(req: ProxiedRequest, upstream_tls: Option<UpstreamTls>) -> ProxiedRequest {
+    match upstream_tls {
+        Some(upstream_tls) => req.with_upstream_tls(upstream_tls),
+        None => req,
     }
+}
 
-    // 첫 번째 줄에서 상태 코드 파싱
-    let status_line = lines[0];
-    let parts: Vec<&str> = status_line.split_whitespace().collect();
-    if parts.len() < 2 {
-        return Err("잘못된 상태 라인".into());
-    }
-
-    let status_code = parts[1].parse::<u16>()?;
-    let status = StatusCode::from_u16(status_code)?;
-
-    // 헤더와 본문 분리
-    let mut header_end = 0;
-    for (i, line) in lines.iter().enumerate() {
-        if line.is_empty() {
-            header_end = i;
-            break;
-        }
+/// 업스트림 연결에 사용한 클라이언트 인증서가 있으면 요청에 기록
+fn with_client_identity(req: ProxiedRequest, client_identity: Option<String>) -> ProxiedRequest {
+    match client_identity {
+        Some(client_identity) => req.with_client_identity(client_identity),
+        None => req,
     }
+}
 
-    // 헤더 파싱 (content-length 제외)
-    let mut headers = HeaderMap::new();
-    for line in &lines[1..header_end] {
-        if let Some(colon_pos) = line.find(':') {
-            let name = &line[..colon_pos].trim();
-            let value = &line[colon_pos + 1..].trim();
-
-            // content-length 헤더는 제외 (실제 본문 길이에 맞게 자동 설정됨)
-            if name.to_lowercase() == "content-length" {
-                continue;
-            }
-
-            if let (Ok(header_name), Ok(header_value)) = (
-                name.parse::<proxyapi_v2::hyper::http::HeaderName>(),
-                value.parse::<HeaderValue>(),
-            ) {
-                headers.insert(header_name, header_value);
+/// 업스트림 TLS 세션 정보를 UI용으로 변환 (해석하지 못한 인증서는 건너뜀)
+pub(crate) fn client_upstream_session(session: &upstream_tls::UpstreamSession) -> UpstreamSession {
+    let certificates = session
+        .certificates
+        .iter()
+        .filter_map(|der| match CertificateInfo::from_der(der) {
+            Ok(info) => Some(info),
+            Err(e) => {
+                eprintln!("⚠️ 업스트림 인증서 해석 실패: {}", e);
+                None
             }
-        }
+        })
+        .collect();
+
+    UpstreamSession {
+        version: session.version.map(|version| version.as_str().to_string()),
+        cipher_suite: session.cipher_suite.clone(),
+        alpn: session
+            .alpn
+            .as_ref()
+            .map(|alpn| String::from_utf8_lossy(alpn).into_owned()),
+        certificates,
     }
+}
 
-    // 본문 추출
-    let body_text = if header_end + 1 < lines.len() {
-        lines[header_end + 1..].join("\n")
-    } else {
-        String::new()
-    };
+/// 업스트림 TLS 세션 정보가 있으면 응답에 기록
+fn with_upstream_session(
+    res: ProxiedResponse,
+    upstream_session: Option<UpstreamSession>,
+) -> ProxiedResponse {
+    match upstream_session {
+        Some(upstream_session) => res.with_upstream_session(upstream_session),
+        None => res,
+    }
+}
 
-    // Response 생성
-    let mut response = Response::builder()
-        .status(status)
-        .body(Body::from(body_text))?;
+/// WebSocket 업그레이드 요청인지 확인
+fn is_websocket_upgrade(headers: &HeaderMap) -> bool {
+    headers
+        .get("upgrade")
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.eq_ignore_ascii_case("websocket"))
+}
 
-    // 헤더 추가
-    *response.headers_mut() = headers;
+/// WebSocket 메시지를 UI용 프레임으로 변환
+fn websocket_frame(ctx: &WebSocketContext, msg: &Message) -> WebSocketFrame {
+    let direction = match ctx.direction() {
+        websocket::WebSocketDirection::ClientToServer => WebSocketDirection::ClientToServer,
+        websocket::WebSocketDirection::ServerToClient => WebSocketDirection::ServerToClient,
+    };
+    let (opcode, payload) = match msg {
+        Message::Text(text) => (
+            WebSocketOpcode::Text,
+            Bytes::copy_from_slice(text.as_bytes()),
+        ),
+        Message::Binary(data) => (WebSocketOpcode::Binary, data.clone()),
+        Message::Ping(data) => (WebSocketOpcode::Ping, data.clone()),
+        Message::Pong(data) => (WebSocketOpcode::Pong, data.clone()),
+        Message::Close(_) => (WebSocketOpcode::Close, Bytes::new()),
+        // 원시 프레임은 읽기 중에는 전달되지 않음
+        Message::Frame(frame) => (
+            WebSocketOpcode::Binary,
+            Bytes::copy_from_slice(frame.payload()),
+        ),
+    };
 
-    Ok(response)
+    let frame = WebSocketFrame::new(
+        ctx.connection_id(),
+        direction,
+        opcode,
+        payload,
+        chrono::Local::now()
+            .timestamp_nanos_opt()
+            .unwrap_or_default(),
+    );
+    match msg {
+        Message::Close(Some(close)) => frame.with_close(close.code.into(), close.reason.as_str()),
+        _ => frame,
+    }
 }
 
-// WebSocket 핸들러 구현 (나중에 사용할 수 있도록 보존)
 impl WebSocketHandler for LoggingHandler {
-    async fn handle_message(&mut self, _ctx: &WebSocketContext, msg: Message) -> Option<Message> {
-        // WebSocket 메시지는 현재 RequestInfo 구조에 맞지 않으므로 로깅만 수행
-        // println!("WebSocket Message: {:?}", msg);
+    async fn handle_message(&mut self, ctx: &WebSocketContext, msg: Message) -> Option<Message> {
+        // WebSocket 규칙 적용 (삭제된 메시지는 전달하지 않음)
+        let msg = self.websocket_rules.apply(ctx, msg)?;
+        let mut frame = websocket_frame(ctx, &msg);
+
+        // 업그레이드 요청과 연결 (양쪽 Close 프레임을 모두 받으면 정리)
+        {
+            let mut websockets = self.websockets.lock().unwrap_or_else(|e| e.into_inner());
+            let connection_id = ctx.connection_id();
+            if let Some(link) = websockets.get_mut(&connection_id) {
+                frame = frame.with_request_id(link.request_id.clone());
+                if matches!(msg, Message::Close(_)) {
+                    if link.closing {
+                        websockets.remove(&connection_id);
+                    } else {
+                        link.closing = true;
+                    }
+                }
+            }
+        }
+
+        // 프레임 전송 실패 (프록시 종료 중, 무시)
+        let _ = self.frames.send(frame);
         Some(msg)
     }
 }
@@ -635,11 +1187,45 @@ pub struct ProxyStartResult {
     pub message: String,
 }
 
+/// 추가 리스너(SOCKS5, 투명 프록시) 바인딩
+///
+/// 포트 설정 오류는 경고만 출력하고, 바인딩 실패는 프록시 시작 실패로 처리합니다.
+async fn bind_extra_listener(
+    port: Result<Option<u16>, String>,
+    addr: SocketAddr,
+    name: &str,
+) -> Result<Option<TcpListener>, ProxyStartResult> {
+    match port {
+        Ok(Some(port)) => match TcpListener::bind(SocketAddr::new(addr.ip(), port)).await {
+            Ok(listener) => {
+                println!("✅ 포트 {}에서 {} 리스너 시작됨", port, name);
+                Ok(Some(listener))
+            }
+            Err(e) => {
+                let error_msg = format!("{} 포트 {} 바인딩 실패: {}", name, port, e);
+                eprintln!("❌ {}", error_msg);
+                Err(ProxyStartResult {
+                    status: false,
+                    message: error_msg,
+                })
+            }
+        },
+        Ok(None) => Ok(None),
+        Err(e) => {
+            eprintln!("⚠️ {}", e);
+            Ok(None)
+        }
+    }
+}
+
 /// hudsucker 프록시 시작 (실제 프록시 서버 실행)
 #[tauri::command]
 pub async fn start_proxy_v2<R: Runtime>(
     app: AppHandle<R>,
     proxy: State<'_, ProxyV2State>,
+    capture: State<'_, CaptureState>,
+    storage: State<'_, TrafficStore>,
+    breakpoints: State<'_, BreakpointManager>,
     addr: SocketAddr,
 ) -> Result<ProxyStartResult, ProxyStartResult> {
     // 이미 프록시가 실행 중인지 확인
@@ -658,13 +1244,23 @@ pub async fn start_proxy_v2<R: Runtime>(
     }
     drop(proxy_guard); // 락 해제
 
-    // CA 인증서 생성 (proxyapi_v2의 build_ca 함수 사용)
+    // 설치별 루트 CA 로드 (처음 실행하면 앱 데이터 디렉터리에 생성)
     println!("🔐 CA 인증서 생성/로드 시도 중...");
-    let ca = match build_ca() {
+    let ca = match load_root_ca(&app).and_then(|root_ca| {
+        RcgenAuthority::from_root_ca(&root_ca, 1_000, aws_lc_rs::default_provider())
+            .map_err(|e| format!("CA 인증서 파싱 실패: {}", e))
+    }) {
         Ok(ca) => {
             println!("✅ CA 인증서 로드 완료");
             println!("   - CA 인증서가 성공적으로 생성/로드되었습니다");
-            ca
+            // 재시작해도 호스트마다 인증서를 다시 서명하지 않도록 디스크 저장소 사용
+            match leaf_cert_store(&app) {
+                Ok(store) => ca.with_leaf_store(&store),
+                Err(e) => {
+                    eprintln!("⚠️ {}", e);
+                    ca
+                }
+            }
         }
         Err(e) => {
             let error_msg = format!("CA 인증서 생성 실패: {}", e);
@@ -679,12 +1275,18 @@ pub async fn start_proxy_v2<R: Runtime>(
 
     // 이벤트 전송을 위한 채널 생성 (proxy.rs와 동일한 구조)
     let (tx, rx) = std::sync::mpsc::sync_channel(1);
+    // WebSocket 프레임은 메시지 전달을 막지 않도록 별도 채널로 전송
+    let (frame_tx, mut frame_rx) = tokio::sync::mpsc::unbounded_channel();
+    // SSE 이벤트도 응답 전달을 막지 않도록 별도 채널로 전송
+    let (sse_tx, mut sse_rx) = tokio::sync::mpsc::unbounded_channel();
 
-    // 세션 데이터 로드 (proxy.rs와 동일한 방식)
-    let store = match app.store("session.json") {
-        Ok(store) => store,
-        Err(e) => {
-            let error_msg = format!("세션 스토어 로드 실패: {}", e);
+    // 로깅 핸들러 생성
+    let handler = LoggingHandler::new(tx.clone(), frame_tx, sse_tx, breakpoints.inner().clone());
+
+    // 규칙(세션 포함)을 핸들러에 전달
+    let rules = match load_rules(&app) {
+        Ok(rules) => rules,
+        Err(error_msg) => {
             eprintln!("❌ {}", error_msg);
             return Err(ProxyStartResult {
                 status: false,
@@ -692,13 +1294,53 @@ pub async fn start_proxy_v2<R: Runtime>(
             });
         }
     };
-    let sessions = store.get("sessions").unwrap_or_default();
+    for error in handler.update_rules(rules) {
+        eprintln!("⚠️ 규칙 로드 실패 (건너뜀): {}", error);
+    }
 
-    // 로깅 핸들러 생성
-    let handler = LoggingHandler::new(tx.clone());
+    // WebSocket 메시지 규칙 로드
+    match load_websocket_rules(&app) {
+        Ok(rules) => {
+            for error in handler.update_websocket_rules(rules) {
+                eprintln!("⚠️ WebSocket 규칙 로드 실패 (건너뜀): {}", error);
+            }
+        }
+        Err(e) => eprintln!("⚠️ {}", e),
+    }
+
+    // Map Remote 규칙 로드
+    match load_map_remote(&app) {
+        Ok(rules) => handler.update_map_remote(rules),
+        Err(e) => eprintln!("⚠️ {}", e),
+    }
+
+    // Map Local 규칙 로드
+    match load_map_local(&app) {
+        Ok(rules) => handler.update_map_local(rules),
+        Err(e) => eprintln!("⚠️ {}", e),
+    }
+
+    // gRPC 메시지 변환용 protobuf 디스크립터 로드
+    match load_proto_descriptors(&app) {
+        Ok(descriptors) => handler.update_proto_descriptors(descriptors),
+        Err(e) => eprintln!("⚠️ {}", e),
+    }
+
+    // 본문 캡처 정책 로드
+    match load_capture_policy(&app) {
+        Ok(policy) => handler.update_capture_policy(policy),
+        Err(e) => eprintln!("⚠️ {}", e),
+    }
 
-    // 세션 데이터를 핸들러에 전달
-    handler.update_sessions(sessions).await;
+    // 브레이크포인트 로드
+    match load_breakpoints(&app) {
+        Ok(list) => {
+            for error in breakpoints.set_breakpoints(list) {
+                eprintln!("⚠️ 브레이크포인트 로드 실패 (건너뜀): {}", error);
+            }
+        }
+        Err(e) => eprintln!("⚠️ {}", e),
+    }
 
     // TCP 리스너 생성
     let listener = match TcpListener::bind(addr).await {
@@ -716,13 +1358,58 @@ pub async fn start_proxy_v2<R: Runtime>(
         }
     };
 
-    // 하이브리드 클라이언트 생성 (모든 인증서 허용)
-    let hybrid_client = match create_hybrid_client() {
+    // 상위 프록시 설정 로드 (설정이 잘못되면 직접 연결하지 않고 시작 실패)
+    let upstream_proxy = match load_upstream_proxy(&app) {
+        Ok(upstream_proxy) => upstream_proxy,
+        Err(error_msg) => {
+            eprintln!("❌ {}", error_msg);
+            return Err(ProxyStartResult {
+                status: false,
+                message: error_msg,
+            });
+        }
+    };
+
+    // 업스트림 인증서 검증 정책 로드 (CA 파일을 읽지 못하면 시작 실패)
+    let tls_verification = match load_tls_verification(&app) {
+        Ok(policy) => policy,
+        Err(error_msg) => {
+            eprintln!("❌ {}", error_msg);
+            return Err(ProxyStartResult {
+                status: false,
+                message: error_msg,
+            });
+        }
+    };
+
+    // 호스트별 클라이언트 인증서 로드 (인증서 파일을 읽지 못하면 시작 실패)
+    let provider = aws_lc_rs::default_provider();
+    let client_identities = match load_client_identities(&app, &provider) {
+        Ok(identities) => identities,
+        Err(error_msg) => {
+            eprintln!("❌ {}", error_msg);
+            return Err(ProxyStartResult {
+                status: false,
+                message: error_msg,
+            });
+        }
+    };
+
+    // 하이브리드 클라이언트 생성 (검증 정책에 따라 인증서 확인)
+    let hybrid_client = match hybrid_client(
+        provider.clone(),
+        &tls_verification,
+        &client_identities,
+        upstream_proxy.clone(),
+    ) {
         Ok(client) => {
             println!("✅ 하이브리드 클라이언트 생성 완료");
             println!("   - 기본 프로바이더 사용");
-            println!("   - 모든 인증서 허용 (DangerousCertificateVerifier)");
-            println!("   - HTTP/1.1 지원");
+            println!("   - 신뢰 저장소 + 추가 CA로 인증서 검증");
+            if !client_identities.is_empty() {
+                println!("   - 호스트별 클라이언트 인증서 사용");
+            }
+            println!("   - HTTP/1.1, HTTP/2 지원");
             client
         }
         Err(e) => {
@@ -735,11 +1422,47 @@ pub async fn start_proxy_v2<R: Runtime>(
         }
     };
 
-    // 프록시 빌더로 프록시 구성 (하이브리드 클라이언트 사용)
-    let proxy_builder = match ProxyBuilder::new()
-        .with_listener(listener)
-        .with_ca(ca)
+    // rustls 핸드셰이크에 실패한 요청을 다시 보낼 레거시 TLS(OpenSSL) 클라이언트 (같은 검증 정책과 클라이언트 인증서 사용)
+    let handler = match ReplayClient::legacy(
+        provider,
+        &tls_verification,
+        &client_identities,
+        upstream_proxy.clone(),
+    ) {
+        Ok(client) => handler.with_legacy_client(client),
+        Err(e) => {
+            eprintln!(
+                "⚠️ 레거시 TLS 클라이언트 생성 실패 (TLS 대체 요청 사용 안함): {}",
+                e
+            );
+            handler
+        }
+    };
+
+    // SOCKS5, 투명 프록시 리스너 생성 (설정된 경우 HTTP 프록시와 같은 주소의 다른 포트)
+    let socks5_listener = bind_extra_listener(load_socks5_port(&app), addr, "SOCKS5").await?;
+    let transparent_listener =
+        bind_extra_listener(load_transparent_port(&app), addr, "투명 프록시").await?;
+
+    // 상위 프록시는 CONNECT 터널과 WebSocket 연결에도 적용
+    let mut client_builder = ProxyBuilder::new().with_listener(listener).with_ca(ca);
+    if let Some(upstream_proxy) = upstream_proxy {
+        client_builder = client_builder.with_upstream_proxy(upstream_proxy);
+    }
+
+    let mut handlers_builder = client_builder
         .with_client(hybrid_client) // 하이브리드 클라이언트 사용
+        .with_map_remote(handler.map_remote())
+        .with_websocket_injector(handler.websocket_injector());
+    if let Some(socks5_listener) = socks5_listener {
+        handlers_builder = handlers_builder.with_socks5_listener(socks5_listener);
+    }
+    if let Some(transparent_listener) = transparent_listener {
+        handlers_builder = handlers_builder.with_transparent_listener(transparent_listener);
+    }
+
+    // 프록시 빌더로 프록시 구성 (하이브리드 클라이언트 사용)
+    let proxy_builder = match handlers_builder
         .with_http_handler(handler.clone())
         .with_websocket_handler(handler.clone())
         .build()
@@ -749,7 +1472,7 @@ pub async fn start_proxy_v2<R: Runtime>(
             println!("   - CA 인증서: 로드됨");
             println!("   - TLS 클라이언트: 하이브리드 클라이언트 (모든 인증서 허용)");
             println!("   - HTTP 핸들러: 로깅 핸들러");
-            println!("   - WebSocket: 직접 통과 (핸들러 없음)");
+            println!("   - WebSocket 핸들러: 로깅 핸들러 (프레임 캡처)");
             builder
         }
         Err(e) => {
@@ -784,10 +1507,28 @@ pub async fn start_proxy_v2<R: Runtime>(
     let mut proxy_guard = proxy.lock().await;
     proxy_guard.replace((close_tx, thread, handler.clone()));
 
+    // WebSocket 프레임 이벤트 전송
+    let frame_app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        while let Some(frame) = frame_rx.recv().await {
+            let _ = frame_app.emit("proxy_websocket_frame", frame);
+        }
+    });
+
+    // SSE 이벤트 전송
+    let sse_app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        while let Some(event) = sse_rx.recv().await {
+            let _ = sse_app.emit("proxy_sse_event", event);
+        }
+    });
+
     // 이벤트 전송을 위한 백그라운드 태스크 (proxy.rs와 동일한 구조)
+    let capture = capture.inner().clone();
+    let storage = storage.inner().clone();
     tauri::async_runtime::spawn(async move {
         for event in rx.iter() {
-            let _ = app.emit("proxy_event", event);
+            publish_exchange(&app, &capture, &storage, event).await;
         }
     });
 
@@ -855,34 +1596,90 @@ pub async fn proxy_v2_status(proxy: tauri::State<'_, ProxyV2State>) -> Result<bo
 pub async fn store_changed_v2<R: Runtime>(
     app: AppHandle<R>,
     proxy: State<'_, ProxyV2State>,
+    storage: State<'_, TrafficStore>,
+    breakpoints: State<'_, BreakpointManager>,
 ) -> Result<(), String> {
+    // 트래픽 보존 정책은 프록시 실행 여부와 관계없이 적용
+    match load_retention(&app) {
+        Ok(retention) => {
+            let storage = storage.inner().clone();
+            match tokio::task::spawn_blocking(move || storage.set_retention(retention)).await {
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => eprintln!("⚠️ {}", e),
+                Err(e) => eprintln!("⚠️ 트래픽 보존 정책 적용 실패: {}", e),
+            }
+        }
+        Err(e) => eprintln!("⚠️ {}", e),
+    }
+
     let mut proxy_guard = proxy.lock().await;
 
     if proxy_guard.is_none() {
-        println!("store_changed_v2: Proxy V2가 실행 중이 아니므로 세션 업데이트를 무시합니다");
+        println!("store_changed_v2: Proxy V2가 실행 중이 아니므로 규칙 업데이트를 무시합니다");
         return Ok(());
     }
 
-    // 세션 데이터 로드
-    let store = app.store("session.json").map_err(|e| e.to_string())?;
-    let sessions = store.get("sessions").unwrap_or_default();
-
-    let session_count = if let JsonValue::Array(arr) = &sessions {
-        arr.len()
-    } else {
-        0
-    };
+    // 규칙(세션 포함) 로드
+    let rules = load_rules(&app)?;
 
-    println!(
-        "🔄 Proxy V2 세션 데이터 업데이트: {} 개의 세션",
-        session_count
-    );
+    println!("🔄 Proxy V2 규칙 업데이트: {} 개의 규칙", rules.len());
 
-    // 핸들러에 세션 데이터 전달
+    // 핸들러에 규칙 전달
     if let Some((_close_tx, _thread, handler)) = proxy_guard.as_mut() {
-        handler.update_sessions(sessions).await;
-        println!("✅ Proxy V2 핸들러에 세션 데이터 업데이트 완료");
+        for error in handler.update_rules(rules) {
+            eprintln!("⚠️ 규칙 로드 실패 (건너뜀): {}", error);
+        }
+        for error in handler.update_websocket_rules(load_websocket_rules(&app)?) {
+            eprintln!("⚠️ WebSocket 규칙 로드 실패 (건너뜀): {}", error);
+        }
+        handler.update_map_remote(load_map_remote(&app)?);
+        handler.update_map_local(load_map_local(&app)?);
+        handler.update_proto_descriptors(load_proto_descriptors(&app)?);
+        handler.update_capture_policy(load_capture_policy(&app)?);
+        println!("✅ Proxy V2 핸들러에 규칙 업데이트 완료");
+    }
+
+    // 브레이크포인트 업데이트
+    for error in breakpoints.set_breakpoints(load_breakpoints(&app)?) {
+        eprintln!("⚠️ 브레이크포인트 로드 실패 (건너뜀): {}", error);
     }
 
     Ok(())
 }
+
+/// 실행 중인 WebSocket 연결에 프레임 주입
+///
+/// `direction`이 `client_to_server`면 서버로, `server_to_client`면 클라이언트로 전송하며,
+/// 주입한 프레임도 `proxy_websocket_frame` 이벤트로 캡처됩니다.
+#[tauri::command]
+pub async fn inject_websocket_frame(
+    proxy: State<'_, ProxyV2State>,
+    connection_id: u64,
+    direction: WebSocketDirection,
+    opcode: WebSocketOpcode,
+    payload: Vec<u8>,
+) -> Result<(), String> {
+    let injector = match proxy.lock().await.as_ref() {
+        Some((_close_tx, _thread, handler)) => handler.websocket_injector(),
+        None => return Err("Proxy V2가 실행 중이 아닙니다".to_string()),
+    };
+
+    let message = match opcode {
+        WebSocketOpcode::Text => Message::text(
+            String::from_utf8(payload)
+                .map_err(|_| "텍스트 프레임이 UTF-8이 아닙니다".to_string())?,
+        ),
+        WebSocketOpcode::Binary => Message::binary(payload),
+        WebSocketOpcode::Ping => Message::Ping(payload.into()),
+        WebSocketOpcode::Pong => Message::Pong(payload.into()),
+        WebSocketOpcode::Close => Message::Close(None),
+    };
+    let direction = match direction {
+        WebSocketDirection::ClientToServer => websocket::WebSocketDirection::ClientToServer,
+        WebSocketDirection::ServerToClient => websocket::WebSocketDirection::ServerToClient,
+    };
+
+    injector
+        .inject(connection_id, direction, message)
+        .map_err(|e| format!("WebSocket 프레임 주입 실패: {}", e))
+}
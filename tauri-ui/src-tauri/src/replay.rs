@@ -0,0 +1,164 @@
+use crate::capture::{publish_exchange, CaptureState};
+use crate::capture_policy::load_capture_policy;
+use crate::proxy_v2::{client_timings, client_upstream_session};
+use crate::rules::{load_client_identities, load_tls_verification, load_upstream_proxy};
+use crate::storage::TrafficStore;
+use futures_util::StreamExt;
+use proxy_v2_models::{ClientRequest, ProxiedRequest, ProxiedResponse, RequestInfo};
+use proxyapi_v2::hyper::Request;
+use proxyapi_v2::replay::{ReplayClient, ReplayOptions};
+use serde::Deserialize;
+use std::time::{Duration, UNIX_EPOCH};
+use tauri::{AppHandle, Runtime, State};
+use tokio_rustls::rustls::crypto::aws_lc_rs;
+
+/// 재전송 옵션 (UI에서 전달, 생략한 값은 기본값)
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ReplayRequestOptions {
+    /// 보낼 횟수 (기본 1)
+    count: Option<usize>,
+    /// 동시에 진행할 요청 수 (기본 1)
+    concurrency: Option<usize>,
+    /// 요청을 시작하는 간격 (ms)
+    delay_ms: Option<u64>,
+}
+
+impl From<ReplayRequestOptions> for ReplayOptions {
+    fn from(options: ReplayRequestOptions) -> Self {
+        let defaults = ReplayOptions::default();
+        ReplayOptions {
+            count: options.count.unwrap_or(defaults.count),
+            concurrency: options.concurrency.unwrap_or(defaults.concurrency),
+            delay: options
+                .delay_ms
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.delay),
+        }
+    }
+}
+
+/// 캡처한(또는 편집한) 요청을 다시 보내고 결과를 새 교환으로 UI 목록에 추가
+///
+/// 결과는 프록시로 캡처한 교환과 같은 `proxy_event` 이벤트로 전달되며, 성공한 요청 수를 반환합니다.
+#[tauri::command]
+pub async fn replay_request<R: Runtime>(
+    app: AppHandle<R>,
+    capture: State<'_, CaptureState>,
+    storage: State<'_, TrafficStore>,
+    request: ClientRequest,
+    options: Option<ReplayRequestOptions>,
+) -> Result<usize, String> {
+    let policy = load_capture_policy(&app)?;
+    let body = match request.truncated() {
+        None => request.body().clone(),
+        // 일부만 캡처된 본문은 임시 파일에 저장된 전체 본문으로 복원 (임시 디렉터리 안의 파일만 허용)
+        Some(truncated) => match &truncated.spill_path {
+            Some(path) => tokio::fs::read(policy.resolve_spill_path(path).await?)
+                .await
+                .map_err(|e| format!("본문 임시 파일 읽기 실패 ({}): {}", path, e))?
+                .into(),
+            None => return Err("본문이 일부만 캡처된 요청은 다시 보낼 수 없습니다".to_string()),
+        },
+    };
+
+    let mut builder = Request::builder()
+        .method(request.method().clone())
+        .uri(request.uri().clone());
+    if let Some(headers) = builder.headers_mut() {
+        headers.extend(request.headers().clone());
+    }
+    let req = builder
+        .body(body)
+        .map_err(|e| format!("요청 생성 실패: {}", e))?;
+
+    let upstream_proxy = load_upstream_proxy(&app)?;
+    let tls_verification = load_tls_verification(&app)?;
+    let provider = aws_lc_rs::default_provider();
+    let client_identities = load_client_identities(&app, &provider)?;
+    let client = ReplayClient::new(
+        provider,
+        &tls_verification,
+        &client_identities,
+        upstream_proxy,
+    )
+    .map_err(|e| format!("재전송 클라이언트 생성 실패: {}", e))?;
+    let options = ReplayOptions::from(options.unwrap_or_default());
+
+    let now = || {
+        chrono::Local::now()
+            .timestamp_nanos_opt()
+            .unwrap_or_default()
+    };
+    let proxied_request = |time: i64| {
+        let proxied_request = ProxiedRequest::new(
+            request.method().clone(),
+            request.uri().clone(),
+            *request.version(),
+            request.headers().clone(),
+            request.body().clone(),
+            time,
+        );
+        match request.truncated() {
+            Some(truncated) => proxied_request.with_truncated(truncated.clone()),
+            None => proxied_request,
+        }
+    };
+
+    let mut succeeded = 0;
+    let mut results = client.replay(req, options);
+    while let Some((index, result)) = results.next().await {
+        let info = match result {
+            Ok(replayed) => {
+                succeeded += 1;
+                let sent_at = replayed
+                    .sent_at
+                    .duration_since(UNIX_EPOCH)
+                    .map(|since| since.as_nanos() as i64)
+                    .unwrap_or_else(|_| now());
+                let timings = client_timings(&replayed.timings, replayed.receive);
+                let (parts, body) = replayed.response.into_parts();
+                let name = format!("{}-replay-{}-response", now(), index);
+                let (body, truncated) = policy.record(&parts.headers, &name, &body).await;
+
+                let mut proxied_request = proxied_request(sent_at)
+                    .with_timings(timings)
+                    .with_upstream_version(parts.version);
+                if let Some(client_identity) = replayed.client_identity {
+                    proxied_request = proxied_request.with_client_identity(client_identity);
+                }
+                let mut proxied_response =
+                    ProxiedResponse::new(parts.status, parts.version, parts.headers, body, now())
+                        .with_timings(timings);
+                if let Some(trailers) = replayed.trailers {
+                    proxied_response = proxied_response.with_trailers(trailers);
+                }
+                if let Some(truncated) = truncated {
+                    proxied_response = proxied_response.with_truncated(truncated);
+                }
+                if let Some(session) = &replayed.upstream_session {
+                    proxied_response =
+                        proxied_response.with_upstream_session(client_upstream_session(session));
+                }
+                RequestInfo(
+                    Some(proxied_request.for_client()),
+                    Some(proxied_response.for_client()),
+                )
+            }
+            Err(e) => {
+                eprintln!("⚠️ 요청 재전송 실패 ({}번째): {}", index + 1, e);
+                // 실패한 요청도 응답 없이 목록에 남김
+                RequestInfo(Some(proxied_request(now()).for_client()), None)
+            }
+        };
+        publish_exchange(&app, &capture, &storage, info).await;
+    }
+
+    println!(
+        "✅ 요청 재전송 완료: {}/{} 성공 → {}",
+        succeeded,
+        options.count.max(1),
+        request.uri()
+    );
+    Ok(succeeded)
+}
@@ -0,0 +1,501 @@
+use proxyapi_v2::client_identity::{ClientIdentities, ClientIdentity};
+use proxyapi_v2::grpc::ProtoDescriptors;
+use proxyapi_v2::map_local::MapLocalRule;
+use proxyapi_v2::map_remote::MapRemoteRule;
+use proxyapi_v2::rules::{Rule, RuleAction, RuleMatcher, WebSocketRule};
+use proxyapi_v2::rustls::crypto::CryptoProvider;
+use proxyapi_v2::upstream_proxy::UpstreamProxy;
+use proxyapi_v2::upstream_tls::TlsVerificationPolicy;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::{JsonValue, StoreExt};
+
+/// 규칙이 저장된 스토어 파일
+const RULE_STORE: &str = "session.json";
+
+/// 스토어에서 규칙 목록 로드
+///
+/// `rules` 키의 규칙과 기존 `sessions` 키의 세션(URL/메서드 매칭 후 응답 교체)을 함께 반환합니다.
+pub fn load_rules<R: Runtime>(app: &AppHandle<R>) -> Result<Vec<Rule>, String> {
+    let store = app
+        .store(RULE_STORE)
+        .map_err(|e| format!("세션 스토어 로드 실패: {}", e))?;
+
+    let mut rules = parse_rules(store.get("rules").unwrap_or_default());
+    rules.extend(sessions_to_rules(store.get("sessions").unwrap_or_default()));
+
+    Ok(rules)
+}
+
+/// 스토어에서 WebSocket 메시지 규칙 목록 로드 (`websocketRules` 키, 형식이 잘못된 규칙은 건너뜀)
+pub fn load_websocket_rules<R: Runtime>(app: &AppHandle<R>) -> Result<Vec<WebSocketRule>, String> {
+    let store = app
+        .store(RULE_STORE)
+        .map_err(|e| format!("세션 스토어 로드 실패: {}", e))?;
+
+    let JsonValue::Array(items) = store.get("websocketRules").unwrap_or_default() else {
+        return Ok(Vec::new());
+    };
+
+    Ok(items
+        .into_iter()
+        .filter_map(|item| match serde_json::from_value::<WebSocketRule>(item) {
+            Ok(rule) => Some(rule),
+            Err(e) => {
+                eprintln!("⚠️ WebSocket 규칙 파싱 실패 (건너뜀): {}", e);
+                None
+            }
+        })
+        .collect())
+}
+
+/// 스토어의 Map Remote 설정 (`mapRemote` 키)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MapRemoteConfig {
+    #[serde(default = "enabled_by_default")]
+    enabled: bool,
+    from: String,
+    to: String,
+    #[serde(default)]
+    preserve_host: bool,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+/// 스토어에서 Map Remote 규칙 목록 로드 (형식이 잘못된 규칙은 건너뜀)
+pub fn load_map_remote<R: Runtime>(app: &AppHandle<R>) -> Result<Vec<MapRemoteRule>, String> {
+    let store = app
+        .store(RULE_STORE)
+        .map_err(|e| format!("세션 스토어 로드 실패: {}", e))?;
+
+    let JsonValue::Array(items) = store.get("mapRemote").unwrap_or_default() else {
+        return Ok(Vec::new());
+    };
+
+    Ok(items
+        .into_iter()
+        .filter_map(|item| {
+            let config = match serde_json::from_value::<MapRemoteConfig>(item) {
+                Ok(config) => config,
+                Err(e) => {
+                    eprintln!("⚠️ Map Remote 설정 파싱 실패 (건너뜀): {}", e);
+                    return None;
+                }
+            };
+            if !config.enabled {
+                return None;
+            }
+
+            match MapRemoteRule::new(&config.from, &config.to) {
+                Ok(rule) => Some(rule.preserve_host(config.preserve_host)),
+                Err(e) => {
+                    eprintln!("⚠️ Map Remote 규칙 생성 실패 (건너뜀): {}", e);
+                    None
+                }
+            }
+        })
+        .collect())
+}
+
+/// 스토어의 Map Local 설정 (`mapLocal` 키)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MapLocalConfig {
+    #[serde(default = "enabled_by_default")]
+    enabled: bool,
+    from: String,
+    path: String,
+    #[serde(default)]
+    index_files: Option<Vec<String>>,
+}
+
+/// 스토어에서 Map Local 규칙 목록 로드 (형식이 잘못된 규칙은 건너뜀)
+pub fn load_map_local<R: Runtime>(app: &AppHandle<R>) -> Result<Vec<MapLocalRule>, String> {
+    let store = app
+        .store(RULE_STORE)
+        .map_err(|e| format!("세션 스토어 로드 실패: {}", e))?;
+
+    let JsonValue::Array(items) = store.get("mapLocal").unwrap_or_default() else {
+        return Ok(Vec::new());
+    };
+
+    Ok(items
+        .into_iter()
+        .filter_map(|item| {
+            let config = match serde_json::from_value::<MapLocalConfig>(item) {
+                Ok(config) => config,
+                Err(e) => {
+                    eprintln!("⚠️ Map Local 설정 파싱 실패 (건너뜀): {}", e);
+                    return None;
+                }
+            };
+            if !config.enabled {
+                return None;
+            }
+
+            match MapLocalRule::new(&config.from, config.path) {
+                Ok(rule) => Some(match config.index_files {
+                    Some(index_files) => rule.index_files(index_files),
+                    None => rule,
+                }),
+                Err(e) => {
+                    eprintln!("⚠️ Map Local 규칙 생성 실패 (건너뜀): {}", e);
+                    None
+                }
+            }
+        })
+        .collect())
+}
+
+/// 스토어의 protobuf 디스크립터 설정 (`protoDescriptors` 키)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProtoDescriptorConfig {
+    #[serde(default = "enabled_by_default")]
+    enabled: bool,
+    /// `.proto` 파일 또는 직렬화된 `FileDescriptorSet` 파일 경로
+    path: PathBuf,
+    /// `.proto` 파일의 import 검색 경로
+    #[serde(default)]
+    include_paths: Vec<PathBuf>,
+}
+
+/// 스토어에서 gRPC 메시지 변환용 protobuf 디스크립터 로드 (읽을 수 없는 파일은 건너뜀)
+///
+/// `.proto` 파일은 `protoc`로 컴파일하고, 그 외 파일은 `FileDescriptorSet`으로 읽습니다.
+pub fn load_proto_descriptors<R: Runtime>(app: &AppHandle<R>) -> Result<ProtoDescriptors, String> {
+    let store = app
+        .store(RULE_STORE)
+        .map_err(|e| format!("세션 스토어 로드 실패: {}", e))?;
+
+    let mut descriptors = ProtoDescriptors::new();
+    let JsonValue::Array(items) = store.get("protoDescriptors").unwrap_or_default() else {
+        return Ok(descriptors);
+    };
+
+    for item in items {
+        let config = match serde_json::from_value::<ProtoDescriptorConfig>(item) {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("⚠️ protobuf 디스크립터 설정 파싱 실패 (건너뜀): {}", e);
+                continue;
+            }
+        };
+        if !config.enabled {
+            continue;
+        }
+
+        let result = if config.path.extension().is_some_and(|ext| ext == "proto") {
+            descriptors.add_proto_files(&[&config.path], &config.include_paths)
+        } else {
+            match std::fs::read(&config.path) {
+                Ok(bytes) => descriptors.add_file_descriptor_set(&bytes),
+                Err(e) => {
+                    eprintln!(
+                        "⚠️ protobuf 디스크립터 읽기 실패 (건너뜀): {}: {}",
+                        config.path.display(),
+                        e
+                    );
+                    continue;
+                }
+            }
+        };
+        if let Err(e) = result {
+            eprintln!(
+                "⚠️ protobuf 디스크립터 로드 실패 (건너뜀): {}: {}",
+                config.path.display(),
+                e
+            );
+        }
+    }
+
+    Ok(descriptors)
+}
+
+/// 스토어의 상위 프록시 설정 (`upstreamProxy` 키)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpstreamProxyConfig {
+    #[serde(default = "enabled_by_default")]
+    enabled: bool,
+    url: String,
+    #[serde(default)]
+    username: Option<String>,
+    #[serde(default)]
+    password: Option<String>,
+    #[serde(default)]
+    bypass: Vec<String>,
+}
+
+/// 스토어에서 상위 프록시 설정 로드 (설정이 없거나 비활성화되어 있으면 None)
+pub fn load_upstream_proxy<R: Runtime>(
+    app: &AppHandle<R>,
+) -> Result<Option<UpstreamProxy>, String> {
+    let store = app
+        .store(RULE_STORE)
+        .map_err(|e| format!("세션 스토어 로드 실패: {}", e))?;
+
+    let Some(value) = store.get("upstreamProxy").filter(|value| !value.is_null()) else {
+        return Ok(None);
+    };
+
+    let config = serde_json::from_value::<UpstreamProxyConfig>(value)
+        .map_err(|e| format!("상위 프록시 설정 파싱 실패: {}", e))?;
+    if !config.enabled {
+        return Ok(None);
+    }
+
+    let mut upstream_proxy = UpstreamProxy::new(&config.url)
+        .map_err(|e| format!("상위 프록시 설정 오류: {}", e))?
+        .with_bypass(config.bypass);
+    if let Some(username) = config.username.filter(|username| !username.is_empty()) {
+        upstream_proxy = upstream_proxy.with_auth(username, config.password.unwrap_or_default());
+    }
+
+    Ok(Some(upstream_proxy))
+}
+
+/// 업스트림 인증서 검증 설정 (`tlsVerification` 키)
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TlsVerificationConfig {
+    /// 추가로 신뢰할 CA 인증서 파일 (PEM)
+    #[serde(default)]
+    extra_ca_files: Vec<PathBuf>,
+    /// 검증에 실패해도 연결할 호스트 패턴 (`*`, `*.example.com`, `example.com`)
+    #[serde(default)]
+    insecure_hosts: Vec<String>,
+}
+
+/// 스토어에서 업스트림 인증서 검증 정책 로드 (설정이 없으면 모든 호스트 검증)
+pub fn load_tls_verification<R: Runtime>(
+    app: &AppHandle<R>,
+) -> Result<TlsVerificationPolicy, String> {
+    let store = app
+        .store(RULE_STORE)
+        .map_err(|e| format!("세션 스토어 로드 실패: {}", e))?;
+
+    let value = store.get("tlsVerification").unwrap_or_default();
+    let config = if value.is_null() {
+        TlsVerificationConfig::default()
+    } else {
+        serde_json::from_value::<TlsVerificationConfig>(value)
+            .map_err(|e| format!("인증서 검증 설정 파싱 실패: {}", e))?
+    };
+
+    let mut policy = TlsVerificationPolicy::new();
+    for path in &config.extra_ca_files {
+        let pem = std::fs::read(path)
+            .map_err(|e| format!("CA 인증서 읽기 실패 ({}): {}", path.display(), e))?;
+        policy = policy
+            .with_extra_ca_pem(&pem)
+            .map_err(|e| format!("CA 인증서 오류 ({}): {}", path.display(), e))?;
+    }
+    for pattern in config.insecure_hosts {
+        policy = policy.with_insecure_host(pattern);
+    }
+
+    Ok(policy)
+}
+
+/// 업스트림 서버에 보낼 클라이언트 인증서 설정 (`clientIdentities` 키의 항목)
+///
+/// `pkcs12File`이 있으면 PKCS#12 파일을, 없으면 `certFile`과 `keyFile`(PEM)을 사용합니다.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ClientIdentityConfig {
+    /// 교환마다 기록할 인증서 이름
+    name: String,
+    /// 인증서를 보낼 호스트 패턴 (`*`, `*.example.com`, `example.com`)
+    hosts: Vec<String>,
+    #[serde(default = "enabled_by_default")]
+    enabled: bool,
+    /// 인증서 체인 파일 (PEM)
+    cert_file: Option<PathBuf>,
+    /// 개인 키 파일 (PEM)
+    key_file: Option<PathBuf>,
+    /// PKCS#12 파일 (.p12, .pfx)
+    pkcs12_file: Option<PathBuf>,
+    /// PKCS#12 파일 암호
+    #[serde(default)]
+    password: String,
+}
+
+/// 스토어에서 호스트별 클라이언트 인증서 로드 (설정이 없으면 빈 목록)
+pub fn load_client_identities<R: Runtime>(
+    app: &AppHandle<R>,
+    provider: &CryptoProvider,
+) -> Result<ClientIdentities, String> {
+    let store = app
+        .store(RULE_STORE)
+        .map_err(|e| format!("세션 스토어 로드 실패: {}", e))?;
+
+    let value = store.get("clientIdentities").unwrap_or_default();
+    if value.is_null() {
+        return Ok(ClientIdentities::new());
+    }
+    let configs = serde_json::from_value::<Vec<ClientIdentityConfig>>(value)
+        .map_err(|e| format!("클라이언트 인증서 설정 파싱 실패: {}", e))?;
+
+    let read = |path: &PathBuf| {
+        std::fs::read(path).map_err(|e| format!("파일 읽기 실패 ({}): {}", path.display(), e))
+    };
+    let mut identities = ClientIdentities::new();
+    for config in configs.into_iter().filter(|config| config.enabled) {
+        let identity = match (&config.pkcs12_file, &config.cert_file, &config.key_file) {
+            (Some(pkcs12_file), _, _) => ClientIdentity::from_pkcs12(
+                config.name.as_str(),
+                &read(pkcs12_file)?,
+                &config.password,
+                provider,
+            ),
+            (None, Some(cert_file), Some(key_file)) => ClientIdentity::from_pem(
+                config.name.as_str(),
+                &read(cert_file)?,
+                &read(key_file)?,
+                provider,
+            ),
+            _ => {
+                return Err(format!(
+                "클라이언트 인증서 설정 오류 ({}): pkcs12File 또는 certFile과 keyFile이 필요합니다",
+                config.name
+            ))
+            }
+        }
+        .map_err(|e| format!("클라이언트 인증서 오류 ({}): {}", config.name, e))?;
+
+        for pattern in config.hosts {
+            identities = identities.with_identity(pattern, identity.clone());
+        }
+    }
+
+    Ok(identities)
+}
+
+/// 스토어에서 SOCKS5 리스너 포트 로드 (`socks5Port` 키, 설정이 없으면 None)
+pub fn load_socks5_port<R: Runtime>(app: &AppHandle<R>) -> Result<Option<u16>, String> {
+    load_port(app, "socks5Port", "SOCKS5")
+}
+
+/// 스토어에서 투명 프록시 리스너 포트 로드 (`transparentPort` 키, 설정이 없으면 None)
+pub fn load_transparent_port<R: Runtime>(app: &AppHandle<R>) -> Result<Option<u16>, String> {
+    load_port(app, "transparentPort", "투명 프록시")
+}
+
+/// 스토어에서 리스너 포트 설정 로드
+fn load_port<R: Runtime>(app: &AppHandle<R>, key: &str, name: &str) -> Result<Option<u16>, String> {
+    let store = app
+        .store(RULE_STORE)
+        .map_err(|e| format!("세션 스토어 로드 실패: {}", e))?;
+
+    match store.get(key) {
+        None | Some(JsonValue::Null) => Ok(None),
+        Some(value) => value
+            .as_u64()
+            .and_then(|port| u16::try_from(port).ok())
+            .filter(|port| *port != 0)
+            .map(Some)
+            .ok_or_else(|| format!("{} 포트 설정 오류: {}", name, value)),
+    }
+}
+
+/// JSON 규칙 목록 파싱 (형식이 잘못된 규칙은 건너뜀)
+fn parse_rules(value: JsonValue) -> Vec<Rule> {
+    let JsonValue::Array(items) = value else {
+        return Vec::new();
+    };
+
+    items
+        .into_iter()
+        .filter_map(|item| match serde_json::from_value::<Rule>(item) {
+            Ok(rule) => Some(rule),
+            Err(e) => {
+                eprintln!("⚠️ 규칙 파싱 실패 (건너뜀): {}", e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// 기존 세션 데이터를 응답 교체 규칙으로 변환
+fn sessions_to_rules(value: JsonValue) -> Vec<Rule> {
+    let JsonValue::Array(sessions) = value else {
+        return Vec::new();
+    };
+
+    sessions
+        .iter()
+        .enumerate()
+        .filter_map(|(index, session)| {
+            let url = session.get("url").and_then(|v| v.as_str())?;
+            let method = session
+                .get("method")
+                .and_then(|v| v.as_str())
+                .unwrap_or("GET");
+
+            let default_response = JsonValue::Object(serde_json::Map::new());
+            let response = session.get("response").unwrap_or(&default_response);
+
+            Some(Rule {
+                id: format!("session-{}", index),
+                name: url.to_string(),
+                enabled: true,
+                matcher: RuleMatcher {
+                    methods: vec![method.to_uppercase()],
+                    url_contains: Some(url.to_string()),
+                    ..Default::default()
+                },
+                actions: vec![session_response_action(response)],
+            })
+        })
+        .collect()
+}
+
+/// 세션의 response 데이터로부터 응답 교체 액션 생성
+fn session_response_action(response: &JsonValue) -> RuleAction {
+    let status = response
+        .get("status")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(200) as u16;
+
+    // 헤더 추출 (content-length 제외, 실제 본문 길이에 맞게 자동 설정됨)
+    let mut headers: BTreeMap<String, String> = response
+        .get("headers")
+        .and_then(JsonValue::as_object)
+        .map(|obj| {
+            obj.iter()
+                .filter(|(k, _)| k.to_lowercase() != "content-length")
+                .filter_map(|(k, v)| Some((k.to_lowercase(), v.as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // 기본 Content-Type 헤더 설정 (없는 경우)
+    headers
+        .entry("content-type".to_string())
+        .or_insert_with(|| "application/json".to_string());
+
+    // 세션 응답임을 나타내는 특별한 헤더 추가
+    headers.insert("x-cheolsu-proxy-session".to_string(), "true".to_string());
+    headers.insert("x-cheolsu-proxy-version".to_string(), "v2".to_string());
+
+    let body = match response.get("data") {
+        Some(JsonValue::String(s)) => s.clone(),
+        Some(data @ (JsonValue::Object(_) | JsonValue::Array(_))) => {
+            serde_json::to_string(data).unwrap_or_default()
+        }
+        Some(data) => data.to_string(),
+        None => String::new(),
+    };
+
+    RuleAction::ReplaceResponse {
+        status,
+        headers,
+        body,
+    }
+}
@@ -0,0 +1,15 @@
+use proxy_v2_models::{parse_curl, snippet, ClientRequest, SnippetLanguage};
+
+/// 요청을 cURL, HTTPie, fetch, Python requests, reqwest 코드로 변환
+#[tauri::command]
+pub fn request_snippet(request: ClientRequest, language: SnippetLanguage) -> String {
+    snippet::generate(&request, language)
+}
+
+/// cURL 명령을 요청으로 변환 (편집 후 `replay_request`로 보낼 수 있음)
+#[tauri::command]
+pub fn parse_curl_command(command: String) -> Result<ClientRequest, String> {
+    parse_curl(&command)
+        .map(|request| request.for_client())
+        .map_err(|e| format!("cURL 명령 파싱 실패: {}", e))
+}
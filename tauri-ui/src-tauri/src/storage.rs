@@ -0,0 +1,899 @@
+use crate::capture_policy::remove_spill_files;
+use proxy_v2_models::{Bytes, ClientRequest, ClientResponse, HeaderMap, RequestInfo};
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Runtime, State};
+use tauri_plugin_store::{JsonValue, StoreExt};
+
+/// 보존 정책 설정이 저장된 스토어 파일
+const SETTINGS_STORE: &str = "session.json";
+
+/// 검색 색인에 넣는 본문 최대 크기
+const MAX_INDEXED_BODY: usize = 256 * 1024;
+
+/// 보존 정책을 적용하는 저장 간격
+const PRUNE_INTERVAL: usize = 100;
+
+/// 한 페이지 최대 항목 수
+const MAX_PAGE_SIZE: usize = 500;
+
+const SCHEMA: &str = "
+    PRAGMA journal_mode = WAL;
+    PRAGMA foreign_keys = ON;
+
+    CREATE TABLE IF NOT EXISTS bodies (
+        hash TEXT PRIMARY KEY,
+        data BLOB NOT NULL
+    );
+
+    CREATE TABLE IF NOT EXISTS exchanges (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        request_id TEXT,
+        method TEXT,
+        url TEXT,
+        status INTEGER,
+        request_time INTEGER,
+        response_time INTEGER,
+        request_body TEXT REFERENCES bodies(hash),
+        response_body TEXT REFERENCES bodies(hash),
+        size INTEGER NOT NULL,
+        info TEXT NOT NULL,
+        created_at INTEGER NOT NULL
+    );
+
+    CREATE INDEX IF NOT EXISTS exchanges_created_at ON exchanges(created_at);
+
+    CREATE VIRTUAL TABLE IF NOT EXISTS exchanges_fts
+        USING fts5(url, headers, body, tokenize = 'trigram');
+
+    CREATE TRIGGER IF NOT EXISTS exchanges_fts_delete AFTER DELETE ON exchanges BEGIN
+        DELETE FROM exchanges_fts WHERE rowid = old.id;
+    END;
+";
+
+/// 트래픽 보존 정책 (스토어의 `trafficRetention` 키)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Retention {
+    /// 보관 기간 (일, None이면 무제한)
+    #[serde(default)]
+    pub max_age_days: Option<u64>,
+    /// 최대 저장 크기 (MB, None이면 무제한)
+    #[serde(default)]
+    pub max_size_mb: Option<u64>,
+}
+
+impl Default for Retention {
+    fn default() -> Self {
+        Self {
+            max_age_days: Some(7),
+            max_size_mb: Some(1024),
+        }
+    }
+}
+
+/// 목록/검색 결과 항목 (본문 제외)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrafficSummary {
+    pub id: i64,
+    pub request_id: Option<String>,
+    pub method: Option<String>,
+    pub url: Option<String>,
+    pub status: Option<u16>,
+    pub request_time: Option<i64>,
+    pub response_time: Option<i64>,
+    /// 요청부터 응답까지 걸린 시간 (ms)
+    pub duration: Option<i64>,
+    pub size: i64,
+}
+
+/// 페이지 단위 조회 결과
+#[derive(Debug, Clone, Serialize)]
+pub struct TrafficPage {
+    pub total: i64,
+    pub items: Vec<TrafficSummary>,
+}
+
+/// 디스크에 저장되는 트래픽 저장소 (SQLite)
+///
+/// 본문은 SHA-256 해시로 중복을 제거해 저장하고, URL/헤더/본문은 FTS5 색인으로 검색합니다.
+#[derive(Clone)]
+pub struct TrafficStore {
+    inner: Arc<Mutex<Inner>>,
+}
+
+struct Inner {
+    conn: Connection,
+    retention: Retention,
+    inserts_since_prune: usize,
+}
+
+impl TrafficStore {
+    /// 파일 저장소 열기 (없으면 생성)
+    pub fn open(path: impl AsRef<Path>) -> rusqlite::Result<Self> {
+        Self::from_connection(Connection::open(path)?)
+    }
+
+    /// 메모리 저장소 열기 (파일 저장소를 열 수 없을 때 사용)
+    pub fn open_in_memory() -> rusqlite::Result<Self> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    fn from_connection(conn: Connection) -> rusqlite::Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self {
+            inner: Arc::new(Mutex::new(Inner {
+                conn,
+                retention: Retention::default(),
+                inserts_since_prune: 0,
+            })),
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 보존 정책 변경 후 즉시 적용
+    pub fn set_retention(&self, retention: Retention) -> Result<usize, String> {
+        let mut inner = self.lock();
+        inner.retention = retention;
+        let spill_paths = inner.prune()?;
+        let deleted = spill_paths.len();
+        let unreferenced = inner.unreferenced_spill_paths(spill_paths);
+        drop(inner);
+
+        remove_spill_files(unreferenced);
+        Ok(deleted)
+    }
+
+    /// 교환 저장 (일정 개수마다 보존 정책 적용)
+    pub fn insert(&self, info: &RequestInfo) -> Result<i64, String> {
+        let mut inner = self.lock();
+        let id = inner
+            .insert(info)
+            .map_err(|e| format!("트래픽 저장 실패: {}", e))?;
+
+        inner.inserts_since_prune += 1;
+        if inner.inserts_since_prune >= PRUNE_INTERVAL {
+            let spill_paths = inner.prune()?;
+            let unreferenced = inner.unreferenced_spill_paths(spill_paths);
+            drop(inner);
+            remove_spill_files(unreferenced);
+        }
+
+        Ok(id)
+    }
+
+    /// 최신순 목록 조회 (`query`가 있으면 URL/헤더/본문 전문 검색)
+    pub fn list(
+        &self,
+        query: Option<&str>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<TrafficPage, String> {
+        self.lock()
+            .list(query, offset, limit.min(MAX_PAGE_SIZE))
+            .map_err(|e| format!("트래픽 조회 실패: {}", e))
+    }
+
+    /// 저장된 교환 조회
+    pub fn get(&self, id: i64) -> Result<Option<RequestInfo>, String> {
+        self.lock().get(id)
+    }
+
+    /// 교환 삭제 (삭제된 개수 반환, 교환의 본문 임시 파일도 삭제)
+    pub fn delete(&self, ids: &[i64]) -> Result<usize, String> {
+        let mut inner = self.lock();
+        let spill_paths = inner
+            .delete(ids)
+            .map_err(|e| format!("트래픽 삭제 실패: {}", e))?;
+        let deleted = spill_paths.len();
+        let unreferenced = inner.unreferenced_spill_paths(spill_paths);
+        drop(inner);
+
+        remove_spill_files(unreferenced);
+        Ok(deleted)
+    }
+
+    /// 저장된 트래픽 전체 삭제 (본문 임시 파일 포함)
+    pub fn clear(&self) -> Result<(), String> {
+        let spill_paths = self
+            .lock()
+            .clear()
+            .map_err(|e| format!("트래픽 삭제 실패: {}", e))?;
+
+        remove_spill_files(spill_paths.into_iter().flatten());
+        Ok(())
+    }
+
+    /// 저장된 교환이 참조하는 본문 임시 파일 경로
+    pub fn spill_paths(&self) -> Result<HashSet<String>, String> {
+        self.lock()
+            .spill_paths()
+            .map_err(|e| format!("트래픽 조회 실패: {}", e))
+    }
+}
+
+impl Inner {
+    fn insert(&mut self, info: &RequestInfo) -> rusqlite::Result<i64> {
+        let RequestInfo(request, response) = info;
+        let request_body = request.as_ref().map(|req| req.body().clone());
+        let response_body = response.as_ref().map(|res| res.body().clone());
+
+        let tx = self.conn.transaction()?;
+        let request_hash = request_body
+            .as_ref()
+            .map(|body| put_body(&tx, body))
+            .transpose()?;
+        let response_hash = response_body
+            .as_ref()
+            .map(|body| put_body(&tx, body))
+            .transpose()?;
+
+        let meta = strip_bodies(info);
+        let size = meta.len()
+            + request_body.as_ref().map_or(0, Bytes::len)
+            + response_body.as_ref().map_or(0, Bytes::len);
+
+        tx.execute(
+            "INSERT INTO exchanges (request_id, method, url, status, request_time, response_time,
+                request_body, response_body, size, info, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                request.as_ref().map(|req| req.id().to_string()),
+                request.as_ref().map(|req| req.method().to_string()),
+                request.as_ref().map(|req| req.uri().to_string()),
+                response.as_ref().map(|res| res.status().as_u16()),
+                request.as_ref().map(ClientRequest::time),
+                response.as_ref().map(ClientResponse::time),
+                request_hash,
+                response_hash,
+                size as i64,
+                meta,
+                now_millis(),
+            ],
+        )?;
+        let id = tx.last_insert_rowid();
+
+        let mut headers = String::new();
+        if let Some(req) = request {
+            push_headers(&mut headers, req.headers());
+        }
+        if let Some(res) = response {
+            push_headers(&mut headers, res.headers());
+        }
+        let body = [request_body, response_body]
+            .iter()
+            .flatten()
+            .filter_map(|body| indexable_text(body))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        tx.execute(
+            "INSERT INTO exchanges_fts (rowid, url, headers, body) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                id,
+                request.as_ref().map(|req| req.uri().to_string()),
+                headers,
+                body
+            ],
+        )?;
+
+        tx.commit()?;
+        Ok(id)
+    }
+
+    fn list(
+        &self,
+        query: Option<&str>,
+        offset: usize,
+        limit: usize,
+    ) -> rusqlite::Result<TrafficPage> {
+        const COLUMNS: &str = "e.id, e.request_id, e.method, e.url, e.status, e.request_time,
+            e.response_time, e.size";
+
+        let query = query.map(str::trim).filter(|query| !query.is_empty());
+        let (filter, args): (&str, Vec<String>) = match query {
+            None => ("", Vec::new()),
+            // trigram 색인은 3글자 이상만 검색할 수 있으므로 짧은 검색어는 URL에서만 찾음
+            Some(query) if query.chars().count() < 3 => (
+                "WHERE e.url LIKE ?1 ESCAPE '\\'",
+                vec![format!("%{}%", escape_like(query))],
+            ),
+            Some(query) => (
+                "JOIN exchanges_fts f ON f.rowid = e.id WHERE exchanges_fts MATCH ?1",
+                vec![format!("\"{}\"", query.replace('"', "\"\""))],
+            ),
+        };
+
+        let total = self.conn.query_row(
+            &format!("SELECT COUNT(*) FROM exchanges e {}", filter),
+            params_from_iter(args.iter()),
+            |row| row.get(0),
+        )?;
+
+        let mut statement = self.conn.prepare(&format!(
+            "SELECT {} FROM exchanges e {} ORDER BY e.id DESC LIMIT {} OFFSET {}",
+            COLUMNS, filter, limit, offset
+        ))?;
+        let items = statement
+            .query_map(params_from_iter(args.iter()), |row| {
+                let request_time: Option<i64> = row.get(5)?;
+                let response_time: Option<i64> = row.get(6)?;
+                Ok(TrafficSummary {
+                    id: row.get(0)?,
+                    request_id: row.get(1)?,
+                    method: row.get(2)?,
+                    url: row.get(3)?,
+                    status: row.get(4)?,
+                    request_time,
+                    response_time,
+                    duration: request_time
+                        .zip(response_time)
+                        // 시각은 나노초 단위로 저장됨
+                        .map(|(request, response)| (response - request) / 1_000_000),
+                    size: row.get(7)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(TrafficPage { total, items })
+    }
+
+    fn get(&self, id: i64) -> Result<Option<RequestInfo>, String> {
+        let row = self
+            .conn
+            .query_row(
+                "SELECT e.info, req.data, res.data FROM exchanges e
+                 LEFT JOIN bodies req ON req.hash = e.request_body
+                 LEFT JOIN bodies res ON res.hash = e.response_body
+                 WHERE e.id = ?1",
+                params![id],
+                |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, Option<Vec<u8>>>(1)?,
+                        row.get::<_, Option<Vec<u8>>>(2)?,
+                    ))
+                },
+            )
+            .optional()
+            .map_err(|e| format!("트래픽 조회 실패: {}", e))?;
+
+        let Some((meta, request_body, response_body)) = row else {
+            return Ok(None);
+        };
+
+        restore_bodies(&meta, request_body, response_body)
+            .map(Some)
+            .map_err(|e| format!("저장된 트래픽 복원 실패: {}", e))
+    }
+
+    /// 교환 삭제 (삭제된 교환마다 본문 임시 파일 경로 반환)
+    fn delete(&mut self, ids: &[i64]) -> rusqlite::Result<Vec<Vec<String>>> {
+        let tx = self.conn.transaction()?;
+        let mut deleted = Vec::new();
+        for id in ids {
+            deleted.extend(delete_returning_spills(
+                &tx,
+                "DELETE FROM exchanges WHERE id = ?1 RETURNING info",
+                params![id],
+            )?);
+        }
+        delete_orphan_bodies(&tx)?;
+        tx.commit()?;
+        Ok(deleted)
+    }
+
+    fn clear(&mut self) -> rusqlite::Result<Vec<Vec<String>>> {
+        let tx = self.conn.transaction()?;
+        let deleted = delete_returning_spills(&tx, "DELETE FROM exchanges RETURNING info", [])?;
+        tx.execute("DELETE FROM bodies", [])?;
+        tx.commit()?;
+        self.conn.execute_batch("VACUUM;")?;
+        Ok(deleted)
+    }
+
+    /// 삭제된 교환의 본문 임시 파일 중 남은 교환이 참조하지 않는 파일
+    ///
+    /// 다시 보낸 요청은 원래 교환의 임시 파일을 함께 참조하므로 남은 교환이 참조하는 파일은 제외하며,
+    /// 참조 여부를 확인하지 못하면 삭제하지 않습니다.
+    fn unreferenced_spill_paths(&self, deleted: Vec<Vec<String>>) -> Vec<String> {
+        let deleted: Vec<String> = deleted.into_iter().flatten().collect();
+        if deleted.is_empty() {
+            return deleted;
+        }
+
+        match self.spill_paths() {
+            Ok(referenced) => deleted
+                .into_iter()
+                .filter(|path| !referenced.contains(path))
+                .collect(),
+            Err(e) => {
+                eprintln!("⚠️ 본문 임시 파일 참조 확인 실패: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    fn spill_paths(&self) -> rusqlite::Result<HashSet<String>> {
+        let mut statement = self
+            .conn
+            .prepare("SELECT info FROM exchanges WHERE info LIKE '%\"spill_path\"%'")?;
+        let infos = statement
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(infos.iter().flat_map(|info| spill_paths_of(info)).collect())
+    }
+
+    /// 보존 정책 적용 (삭제된 교환마다 본문 임시 파일 경로 반환)
+    fn prune(&mut self) -> Result<Vec<Vec<String>>, String> {
+        self.inserts_since_prune = 0;
+        self.prune_inner()
+            .map_err(|e| format!("트래픽 보존 정책 적용 실패: {}", e))
+    }
+
+    fn prune_inner(&mut self) -> rusqlite::Result<Vec<Vec<String>>> {
+        let Retention {
+            max_age_days,
+            max_size_mb,
+        } = self.retention;
+
+        let tx = self.conn.transaction()?;
+        let mut deleted = Vec::new();
+
+        if let Some(days) = max_age_days {
+            let cutoff = now_millis() - (days as i64) * 24 * 60 * 60 * 1000;
+            deleted.extend(delete_returning_spills(
+                &tx,
+                "DELETE FROM exchanges WHERE created_at < ?1 RETURNING info",
+                params![cutoff],
+            )?);
+        }
+
+        if let Some(mb) = max_size_mb {
+            // 본문은 중복 제거 전 크기로 계산하므로 실제 디스크 사용량보다 크게 잡힘
+            let max_size = (mb as i64) * 1024 * 1024;
+            let total: i64 =
+                tx.query_row("SELECT COALESCE(SUM(size), 0) FROM exchanges", [], |row| {
+                    row.get(0)
+                })?;
+
+            if total > max_size {
+                // 오래된 것부터 누적 크기를 계산해 초과분 삭제
+                deleted.extend(delete_returning_spills(
+                    &tx,
+                    "DELETE FROM exchanges WHERE id IN (
+                        SELECT id FROM (
+                            SELECT id, SUM(size) OVER (ORDER BY id) AS running FROM exchanges
+                        ) WHERE running <= ?1
+                    ) RETURNING info",
+                    params![total - max_size],
+                )?);
+                // 경계에 걸친 항목까지 삭제해 한도 아래로 맞춤
+                let total: i64 =
+                    tx.query_row("SELECT COALESCE(SUM(size), 0) FROM exchanges", [], |row| {
+                        row.get(0)
+                    })?;
+                if total > max_size {
+                    deleted.extend(delete_returning_spills(
+                        &tx,
+                        "DELETE FROM exchanges WHERE id = (SELECT MIN(id) FROM exchanges)
+                         RETURNING info",
+                        [],
+                    )?);
+                }
+            }
+        }
+
+        if !deleted.is_empty() {
+            delete_orphan_bodies(&tx)?;
+        }
+        tx.commit()?;
+        Ok(deleted)
+    }
+}
+
+/// 본문 저장 후 해시 반환 (이미 있으면 재사용)
+fn put_body(conn: &Connection, body: &Bytes) -> rusqlite::Result<String> {
+    let hash = format!("{:x}", Sha256::digest(body));
+    conn.execute(
+        "INSERT OR IGNORE INTO bodies (hash, data) VALUES (?1, ?2)",
+        params![hash, body.as_ref()],
+    )?;
+    Ok(hash)
+}
+
+/// `RETURNING info`로 교환을 삭제하고 삭제된 교환마다 본문 임시 파일 경로 반환
+fn delete_returning_spills(
+    conn: &Connection,
+    sql: &str,
+    params: impl rusqlite::Params,
+) -> rusqlite::Result<Vec<Vec<String>>> {
+    let mut statement = conn.prepare(sql)?;
+    let infos = statement
+        .query_map(params, |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(infos.iter().map(|info| spill_paths_of(info)).collect())
+}
+
+/// 저장된 JSON에서 요청/응답 본문의 임시 파일 경로 추출
+fn spill_paths_of(meta: &str) -> Vec<String> {
+    let Ok(Value::Array(parts)) = serde_json::from_str::<Value>(meta) else {
+        return Vec::new();
+    };
+    parts
+        .iter()
+        .filter_map(|part| part.get("truncated")?.get("spill_path")?.as_str())
+        .map(str::to_string)
+        .collect()
+}
+
+fn delete_orphan_bodies(conn: &Connection) -> rusqlite::Result<usize> {
+    conn.execute(
+        "DELETE FROM bodies WHERE hash NOT IN (
+            SELECT request_body FROM exchanges WHERE request_body IS NOT NULL
+            UNION
+            SELECT response_body FROM exchanges WHERE response_body IS NOT NULL
+        )",
+        [],
+    )
+}
+
+/// 본문을 비운 `RequestInfo` JSON (본문은 `bodies` 테이블에 따로 저장)
+fn strip_bodies(info: &RequestInfo) -> String {
+    let mut value = serde_json::to_value(info).unwrap_or(Value::Null);
+    if let Value::Array(parts) = &mut value {
+        for part in parts.iter_mut() {
+            if let Some(body) = part.get_mut("body") {
+                *body = Value::Array(Vec::new());
+            }
+        }
+    }
+    value.to_string()
+}
+
+/// 저장된 JSON에 본문을 채워 `RequestInfo` 복원
+fn restore_bodies(
+    meta: &str,
+    request_body: Option<Vec<u8>>,
+    response_body: Option<Vec<u8>>,
+) -> serde_json::Result<RequestInfo> {
+    let mut value: Value = serde_json::from_str(meta)?;
+    if let Value::Array(parts) = &mut value {
+        for (part, body) in parts.iter_mut().zip([request_body, response_body]) {
+            if let (Some(slot), Some(body)) = (part.get_mut("body"), body) {
+                *slot = serde_json::to_value(Bytes::from(body))?;
+            }
+        }
+    }
+    serde_json::from_value(value)
+}
+
+fn push_headers(out: &mut String, headers: &HeaderMap) {
+    for (name, value) in headers {
+        out.push_str(name.as_str());
+        out.push_str(": ");
+        out.push_str(&String::from_utf8_lossy(value.as_bytes()));
+        out.push('\n');
+    }
+}
+
+/// 검색 색인용 본문 텍스트 (UTF-8 텍스트가 아니면 None)
+fn indexable_text(body: &Bytes) -> Option<&str> {
+    let body = &body[..body.len().min(MAX_INDEXED_BODY)];
+    match std::str::from_utf8(body) {
+        Ok(text) => Some(text),
+        // 잘린 위치가 문자 중간이면 그 앞까지만 사용
+        Err(e) if e.error_len().is_none() => std::str::from_utf8(&body[..e.valid_up_to()]).ok(),
+        Err(_) => None,
+    }
+}
+
+fn escape_like(query: &str) -> String {
+    query
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or_default()
+}
+
+/// 스토어에서 트래픽 보존 정책 로드 (설정이 없으면 기본값)
+pub fn load_retention<R: Runtime>(app: &AppHandle<R>) -> Result<Retention, String> {
+    let store = app
+        .store(SETTINGS_STORE)
+        .map_err(|e| format!("세션 스토어 로드 실패: {}", e))?;
+
+    match store.get("trafficRetention") {
+        None | Some(JsonValue::Null) => Ok(Retention::default()),
+        Some(value) => {
+            serde_json::from_value(value).map_err(|e| format!("트래픽 보존 정책 설정 오류: {}", e))
+        }
+    }
+}
+
+/// 저장소 작업을 블로킹 스레드에서 실행
+pub(crate) async fn run_blocking<T, F>(store: &TrafficStore, f: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(TrafficStore) -> Result<T, String> + Send + 'static,
+{
+    let store = store.clone();
+    tokio::task::spawn_blocking(move || f(store))
+        .await
+        .map_err(|e| format!("트래픽 저장소 작업 실패: {}", e))?
+}
+
+/// 저장된 트래픽 목록 조회 (최신순, `query`가 있으면 URL/헤더/본문 검색)
+#[tauri::command]
+pub async fn list_traffic(
+    storage: State<'_, TrafficStore>,
+    query: Option<String>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Result<TrafficPage, String> {
+    run_blocking(&storage, move |store| {
+        store.list(query.as_deref(), offset.unwrap_or(0), limit.unwrap_or(100))
+    })
+    .await
+}
+
+/// 저장된 교환 하나를 본문까지 조회
+#[tauri::command]
+pub async fn get_traffic(
+    storage: State<'_, TrafficStore>,
+    id: i64,
+) -> Result<Option<RequestInfo>, String> {
+    run_blocking(&storage, move |store| store.get(id)).await
+}
+
+/// 저장된 교환 삭제
+#[tauri::command]
+pub async fn delete_traffic(
+    storage: State<'_, TrafficStore>,
+    ids: Vec<i64>,
+) -> Result<usize, String> {
+    run_blocking(&storage, move |store| store.delete(&ids)).await
+}
+
+/// 저장된 트래픽 전체 삭제
+#[tauri::command]
+pub async fn clear_traffic(storage: State<'_, TrafficStore>) -> Result<(), String> {
+    run_blocking(&storage, |store| store.clear()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proxy_v2_models::{Method, ProxiedRequest, ProxiedResponse, StatusCode, Version};
+
+    fn exchange(
+        url: &str,
+        headers: &[(&'static str, &str)],
+        request: &str,
+        response: &str,
+    ) -> RequestInfo {
+        let mut header_map = HeaderMap::new();
+        for (name, value) in headers {
+            header_map.insert(*name, value.parse().unwrap());
+        }
+        let request = ProxiedRequest::new(
+            Method::POST,
+            url.parse().unwrap(),
+            Version::HTTP_11,
+            header_map,
+            Bytes::from(request.to_string()),
+            1_000_000,
+        );
+        let response = ProxiedResponse::new(
+            StatusCode::OK,
+            Version::HTTP_11,
+            HeaderMap::new(),
+            Bytes::from(response.to_string()),
+            2_000_000,
+        );
+        RequestInfo(Some(request.for_client()), Some(response.for_client()))
+    }
+
+    fn body_count(store: &TrafficStore, body: &str) -> i64 {
+        store
+            .lock()
+            .conn
+            .query_row(
+                "SELECT COUNT(*) FROM bodies WHERE data = ?1",
+                params![body.as_bytes()],
+                |row| row.get(0),
+            )
+            .unwrap()
+    }
+
+    fn ids(page: &TrafficPage) -> Vec<i64> {
+        page.items.iter().map(|item| item.id).collect()
+    }
+
+    #[test]
+    fn same_body_is_stored_once() {
+        let store = TrafficStore::open_in_memory().unwrap();
+        let first = store
+            .insert(&exchange("https://a.test/", &[], "req-a", "same body"))
+            .unwrap();
+        let second = store
+            .insert(&exchange("https://b.test/", &[], "req-b", "same body"))
+            .unwrap();
+
+        assert_eq!(body_count(&store, "same body"), 1);
+        for id in [first, second] {
+            let RequestInfo(_, response) = store.get(id).unwrap().unwrap();
+            assert_eq!(response.unwrap().body().as_ref(), b"same body");
+        }
+    }
+
+    #[test]
+    fn search_matches_url_headers_and_body() {
+        let store = TrafficStore::open_in_memory().unwrap();
+        let by_url = store
+            .insert(&exchange("https://a.test/findme/path", &[], "", "plain"))
+            .unwrap();
+        let by_header = store
+            .insert(&exchange(
+                "https://b.test/",
+                &[("x-trace", "trace-marker")],
+                "",
+                "plain",
+            ))
+            .unwrap();
+        let by_body = store
+            .insert(&exchange(
+                "https://c.test/",
+                &[],
+                "",
+                "a needle in the body",
+            ))
+            .unwrap();
+
+        for (query, id) in [
+            ("findme", by_url),
+            ("trace-marker", by_header),
+            ("needle", by_body),
+        ] {
+            let page = store.list(Some(query), 0, 10).unwrap();
+            assert_eq!(page.total, 1, "{}", query);
+            assert_eq!(ids(&page), vec![id], "{}", query);
+        }
+
+        // 짧은 검색어는 URL에서만 찾음
+        let page = store.list(Some("c."), 0, 10).unwrap();
+        assert_eq!(ids(&page), vec![by_body]);
+        assert_eq!(store.list(Some("nomatch"), 0, 10).unwrap().total, 0);
+    }
+
+    #[test]
+    fn list_pages_newest_first() {
+        let store = TrafficStore::open_in_memory().unwrap();
+        let inserted: Vec<i64> = (0..5)
+            .map(|i| {
+                store
+                    .insert(&exchange(&format!("https://a.test/{}", i), &[], "", ""))
+                    .unwrap()
+            })
+            .collect();
+
+        let first = store.list(None, 0, 2).unwrap();
+        assert_eq!(first.total, 5);
+        assert_eq!(ids(&first), vec![inserted[4], inserted[3]]);
+
+        let last = store.list(None, 4, 2).unwrap();
+        assert_eq!(last.total, 5);
+        assert_eq!(ids(&last), vec![inserted[0]]);
+
+        assert!(store.list(None, 5, 2).unwrap().items.is_empty());
+    }
+
+    #[test]
+    fn prune_by_age() {
+        let store = TrafficStore::open_in_memory().unwrap();
+        let old = store
+            .insert(&exchange("https://a.test/old", &[], "", "old body"))
+            .unwrap();
+        let recent = store
+            .insert(&exchange("https://a.test/recent", &[], "", "recent body"))
+            .unwrap();
+        store
+            .lock()
+            .conn
+            .execute(
+                "UPDATE exchanges SET created_at = ?1 WHERE id = ?2",
+                params![now_millis() - 2 * 24 * 60 * 60 * 1000, old],
+            )
+            .unwrap();
+
+        let deleted = store
+            .set_retention(Retention {
+                max_age_days: Some(1),
+                max_size_mb: None,
+            })
+            .unwrap();
+
+        assert_eq!(deleted, 1);
+        assert_eq!(ids(&store.list(None, 0, 10).unwrap()), vec![recent]);
+        assert_eq!(body_count(&store, "old body"), 0);
+        assert_eq!(body_count(&store, "recent body"), 1);
+    }
+
+    #[test]
+    fn prune_by_total_size() {
+        let store = TrafficStore::open_in_memory().unwrap();
+        let inserted: Vec<i64> = (0..3)
+            .map(|i| {
+                let body = i.to_string().repeat(600 * 1024);
+                store
+                    .insert(&exchange("https://a.test/large", &[], "", &body))
+                    .unwrap()
+            })
+            .collect();
+
+        let deleted = store
+            .set_retention(Retention {
+                max_age_days: None,
+                max_size_mb: Some(1),
+            })
+            .unwrap();
+
+        // 가장 오래된 것부터 삭제해 1MB 아래로 맞춤
+        assert_eq!(deleted, 2);
+        assert_eq!(ids(&store.list(None, 0, 10).unwrap()), vec![inserted[2]]);
+        let bodies: i64 = store
+            .lock()
+            .conn
+            .query_row(
+                "SELECT COUNT(*) FROM bodies WHERE length(data) > 0",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(bodies, 1);
+    }
+
+    #[test]
+    fn delete_releases_only_unshared_bodies() {
+        let store = TrafficStore::open_in_memory().unwrap();
+        let deleted = store
+            .insert(&exchange(
+                "https://a.test/",
+                &[],
+                "unique body",
+                "shared body",
+            ))
+            .unwrap();
+        let kept = store
+            .insert(&exchange(
+                "https://b.test/",
+                &[],
+                "other body",
+                "shared body",
+            ))
+            .unwrap();
+
+        assert_eq!(store.delete(&[deleted]).unwrap(), 1);
+
+        assert!(store.get(deleted).unwrap().is_none());
+        assert_eq!(body_count(&store, "unique body"), 0);
+        assert_eq!(body_count(&store, "shared body"), 1);
+        let RequestInfo(_, response) = store.get(kept).unwrap().unwrap();
+        assert_eq!(response.unwrap().body().as_ref(), b"shared body");
+    }
+}
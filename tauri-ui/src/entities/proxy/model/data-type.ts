@@ -11,6 +11,8 @@ export type DataType =
   | 'Audio'
   | 'Document'
   | 'Archive'
+  | 'Grpc'
+  | 'Protobuf'
   | 'Binary'
   | 'Empty'
   | 'Unknown';
@@ -32,6 +34,8 @@ export const dataTypeToMonacoLanguage = (dataType: DataType): string => {
       return 'javascript';
     case 'Text':
       return 'plaintext';
+    case 'Grpc':
+      return 'json';
     case 'Image':
     case 'Video':
     case 'Audio':
@@ -72,6 +76,10 @@ export const dataTypeToMimeType = (dataType: DataType): string => {
       return 'application/pdf';
     case 'Archive':
       return 'application/zip';
+    case 'Grpc':
+      return 'application/grpc';
+    case 'Protobuf':
+      return 'application/x-protobuf';
     case 'Binary':
       return 'application/octet-stream';
     case 'Empty':
@@ -109,6 +117,10 @@ export const dataTypeToDisplayName = (dataType: DataType): string => {
       return 'Document';
     case 'Archive':
       return 'Archive';
+    case 'Grpc':
+      return 'gRPC';
+    case 'Protobuf':
+      return 'Protobuf';
     case 'Binary':
       return 'Binary Data';
     case 'Empty':
@@ -146,6 +158,9 @@ export const dataTypeToIcon = (dataType: DataType): string => {
       return '📕';
     case 'Archive':
       return '📦';
+    case 'Grpc':
+    case 'Protobuf':
+      return '🧩';
     case 'Binary':
       return '📦';
     case 'Empty':
@@ -209,5 +224,5 @@ export const isCompressedDataType = (dataType: DataType): boolean => {
  * 데이터 타입이 바이너리인지 확인
  */
 export const isBinaryDataType = (dataType: DataType): boolean => {
-  return ['Image', 'Video', 'Audio', 'Document', 'Archive', 'Binary'].includes(dataType);
+  return ['Image', 'Video', 'Audio', 'Document', 'Archive', 'Grpc', 'Protobuf', 'Binary'].includes(dataType);
 };
@@ -30,6 +30,81 @@ export interface ResponsePayload {
   data?: Record<string, unknown> | string;
 }
 
+// 구간별 소요 시간 (ms, 측정하지 못한 구간은 null)
+export interface Timings {
+  dns: number | null;
+  connect: number | null;
+  ssl: number | null;
+  wait: number | null;
+  receive: number | null;
+}
+
+// 캡처 정책에 따라 일부만 기록된 본문 (body에는 앞부분만 담김)
+export interface BodyTruncation {
+  size: number; // 실제로 전달된 본문의 전체 크기 (bytes)
+  spill_path?: string; // 전체 본문을 저장한 임시 파일 경로
+}
+
+// 업스트림 서버 인증서 검증 결과
+export type CertificateStatus =
+  | 'valid'
+  | 'expired'
+  | 'not_yet_valid'
+  | 'hostname_mismatch'
+  | 'self_signed'
+  | 'unknown_issuer'
+  | 'revoked'
+  | 'invalid';
+
+export interface UpstreamTls {
+  status: CertificateStatus;
+  insecure: boolean; // 검증 예외 호스트라서 검증 결과와 관계없이 연결한 경우
+  detail?: string; // 검증 실패 사유 (invalid인 경우)
+}
+
+// 업스트림 인증서 검증 설정 (session.json의 tlsVerification 키)
+export interface TlsVerificationSettings {
+  extraCaFiles: string[]; // 추가로 신뢰할 CA 인증서 파일 (PEM)
+  insecureHosts: string[]; // 검증에 실패해도 연결할 호스트 패턴 (*, *.example.com, example.com)
+}
+
+// 업스트림 서버가 보낸 인증서 하나의 정보
+export interface CertificateInfo {
+  subject: string; // 주체 DN
+  issuer: string; // 발급자 DN
+  serial_number: string; // 일련번호 (:로 구분한 16진수)
+  subject_alt_names: string[]; // 주체 대체 이름 (DNS 이름, IP 주소 등)
+  not_before: string; // 유효 기간 시작 (RFC 3339)
+  not_after: string; // 유효 기간 끝 (RFC 3339)
+  sha256_fingerprint: string;
+  sha1_fingerprint: string;
+  is_ca: boolean;
+}
+
+// 업스트림 TLS 연결에서 협상한 세션 정보
+export interface UpstreamSession {
+  version?: string; // 협상된 TLS 버전 (예: TLS 1.3)
+  cipher_suite?: string; // 협상된 암호 스위트
+  alpn?: string; // 협상된 ALPN 프로토콜 (예: h2)
+  certificates: CertificateInfo[]; // 서버 인증서부터 시작하는 인증서 체인
+}
+
+// 업스트림 서버에 보낼 클라이언트 인증서 설정 (session.json의 clientIdentities 키 항목)
+// pkcs12File이 있으면 PKCS#12 파일을, 없으면 certFile과 keyFile(PEM)을 사용
+export interface ClientIdentitySettings {
+  name: string; // 교환마다 기록할 인증서 이름
+  hosts: string[]; // 인증서를 보낼 호스트 패턴 (*, *.example.com, example.com)
+  enabled?: boolean;
+  certFile?: string;
+  keyFile?: string;
+  pkcs12File?: string;
+  password?: string; // PKCS#12 파일 암호
+}
+
+// export_ca_certificate 커맨드로 내보낼 루트 CA 인증서 형식
+// crt는 DER 인증서, mobileconfig는 iOS/macOS 구성 프로파일
+export type CaExportFormat = 'pem' | 'der' | 'crt' | 'mobileconfig';
+
 export interface HttpRequest {
   method: string;
   uri: string;
@@ -38,6 +113,11 @@ export interface HttpRequest {
   body: Uint8Array;
   time: number;
   id: string; // 고유 ID 추가
+  timings?: Timings; // 응답까지의 구간별 소요 시간
+  upstream_version?: string; // 업스트림 서버와 실제로 사용한 HTTP 버전 (version은 클라이언트 쪽)
+  truncated?: BodyTruncation; // 본문이 일부만 캡처된 경우
+  upstream_tls?: UpstreamTls; // 업스트림 서버 인증서 검증 결과 (TLS 연결인 경우)
+  client_identity?: string; // 업스트림 연결에 사용한 클라이언트 인증서 이름 (mTLS)
   data_type: DataType; // 데이터 타입 정보 추가
   body_json?: any; // JSON 파싱된 데이터 (JSON 타입인 경우)
 }
@@ -48,6 +128,10 @@ export interface HttpResponse {
   headers: Record<string, string>;
   body: Uint8Array;
   time: number;
+  timings?: Timings; // 응답까지의 구간별 소요 시간
+  trailers?: Record<string, string>; // 본문 뒤에 전달된 트레일러 (gRPC의 grpc-status 등)
+  truncated?: BodyTruncation; // 본문이 일부만 캡처된 경우
+  upstream_session?: UpstreamSession; // 업스트림 TLS 세션 정보 (TLS 연결인 경우)
   data_type: DataType; // 데이터 타입 정보 추가
   body_json?: any; // JSON 파싱된 데이터 (JSON 타입인 경우)
 }
@@ -59,5 +143,43 @@ export interface HttpTransaction {
 
 export type ProxyEventTuple = [HttpTransaction['request'], HttpTransaction['response']];
 
+// WebSocket 프레임 ('proxy_websocket_frame' 이벤트)
+export type WebSocketDirection = 'client_to_server' | 'server_to_client';
+
+export type WebSocketOpcode = 'text' | 'binary' | 'ping' | 'pong' | 'close';
+
+export interface WebSocketFrame {
+  connection_id: number; // 같은 연결의 프레임은 같은 값
+  request_id?: string; // 업그레이드 요청의 HttpRequest.id
+  direction: WebSocketDirection;
+  opcode: WebSocketOpcode;
+  payload: Uint8Array;
+  size: number;
+  time: number;
+  close_code?: number;
+  close_reason?: string;
+}
+
+// 스트리밍 응답의 Server-Sent Event ('proxy_sse_event' 이벤트)
+export interface ServerSentEvent {
+  request_id: string; // 응답을 받은 HttpRequest.id
+  index: number; // 같은 응답 안에서 도착한 순서
+  id?: string; // 마지막으로 받은 이벤트 ID
+  event?: string; // 이벤트 타입 (없으면 message)
+  data: string;
+  retry?: number; // 재연결 대기 시간 (ms)
+  time: number;
+}
+
+// 요청 재전송 옵션 ('replay_request' 명령, 생략한 값은 기본값)
+export interface ReplayOptions {
+  count?: number; // 보낼 횟수 (기본 1)
+  concurrency?: number; // 동시에 진행할 요청 수 (기본 1)
+  delayMs?: number; // 요청을 시작하는 간격 (ms)
+}
+
+// 코드 스니펫 언어 ('request_snippet' 명령)
+export type SnippetLanguage = 'curl' | 'httpie' | 'fetch' | 'python' | 'reqwest';
+
 // Re-export DataType for convenience
 export { DataType } from './data-type';
@@ -41,8 +41,8 @@ export const formatBodyContent = (body: Uint8Array, dataType: DataType, bodyJson
     return '';
   }
 
-  // JSON 타입이고 body_json이 있으면 바로 포맷팅
-  if (dataType === 'Json' && bodyJson) {
+  // JSON 타입이거나 gRPC 메시지를 JSON으로 변환한 경우 body_json을 바로 포맷팅
+  if ((dataType === 'Json' || dataType === 'Grpc') && bodyJson) {
     return JSON.stringify(bodyJson, null, 2);
   }
 
@@ -78,7 +78,7 @@ export const getBodyForDisplay = (body: Uint8Array, dataType: DataType, bodyJson
     return '';
   }
 
-  if (isTextBasedDataType(dataType)) {
+  if (isTextBasedDataType(dataType) || (dataType === 'Grpc' && bodyJson)) {
     return formatBodyContent(body, dataType, bodyJson);
   }
 